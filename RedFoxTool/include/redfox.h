@@ -0,0 +1,69 @@
+/* RedFox C API - generated by cbindgen, do not edit */
+
+#ifndef REDFOX_H
+#define REDFOX_H
+
+#include <stdbool.h>
+#include <stdint.h>
+
+/* تم استلام نتيجة */
+#define REDFOX_OK 0
+
+/* انتهى الفحص ولا توجد نتائج أخرى */
+#define REDFOX_DONE 1
+
+/* لم تصل نتيجة خلال المهلة (الفحص مستمر) */
+#define REDFOX_PENDING 2
+
+/* خطأ (التفاصيل في redfox_last_error) */
+#define REDFOX_ERROR -1
+
+/* فحص قيد التشغيل (مقبض معتم من جهة C) */
+typedef struct RedFoxScan RedFoxScan;
+
+/* نتيجة محاولة واحدة (النصوص مملوكة للمكتبة حتى redfox_result_free) */
+typedef struct RedFoxResult {
+  /* اسم المستخدم */
+  char *username;
+  /* كلمة المرور */
+  char *password;
+  /* هل نجحت المصادقة؟ */
+  bool success;
+  /* رمز الحالة (HTTP فقط) */
+  uint16_t status_code;
+  /* زمن الاستجابة بالمللي ثانية */
+  uint64_t response_time_ms;
+  /* رسالة الخطأ أو NULL */
+  char *error;
+} RedFoxResult;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+/* إصدار المكتبة */
+const char *redfox_version(void);
+
+/* آخر خطأ في الخيط الحالي (NULL إن لم يوجد، صالح حتى الاستدعاء الفاشل التالي) */
+const char *redfox_last_error(void);
+
+/* بدء فحص من كائن JSON بنفس أسماء خيارات الوضع التفاعلي (NULL عند الفشل) */
+RedFoxScan *redfox_scan(const char *config_json);
+
+/* انتظار النتيجة التالية حتى `timeout_ms` (REDFOX_OK, REDFOX_PENDING, REDFOX_DONE, REDFOX_ERROR) */
+int redfox_result_next(RedFoxScan *scan, RedFoxResult *out, uint64_t timeout_ms);
+
+/* تحرير نصوص نتيجة (الهيكل نفسه ملك للمستدعي) */
+void redfox_result_free(RedFoxResult *result);
+
+/* طلب إيقاف الفحص (تستمر redfox_result_next حتى REDFOX_DONE) */
+void redfox_scan_cancel(RedFoxScan *scan);
+
+/* إيقاف الفحص وتحرير مقبضه */
+void redfox_scan_free(RedFoxScan *scan);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* REDFOX_H */
@@ -0,0 +1,463 @@
+//! وضع الوكيل (mTLS)
+//! وكلاء دائمون ينضمون إلى المنسق عبر TLS متبادل المصادقة ويبلغون سعتهم وينفذون ما يُسند إليهم من أزواج
+
+use std::collections::{BTreeMap, HashMap};
+use std::net::SocketAddr;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+use anyhow::{Context, Result};
+use futures::stream::{BoxStream, TryStreamExt};
+use parking_lot::{Mutex, RwLock};
+use rustls::pki_types::{CertificateDer, PrivateKeyDer, ServerName};
+use rustls::server::WebPkiClientVerifier;
+use rustls::{ClientConfig, RootCertStore, ServerConfig};
+use tokio::io::{AsyncBufReadExt, AsyncRead, AsyncWrite, AsyncWriteExt, BufReader};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::mpsc;
+use tokio_rustls::{TlsAcceptor, TlsConnector};
+
+use crate::queue::{batches, run_batch};
+use crate::scanner::ScanResult;
+use crate::session::SessionConfig;
+use crate::telemetry::Logger;
+
+/// مهلة إعادة الاتصال بعد انقطاع الوكيل عن المنسق
+const RECONNECT_DELAY: Duration = Duration::from_secs(5);
+
+/// متوسط الدفعات الجارية لكل وكيل (تُوزع بنسبة السعات، فلا يُحجز في الذاكرة إلا ما يُنفذ فعلًا)
+const BATCHES_PER_AGENT: usize = 2;
+
+/// ملفات الهوية لـ TLS المتبادل (شهادة الطرف ومفتاحه وسلطة التوقيع الموثوقة)
+#[derive(Debug, Clone)]
+pub struct TlsFiles {
+    /// شهادة الطرف (PEM)
+    pub cert: PathBuf,
+    /// مفتاح الطرف الخاص (PEM)
+    pub key: PathBuf,
+    /// سلطة التوقيع الموثوقة (PEM)
+    pub ca: PathBuf,
+}
+
+impl TlsFiles {
+    /// سلسلة الشهادات والمفتاح الخاص
+    fn identity(&self) -> Result<(Vec<CertificateDer<'static>>, PrivateKeyDer<'static>)> {
+        let certs = read_certs(&self.cert)?;
+        let key = std::fs::read(&self.key).context(format!("فشل في قراءة المفتاح: {}", self.key.display()))?;
+        let key = rustls_pemfile::private_key(&mut key.as_slice())
+            .context(format!("مفتاح غير صالح: {}", self.key.display()))?
+            .ok_or_else(|| anyhow::anyhow!("لا يوجد مفتاح خاص في: {}", self.key.display()))?;
+        
+        Ok((certs, key))
+    }
+    
+    /// مخزن سلطات التوقيع الموثوقة للطرف الآخر
+    fn roots(&self) -> Result<Arc<RootCertStore>> {
+        let mut roots = RootCertStore::empty();
+        for cert in read_certs(&self.ca)? {
+            roots.add(cert).context(format!("شهادة CA غير صالحة: {}", self.ca.display()))?;
+        }
+        Ok(Arc::new(roots))
+    }
+    
+    /// مستقبل TLS للمنسق يشترط شهادة عميل موقعة من CA
+    pub fn acceptor(&self) -> Result<TlsAcceptor> {
+        let verifier = WebPkiClientVerifier::builder(self.roots()?)
+            .build()
+            .context("فشل في بناء متحقق شهادات الوكلاء")?;
+        let (certs, key) = self.identity()?;
+        let config = ServerConfig::builder()
+            .with_client_cert_verifier(verifier)
+            .with_single_cert(certs, key)
+            .context("شهادة المنسق غير صالحة")?;
+        
+        Ok(TlsAcceptor::from(Arc::new(config)))
+    }
+    
+    /// موصل TLS للوكيل يقدم شهادته ويتحقق من شهادة المنسق
+    pub fn connector(&self) -> Result<TlsConnector> {
+        let (certs, key) = self.identity()?;
+        let config = ClientConfig::builder()
+            .with_root_certificates(self.roots()?)
+            .with_client_auth_cert(certs, key)
+            .context("شهادة الوكيل غير صالحة")?;
+        
+        Ok(TlsConnector::from(Arc::new(config)))
+    }
+}
+
+/// قراءة شهادات PEM من ملف
+fn read_certs(path: &PathBuf) -> Result<Vec<CertificateDer<'static>>> {
+    let pem = std::fs::read(path).context(format!("فشل في قراءة الشهادة: {}", path.display()))?;
+    let certs = rustls_pemfile::certs(&mut pem.as_slice())
+        .collect::<std::result::Result<Vec<_>, _>>()
+        .context(format!("شهادة غير صالحة: {}", path.display()))?;
+    
+    if certs.is_empty() {
+        return Err(anyhow::anyhow!("لا توجد شهادات في: {}", path.display()));
+    }
+    Ok(certs)
+}
+
+/// رسائل الوكيل إلى المنسق (سطر JSON لكل رسالة)
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum AgentMessage {
+    /// التسجيل عند الاتصال مع السعة المتاحة
+    Register { name: String, capacity: usize, version: String },
+    /// نتيجة محاولة ضمن مهمة مسندة
+    Result { job: u64, result: Box<ScanResult> },
+    /// اكتمال مهمة مسندة
+    Done { job: u64 },
+}
+
+/// رسائل المنسق إلى الوكيل
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum CoordinatorMessage {
+    /// إسناد أزواج للاختبار بإعدادات الفحص
+    Assign { job: u64, config: Box<SessionConfig>, pairs: Vec<(String, String)> },
+}
+
+/// حدث مهمة مسندة كما يراه المنسق
+enum AssignmentEvent {
+    Result(Box<ScanResult>),
+    Done(u64),
+    Lost(u64, String),
+}
+
+/// كتابة رسالة كسطر JSON
+async fn send_line<W: AsyncWrite + Unpin>(writer: &mut W, message: &impl serde::Serialize) -> Result<()> {
+    let mut line = serde_json::to_vec(message)?;
+    line.push(b'\n');
+    writer.write_all(&line).await?;
+    writer.flush().await?;
+    Ok(())
+}
+
+/// وكيل متصل بالمنسق
+pub struct AgentHandle {
+    /// الاسم المعلن عند التسجيل
+    pub name: String,
+    /// عدد المحاولات المتزامنة التي يقبلها
+    pub capacity: usize,
+    /// عنوان الاتصال
+    pub address: SocketAddr,
+    /// وقت التسجيل
+    pub connected_at: chrono::DateTime<chrono::Utc>,
+    sender: mpsc::UnboundedSender<CoordinatorMessage>,
+}
+
+/// سجل الوكلاء المتصلين وتوزيع المهام عليهم (جانب المنسق)
+#[derive(Default)]
+pub struct AgentPool {
+    agents: RwLock<BTreeMap<u64, Arc<AgentHandle>>>,
+    assignments: Mutex<HashMap<u64, (u64, mpsc::UnboundedSender<AssignmentEvent>)>>,
+    next_agent: AtomicU64,
+    next_job: AtomicU64,
+}
+
+impl AgentPool {
+    /// الوكلاء المتصلون حاليًا
+    pub fn agents(&self) -> Vec<Arc<AgentHandle>> {
+        self.agents.read().values().cloned().collect()
+    }
+    
+    /// هل يوجد وكيل متصل؟
+    pub fn is_empty(&self) -> bool {
+        self.agents.read().is_empty()
+    }
+    
+    /// الاستماع لاتصالات الوكلاء عبر TLS المتبادل
+    pub async fn listen(self: Arc<Self>, address: SocketAddr, acceptor: TlsAcceptor) -> Result<()> {
+        let listener = TcpListener::bind(address)
+            .await
+            .context(format!("فشل في الاستماع للوكلاء على: {}", address))?;
+        Logger.success(&format!("بانتظار الوكلاء (mTLS) على: {}", address));
+        
+        loop {
+            let (stream, peer) = listener.accept().await?;
+            let pool = Arc::clone(&self);
+            let acceptor = acceptor.clone();
+            
+            tokio::spawn(async move {
+                let outcome = match acceptor.accept(stream).await {
+                    Ok(stream) => pool.handle(stream, peer).await,
+                    Err(e) => Err(anyhow::anyhow!("فشل مصافحة TLS: {}", e)),
+                };
+                if let Err(e) = outcome {
+                    Logger.warn(&format!("اتصال وكيل {}: {:#}", peer, e));
+                }
+            });
+        }
+    }
+    
+    /// خدمة اتصال وكيل واحد حتى انقطاعه
+    async fn handle<S: AsyncRead + AsyncWrite + Unpin + Send + 'static>(&self, stream: S, peer: SocketAddr) -> Result<()> {
+        let (reader, mut writer) = tokio::io::split(stream);
+        let mut lines = BufReader::new(reader).lines();
+        
+        let first = lines.next_line().await?.context("انقطع الوكيل قبل التسجيل")?;
+        let AgentMessage::Register { name, capacity, version } = serde_json::from_str(&first)? else {
+            return Err(anyhow::anyhow!("أول رسالة يجب أن تكون تسجيلًا"));
+        };
+        
+        let (sender, mut outbox) = mpsc::unbounded_channel();
+        let id = self.next_agent.fetch_add(1, Ordering::Relaxed);
+        self.agents.write().insert(id, Arc::new(AgentHandle {
+            name: name.clone(),
+            capacity: capacity.max(1),
+            address: peer,
+            connected_at: chrono::Utc::now(),
+            sender,
+        }));
+        Logger.success(&format!("انضم الوكيل {} ({}، السعة {}، الإصدار {})", name, peer, capacity, version));
+        
+        let writer_task = tokio::spawn(async move {
+            while let Some(message) = outbox.recv().await {
+                if send_line(&mut writer, &message).await.is_err() {
+                    break;
+                }
+            }
+        });
+        
+        let outcome = self.read_messages(&mut lines).await;
+        
+        // الانقطاع: إزالة الوكيل وإبلاغ المهام المسندة إليه
+        writer_task.abort();
+        self.agents.write().remove(&id);
+        self.assignments.lock().retain(|job, (agent, events)| {
+            if *agent == id {
+                let _ = events.send(AssignmentEvent::Lost(*job, name.clone()));
+            }
+            *agent != id
+        });
+        Logger.warn(&format!("انقطع الوكيل {} ({})", name, peer));
+        
+        outcome
+    }
+    
+    /// قراءة رسائل الوكيل وتمريرها إلى المهام المنتظرة
+    async fn read_messages<R: AsyncRead + Unpin>(&self, lines: &mut tokio::io::Lines<BufReader<R>>) -> Result<()> {
+        while let Some(line) = lines.next_line().await? {
+            match serde_json::from_str(&line)? {
+                AgentMessage::Result { job, result } => {
+                    if let Some((_, events)) = self.assignments.lock().get(&job) {
+                        let _ = events.send(AssignmentEvent::Result(result));
+                    }
+                }
+                AgentMessage::Done { job } => {
+                    if let Some((_, events)) = self.assignments.lock().remove(&job) {
+                        let _ = events.send(AssignmentEvent::Done(job));
+                    }
+                }
+                AgentMessage::Register { .. } => {
+                    return Err(anyhow::anyhow!("تسجيل مكرر على نفس الاتصال"));
+                }
+            }
+        }
+        Ok(())
+    }
+    
+    /// توزيع بث الأزواج على الوكلاء دفعةً دفعة حسب سعاتهم وجمع النتائج (on_result لكل نتيجة فور وصولها)
+    ///
+    /// الدفعة التالية تُسحب من البث عند فراغ مكان لدى أحد الوكلاء، فالأسرع يتلقى دفعات أكثر.
+    pub async fn dispatch(
+        &self,
+        config: &SessionConfig,
+        pairs: BoxStream<'static, Result<(String, String)>>,
+        batch_size: usize,
+        mut on_result: impl FnMut(&ScanResult),
+    ) -> Result<Vec<ScanResult>> {
+        let agents: Vec<(u64, Arc<AgentHandle>)> = self
+            .agents
+            .read()
+            .iter()
+            .map(|(id, agent)| (*id, Arc::clone(agent)))
+            .collect();
+        if agents.is_empty() {
+            return Err(anyhow::anyhow!("لا يوجد وكلاء متصلون"));
+        }
+        
+        let capacities: Vec<usize> = agents.iter().map(|(_, agent)| agent.capacity).collect();
+        let mut slots: Vec<usize> = shares(agents.len() * BATCHES_PER_AGENT, &capacities)
+            .into_iter()
+            .map(|slots| slots.max(1))
+            .collect();
+        let mut load = vec![0; agents.len()];
+        let mut running: HashMap<u64, usize> = HashMap::new();
+        
+        let (events, mut receiver) = mpsc::unbounded_channel();
+        let mut pending = batches(pairs, batch_size);
+        let mut exhausted = false;
+        let mut results = Vec::new();
+        
+        loop {
+            // إسناد الدفعات التالية للوكيل الأكثر أماكن شاغرة
+            while !exhausted {
+                let Some(index) = (0..agents.len())
+                    .filter(|&i| load[i] < slots[i])
+                    .max_by_key(|&i| slots[i] - load[i])
+                else {
+                    break;
+                };
+                let Some(pairs) = pending.try_next().await? else {
+                    exhausted = true;
+                    break;
+                };
+                
+                let (agent_id, agent) = &agents[index];
+                let job = self.next_job.fetch_add(1, Ordering::Relaxed);
+                self.assignments.lock().insert(job, (*agent_id, events.clone()));
+                
+                let share = pairs.len();
+                let assign = CoordinatorMessage::Assign {
+                    job,
+                    config: Box::new(config.clone()),
+                    pairs,
+                };
+                if agent.sender.send(assign).is_err() {
+                    self.assignments.lock().remove(&job);
+                    return Err(anyhow::anyhow!("انقطع الوكيل {} أثناء الإسناد", agent.name));
+                }
+                
+                Logger.info(&format!("إسناد {} زوج إلى الوكيل {}", share, agent.name));
+                running.insert(job, index);
+                load[index] += 1;
+            }
+            
+            if running.is_empty() {
+                if !exhausted {
+                    return Err(anyhow::anyhow!("انقطع كل الوكلاء قبل إسناد كل الأزواج"));
+                }
+                break;
+            }
+            
+            match receiver.recv().await {
+                Some(AssignmentEvent::Result(result)) => {
+                    on_result(&result);
+                    results.push(*result);
+                }
+                Some(AssignmentEvent::Done(job)) => {
+                    if let Some(index) = running.remove(&job) {
+                        load[index] -= 1;
+                    }
+                }
+                Some(AssignmentEvent::Lost(job, name)) => {
+                    Logger.warn(&format!("فُقدت الأزواج غير المكتملة لدى الوكيل {}", name));
+                    // الوكيل المنقطع لا يتلقى دفعات أخرى
+                    if let Some(index) = running.remove(&job) {
+                        load[index] -= 1;
+                        slots[index] = 0;
+                    }
+                }
+                None => break,
+            }
+        }
+        
+        Ok(results)
+    }
+}
+
+/// تقسيم عدد الأزواج على الوكلاء بنسبة سعاتهم (الباقي للأوائل)
+fn shares(total: usize, capacities: &[usize]) -> Vec<usize> {
+    let sum: usize = capacities.iter().sum();
+    if sum == 0 {
+        return vec![0; capacities.len()];
+    }
+    
+    let mut shares: Vec<usize> = capacities.iter().map(|c| total * c / sum).collect();
+    let mut remainder = total - shares.iter().sum::<usize>();
+    for share in shares.iter_mut() {
+        if remainder == 0 {
+            break;
+        }
+        *share += 1;
+        remainder -= 1;
+    }
+    
+    shares
+}
+
+/// تشغيل الوكيل: الانضمام إلى المنسق وتنفيذ ما يُسند إليه مع إعادة الاتصال عند الانقطاع
+pub async fn join(coordinator: &str, name: &str, capacity: usize, tls: &TlsFiles) -> Result<()> {
+    let connector = tls.connector()?;
+    
+    loop {
+        match session(coordinator, name, capacity, &connector).await {
+            Ok(()) => Logger.warn("أغلق المنسق الاتصال"),
+            Err(e) => Logger.warn(&format!("انقطع الاتصال بالمنسق: {:#}", e)),
+        }
+        tokio::time::sleep(RECONNECT_DELAY).await;
+    }
+}
+
+/// جلسة اتصال واحدة بالمنسق
+async fn session(coordinator: &str, name: &str, capacity: usize, connector: &TlsConnector) -> Result<()> {
+    let host = coordinator
+        .rsplit_once(':')
+        .map_or(coordinator, |(host, _)| host)
+        .trim_start_matches('[')
+        .trim_end_matches(']');
+    let server_name = ServerName::try_from(host.to_string())
+        .context(format!("اسم منسق غير صالح: {}", host))?;
+    
+    let stream = TcpStream::connect(coordinator)
+        .await
+        .context(format!("فشل في الاتصال بالمنسق: {}", coordinator))?;
+    let stream = connector
+        .connect(server_name, stream)
+        .await
+        .context("فشل مصافحة TLS مع المنسق")?;
+    let (reader, mut writer) = tokio::io::split(stream);
+    
+    send_line(&mut writer, &AgentMessage::Register {
+        name: name.to_string(),
+        capacity,
+        version: env!("CARGO_PKG_VERSION").to_string(),
+    })
+    .await?;
+    Logger.success(&format!("انضم إلى المنسق: {}", coordinator));
+    
+    // كل المهام تكتب عبر قناة واحدة حتى لا تتداخل الأسطر
+    let (outbox, mut messages) = mpsc::unbounded_channel::<AgentMessage>();
+    let writer_task = tokio::spawn(async move {
+        while let Some(message) = messages.recv().await {
+            if send_line(&mut writer, &message).await.is_err() {
+                break;
+            }
+        }
+    });
+    
+    let mut lines = BufReader::new(reader).lines();
+    while let Some(line) = lines.next_line().await? {
+        let CoordinatorMessage::Assign { job, config, pairs } = serde_json::from_str(&line)?;
+        let outbox = outbox.clone();
+        
+        Logger.info(&format!("مهمة {}: {} زوج", job, pairs.len()));
+        tokio::spawn(async move {
+            let config = SessionConfig { threads: capacity, ..*config };
+            for result in run_batch(&config, pairs).await {
+                let _ = outbox.send(AgentMessage::Result { job, result: Box::new(result) });
+            }
+            let _ = outbox.send(AgentMessage::Done { job });
+        });
+    }
+    
+    writer_task.abort();
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_shares_follow_capacity() {
+        assert_eq!(shares(10, &[20, 20]), vec![5, 5]);
+        assert_eq!(shares(10, &[30, 10]), vec![8, 2]);
+        assert_eq!(shares(7, &[1, 1, 1]), vec![3, 2, 2]);
+        assert_eq!(shares(3, &[0, 0]), vec![0, 0]);
+    }
+}
@@ -3,17 +3,17 @@
 
 use std::sync::Arc;
 use std::time::{Instant, Duration};
-use dashmap::DashMap;
+#[cfg(feature = "rayon")]
 use rayon::prelude::*;
-use tokio::sync::mpsc;
-use anyhow::{Result, Context};
-use parking_lot::RwLock;
+use tokio::sync::{mpsc, Semaphore};
+use anyhow::Result;
 
 use crate::http_client::HttpClient;
 use crate::scanner::ScanResult;
 
 /// وضع الهجوم
-#[derive(Debug, Clone, Copy, PartialEq, serde::Serialize)]
+#[derive(Debug, Clone, Copy, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum AttackMode {
     /// أقصى سرعة
     Fast,
@@ -25,6 +25,28 @@ pub enum AttackMode {
     Aggressive,
 }
 
+impl AttackMode {
+    /// الوضع من اسمه (غير المعروف يعامل كـ normal)
+    pub fn from_name(name: &str) -> Self {
+        match name.to_lowercase().as_str() {
+            "fast" => AttackMode::Fast,
+            "stealth" => AttackMode::Stealth,
+            "aggressive" => AttackMode::Aggressive,
+            _ => AttackMode::Normal,
+        }
+    }
+    
+    /// اسم الوضع كما يظهر في النتائج والتقارير
+    pub fn name(&self) -> &'static str {
+        match self {
+            AttackMode::Fast => "fast",
+            AttackMode::Normal => "normal",
+            AttackMode::Stealth => "stealth",
+            AttackMode::Aggressive => "aggressive",
+        }
+    }
+}
+
 /// محرك التخمين
 pub struct Bruteforcer {
     client: Arc<HttpClient>,
@@ -32,7 +54,6 @@ pub struct Bruteforcer {
     passwords: Vec<String>,
     max_workers: usize,
     rate_limit: Option<u32>,
-    results: Arc<DashMap<String, ScanResult>>,
 }
 
 impl Bruteforcer {
@@ -49,13 +70,17 @@ impl Bruteforcer {
             passwords,
             max_workers,
             rate_limit: None,
-            results: Arc::new(DashMap::new()),
         }
     }
     
     /// تعيين حد المعدل
     pub fn set_rate_limit(&mut self, requests_per_second: u32) {
         self.rate_limit = Some(requests_per_second);
+        
+        // تطبيق الحد على العميل المشترك بين جميع المهام
+        let mut client = (*self.client).clone();
+        client.set_rate_limit(self.rate_limit);
+        self.client = Arc::new(client);
     }
     
     /// تشغيل الهجوم حسب الوضع
@@ -73,7 +98,7 @@ impl Bruteforcer {
         let start = Instant::now();
         let total = self.users.len() * self.passwords.len();
         
-        println!("[+] بدء الهجوم السريع: {} محاولة", total);
+        tracing::info!("بدء الهجوم السريع: {} محاولة", total);
         
         #[cfg(feature = "rayon")]
         let results: Vec<ScanResult> = self.users
@@ -89,8 +114,8 @@ impl Bruteforcer {
         let results = self.attack_normal().await?;
         
         let duration = start.elapsed();
-        println!(
-            "[+] اكتمل في {:.2?} ({:.0} محاولة/ثانية)",
+        tracing::info!(
+            "اكتمل في {:.2?} ({:.0} محاولة/ثانية)",
             duration,
             total as f64 / duration.as_secs_f64()
         );
@@ -102,17 +127,23 @@ impl Bruteforcer {
     async fn attack_normal(&self) -> Result<Vec<ScanResult>> {
         let (tx, mut rx) = mpsc::channel(1000);
         let client = Arc::clone(&self.client);
+        let (users, passwords) = (self.users.clone(), self.passwords.clone());
+        let semaphore = Arc::new(Semaphore::new(self.max_workers.max(1)));
         
-        // إنتاج المهام
+        // إنتاج المهام (المحاولات الجارية محدودة بعدد العمال)
         let producer = tokio::spawn(async move {
-            for username in &self.users {
-                for password in &self.passwords {
+            for username in &users {
+                for password in &passwords {
+                    let Ok(permit) = Arc::clone(&semaphore).acquire_owned().await else {
+                        return;
+                    };
                     let tx = tx.clone();
                     let client = Arc::clone(&client);
                     let u = username.clone();
                     let p = password.clone();
                     
                     tokio::spawn(async move {
+                        let _permit = permit;
                         let result = client.test_login(&u, &p).await;
                         let _ = tx.send((u, p, result)).await;
                     });
@@ -132,6 +163,11 @@ impl Bruteforcer {
                     response_time: Duration::default(),
                     error: None,
                     timestamp: chrono::Utc::now(),
+                    metadata: None,
+                    captcha_detected: false,
+                    banner: None,
+                    http_version: None,
+                    ..Default::default()
                 },
                 Err(_) => ScanResult {
                     username,
@@ -141,6 +177,11 @@ impl Bruteforcer {
                     response_time: Duration::default(),
                     error: Some("فشل".to_string()),
                     timestamp: chrono::Utc::now(),
+                    metadata: None,
+                    captcha_detected: false,
+                    banner: None,
+                    http_version: None,
+                    ..Default::default()
                 },
             };
             
@@ -168,6 +209,11 @@ impl Bruteforcer {
                             response_time: Duration::default(),
                             error: None,
                             timestamp: chrono::Utc::now(),
+                            metadata: None,
+                            captcha_detected: false,
+                            banner: None,
+                            http_version: None,
+                            ..Default::default()
                         });
                     }
                     Err(_) => {
@@ -179,6 +225,11 @@ impl Bruteforcer {
                             response_time: Duration::default(),
                             error: Some("فشل".to_string()),
                             timestamp: chrono::Utc::now(),
+                            metadata: None,
+                            captcha_detected: false,
+                            banner: None,
+                            http_version: None,
+                            ..Default::default()
                         });
                     }
                 }
@@ -211,6 +262,11 @@ impl Bruteforcer {
                                 response_time: Duration::default(),
                                 error: None,
                                 timestamp: chrono::Utc::now(),
+                                metadata: None,
+                                captcha_detected: false,
+                                banner: None,
+                                http_version: None,
+                                ..Default::default()
                             });
                             break;
                         }
@@ -232,6 +288,11 @@ impl Bruteforcer {
                         response_time: Duration::default(),
                         error: Some(e.to_string()),
                         timestamp: chrono::Utc::now(),
+                        metadata: None,
+                        captcha_detected: false,
+                        banner: None,
+                        http_version: None,
+                        ..Default::default()
                     });
                 }
             }
@@ -241,13 +302,14 @@ impl Bruteforcer {
     }
     
     /// اختبار زوج واحد
+    #[cfg(feature = "rayon")]
     fn test_pair(&self, username: &str, password: &str) -> ScanResult {
         let start = Instant::now();
         
         // استخدام قناة Tokio غير متزامنة
         let rt = tokio::runtime::Handle::current();
         
-        let result = rt.block_on(async {
+        rt.block_on(async {
             match self.client.test_login(username, password).await {
                 Ok(response) => ScanResult {
                     username: username.to_string(),
@@ -257,6 +319,11 @@ impl Bruteforcer {
                     response_time: start.elapsed(),
                     error: None,
                     timestamp: chrono::Utc::now(),
+                    metadata: None,
+                    captcha_detected: false,
+                    banner: None,
+                    http_version: None,
+                    ..Default::default()
                 },
                 Err(e) => ScanResult {
                     username: username.to_string(),
@@ -266,23 +333,22 @@ impl Bruteforcer {
                     response_time: start.elapsed(),
                     error: Some(e.to_string()),
                     timestamp: chrono::Utc::now(),
+                    metadata: None,
+                    captcha_detected: false,
+                    banner: None,
+                    http_version: None,
+                    ..Default::default()
                 },
             }
-        });
-        
-        // تخزين النتيجة
-        let key = format!("{}:{}", username, password);
-        self.results.insert(key, result.clone());
-        
-        result
+        })
     }
     
     /// هجوم ذكي (يجرب الأكثر شيوعًا أولاً)
     pub async fn smart_attack(&self) -> Result<Vec<ScanResult>> {
-        println!("[+] بدء الهجوم الذكي");
+        tracing::info!("بدء الهجوم الذكي");
         
         // فرز كلمات المرور حسب الشهرة (إذا كانت معلومة)
-        let mut passwords = self.passwords.clone();
+        let passwords = self.passwords.clone();
         
         // يمكن إضافة منطق الفرز هنا
         // مثلاً: تجربة كلمات المرور القصيرة أولاً
@@ -297,21 +363,23 @@ impl Bruteforcer {
             if self.users.contains(&username.to_string()) {
                 for password in common_passwords.iter() {
                     if passwords.contains(&password.to_string()) {
-                        match self.client.test_login(username, password).await {
-                            Ok(response) => {
-                                if response.status().is_success() {
-                                    results.push(ScanResult {
-                                        username: username.to_string(),
-                                        password: password.to_string(),
-                                        success: true,
-                                        status_code: response.status().as_u16(),
-                                        response_time: Duration::default(),
-                                        error: None,
-                                        timestamp: chrono::Utc::now(),
-                                    });
-                                }
+                        if let Ok(response) = self.client.test_login(username, password).await {
+                            if response.status().is_success() {
+                                results.push(ScanResult {
+                                    username: username.to_string(),
+                                    password: password.to_string(),
+                                    success: true,
+                                    status_code: response.status().as_u16(),
+                                    response_time: Duration::default(),
+                                    error: None,
+                                    timestamp: chrono::Utc::now(),
+                                    metadata: None,
+                                    captcha_detected: false,
+                                    banner: None,
+                                    http_version: None,
+                                    ..Default::default()
+                                });
                             }
-                            Err(_) => {}
                         }
                     }
                 }
@@ -0,0 +1,194 @@
+//! معايرة استجابة الفشل
+//! أخذ بصمة لاستجابة تسجيل الدخول الفاشل لتصنيف الاستجابات اللاحقة
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+/// مؤشرات الفشل في محتوى الاستجابة
+pub(crate) const FAILURE_INDICATORS: [&str; 8] = [
+    "invalid", "incorrect", "wrong", "failed", "error",
+    "login failed", "access denied", "unauthorized",
+];
+
+/// الحد الأدنى لهامش تفاوت طول الاستجابة (بايت)
+const MIN_LENGTH_TOLERANCE: usize = 32;
+
+#[allow(clippy::unwrap_used)] // نمط ثابت لا يفشل تجميعه
+static TITLE_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?is)<title[^>]*>(.*?)</title>").unwrap()
+});
+
+/// بصمة استجابة واحدة
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResponseFingerprint {
+    /// رمز الحالة
+    pub status: u16,
+    
+    /// طول المحتوى
+    pub length: usize,
+    
+    /// وجهة التحويل (ترويسة Location أو الرابط النهائي بعد التحويل)
+    pub redirect: Option<String>,
+    
+    /// عنوان الصفحة
+    pub title: Option<String>,
+    
+    /// مؤشرات الفشل الموجودة في المحتوى
+    pub markers: Vec<String>,
+}
+
+impl ResponseFingerprint {
+    /// بناء بصمة من أجزاء الاستجابة
+    pub fn from_parts(status: u16, redirect: Option<String>, body: &str) -> Self {
+        let body_lower = body.to_lowercase();
+        
+        Self {
+            status,
+            length: body.len(),
+            redirect: redirect.map(|r| normalize_redirect(&r)),
+            title: TITLE_RE
+                .captures(body)
+                .map(|c| c[1].trim().to_string())
+                .filter(|t| !t.is_empty()),
+            markers: FAILURE_INDICATORS
+                .iter()
+                .filter(|indicator| body_lower.contains(*indicator))
+                .map(|indicator| (*indicator).to_string())
+                .collect(),
+        }
+    }
+}
+
+/// خط أساس استجابة الفشل المستخلص من محاولات خاطئة متعمدة
+#[derive(Debug, Clone)]
+pub struct FailureBaseline {
+    status: u16,
+    redirect: Option<String>,
+    title: Option<String>,
+    markers: Vec<String>,
+    min_length: usize,
+    max_length: usize,
+    samples: usize,
+}
+
+impl FailureBaseline {
+    /// بناء خط الأساس من عينات الفشل
+    pub fn from_samples(samples: &[ResponseFingerprint]) -> Option<Self> {
+        let first = samples.first()?;
+        
+        // اعتماد الخصائص الثابتة عبر جميع العينات فقط
+        let consistent = |f: fn(&ResponseFingerprint) -> &Option<String>| {
+            let value = f(first);
+            samples.iter().all(|s| f(s) == value).then(|| value.clone()).flatten()
+        };
+        
+        if samples.iter().any(|s| s.status != first.status) {
+            tracing::warn!("رموز حالة غير متسقة في عينات الفشل");
+        }
+        
+        let markers = first
+            .markers
+            .iter()
+            .filter(|m| samples.iter().all(|s| s.markers.contains(m)))
+            .cloned()
+            .collect();
+        
+        Some(Self {
+            status: first.status,
+            redirect: consistent(|s| &s.redirect),
+            title: consistent(|s| &s.title),
+            markers,
+            min_length: samples.iter().map(|s| s.length).min().unwrap_or(0),
+            max_length: samples.iter().map(|s| s.length).max().unwrap_or(0),
+            samples: samples.len(),
+        })
+    }
+    
+    /// هل تطابق البصمة استجابة الفشل؟
+    pub fn is_failure(&self, fingerprint: &ResponseFingerprint) -> bool {
+        if fingerprint.status != self.status {
+            return false;
+        }
+        
+        if self.redirect.is_some() && fingerprint.redirect != self.redirect {
+            return false;
+        }
+        
+        if self.title.is_some() && fingerprint.title != self.title {
+            return false;
+        }
+        
+        if !self.markers.is_empty() && !self.markers.iter().any(|m| fingerprint.markers.contains(m)) {
+            return false;
+        }
+        
+        // السماح بتفاوت الطول الناتج عن المحتوى الديناميكي (مثل انعكاس اسم المستخدم)
+        let tolerance = ((self.max_length - self.min_length) * 2)
+            .max(self.max_length / 20)
+            .max(MIN_LENGTH_TOLERANCE);
+        
+        fingerprint.length + tolerance >= self.min_length
+            && fingerprint.length <= self.max_length + tolerance
+    }
+    
+    /// المسافة بين طول استجابة ونطاق أطوال الفشل
+    pub fn length_distance(&self, length: usize) -> usize {
+        if length < self.min_length {
+            self.min_length - length
+        } else {
+            length.saturating_sub(self.max_length)
+        }
+    }
+    
+    /// ملخص خط الأساس للعرض
+    pub fn summary(&self) -> String {
+        format!(
+            "الحالة {}، الطول {}-{}، التحويل: {}، المؤشرات: [{}] ({} عينات)",
+            self.status,
+            self.min_length,
+            self.max_length,
+            self.redirect.as_deref().unwrap_or("-"),
+            self.markers.join(", "),
+            self.samples
+        )
+    }
+}
+
+/// توحيد وجهة التحويل بإزالة معاملات الاستعلام المتغيرة
+fn normalize_redirect(location: &str) -> String {
+    location
+        .split(['?', '#'])
+        .next()
+        .unwrap_or(location)
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_baseline_classifies_responses() {
+        let failure = |user: &str| {
+            ResponseFingerprint::from_parts(
+                200,
+                None,
+                &format!("<title>Login</title><p>Invalid password for {}</p>", user),
+            )
+        };
+        
+        let baseline = FailureBaseline::from_samples(&[failure("a"), failure("bbbbbbbb")]).unwrap();
+        
+        assert!(baseline.is_failure(&failure("admin")));
+        assert!(!baseline.is_failure(&ResponseFingerprint::from_parts(
+            200,
+            None,
+            "<title>Dashboard</title><p>Welcome back</p>",
+        )));
+        assert!(!baseline.is_failure(&ResponseFingerprint::from_parts(
+            302,
+            Some("/home".to_string()),
+            "",
+        )));
+    }
+}
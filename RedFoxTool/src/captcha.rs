@@ -0,0 +1,59 @@
+//! كشف اختبارات CAPTCHA
+//! التعرف على reCAPTCHA و hCaptcha وتحديات Cloudflare في استجابات تسجيل الدخول
+
+use std::fmt;
+
+use crate::matcher::CapturedResponse;
+
+/// نوع اختبار CAPTCHA المكتشف
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum CaptchaKind {
+    /// Google reCAPTCHA
+    ReCaptcha,
+    /// hCaptcha
+    HCaptcha,
+    /// Cloudflare Turnstile
+    Turnstile,
+    /// صفحة تحدي Cloudflare
+    CloudflareChallenge,
+}
+
+impl fmt::Display for CaptchaKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            CaptchaKind::ReCaptcha => "reCAPTCHA",
+            CaptchaKind::HCaptcha => "hCaptcha",
+            CaptchaKind::Turnstile => "Cloudflare Turnstile",
+            CaptchaKind::CloudflareChallenge => "Cloudflare Challenge",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// علامات المحتوى لكل نوع
+const BODY_MARKERS: [(CaptchaKind, &[&str]); 4] = [
+    (CaptchaKind::CloudflareChallenge, &["cf-chl-", "challenge-platform", "cf_chl_opt", "just a moment..."]),
+    (CaptchaKind::Turnstile, &["cf-turnstile", "challenges.cloudflare.com/turnstile"]),
+    (CaptchaKind::HCaptcha, &["h-captcha", "hcaptcha.com/1/api.js", "js.hcaptcha.com"]),
+    (CaptchaKind::ReCaptcha, &["g-recaptcha", "google.com/recaptcha", "recaptcha/api.js", "grecaptcha"]),
+];
+
+/// كشف اختبار CAPTCHA في الاستجابة
+pub fn detect(response: &CapturedResponse) -> Option<CaptchaKind> {
+    // ترويسة Cloudflare الصريحة للتحدي
+    let mitigated = response
+        .headers
+        .get("cf-mitigated")
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|v| v.eq_ignore_ascii_case("challenge"));
+    if mitigated {
+        return Some(CaptchaKind::CloudflareChallenge);
+    }
+    
+    let body_lower = response.body.to_lowercase();
+    
+    BODY_MARKERS
+        .iter()
+        .find(|(_, markers)| markers.iter().any(|m| body_lower.contains(m)))
+        .map(|(kind, _)| *kind)
+}
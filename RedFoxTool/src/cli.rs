@@ -1,9 +1,23 @@
 //! معالج سطر الأوامر لـ RedFoxTool
 //! يستخدم Clap لتحليل الوسائط
 
-use clap::{Parser, Subcommand, ArgAction};
+use clap::{Parser, Subcommand, ArgAction, CommandFactory};
+use clap::builder::{PossibleValue, PossibleValuesParser};
+use clap_complete::Shell;
 use std::path::PathBuf;
 
+use crate::http_client::{AuthType, HttpVersion, ResolveOverride, TlsVersion};
+use crate::login_body::BodyFormat;
+use crate::modules::audit_file::FileFormat;
+use crate::modules::generator::harvest::DEFAULT_MIN_WORD_LEN;
+use crate::modules::generator::mutate::DEFAULT_MAX_EXPANSION;
+use crate::modules::generator::prince::{DEFAULT_MAX_ELEMENTS, DEFAULT_MAX_LENGTH, DEFAULT_MIN_ELEMENTS};
+use crate::modules::hashcrack::HashKind;
+use crate::modules::snmp::{SnmpAuthProtocol, SnmpVersion};
+use crate::modules::{Protocol, TlsMode, DEFAULT_UDP_RETRIES, DEFAULT_UDP_TIMEOUT_MS};
+use crate::proxy_check::AnonymityLevel;
+use crate::wordlist::{ByteSize, PasswordPolicy};
+
 /// الوسائط الأساسية لـ RedFoxTool
 #[derive(Parser, Debug)]
 #[command(
@@ -26,7 +40,51 @@ RedFoxTool - أداة اختبار اختراق متقدمة لأنظمة الم
 أمثلة:
   redfox scan --url http://target.com/login -U admin -P passwords.txt
   redfox scan --url https://target.com -U users.txt -P rockyou.txt -T 50 --mode fast
+  redfox scan --url https://target.com --combo-file combos.txt
+  redfox scan --url https://blog.target.com --preset wordpress -U admin -P passwords.txt
+  redfox scan --protocol ssh --url 10.0.0.5:22 -U root -P passwords.txt
+  redfox scan --protocol ftp --service-tls explicit --url 10.0.0.5 -U users.txt -P passwords.txt
+  redfox scan --protocol snmp --url 10.0.0.1 -U snmp -P communities.txt
+  redfox scan --url ldaps://dc01.corp.local --ldap-user-template '^USER^@corp.local' -U users.txt -P spray.txt -m stealth
+  redfox scan --url https://10.0.0.5/login --sni intranet.corp.local -U admin -P passwords.txt
+  redfox scan --url https://target.com/auth --method GET --query 'u=^USER^&p=^PASS^' -U admin -P passwords.txt
+  redfox scan --url https://api.target.com/v1/me --secret-header 'X-Api-Key: ^PASS^' -P keys.txt
+  redfox scan --url https://sso.target.com/realms/main/protocol/openid-connect/token --auth-type oauth --client-id web -U users.txt -P pass.txt
+  redfox jwt-crack --token eyJhbGciOiJIUzI1NiJ9... -P secrets.txt
+  redfox scan --url https://target.com/login -U admin -P base.txt --rules best64.rule
+  redfox hash-crack --hashes dump.txt --hash-type ntlm -P rockyou.txt -o cracked.json
+  redfox audit-file --file .htpasswd -P common.txt
+  redfox generate -w acme.txt --mask 'Acme?1?d?d' -c '?s!'
+  redfox generate -w pins.txt --min-len 4 --max-len 6 --chars '?d'
+  redfox generate -w mutated.txt --base company.txt --leet --case --suffix ! --max-expansion 100
+  redfox generate -w spray.txt --base seasons.txt --case --append-years --append-symbols
+  redfox generate -w names.txt --base staff-names.txt --arabic --case --append-years
+  redfox generate -w acme-words.txt --crawl https://acme.com --depth 2 --users-output acme-users.txt
+  redfox generate -w prince.txt --prince acme-words.txt --prince years.txt --pw-min 8 --pw-max 14
+  redfox vault list --target app.target.com
+  redfox report regenerate results.ndjson -o acme --template corporate.html.tera
+  redfox report merge day1.json day2.json -o combined --format html --redact
+  redfox scan --targets 10.0.0.0/24:8080,443 --url https://x/login -U admin -P pass.txt
+  hashcat --stdout -r best64.rule words.txt | redfox scan --url https://target.com/login -U admin -P -
+  redfox discover --url https://site.com -o logins.txt && redfox scan --targets logins.txt -U admin -P pass.txt
+  redfox scan --url https://target.com/login -U admin -P pass.txt --slack-webhook https://hooks.slack.com/services/T/B/X
+  redfox --config redfox.toml scan --url https://target.com/login -U admin -P pass.txt --profile client-a
+  redfox config init && redfox config use lab && redfox scan -U admin -P pass.txt
+  redfox shell
+  redfox --output-format json scan --url https://target.com/login -U admin -P pass.txt | jq 'select(.event == "credential")'
+  redfox completions zsh > ~/.zfunc/_redfox
+  redfox scan --url https://target.com/login -U users.txt -P pass.txt --discord-webhook https://discord.com/api/webhooks/ID/TOKEN
+  redfox scan --url https://app.target.com --raw-request login.req -U admin -P passwords.txt
+  redfox defaults --url http://192.168.1.1
+  redfox resume .redfox-session
   redfox benchmark --url http://test.com --users users.txt --passwords passwords.txt
+
+رموز الخروج:
+  0  عُثر على بيانات اعتماد (أو نجح الأمر)
+  1  اكتمل الفحص دون إصابات
+  2  خطأ في الاستخدام أو الإعدادات
+  3  الهدف غير قابل للوصول
+  4  أُلغي الفحص (Ctrl+C)
     "#
 )]
 pub struct Cli {
@@ -34,11 +92,11 @@ pub struct Cli {
     #[command(subcommand)]
     pub command: Command,
     
-    /// الوضع التفصيلي
+    /// الوضع التفصيلي: -v معلومات، -vv تصحيح، -vvv تتبع (مع المكتبات الخارجية)
     #[arg(short, long, global = true, action = ArgAction::Count)]
     pub verbose: u8,
     
-    /// الوضع الهادئ (عدم عرض البانر)
+    /// الوضع الهادئ (لا بانر ولا رسائل ولا أشرطة تقدم، الأخطاء والنتائج فقط)
     #[arg(short, long, global = true)]
     pub quiet: bool,
     
@@ -49,58 +107,356 @@ pub struct Cli {
     /// ملف الإعدادات
     #[arg(short, long, global = true, value_name = "FILE")]
     pub config: Option<PathBuf>,
+    
+    /// تنسيق مخرجات stdout [text, json] (json: أسطر NDJSON لبيانات الاعتماد وملخص نهائي، بلا بانر أو ألوان)
+    #[arg(long, global = true, value_enum, default_value = "text", value_name = "FORMAT")]
+    pub output_format: crate::output::OutputFormat,
+    
+    /// تنسيق السجلات [text, json] (json: كائن JSON لكل حدث على stderr للشحن إلى Loki/ELK)
+    #[arg(long, global = true, value_enum, default_value = "text", value_name = "FORMAT")]
+    pub log_format: crate::telemetry::LogFormat,
+    
+    /// تصدير نطاقات الفحص والمحاولات عبر OTLP/gRPC إلى Jaeger أو Tempo (مثال: http://localhost:4317)
+    #[arg(long, global = true, env = "OTEL_EXPORTER_OTLP_ENDPOINT", value_name = "URL")]
+    pub otlp_endpoint: Option<String>,
+    
+    /// كتابة كلمات المرور المكتشفة بنص واضح في التقارير و potfile وملف الجلسة (افتراضيًا تُحفظ كاملة في الخزنة المشفرة فقط)
+    #[arg(long, global = true)]
+    pub plaintext: bool,
 }
 
 /// الأوامر المتاحة
 #[derive(Subcommand, Debug)]
+// تُحلل مرة واحدة عند البدء فلا يهم حجم متغير Scan
+#[allow(clippy::large_enum_variant)]
 pub enum Command {
     /// تنفيذ فحص على هدف
     #[command(arg_required_else_help = true)]
     Scan {
-        /// رابط صفحة تسجيل الدخول (مطلوب)
+        /// رابط صفحة تسجيل الدخول أو عنوان الخدمة host:port (مطلوب إلا مع --targets أو url في الملف الشخصي، ومع --targets يصبح قالبًا للمخطط والمسار)
         #[arg(short, long, value_name = "URL")]
-        url: String,
+        url: Option<String>,
         
-        /// اسم المستخدم أو ملف المستخدمين
-        #[arg(short, long, value_name = "USER|FILE")]
-        user: String,
+        /// أهداف متعددة: نطاق CIDR مع منافذ (10.0.0.0/24:8080,443) يُفحص بحثًا عن المضيفين الحيين، أو ملف روابط
+        #[arg(long, value_name = "SPEC|FILE")]
+        targets: Option<String>,
         
-        /// ملف كلمات المرور (مطلوب)
-        #[arg(short = 'P', long, value_name = "FILE")]
-        password_file: String,
+        /// مهلة فحص اتصال TCP للمضيفين عند --targets بالمللي ثانية
+        #[arg(long, default_value_t = crate::discovery::DEFAULT_PROBE_TIMEOUT_MS, value_name = "MS")]
+        probe_timeout: u64,
         
-        /// عدد الخيوط المتوازية
-        #[arg(short, long, default_value_t = 20, value_name = "NUM")]
-        threads: usize,
+        /// عدد الأهداف المفحوصة في آن واحد عند --targets (حد المعدل و--threads يُطبقان لكل مضيف لا لكل التشغيل)
+        #[arg(long, default_value_t = 1, value_name = "N")]
+        parallel_targets: usize,
         
-        /// مهلة الطلب بالثواني
-        #[arg(long, default_value_t = 30, value_name = "SECONDS")]
-        timeout: u64,
+        /// بروتوكول الهدف [http, ssh, ftp, smtp, mongodb, ldap, snmp] (يُستنتج من مخطط الرابط إن لم يُحدد)
+        #[arg(long, value_name = "PROTOCOL")]
+        protocol: Option<Protocol>,
+        
+        /// وضع TLS لوحدات البروتوكولات [none, explicit, implicit] (explicit = AUTH TLS / STARTTLS، و implicit لمخططات ftps/smtps/ldaps)
+        #[arg(long, value_name = "MODE")]
+        service_tls: Option<TlsMode>,
+        
+        /// قالب اسم الربط في LDAP بالعنصر ^USER^ (مثال: ^USER^@corp.local للـ UPN أو uid=^USER^,ou=people,dc=corp للـ DN)
+        #[arg(long, value_name = "TEMPLATE")]
+        ldap_user_template: Option<String>,
+        
+        /// إصدار SNMP [1, 2c, 3] (في 1 و 2c تُستخدم كلمات المرور كسلاسل مجتمع)
+        #[arg(long, default_value = "2c", value_name = "VERSION")]
+        snmp_version: SnmpVersion,
+        
+        /// بروتوكول مصادقة SNMPv3 [md5, sha]
+        #[arg(long, default_value = "sha", value_name = "PROTOCOL")]
+        snmp_auth: SnmpAuthProtocol,
+        
+        /// مهلة انتظار رد UDP بالمللي ثانية
+        #[arg(long, default_value_t = DEFAULT_UDP_TIMEOUT_MS, value_name = "MS")]
+        udp_timeout: u64,
+        
+        /// عدد مرات إعادة إرسال طلب UDP بدون رد
+        #[arg(long, default_value_t = DEFAULT_UDP_RETRIES, value_name = "NUM")]
+        udp_retries: u32,
+        
+        /// اسم المستخدم أو ملف المستخدمين أو - لـ stdin (يدعم CSV بأعمدة: username, display_name, department, privilege)
+        #[arg(short, long, value_name = "USER|FILE", required_unless_present_any = ["combo_file", "secret_header"])]
+        user: Option<String>,
+        
+        /// ملف كلمات المرور، أو رابط http(s) يُنزل ويُخزن في ~/.redfox/cache، أو - للبث من stdin (مطلوب إلا مع --combo-file)
+        #[arg(short = 'P', long, value_name = "FILE|URL", required_unless_present = "combo_file")]
+        password_file: Option<String>,
+        
+        /// ملف أزواج username:password لاختبار الأزواج المحددة فقط
+        #[arg(long, value_name = "FILE", conflicts_with_all = ["user", "password_file"])]
+        combo_file: Option<String>,
+        
+        /// ملف قواعد hashcat/John (.rule) يُطبق على كل كلمة مرور أثناء الهجوم (مثل best64.rule)
+        #[arg(long, value_name = "FILE", conflicts_with = "combo_file")]
+        rules: Option<String>,
+        
+        /// عدد الخيوط المتوازية (افتراضيًا 20 أو threads في الملف الشخصي)
+        #[arg(short, long, value_name = "NUM")]
+        threads: Option<usize>,
+        
+        /// مهلة الطلب بالثواني (افتراضيًا 30 أو timeout في الملف الشخصي)
+        #[arg(long, value_name = "SECONDS")]
+        timeout: Option<u64>,
         
         /// حفظ النتائج في ملف
         #[arg(short, long, value_name = "FILE")]
         output: Option<String>,
         
-        /// تنسيق المخرجات [txt, json, html, csv, xml]
+        /// تنسيق المخرجات [txt, json, html, pdf, csv, xml, ndjson, sqlite]
         #[arg(long, value_name = "FORMAT")]
         format: Option<String>,
         
-        /// الوضع التفصيلي
-        #[arg(short, long)]
-        verbose: bool,
+        /// قالب Tera مخصص للتقرير (report.html.tera) بدل التنسيقات المدمجة
+        #[arg(long, value_name = "FILE")]
+        template: Option<PathBuf>,
+        
+        /// إخفاء كلمات المرور في التقرير (p*******d)، مع تجزئة HMAC في JSON عند ضبط REDFOX_REDACTION_KEY
+        #[arg(long)]
+        redact: bool,
         
         /// خادم بروكسي (مثال: http://127.0.0.1:8080)
         #[arg(long, value_name = "URL")]
         proxy: Option<String>,
         
-        /// وضع الهجوم [fast, normal, stealth, aggressive]
-        #[arg(short, long, default_value = "normal", value_name = "MODE")]
-        mode: String,
+        /// وضع الهجوم [fast, normal, stealth, aggressive] (افتراضيًا normal)
+        #[arg(short, long, value_name = "MODE")]
+        mode: Option<String>,
         
         /// تحديد حد المعدل (طلبات/ثانية)
         #[arg(long, value_name = "RPS")]
         rate_limit: Option<u32>,
         
+        /// ملف قائمة بروكسيات (بروكسي في كل سطر) للتوزيع بالتناوب
+        #[arg(long, value_name = "FILE")]
+        proxy_file: Option<String>,
+        
+        /// حد المعدل لكل بروكسي (طلبات/ثانية، 1 على الأقل)
+        #[arg(long, value_name = "RPS", value_parser = clap::value_parser!(u32).range(1..))]
+        rate_limit_per_proxy: Option<u32>,
+        
+        /// سباق الاتصالات بين عناوين الهدف المحلولة والتحويل التلقائي عند التعطل
+        #[arg(long)]
+        race_connections: bool,
+        
+        /// إيقاف الفحص بالكامل عند أول بيانات اعتماد صالحة
+        #[arg(long)]
+        stop_on_success: bool,
+        
+        /// إيقاف محاولات المستخدم عند إيجاد كلمة مروره
+        #[arg(long)]
+        stop_per_user: bool,
+        
+        /// تعبير نمطي يطابق محتوى استجابة النجاح
+        #[arg(long, value_name = "REGEX")]
+        success_match: Option<String>,
+        
+        /// تعبير نمطي يطابق محتوى استجابة الفشل
+        #[arg(long, value_name = "REGEX")]
+        failure_match: Option<String>,
+        
+        /// رموز الحالة المتوقعة عند النجاح (مثال: 200,302)
+        #[arg(long, value_name = "CODES", value_delimiter = ',')]
+        success_status: Vec<u16>,
+        
+        /// تعبير نمطي لوجهة التحويل عند النجاح
+        #[arg(long, value_name = "REGEX")]
+        success_redirect: Option<String>,
+        
+        /// الحد الأدنى لفرق طول الاستجابة عن استجابة الفشل (بايت، يتطلب المعايرة)
+        #[arg(long, value_name = "BYTES")]
+        length_delta: Option<usize>,
+        
+        /// ترويسة يجب وجودها عند النجاح (NAME أو NAME:VALUE)
+        #[arg(long, value_name = "HEADER")]
+        success_header: Option<String>,
+        
+        /// أدنى تأخير عشوائي بين الطلبات بالمللي ثانية (الوضع الخفي و --jitter)
+        #[arg(long, default_value_t = crate::scanner::DEFAULT_MIN_DELAY_MS, value_name = "MS")]
+        min_delay: u64,
+        
+        /// أقصى تأخير عشوائي بين الطلبات بالمللي ثانية
+        #[arg(long, default_value_t = crate::scanner::DEFAULT_MAX_DELAY_MS, value_name = "MS")]
+        max_delay: u64,
+        
+        /// تطبيق التأخير العشوائي في الوضع العادي
+        #[arg(long)]
+        jitter: bool,
+        
+        /// كشف WAF قبل الفحص وتطبيق ملف تهرب (خيوط أقل، تأخير عشوائي، تدوير وكيل المستخدم)
+        #[arg(long)]
+        evasion: bool,
+        
+        /// إيقاف الفحص عند اكتشاف CAPTCHA أو تحدي Cloudflare
+        #[arg(long)]
+        halt_on_captcha: bool,
+        
+        /// اختبار أشهر 50 زوج افتراضي (admin:admin...) قبل القائمة الرئيسية
+        #[arg(long)]
+        quick_defaults: bool,
+        
+        /// تعبير XPath يجب أن يتحقق في استجابة XML/SOAP (مثال: "//*[local-name()='LoginResult']='true'")
+        #[arg(long, value_name = "XPATH")]
+        success_xpath: Option<String>,
+        
+        /// عدد محاولات المعايرة الخاطئة لأخذ بصمة استجابة الفشل (0 للتعطيل)
+        #[arg(long, default_value_t = crate::scanner::DEFAULT_CALIBRATION_SAMPLES, value_name = "NUM")]
+        calibration_samples: usize,
+        
+        /// ملف الجلسة لحفظ التقدم واستئنافه لاحقًا
+        #[arg(long, default_value = crate::session::DEFAULT_SESSION_FILE, value_name = "FILE")]
+        session: PathBuf,
+        
+        /// تعطيل حفظ الجلسة
+        #[arg(long)]
+        no_session: bool,
+        
+        /// إلحاق كل نتيجة فور إنتاجها بملف JSONL (لا تضيع النتائج عند انهيار الفحوصات الطويلة)
+        #[arg(long, value_name = "FILE")]
+        output_stream: Option<PathBuf>,
+        
+        /// توزيع الأزواج على عمال عبر طابور Redis بدل الفحص المحلي (redis://host:6379)
+        #[arg(long, value_name = "REDIS_URL")]
+        queue: Option<String>,
+        
+        /// عدد الأزواج في كل دفعة بالطابور الموزع أو عند الإسناد إلى الوكلاء
+        #[arg(long, default_value_t = crate::queue::DEFAULT_BATCH_SIZE, value_name = "N")]
+        batch_size: usize,
+        
+        /// إضافة WASM لبناء الطلب وكشف النجاح (اسم في ~/.redfox/plugins/ أو مسار ملف .wasm)
+        #[arg(long, value_name = "NAME|PATH")]
+        plugin: Option<String>,
+        
+        /// رابط Webhook يستقبل JSON لكل بيانات اعتماد صالحة وعند اكتمال الفحص
+        #[arg(long, value_name = "URL")]
+        webhook: Option<String>,
+        
+        /// سر توقيع حمولة Webhook بـ HMAC-SHA256 (ترويسة X-RedFox-Signature)
+        #[arg(long, value_name = "SECRET", requires = "webhook")]
+        webhook_secret: Option<String>,
+        
+        /// رابط Slack Incoming Webhook لإشعارات بيانات الاعتماد واكتمال الفحص
+        #[arg(long, value_name = "URL", conflicts_with = "slack_token")]
+        slack_webhook: Option<String>,
+        
+        /// رمز بوت Slack (xoxb-...) للإرسال عبر chat.postMessage
+        #[arg(long, value_name = "TOKEN", requires = "slack_channel")]
+        slack_token: Option<String>,
+        
+        /// قناة Slack عند استخدام رمز البوت
+        #[arg(long, value_name = "CHANNEL", requires = "slack_token")]
+        slack_channel: Option<String>,
+        
+        /// رابط Discord Webhook لملخصات بيانات الاعتماد وإحصائيات الفحص
+        #[arg(long, value_name = "URL")]
+        discord_webhook: Option<String>,
+        
+        /// مستلمو ملخص البريد (يتجاوز قائمة to في [notifiers.email])
+        #[arg(long, value_name = "ADDR", value_delimiter = ',')]
+        email_to: Vec<String>,
+        
+        /// تعطيل الإشعارات المعرفة في ملف الإعدادات (خيارات سطر الأوامر تبقى فعالة)
+        #[arg(long)]
+        no_notify: bool,
+        
+        /// الملف الشخصي من ملف الإعدادات ([profiles.<name>]): الهدف والترويسات وقواعد النجاح والإشعارات (افتراضيًا active_profile)
+        #[arg(long, value_name = "NAME")]
+        profile: Option<String>,
+        
+        /// تعطيل potfile (~/.redfox/redfox.pot) لبيانات الاعتماد المكتشفة سابقًا
+        #[arg(long)]
+        no_pot: bool,
+        
+        /// عدم حفظ بيانات الاعتماد المكتشفة في الخزنة المشفرة (~/.redfox/vault.bin)
+        #[arg(long)]
+        no_vault: bool,
+        
+        /// إصدار HTTP [auto, 1.1, 2] (2 يرسل HTTP/2 مباشرة دون تفاوض)
+        #[arg(long, default_value = "auto", value_name = "VERSION")]
+        http_version: HttpVersion,
+        
+        /// تخطي التحقق من شهادة TLS (أهداف المختبر ذات الشهادات الموقعة ذاتيًا)
+        #[arg(long)]
+        insecure: bool,
+        
+        /// أدنى إصدار TLS [1.0, 1.1, 1.2, 1.3]
+        #[arg(long, value_name = "VERSION")]
+        tls_min: Option<TlsVersion>,
+        
+        /// أقصى إصدار TLS [1.0, 1.1, 1.2, 1.3]
+        #[arg(long, value_name = "VERSION")]
+        tls_max: Option<TlsVersion>,
+        
+        /// حزمة شهادات جذر إضافية بصيغة PEM للوثوق بها
+        #[arg(long, value_name = "FILE")]
+        ca_cert: Option<PathBuf>,
+        
+        /// تجاوز ترويسة Host (مضيف افتراضي خلف عنوان IP مشترك)
+        #[arg(long, value_name = "HOST")]
+        host_header: Option<String>,
+        
+        /// تجاوز DNS بصيغة curl (host:port:ip)، يمكن تكراره
+        #[arg(long, value_name = "HOST:PORT:IP")]
+        resolve: Vec<ResolveOverride>,
+        
+        /// اسم المضيف المرسل في SNI مع الاتصال بعنوان الرابط الأصلي (يُستخدم في Host أيضًا)
+        #[arg(long, value_name = "NAME")]
+        sni: Option<String>,
+        
+        /// قالب ترويسة السر لتخمين مفاتيح API (مثال: 'X-Api-Key: ^PASS^' أو 'Authorization: Token ^PASS^')
+        #[arg(long, value_name = "HEADER")]
+        secret_header: Option<String>,
+        
+        /// معرف عميل OAuth2 لمنحة كلمة المرور (--auth-type oauth)
+        #[arg(long, value_name = "ID")]
+        client_id: Option<String>,
+        
+        /// سر عميل OAuth2 للعملاء السريين
+        #[arg(long, value_name = "SECRET")]
+        client_secret: Option<String>,
+        
+        /// نطاقات OAuth2 المطلوبة (مثال: 'openid profile')
+        #[arg(long, value_name = "SCOPE")]
+        scope: Option<String>,
+        
+        /// نوع المصادقة [form, basic, ntlm, header, oauth] (NTLM يقبل المستخدمين بصيغة DOMAIN\user)
+        #[arg(long, default_value = "form", value_name = "TYPE")]
+        auth_type: AuthType,
+        
+        /// تنسيق جسم طلب تسجيل الدخول [form, json, xml]
+        #[arg(long, default_value = "form", value_name = "FORMAT")]
+        body_format: BodyFormat,
+        
+        /// طريقة طلب تسجيل الدخول [POST, GET, PUT, PATCH...]
+        #[arg(long, default_value = "POST", value_name = "METHOD")]
+        method: String,
+        
+        /// قالب سلسلة الاستعلام مع ^USER^ و ^PASS^ (مثال: 'u=^USER^&p=^PASS^')
+        #[arg(long, value_name = "QUERY")]
+        query: Option<String>,
+        
+        /// ملف طلب HTTP خام (محفوظ من Burp) مع ^USER^ و ^PASS^ يُعاد إرساله لكل محاولة
+        #[arg(long, value_name = "FILE", conflicts_with_all = ["body_template", "csrf_field", "preset"])]
+        raw_request: Option<PathBuf>,
+        
+        /// قالب جسم الطلب مع ^USER^ و ^PASS^ (مثال: '{"user":"^USER^","pass":"^PASS^"}')
+        #[arg(long, value_name = "TEMPLATE")]
+        body_template: Option<String>,
+        
+        /// إعداد جاهز لنظام معروف [wordpress, joomla, drupal, magento, cpanel, whm, webmin, plesk, roundcube]
+        #[arg(long, value_name = "NAME", value_parser = preset_names(), ignore_case = true)]
+        preset: Option<String>,
+        
+        /// اسم حقل رمز CSRF المستخرج من صفحة تسجيل الدخول قبل كل محاولة
+        #[arg(long, value_name = "FIELD")]
+        csrf_field: Option<String>,
+        
+        /// محدد CSS لرمز CSRF، أو تعبير نمطي ببادئة re: (افتراضيًا input[name=FIELD])
+        #[arg(long, value_name = "SELECTOR", requires = "csrf_field")]
+        csrf_selector: Option<String>,
+        
         /// حقل اسم المستخدم في النموذج
         #[arg(long, default_value = "username", value_name = "FIELD")]
         username_field: String,
@@ -109,15 +465,15 @@ pub enum Command {
         #[arg(long, default_value = "password", value_name = "FIELD")]
         password_field: String,
         
-        /// ملف الكوكيز (للمصادقة)
+        /// ملف كوكيز (Netscape أو name=value) أو سلسلة Cookie مباشرة
         #[arg(long, value_name = "FILE")]
         cookies: Option<String>,
         
-        /// ترويسات HTTP مخصصة
+        /// ترويسات HTTP مخصصة (كائن JSON، مثال: '{"X-Api-Key":"abc"}')
         #[arg(long, value_name = "JSON")]
         headers: Option<String>,
         
-        /// بيانات POST إضافية
+        /// بيانات POST إضافية مع الجسم الافتراضي (كائن JSON، مثال: '{"remember":"1"}')
         #[arg(long, value_name = "JSON")]
         data: Option<String>,
     },
@@ -160,6 +516,110 @@ pub enum Command {
         /// أنماط التوليد
         #[arg(short, long, value_name = "PATTERNS")]
         patterns: Option<Vec<String>>,
+        
+        /// قناع بأسلوب hashcat لتعداد كامل بدل القائمة العشوائية (مثال: ?u?l?l?l?d?d)
+        #[arg(short, long, value_name = "MASK", conflicts_with = "patterns")]
+        mask: Option<String>,
+        
+        /// تعريف مجموعة مخصصة ?1..?4 بالترتيب (مثال: -c '?l?d' -c 'aeiou')
+        #[arg(short, long = "charset", value_name = "CHARSET", requires = "mask")]
+        charsets: Vec<String>,
+        
+        /// أقل طول للتوليد التسلسلي بأسلوب crunch (كل تركيبات --chars)
+        #[arg(long, value_name = "LEN", conflicts_with_all = ["mask", "patterns"])]
+        min_len: Option<usize>,
+        
+        /// أقصى طول للتوليد التسلسلي (الافتراضي: --min-len)
+        #[arg(long, value_name = "LEN", requires = "min_len")]
+        max_len: Option<usize>,
+        
+        /// مجموعة أحرف التوليد التسلسلي (أحرف حرفية أو ?l ?u ?d ?s ?a ?h)
+        #[arg(long, default_value = "?l", value_name = "CHARSET", requires = "min_len")]
+        chars: String,
+        
+        /// فهرس البداية لاستئناف تعداد قناع أو تسلسل (يُلحق بملف الإخراج)
+        #[arg(long, default_value_t = 0, value_name = "INDEX")]
+        start_index: u64,
+        
+        /// قائمة أساس للتحوير (ملف، رابط أو - لـ stdin)
+        #[arg(short, long, value_name = "FILE", conflicts_with_all = ["mask", "min_len", "patterns"])]
+        base: Option<String>,
+        
+        /// ملف قواعد hashcat/John (.rule) يُطبق على القائمة الأساس قبل بقية التحويرات
+        #[arg(long, value_name = "FILE", requires = "base")]
+        rules: Option<PathBuf>,
+        
+        /// استبدالات leet (a→4/@, e→3, o→0, s→5/$ ...)
+        #[arg(long, requires = "base")]
+        leet: bool,
+        
+        /// متغيرات حالة الأحرف (صغيرة، كبيرة، أول حرف كبير، معكوسة)
+        #[arg(long, requires = "base")]
+        case: bool,
+        
+        /// التهجئات العربية/اللاتينية للأسماء (محمد ↔ mohamed/muhammad/mhmd)، العربيزي، وأخطاء تخطيط لوحة المفاتيح
+        #[arg(long, requires = "base")]
+        arabic: bool,
+        
+        /// لاحقة تُضاف إلى كل متغير (قابل للتكرار)
+        #[arg(long = "suffix", value_name = "SUFFIX", requires = "base")]
+        suffixes: Vec<String>,
+        
+        /// إلحاق السنوات (1990 حتى الحالية) وتواريخ MMYY للسنتين الأخيرتين
+        #[arg(long, requires = "base")]
+        append_years: bool,
+        
+        /// إلحاق الرموز ! و @ و 123 (ومع --append-years: 2024! و 2024@)
+        #[arg(long, requires = "base")]
+        append_symbols: bool,
+        
+        /// أقصى عدد متغيرات لكل كلمة أساس
+        #[arg(long, default_value_t = DEFAULT_MAX_EXPANSION, value_name = "NUM")]
+        max_expansion: usize,
+        
+        /// زحف الموقع المستهدف وحصاد كلماته وبريده (بأسلوب CeWL)
+        #[arg(long, value_name = "URL", conflicts_with_all = ["mask", "min_len", "base", "patterns"])]
+        crawl: Option<String>,
+        
+        /// أقصى عمق روابط للزحف
+        #[arg(long, default_value_t = 2, value_name = "NUM", requires = "crawl")]
+        depth: usize,
+        
+        /// أقصى عدد صفحات للزحف
+        #[arg(long, default_value_t = 200, value_name = "NUM", requires = "crawl")]
+        max_pages: usize,
+        
+        /// أقل طول للكلمة المحصودة
+        #[arg(long, default_value_t = DEFAULT_MIN_WORD_LEN, value_name = "LEN", requires = "crawl")]
+        min_word_len: usize,
+        
+        /// ملف أسماء المستخدمين المرشحة (من البريد وأسماء المؤلفين) لاستخدامه مع -U
+        #[arg(long, value_name = "FILE", requires = "crawl")]
+        users_output: Option<String>,
+        
+        /// قائمة عناصر لتراكيب PRINCE (قابل للتكرار: كلمات، سنوات، فواصل)
+        #[arg(long = "prince", value_name = "FILE", conflicts_with_all = ["mask", "min_len", "base", "crawl", "patterns"])]
+        prince: Vec<String>,
+        
+        /// أقل عدد عناصر في كل تركيب
+        #[arg(long, default_value_t = DEFAULT_MIN_ELEMENTS, value_name = "NUM")]
+        elem_cnt_min: usize,
+        
+        /// أقصى عدد عناصر في كل تركيب
+        #[arg(long, default_value_t = DEFAULT_MAX_ELEMENTS, value_name = "NUM")]
+        elem_cnt_max: usize,
+        
+        /// أقل طول للتركيب
+        #[arg(long, default_value_t = 1, value_name = "LEN")]
+        pw_min: usize,
+        
+        /// أقصى طول للتركيب
+        #[arg(long, default_value_t = DEFAULT_MAX_LENGTH, value_name = "LEN")]
+        pw_max: usize,
+        
+        /// أقصى عدد تراكيب تُكتب
+        #[arg(long, value_name = "NUM")]
+        limit: Option<u64>,
     },
     
     /// التحقق من صحة الهدف
@@ -172,21 +632,529 @@ pub enum Command {
     /// عرض قوائم الكلمات المتاحة
     ListWordlists,
     
+    /// عرض إضافات WASM المتاحة في ~/.redfox/plugins/
+    Plugins,
+    
+    /// أخذ بصمة جهاز مدمج (موجه، كاميرا...) واختبار بيانات اعتماده الافتراضية فقط
+    #[command(arg_required_else_help = true)]
+    Defaults {
+        /// رابط واجهة إدارة الجهاز
+        #[arg(short, long, value_name = "URL")]
+        url: String,
+        
+        /// تحديد الشركة المصنعة يدويًا عند فشل أخذ البصمة
+        #[arg(long, value_name = "VENDOR")]
+        vendor: Option<String>,
+        
+        /// عرض بيانات الاعتماد المطابقة دون اختبارها
+        #[arg(long)]
+        list: bool,
+        
+        /// عدد الخيوط
+        #[arg(short, long, default_value_t = 4, value_name = "NUM")]
+        threads: usize,
+        
+        /// مهلة الاتصال بالثواني
+        #[arg(long, default_value_t = 10, value_name = "SECONDS")]
+        timeout: u64,
+        
+        /// حفظ النتائج في ملف
+        #[arg(short, long, value_name = "FILE")]
+        output: Option<String>,
+        
+        /// تنسيق المخرجات [txt, json, html, pdf, csv, xml, ndjson, sqlite]
+        #[arg(long, value_name = "FORMAT")]
+        format: Option<String>,
+    },
+    
+    /// زحف موقع بعمق محدود لاكتشاف نماذج كلمات المرور وتحديات 401 كأهداف لـ scan --targets
+    #[command(arg_required_else_help = true)]
+    Discover {
+        /// رابط بداية الزحف
+        #[arg(short, long, value_name = "URL")]
+        url: String,
+        
+        /// أقصى عمق روابط
+        #[arg(long, default_value_t = 2, value_name = "NUM")]
+        depth: usize,
+        
+        /// أقصى عدد صفحات
+        #[arg(long, default_value_t = 200, value_name = "NUM")]
+        max_pages: usize,
+        
+        /// مهلة كل طلب بالثواني
+        #[arg(long, default_value_t = 10, value_name = "SECONDS")]
+        timeout: u64,
+        
+        /// ملف الأهداف الناتج (سطر لكل رابط)
+        #[arg(short, long, value_name = "FILE")]
+        output: Option<String>,
+    },
+    
+    /// كسر سر توقيع JWT (HS256/384/512) محليًا من قائمة كلمات دون أي اتصال بالشبكة
+    #[command(arg_required_else_help = true)]
+    JwtCrack {
+        /// رمز JWT المراد كسره
+        #[arg(long, value_name = "JWT")]
+        token: String,
+        
+        /// ملف الأسرار المرشحة
+        #[arg(short = 'P', long, value_name = "FILE")]
+        password_file: String,
+        
+        /// عدد الخيوط
+        #[arg(short, long, default_value_t = num_cpus::get(), value_name = "NUM")]
+        threads: usize,
+    },
+    
+    /// هجوم قاموس دون اتصال على ملف تجزئات (MD5, SHA-1, SHA-256, NTLM, bcrypt)
+    #[command(arg_required_else_help = true)]
+    HashCrack {
+        /// ملف التجزئات (سطر لكل تجزئة أو user:hash)
+        #[arg(long, value_name = "FILE")]
+        hashes: String,
+        
+        /// ملف كلمات المرور
+        #[arg(short = 'P', long, value_name = "FILE")]
+        password_file: String,
+        
+        /// فرض نوع التجزئة بدل الكشف التلقائي [md5, sha1, sha256, ntlm, bcrypt]
+        #[arg(long, value_name = "TYPE")]
+        hash_type: Option<HashKind>,
+        
+        /// عدد الخيوط
+        #[arg(short, long, default_value_t = num_cpus::get(), value_name = "NUM")]
+        threads: usize,
+        
+        /// حفظ النتائج في ملف
+        #[arg(short, long, value_name = "FILE")]
+        output: Option<String>,
+        
+        /// تنسيق المخرجات [txt, json, html, pdf, csv, xml, ndjson, sqlite]
+        #[arg(long, value_name = "FORMAT")]
+        format: Option<String>,
+    },
+    
+    /// تدقيق ملف htpasswd أو shadow دون اتصال مقابل قائمة كلمات
+    #[command(arg_required_else_help = true)]
+    AuditFile {
+        /// ملف كلمات المرور (.htpasswd أو /etc/shadow)
+        #[arg(long, value_name = "FILE")]
+        file: String,
+        
+        /// ملف كلمات المرور المرشحة
+        #[arg(short = 'P', long, value_name = "FILE")]
+        password_file: String,
+        
+        /// تنسيق الملف [auto, htpasswd, shadow]
+        #[arg(long, default_value = "auto", value_name = "FORMAT")]
+        file_format: FileFormat,
+        
+        /// عدد الخيوط
+        #[arg(short, long, default_value_t = num_cpus::get(), value_name = "NUM")]
+        threads: usize,
+        
+        /// حفظ النتائج في ملف
+        #[arg(short, long, value_name = "FILE")]
+        output: Option<String>,
+        
+        /// تنسيق المخرجات [txt, json, html, pdf, csv, xml, ndjson, sqlite]
+        #[arg(long, value_name = "FORMAT")]
+        format: Option<String>,
+    },
+    
+    /// فحص قائمة بروكسيات وإخراج ملف منظف ومرتب لـ --proxy-file
+    #[command(arg_required_else_help = true)]
+    ProxyCheck {
+        /// ملف قائمة البروكسيات
+        #[arg(value_name = "FILE")]
+        input: String,
+        
+        /// ملف الإخراج للبروكسيات العاملة (مرتبة حسب زمن الاستجابة)
+        #[arg(short, long, value_name = "FILE")]
+        output: Option<String>,
+        
+        /// نقطة الفحص (يجب أن تعيد JSON يحتوي origin و headers)
+        #[arg(long, default_value = crate::proxy_check::DEFAULT_CHECK_URL, value_name = "URL")]
+        check_url: String,
+        
+        /// مهلة الفحص بالثواني
+        #[arg(long, default_value_t = 10, value_name = "SECONDS")]
+        timeout: u64,
+        
+        /// عدد الفحوصات المتوازية
+        #[arg(short, long, default_value_t = 50, value_name = "NUM")]
+        threads: usize,
+        
+        /// الحد الأدنى لمستوى إخفاء الهوية [transparent, anonymous, elite]
+        #[arg(long, value_name = "LEVEL")]
+        min_anonymity: Option<AnonymityLevel>,
+    },
+    
+    /// استئناف فحص منقطع من ملف جلسة
+    Resume {
+        /// ملف الجلسة
+        #[arg(default_value = crate::session::DEFAULT_SESSION_FILE, value_name = "SESSION")]
+        session: PathBuf,
+    },
+    
+    /// إدارة جلسات الفحص المحفوظة (الهدف، نسبة التقدم، بيانات الاعتماد المكتشفة)
+    #[command(arg_required_else_help = true)]
+    Sessions {
+        /// العملية المطلوبة
+        #[command(subcommand)]
+        action: SessionsCommand,
+    },
+    
+    /// إعادة توليد التقارير من نتائج محفوظة
+    #[command(arg_required_else_help = true)]
+    Report {
+        /// العملية المطلوبة
+        #[command(subcommand)]
+        action: ReportCommand,
+    },
+    
+    /// الخزنة المشفرة لبيانات الاعتماد المكتشفة (عبارة المرور من REDFOX_VAULT_PASSPHRASE أو الطرفية)
+    #[command(arg_required_else_help = true)]
+    Vault {
+        /// العملية المطلوبة
+        #[command(subcommand)]
+        action: VaultCommand,
+    },
+    
+    /// أدوات معالجة قوائم الكلمات
+    #[command(arg_required_else_help = true)]
+    Wordlist {
+        /// العملية المطلوبة
+        #[command(subcommand)]
+        action: WordlistCommand,
+    },
+    
+    /// الوضع التفاعلي (set target, set wordlist, run, show results)
+    Shell,
+    
+    /// عامل يسحب دفعات بيانات الاعتماد من طابور Redis وينفذها ويعيد نتائجها
+    Worker {
+        /// رابط خادم Redis
+        #[arg(long, value_name = "REDIS_URL")]
+        queue: String,
+    },
+    
+    /// وضع الخادم: واجهة REST لإرسال مهام الفحص ومتابعتها وبث نتائجها (SSE) وتنزيل تقاريرها
+    Serve {
+        /// عنوان الاستماع
+        #[arg(long, default_value = crate::server::DEFAULT_LISTEN, value_name = "ADDR")]
+        listen: std::net::SocketAddr,
+        
+        /// رمز Bearer المطلوب في ترويسة Authorization (موصى به خارج localhost)
+        #[arg(long, env = "REDFOX_API_TOKEN", value_name = "TOKEN", hide_env_values = true)]
+        token: Option<String>,
+        
+        /// عنوان استماع الوكلاء عبر TLS المتبادل (المهام تُوزع على الوكلاء المتصلين)
+        #[arg(long, value_name = "ADDR", requires_all = ["cert", "key", "client_ca"])]
+        agents_listen: Option<std::net::SocketAddr>,
+        
+        /// شهادة المنسق (PEM)
+        #[arg(long, value_name = "FILE")]
+        cert: Option<PathBuf>,
+        
+        /// مفتاح المنسق الخاص (PEM)
+        #[arg(long, value_name = "FILE")]
+        key: Option<PathBuf>,
+        
+        /// سلطة التوقيع التي يجب أن تكون شهادات الوكلاء صادرة عنها (PEM)
+        #[arg(long, value_name = "FILE")]
+        client_ca: Option<PathBuf>,
+    },
+    
+    /// وكيل دائم ينضم إلى المنسق عبر TLS متبادل المصادقة وينفذ المهام المسندة إليه
+    Agent {
+        /// عنوان المنسق (host:port)
+        #[arg(long, value_name = "HOST:PORT")]
+        join: String,
+        
+        /// شهادة الوكيل (PEM)
+        #[arg(long, value_name = "FILE")]
+        cert: PathBuf,
+        
+        /// مفتاح الوكيل الخاص (PEM)
+        #[arg(long, value_name = "FILE")]
+        key: PathBuf,
+        
+        /// سلطة التوقيع الموثوقة لشهادة المنسق (PEM)
+        #[arg(long, value_name = "FILE")]
+        ca: PathBuf,
+        
+        /// اسم الوكيل لدى المنسق (الافتراضي: اسم المضيف)
+        #[arg(long, value_name = "NAME")]
+        name: Option<String>,
+        
+        /// عدد خيوط المعالجة المعلنة للمنسق
+        #[arg(long, default_value_t = crate::scanner::DEFAULT_THREADS, value_name = "N")]
+        capacity: usize,
+    },
+    
+    /// توليد سكربت الإكمال التلقائي للصدفة (مثال: redfox completions bash > /etc/bash_completion.d/redfox)
+    #[command(arg_required_else_help = true)]
+    Completions {
+        /// الصدفة [bash, zsh, fish, powershell, elvish]
+        #[arg(value_enum, value_name = "SHELL")]
+        shell: Shell,
+    },
+    
+    /// إدارة ملف الإعدادات والملفات الشخصية
+    #[command(arg_required_else_help = true)]
+    Config {
+        /// العملية المطلوبة
+        #[command(subcommand)]
+        action: ConfigCommand,
+    },
+    
     /// التحقق من التحديثات
     Update,
 }
 
+/// عمليات التقارير
+#[derive(Subcommand, Debug)]
+pub enum ReportCommand {
+    /// إعادة توليد تقرير من نتائج خام محفوظة (JSON, NDJSON, SQLite) دون إعادة الفحص
+    Regenerate {
+        /// ملف النتائج المحفوظة (.json, .ndjson, .jsonl, .db, .sqlite)
+        #[arg(value_name = "FILE")]
+        input: String,
+        
+        /// اسم ملف التقرير الناتج
+        #[arg(short, long, value_name = "FILE")]
+        output: String,
+        
+        /// تنسيق التقرير [txt, json, html, pdf, csv, xml, ndjson, sqlite]
+        #[arg(long, default_value = "html", value_name = "FORMAT")]
+        format: String,
+        
+        /// قالب Tera مخصص للتقرير (يتقدم على --format)
+        #[arg(long, value_name = "FILE")]
+        template: Option<PathBuf>,
+        
+        /// إخفاء كلمات المرور في التقرير (p*******d)، مع تجزئة HMAC في JSON عند ضبط REDFOX_REDACTION_KEY
+        #[arg(long)]
+        redact: bool,
+    },
+    
+    /// دمج عدة تقارير أو ملفات نتائج سابقة في تقرير واحد مع إزالة التكرار
+    Merge {
+        /// ملفات النتائج أو تقارير JSON السابقة
+        #[arg(value_name = "FILE", num_args = 2.., required = true)]
+        inputs: Vec<String>,
+        
+        /// اسم ملف التقرير الناتج
+        #[arg(short, long, value_name = "FILE")]
+        output: String,
+        
+        /// تنسيق التقرير [txt, json, html, pdf, csv, xml, ndjson, sqlite]
+        #[arg(long, default_value = "html", value_name = "FORMAT")]
+        format: String,
+        
+        /// قالب Tera مخصص للتقرير (يتقدم على --format)
+        #[arg(long, value_name = "FILE")]
+        template: Option<PathBuf>,
+        
+        /// إخفاء كلمات المرور في التقرير (p*******d)، مع تجزئة HMAC في JSON عند ضبط REDFOX_REDACTION_KEY
+        #[arg(long)]
+        redact: bool,
+    },
+}
+
+/// عمليات الجلسات
+#[derive(Subcommand, Debug)]
+pub enum SessionsCommand {
+    /// عرض الجلسات المسجلة
+    List,
+    
+    /// عرض تفاصيل جلسة وبيانات الاعتماد المكتشفة فيها
+    Show {
+        /// رقم الجلسة في القائمة أو مسار ملفها
+        #[arg(value_name = "SESSION")]
+        session: String,
+        
+        /// عرض كلمات المرور المكتشفة (مخفية في الجلسة ما لم يُفحص بـ --plaintext، والكاملة في: redfox vault list --show)
+        #[arg(long)]
+        show: bool,
+    },
+    
+    /// حذف ملف جلسة وإزالته من القائمة
+    Delete {
+        /// رقم الجلسة في القائمة أو مسار ملفها
+        #[arg(value_name = "SESSION")]
+        session: String,
+    },
+}
+
+/// عمليات ملف الإعدادات
+#[derive(Subcommand, Debug)]
+pub enum ConfigCommand {
+    /// إنشاء ملف إعدادات من القالب (~/.redfox/config.toml أو --config)
+    Init {
+        /// الكتابة فوق ملف موجود
+        #[arg(long)]
+        force: bool,
+    },
+    
+    /// عرض الملفات الشخصية وإعدادات ملف شخصي (الفعال افتراضيًا)
+    Show {
+        /// اسم الملف الشخصي
+        #[arg(value_name = "PROFILE")]
+        profile: Option<String>,
+    },
+    
+    /// تعيين الملف الشخصي الفعال عند غياب --profile
+    Use {
+        /// اسم الملف الشخصي
+        #[arg(value_name = "PROFILE")]
+        profile: String,
+    },
+}
+
+/// عمليات الخزنة
+#[derive(Subcommand, Debug)]
+pub enum VaultCommand {
+    /// عرض بيانات الاعتماد المحفوظة (كلمات المرور مخفية افتراضيًا)
+    List {
+        /// تصفية حسب جزء من الهدف
+        #[arg(long, value_name = "TEXT")]
+        target: Option<String>,
+        
+        /// إظهار كلمات المرور
+        #[arg(long)]
+        show: bool,
+    },
+    
+    /// تصدير الخزنة كنص صريح (CSV أو JSON حسب الامتداد)
+    Export {
+        /// ملف الإخراج
+        #[arg(short, long, value_name = "FILE")]
+        output: PathBuf,
+    },
+}
+
+/// عمليات قوائم الكلمات
+#[derive(Subcommand, Debug)]
+pub enum WordlistCommand {
+    /// دمج عدة قوائم في ملف واحد
+    Merge {
+        /// ملفات الإدخال
+        #[arg(required = true, value_name = "FILE")]
+        inputs: Vec<String>,
+        
+        /// ملف الإخراج
+        #[arg(short, long, value_name = "FILE")]
+        output: String,
+        
+        /// إزالة التكرارات أثناء الدمج
+        #[arg(short, long)]
+        dedupe: bool,
+        
+        /// إزالة تكرار احتمالية بمرشح بلوم بذاكرة ثابتة للقوائم الضخمة (نسبة الإيجابيات الكاذبة، افتراضيًا 0.001)
+        #[arg(long, value_name = "RATE", num_args = 0..=1, default_missing_value = "0.001", requires = "dedupe")]
+        bloom: Option<f64>,
+    },
+    
+    /// إزالة التكرارات مع الحفاظ على الترتيب
+    Dedupe {
+        /// ملف الإدخال
+        #[arg(value_name = "FILE")]
+        input: String,
+        
+        /// ملف الإخراج
+        #[arg(short, long, value_name = "FILE")]
+        output: String,
+    },
+    
+    /// فرز القائمة (فرز خارجي للملفات الكبيرة)
+    Sort {
+        /// ملف الإدخال
+        #[arg(value_name = "FILE")]
+        input: String,
+        
+        /// ملف الإخراج
+        #[arg(short, long, value_name = "FILE")]
+        output: String,
+        
+        /// إزالة التكرارات بعد الفرز
+        #[arg(short, long)]
+        unique: bool,
+    },
+    
+    /// تصفية القائمة حسب سياسة كلمات المرور
+    Filter {
+        /// ملف الإدخال
+        #[arg(value_name = "FILE")]
+        input: String,
+        
+        /// ملف الإخراج
+        #[arg(short, long, value_name = "FILE")]
+        output: String,
+        
+        /// السياسة (مثال: min=8,max=64,lower,upper,digit,special)
+        #[arg(long, value_name = "POLICY")]
+        policy: PasswordPolicy,
+    },
+    
+    /// إحصائيات القائمة (توزيع الأطوال، تغطية الأحرف، التكرارات، المدة المقدرة للهجوم)
+    Stats {
+        /// ملف الإدخال (أو رابط، أو - لـ stdin)
+        #[arg(value_name = "FILE")]
+        input: String,
+        
+        /// معدل الطلبات المفترض لتقدير المدة (طلب/ثانية)
+        #[arg(long, default_value_t = 10, value_name = "RPS")]
+        rps: u32,
+        
+        /// عدد المستخدمين المستهدفين لتقدير المدة
+        #[arg(long, default_value_t = 1, value_name = "N")]
+        users: usize,
+    },
+    
+    /// تقسيم القائمة إلى أجزاء متوازنة مع بيان JSON (للعمال الموزعين أو جلسات خفية على عدة أيام)
+    Split {
+        /// ملف الإدخال
+        #[arg(value_name = "FILE")]
+        input: String,
+        
+        /// عدد الأجزاء (متوازنة بعدد الإدخالات)
+        #[arg(long, value_name = "N", required_unless_present = "bytes", conflicts_with = "bytes")]
+        parts: Option<usize>,
+        
+        /// أقصى حجم لكل جزء (مثال: 512K، 100M، 1G)
+        #[arg(long, value_name = "SIZE")]
+        bytes: Option<ByteSize>,
+        
+        /// مجلد الأجزاء (افتراضيًا مجلد الملف الأصلي)
+        #[arg(short, long, value_name = "DIR")]
+        output_dir: Option<PathBuf>,
+    },
+}
+
 impl Cli {
     /// تحليل سطر الأوامر
     pub fn parse() -> Self {
-        let cli = Self::parse_from(std::env::args());
+        Self::parse_from(std::env::args())
+    }
+    
+    /// كتابة سكربت الإكمال إلى stdout (مع إكمال قوائم الكلمات المثبتة لـ --password-file)
+    pub fn print_completions(shell: Shell) {
+        let wordlists: Vec<PossibleValue> = crate::utils::wordlists::list_available()
+            .into_iter()
+            .map(PossibleValue::new)
+            .collect();
         
-        // عرض البانر إذا لم يكن الوضع هادئًا
-        if !cli.quiet {
-            // سيتم عرض البانر في main.rs
+        let mut command = Self::command();
+        if !wordlists.is_empty() {
+            command = command.mut_subcommand("scan", |scan| {
+                scan.mut_arg("password_file", |arg| arg.value_parser(PossibleValuesParser::new(wordlists)))
+            });
         }
         
-        cli
+        clap_complete::generate(shell, &mut command, "redfox", &mut std::io::stdout());
     }
     
     /// التحقق مما إذا كان الأمر يحتاج للجذر
@@ -200,39 +1168,7 @@ impl Cli {
     }
 }
 
-/// إعدادات الفحص
-#[derive(Debug, Clone)]
-pub struct ScanSettings {
-    pub url: String,
-    pub user_input: String,
-    pub password_file: String,
-    pub threads: usize,
-    pub timeout: u64,
-    pub mode: AttackMode,
-    pub rate_limit: Option<u32>,
-    pub proxy: Option<String>,
-    pub output_format: String,
-}
-
-/// أنماط الهجوم
-#[derive(Debug, Clone, Copy, PartialEq)]
-pub enum AttackMode {
-    Fast,
-    Normal,
-    Stealth,
-    Aggressive,
-}
-
-impl std::str::FromStr for AttackMode {
-    type Err = String;
-    
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        match s.to_lowercase().as_str() {
-            "fast" => Ok(AttackMode::Fast),
-            "normal" => Ok(AttackMode::Normal),
-            "stealth" => Ok(AttackMode::Stealth),
-            "aggressive" => Ok(AttackMode::Aggressive),
-            _ => Err(format!("وضع غير صالح: {}", s)),
-        }
-    }
+/// أسماء الإعدادات الجاهزة (للتحقق من --preset وإكماله)
+fn preset_names() -> PossibleValuesParser {
+    PossibleValuesParser::new(crate::modules::presets::PRESETS.iter().map(|preset| preset.name))
 }
\ No newline at end of file
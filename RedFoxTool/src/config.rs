@@ -0,0 +1,254 @@
+//! ملف الإعدادات
+//! تحميل إعدادات RedFoxTool (TOML) والملفات الشخصية المسماة
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use anyhow::{Result, Context};
+use serde::{Deserialize, Serialize};
+use tokio::fs as tokio_fs;
+
+use crate::bruteforcer::AttackMode;
+use crate::matcher::SuccessCriteria;
+use crate::notifiers::NotifiersConfig;
+use crate::scanner::ScanOptions;
+
+/// مسار ملف الإعدادات الافتراضي
+pub const DEFAULT_CONFIG: &str = "~/.redfox/config.toml";
+
+/// قالب ملف الإعدادات الذي ينشئه `config init`
+pub const CONFIG_TEMPLATE: &str = include_str!("../configs/redfox.toml");
+
+/// إعدادات الأداة (الأقسام غير المعروفة تُتجاهل)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AppConfig {
+    /// الملف الشخصي الفعال عند غياب --profile (يُعين بـ `config use`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub active_profile: Option<String>,
+    
+    /// الإشعارات العامة
+    #[serde(default)]
+    pub notifiers: NotifiersConfig,
+    
+    /// الملفات الشخصية المسماة ([profiles.<name>])
+    #[serde(default)]
+    pub profiles: BTreeMap<String, Profile>,
+}
+
+/// ملف شخصي مسمى (خيارات سطر الأوامر تتقدم على قيمه)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Profile {
+    /// رابط الهدف الافتراضي
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    
+    /// عدد الخيوط المتوازية
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub threads: Option<usize>,
+    
+    /// مهلة الطلب بالثواني
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeout: Option<u64>,
+    
+    /// وضع الهجوم
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mode: Option<String>,
+    
+    /// خادم بروكسي
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub proxy: Option<String>,
+    
+    /// حد المعدل (طلبات/ثانية)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rate_limit: Option<u32>,
+    
+    /// ترويسات HTTP مخصصة
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub headers: BTreeMap<String, String>,
+    
+    /// قواعد النجاح ([profiles.<name>.success])
+    #[serde(default, skip_serializing_if = "SuccessCriteria::is_empty")]
+    pub success: SuccessCriteria,
+    
+    /// إشعارات الملف الشخصي (تتقدم على العامة)
+    #[serde(default)]
+    pub notifiers: NotifiersConfig,
+}
+
+impl Profile {
+    /// خيارات الماسح من قيم الملف الشخصي (غير المحدد يبقى على القيم الافتراضية)
+    pub fn scan_options(&self) -> ScanOptions {
+        let defaults = ScanOptions::default();
+        
+        ScanOptions {
+            threads: self.threads.unwrap_or(defaults.threads),
+            timeout: self.timeout.map_or(defaults.timeout, Duration::from_secs),
+            mode: self.mode.as_deref().map_or(defaults.mode, AttackMode::from_name),
+            rate_limit: self.rate_limit,
+            success_criteria: self.success.clone(),
+            ..defaults
+        }
+    }
+    
+    /// دمج ترويسات الملف الشخصي مع ترويسات --headers (JSON) التي تتقدم عليها
+    pub fn merge_headers(&self, headers: Option<String>) -> Result<Option<String>> {
+        let mut merged: serde_json::Map<String, serde_json::Value> = self
+            .headers
+            .iter()
+            .map(|(name, value)| (name.clone(), serde_json::Value::String(value.clone())))
+            .collect();
+        
+        if let Some(headers) = headers {
+            let overrides: serde_json::Map<String, serde_json::Value> = serde_json::from_str(&headers)
+                .context("ترويسات --headers يجب أن تكون كائن JSON")?;
+            merged.extend(overrides);
+        }
+        
+        Ok((!merged.is_empty()).then(|| serde_json::Value::Object(merged).to_string()))
+    }
+}
+
+/// مسار ملف الإعدادات (المحدد بـ --config أو الافتراضي)
+pub fn config_path(path: Option<&Path>) -> PathBuf {
+    path.map_or_else(
+        || PathBuf::from(shellexpand::tilde(DEFAULT_CONFIG).as_ref()),
+        Path::to_path_buf,
+    )
+}
+
+impl AppConfig {
+    /// تحميل ملف الإعدادات (غياب الملف الافتراضي ليس خطأ)
+    pub async fn load(path: Option<&Path>) -> Result<Self> {
+        let explicit = path.is_some();
+        let path = config_path(path);
+        if !explicit && !path.exists() {
+            return Ok(Self::default());
+        }
+        
+        let content = tokio_fs::read_to_string(&path)
+            .await
+            .context(format!("فشل في قراءة ملف الإعدادات: {}", path.display()))?;
+        
+        toml::from_str(&content).context(format!("ملف إعدادات غير صالح: {}", path.display()))
+    }
+    
+    /// اسم الملف الشخصي الفعال (--profile أو active_profile)
+    pub fn profile_name<'a>(&'a self, profile: Option<&'a str>) -> Option<&'a str> {
+        profile.or(self.active_profile.as_deref())
+    }
+    
+    /// الملف الشخصي الفعال (ملف فارغ إن لم يُحدد أي ملف)
+    pub fn profile(&self, profile: Option<&str>) -> Result<Profile> {
+        match self.profile_name(profile) {
+            Some(name) => self
+                .profiles
+                .get(name)
+                .cloned()
+                .ok_or_else(|| anyhow::anyhow!("ملف شخصي غير موجود: {}", name)),
+            None => Ok(Profile::default()),
+        }
+    }
+    
+    /// إعدادات الإشعارات لملف شخصي (أو العامة فقط بدونه)
+    pub fn notifiers(&self, profile: Option<&str>) -> Result<NotifiersConfig> {
+        let profile = self.profile(profile)?;
+        Ok(self.notifiers.clone().merge(profile.notifiers))
+    }
+}
+
+/// إنشاء ملف إعدادات من القالب (يرفض الكتابة فوق ملف موجود بدون force)
+pub async fn init(path: &Path, force: bool) -> Result<()> {
+    if path.exists() && !force {
+        return Err(anyhow::anyhow!("ملف الإعدادات موجود مسبقًا (استخدم --force): {}", path.display()));
+    }
+    
+    if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        tokio_fs::create_dir_all(parent).await?;
+    }
+    
+    tokio_fs::write(path, CONFIG_TEMPLATE)
+        .await
+        .context(format!("فشل في كتابة ملف الإعدادات: {}", path.display()))
+}
+
+/// تعيين الملف الشخصي الفعال في ملف الإعدادات (مع الحفاظ على التعليقات والتنسيق)
+pub async fn use_profile(path: &Path, profile: &str) -> Result<()> {
+    let content = tokio_fs::read_to_string(path)
+        .await
+        .context(format!("فشل في قراءة ملف الإعدادات: {} (أنشئه بـ redfox config init)", path.display()))?;
+    
+    let config: AppConfig = toml::from_str(&content)
+        .context(format!("ملف إعدادات غير صالح: {}", path.display()))?;
+    if !config.profiles.contains_key(profile) {
+        return Err(anyhow::anyhow!(
+            "ملف شخصي غير موجود: {} (المتاح: {})",
+            profile,
+            config.profiles.keys().cloned().collect::<Vec<_>>().join(", ")
+        ));
+    }
+    
+    let mut document: toml_edit::DocumentMut = content.parse()?;
+    document["active_profile"] = toml_edit::value(profile);
+    
+    tokio_fs::write(path, document.to_string())
+        .await
+        .context(format!("فشل في كتابة ملف الإعدادات: {}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_profile_notifiers_override_global() {
+        let config: AppConfig = toml::from_str(
+            r##"
+            [general]
+            name = "RedFoxTool"
+            
+            [notifiers.slack]
+            webhook_url = "https://hooks.slack.com/services/global"
+            
+            [profiles.client-a.notifiers.slack]
+            bot_token = "xoxb-1"
+            channel = "#client-a"
+            "##,
+        )
+        .unwrap();
+        
+        let global = config.notifiers(None).unwrap().slack.unwrap();
+        let client = config.notifiers(Some("client-a")).unwrap().slack.unwrap();
+        
+        assert!(global.webhook_url.is_some());
+        assert_eq!(client.channel.as_deref(), Some("#client-a"));
+        assert!(client.webhook_url.is_none());
+        assert!(config.notifiers(Some("missing")).is_err());
+    }
+    
+    #[test]
+    fn test_active_profile_defaults_and_headers() {
+        let config: AppConfig = toml::from_str(
+            r#"
+            active_profile = "client-a"
+            
+            [profiles.client-a]
+            url = "https://client-a.example/login"
+            threads = 5
+            headers = { "X-Api-Key" = "abc", "X-Env" = "prod" }
+            
+            [profiles.client-a.success]
+            status_codes = [302]
+            "#,
+        )
+        .unwrap();
+        
+        let profile = config.profile(None).unwrap();
+        let headers = profile.merge_headers(Some(r#"{"X-Env":"staging"}"#.to_string())).unwrap().unwrap();
+        let headers: serde_json::Value = serde_json::from_str(&headers).unwrap();
+        
+        assert_eq!(profile.threads, Some(5));
+        assert_eq!(profile.success.status_codes, vec![302]);
+        assert_eq!(headers["X-Api-Key"], "abc");
+        assert_eq!(headers["X-Env"], "staging");
+    }
+}
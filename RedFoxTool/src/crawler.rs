@@ -0,0 +1,294 @@
+//! زاحف اكتشاف صفحات الدخول
+//! زحف محدود العمق على نفس المضيف لتحديد نماذج كلمات المرور وتحديات 401 كأهداف هجوم محتملة
+
+use std::collections::{HashSet, VecDeque};
+use std::time::Duration;
+use anyhow::{Result, Context};
+use once_cell::sync::Lazy;
+use reqwest::header::WWW_AUTHENTICATE;
+use scraper::{ElementRef, Html, Selector};
+use url::Url;
+
+/// امتدادات الملفات الثابتة التي لا تُزحف
+const SKIPPED_EXTENSIONS: &[&str] = &[
+    "css", "js", "png", "jpg", "jpeg", "gif", "svg", "ico", "webp", "woff", "woff2", "ttf", "pdf", "zip", "mp4",
+];
+
+#[allow(clippy::unwrap_used)] // محدد ثابت لا يفشل تحليله
+static LINK_SELECTOR: Lazy<Selector> = Lazy::new(|| Selector::parse("a[href], iframe[src]").unwrap());
+#[allow(clippy::unwrap_used)] // محدد ثابت لا يفشل تحليله
+static FORM_SELECTOR: Lazy<Selector> = Lazy::new(|| Selector::parse("form").unwrap());
+#[allow(clippy::unwrap_used)] // محدد ثابت لا يفشل تحليله
+static PASSWORD_SELECTOR: Lazy<Selector> = Lazy::new(|| Selector::parse("input[type=password]").unwrap());
+#[allow(clippy::unwrap_used)] // محدد ثابت لا يفشل تحليله
+static INPUT_SELECTOR: Lazy<Selector> = Lazy::new(|| Selector::parse("input[name]").unwrap());
+
+/// نوع نقطة الدخول المكتشفة
+#[derive(Debug, Clone, PartialEq)]
+pub enum LoginKind {
+    /// نموذج يحتوي حقل كلمة مرور
+    PasswordForm,
+    /// تحدي مصادقة HTTP (401 مع WWW-Authenticate)
+    HttpAuth(String),
+}
+
+/// هدف هجوم محتمل
+#[derive(Debug, Clone)]
+pub struct LoginCandidate {
+    /// الصفحة التي وُجد فيها
+    pub page: String,
+    
+    /// الرابط المستهدف (وجهة النموذج أو الصفحة المحمية)
+    pub target: String,
+    
+    /// طريقة الإرسال
+    pub method: String,
+    
+    /// نوع نقطة الدخول
+    pub kind: LoginKind,
+    
+    /// اسم حقل المستخدم المرجح
+    pub username_field: Option<String>,
+    
+    /// اسم حقل كلمة المرور
+    pub password_field: Option<String>,
+}
+
+impl LoginCandidate {
+    /// خيارات scan المقترحة لهذا الهدف
+    pub fn scan_hint(&self) -> String {
+        match &self.kind {
+            LoginKind::HttpAuth(scheme) if scheme.eq_ignore_ascii_case("ntlm") => "--auth-type ntlm".to_string(),
+            LoginKind::HttpAuth(_) => "--auth-type basic".to_string(),
+            LoginKind::PasswordForm => {
+                let mut hint = Vec::new();
+                if self.method != "POST" {
+                    hint.push(format!("--method {}", self.method));
+                }
+                if let Some(field) = &self.username_field {
+                    hint.push(format!("--username-field {}", field));
+                }
+                if let Some(field) = &self.password_field {
+                    hint.push(format!("--password-field {}", field));
+                }
+                hint.join(" ")
+            }
+        }
+    }
+}
+
+/// حدود الزحف
+#[derive(Debug, Clone)]
+pub struct CrawlOptions {
+    /// أقصى عمق روابط من صفحة البداية
+    pub max_depth: usize,
+    
+    /// أقصى عدد صفحات
+    pub max_pages: usize,
+    
+    /// مهلة كل طلب
+    pub timeout: Duration,
+}
+
+/// محتوى صفحة جُلبت أثناء الزحف
+#[derive(Debug, Clone)]
+pub enum Page {
+    /// تحدي مصادقة HTTP (401) بمخطط WWW-Authenticate
+    Challenge(String),
+    /// مستند HTML
+    Html(String),
+}
+
+/// زحف الموقع وإرجاع نقاط الدخول المكتشفة (بدون تكرار للرابط المستهدف)
+pub async fn crawl(start: &str, options: &CrawlOptions) -> Result<Vec<LoginCandidate>> {
+    let mut targets = HashSet::new();
+    let mut candidates = Vec::new();
+    
+    walk(start, options, |page, content| {
+        let (links, found) = match content {
+            Page::Challenge(scheme) => (
+                Vec::new(),
+                vec![LoginCandidate {
+                    page: page.to_string(),
+                    target: page.to_string(),
+                    method: "GET".to_string(),
+                    kind: LoginKind::HttpAuth(scheme),
+                    username_field: None,
+                    password_field: None,
+                }],
+            ),
+            Page::Html(body) => analyze_page(page, &body),
+        };
+        
+        for candidate in found {
+            if targets.insert(candidate.target.clone()) {
+                candidates.push(candidate);
+            }
+        }
+        links
+    })
+    .await?;
+    
+    Ok(candidates)
+}
+
+/// زحف بالعرض على نفس المضيف: visit تُستدعى لكل صفحة وتعيد روابطها للمتابعة (يُرجع عدد الصفحات المجلوبة)
+pub async fn walk<F>(start: &str, options: &CrawlOptions, mut visit: F) -> Result<usize>
+where
+    F: FnMut(&Url, Page) -> Vec<Url>,
+{
+    let start = Url::parse(start).context(format!("رابط غير صالح: {}", start))?;
+    let client = reqwest::Client::builder()
+        .timeout(options.timeout)
+        .danger_accept_invalid_certs(true)
+        .build()
+        .context("فشل في إنشاء عميل HTTP")?;
+    
+    let mut queue = VecDeque::from([(start.clone(), 0)]);
+    let mut visited = HashSet::from([start.to_string()]);
+    let mut fetched = 0;
+    
+    while let Some((page, depth)) = queue.pop_front() {
+        if fetched >= options.max_pages {
+            break;
+        }
+        fetched += 1;
+        
+        let response = match client.get(page.clone()).send().await {
+            Ok(response) => response,
+            Err(e) => {
+                tracing::debug!(url = %page, error = %e, "فشل في جلب الصفحة");
+                continue;
+            }
+        };
+        
+        if response.status().as_u16() == 401 {
+            let scheme = response
+                .headers()
+                .get(WWW_AUTHENTICATE)
+                .and_then(|v| v.to_str().ok())
+                .and_then(|v| v.split_whitespace().next())
+                .unwrap_or("Basic")
+                .to_string();
+            
+            visit(&page, Page::Challenge(scheme));
+            continue;
+        }
+        
+        let is_html = response
+            .headers()
+            .get(reqwest::header::CONTENT_TYPE)
+            .and_then(|v| v.to_str().ok())
+            .map_or(true, |v| v.contains("html"));
+        if !is_html {
+            continue;
+        }
+        
+        let body = response.text().await.unwrap_or_default();
+        let links = visit(&page, Page::Html(body));
+        
+        if depth < options.max_depth {
+            for link in links {
+                if link.host_str() == start.host_str() && visited.insert(link.to_string()) {
+                    queue.push_back((link, depth + 1));
+                }
+            }
+        }
+    }
+    
+    Ok(fetched)
+}
+
+/// الروابط القابلة للزحف في مستند (بدون المقاطع #)
+pub fn page_links(page: &Url, document: &Html) -> Vec<Url> {
+    document
+        .select(&LINK_SELECTOR)
+        .filter_map(|e| e.value().attr("href").or_else(|| e.value().attr("src")))
+        .filter_map(|href| page.join(href).ok())
+        .filter_map(|mut link| {
+            link.set_fragment(None);
+            crawlable(&link).then_some(link)
+        })
+        .collect()
+}
+
+/// تحليل صفحة: الروابط القابلة للزحف ونماذج كلمات المرور
+pub fn analyze_page(page: &Url, html: &str) -> (Vec<Url>, Vec<LoginCandidate>) {
+    let document = Html::parse_document(html);
+    let links = page_links(page, &document);
+    
+    let forms = document
+        .select(&FORM_SELECTOR)
+        .filter_map(|form| password_form(page, form))
+        .collect();
+    
+    (links, forms)
+}
+
+/// نموذج يحتوي حقل كلمة مرور كهدف محتمل
+fn password_form(page: &Url, form: ElementRef) -> Option<LoginCandidate> {
+    let password = form.select(&PASSWORD_SELECTOR).next()?;
+    
+    let action = form.value().attr("action").filter(|a| !a.trim().is_empty());
+    let target = action.and_then(|a| page.join(a).ok()).unwrap_or_else(|| page.clone());
+    
+    // حقل المستخدم: أول حقل نصي أو بريد، أو اسم يوحي بالمستخدم
+    let username_field = form
+        .select(&INPUT_SELECTOR)
+        .find(|input| {
+            let kind = input.value().attr("type").unwrap_or("text").to_lowercase();
+            let name = input.value().attr("name").unwrap_or("").to_lowercase();
+            kind != "hidden"
+                && (matches!(kind.as_str(), "text" | "email")
+                    || ["user", "login", "email"].iter().any(|hint| name.contains(hint)))
+        })
+        .and_then(|input| input.value().attr("name"))
+        .map(str::to_string);
+    
+    Some(LoginCandidate {
+        page: page.to_string(),
+        target: target.to_string(),
+        method: form.value().attr("method").unwrap_or("GET").to_uppercase(),
+        kind: LoginKind::PasswordForm,
+        username_field,
+        password_field: password.value().attr("name").map(str::to_string),
+    })
+}
+
+/// هل الرابط صفحة HTTP(S) قابلة للزحف (وليس ملفًا ثابتًا)
+fn crawlable(link: &Url) -> bool {
+    if !matches!(link.scheme(), "http" | "https") {
+        return false;
+    }
+    
+    let extension = link.path().rsplit_once('.').map(|(_, e)| e.to_lowercase());
+    !extension.is_some_and(|e| SKIPPED_EXTENSIONS.contains(&e.as_str()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_analyze_page_finds_login_form_and_links() {
+        let page = Url::parse("https://site.com/account/").unwrap();
+        let html = r#"
+            <a href="/about">About</a><a href="logo.png">x</a><a href="mailto:a@b.c">m</a>
+            <form action="../auth/login" method="post">
+                <input type="hidden" name="token" value="1">
+                <input type="email" name="email">
+                <input type="password" name="pass">
+            </form>
+            <form action="/search"><input name="q"></form>
+        "#;
+        
+        let (links, forms) = analyze_page(&page, html);
+        
+        assert_eq!(links, [Url::parse("https://site.com/about").unwrap()]);
+        assert_eq!(forms.len(), 1);
+        assert_eq!(forms[0].target, "https://site.com/auth/login");
+        assert_eq!(forms[0].method, "POST");
+        assert_eq!(forms[0].username_field.as_deref(), Some("email"));
+        assert_eq!(forms[0].password_field.as_deref(), Some("pass"));
+    }
+}
@@ -0,0 +1,114 @@
+//! استخراج رموز CSRF
+//! جلب صفحة تسجيل الدخول قبل كل محاولة واستخراج رمز مكافحة CSRF بمحدد CSS أو تعبير نمطي
+
+use regex::Regex;
+use anyhow::{Result, Context};
+
+/// بادئة المحدد للدلالة على تعبير نمطي بدلًا من محدد CSS
+const REGEX_PREFIX: &str = "re:";
+
+/// طريقة تحديد موقع الرمز في الصفحة
+#[derive(Debug, Clone)]
+enum CsrfSelector {
+    /// محدد CSS (قيمة value أو content أو نص العنصر)
+    Css(String),
+    /// تعبير نمطي (أول مجموعة التقاط)
+    Regex(Regex),
+}
+
+/// رمز CSRF مستخرج لمحاولة واحدة
+#[derive(Debug, Clone)]
+pub struct CsrfToken {
+    /// اسم حقل النموذج
+    pub field: String,
+    
+    /// قيمة الرمز
+    pub value: String,
+    
+    /// كوكيز الجلسة المرتبطة بالرمز
+    pub cookies: Option<String>,
+}
+
+/// مستخرج رموز CSRF
+#[derive(Debug, Clone)]
+pub struct CsrfExtractor {
+    field: String,
+    selector: CsrfSelector,
+}
+
+impl CsrfExtractor {
+    /// إنشاء مستخرج لحقل مع محدد اختياري
+    ///
+    /// المحدد الافتراضي `input[name="FIELD"]`، ويمكن تمرير تعبير نمطي ببادئة `re:`.
+    pub fn new(field: &str, selector: Option<&str>) -> Result<Self> {
+        let selector = match selector {
+            Some(s) if s.starts_with(REGEX_PREFIX) => {
+                let pattern = &s[REGEX_PREFIX.len()..];
+                let re = Regex::new(pattern)
+                    .context(format!("تعبير نمطي غير صالح لـ CSRF: {}", pattern))?;
+                if re.captures_len() < 2 {
+                    return Err(anyhow::anyhow!("تعبير CSRF يجب أن يحتوي مجموعة التقاط"));
+                }
+                CsrfSelector::Regex(re)
+            }
+            Some(s) => {
+                scraper::Selector::parse(s)
+                    .map_err(|e| anyhow::anyhow!("محدد CSS غير صالح: {} ({:?})", s, e))?;
+                CsrfSelector::Css(s.to_string())
+            }
+            None => CsrfSelector::Css(format!("input[name=\"{}\"]", field)),
+        };
+        
+        Ok(Self {
+            field: field.to_string(),
+            selector,
+        })
+    }
+    
+    /// اسم حقل النموذج
+    pub fn field(&self) -> &str {
+        &self.field
+    }
+    
+    /// استخراج الرمز من محتوى الصفحة
+    pub fn extract(&self, html: &str) -> Option<String> {
+        match &self.selector {
+            CsrfSelector::Regex(re) => re
+                .captures(html)
+                .and_then(|c| c.get(1))
+                .map(|m| m.as_str().to_string()),
+            CsrfSelector::Css(selector) => {
+                let selector = scraper::Selector::parse(selector).ok()?;
+                let document = scraper::Html::parse_document(html);
+                let element = document.select(&selector).next()?;
+                
+                element
+                    .value()
+                    .attr("value")
+                    .or_else(|| element.value().attr("content"))
+                    .map(str::to_string)
+                    .or_else(|| Some(element.text().collect::<String>().trim().to_string()))
+            }
+        }
+        .filter(|token| !token.is_empty())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    const PAGE: &str = r#"<html><head><meta name="csrf-token" content="meta-123"></head>
+        <body><form><input type="hidden" name="_token" value="abc123"></form></body></html>"#;
+    
+    #[test]
+    fn test_extract_by_field_css_and_regex() {
+        let by_field = CsrfExtractor::new("_token", None).unwrap();
+        let by_css = CsrfExtractor::new("_token", Some("meta[name=csrf-token]")).unwrap();
+        let by_regex = CsrfExtractor::new("_token", Some(r#"re:value="(\w+)""#)).unwrap();
+        
+        assert_eq!(by_field.extract(PAGE).as_deref(), Some("abc123"));
+        assert_eq!(by_css.extract(PAGE).as_deref(), Some("meta-123"));
+        assert_eq!(by_regex.extract(PAGE).as_deref(), Some("abc123"));
+    }
+}
@@ -0,0 +1,181 @@
+//! اكتشاف الأهداف
+//! توسيع نطاقات CIDR والمنافذ إلى أهداف محددة بعد فحص اتصال TCP سريع لتصفية المضيفين غير الحيين
+
+use std::net::Ipv4Addr;
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
+use anyhow::{Result, Context};
+use tokio::net::TcpStream;
+use tokio::sync::Semaphore;
+use tokio::task::JoinSet;
+use url::Url;
+
+use crate::modules::Protocol;
+use crate::parser::parse_input;
+
+/// أصغر بادئة CIDR مسموحة (‏/16 = 65536 عنوانًا)
+const MIN_PREFIX: u8 = 16;
+
+/// عدد فحوصات الاتصال المتزامنة
+const PROBE_CONCURRENCY: usize = 256;
+
+/// مهلة فحص الاتصال الافتراضية بالمللي ثانية
+pub const DEFAULT_PROBE_TIMEOUT_MS: u64 = 1000;
+
+/// مواصفة أهداف: مضيف أو نطاق CIDR مع قائمة منافذ اختيارية (10.0.0.0/24:8080,443)
+#[derive(Debug, Clone, PartialEq)]
+pub struct TargetSpec {
+    /// المضيفون بعد التوسيع
+    pub hosts: Vec<String>,
+    
+    /// المنافذ (فارغة = منفذ القالب أو البروتوكول الافتراضي)
+    pub ports: Vec<u16>,
+}
+
+impl std::str::FromStr for TargetSpec {
+    type Err = anyhow::Error;
+    
+    fn from_str(s: &str) -> Result<Self> {
+        let (hosts, ports) = match s.trim().split_once(':') {
+            Some((hosts, ports)) => (hosts, parse_ports(ports)?),
+            None => (s.trim(), Vec::new()),
+        };
+        
+        let hosts = match hosts.split_once('/') {
+            Some((network, prefix)) => expand_cidr(network, prefix)?,
+            None if !hosts.is_empty() => vec![hosts.to_string()],
+            None => return Err(anyhow::anyhow!("مواصفة أهداف فارغة")),
+        };
+        
+        Ok(Self { hosts, ports })
+    }
+}
+
+/// تحليل قائمة منافذ مع النطاقات (8080,443,8000-8010)
+pub fn parse_ports(input: &str) -> Result<Vec<u16>> {
+    let mut ports = Vec::new();
+    
+    for part in input.split(',').map(str::trim).filter(|p| !p.is_empty()) {
+        let invalid = || format!("منفذ غير صالح: {}", part);
+        
+        match part.split_once('-') {
+            Some((start, end)) => {
+                let start: u16 = start.parse().context(invalid())?;
+                let end: u16 = end.parse().context(invalid())?;
+                ports.extend(start..=end);
+            }
+            None => ports.push(part.parse().context(invalid())?),
+        }
+    }
+    
+    ports.dedup();
+    Ok(ports)
+}
+
+/// توسيع نطاق IPv4 (بدون عنواني الشبكة والبث إلا في ‏/31 و ‏/32)
+fn expand_cidr(network: &str, prefix: &str) -> Result<Vec<String>> {
+    let address: Ipv4Addr = network.parse().context(format!("عنوان شبكة غير صالح: {}", network))?;
+    let prefix: u8 = prefix.parse().context(format!("بادئة CIDR غير صالحة: {}", prefix))?;
+    
+    if !(MIN_PREFIX..=32).contains(&prefix) {
+        return Err(anyhow::anyhow!("بادئة CIDR خارج النطاق المسموح: /{} (‏/{}-/32)", prefix, MIN_PREFIX));
+    }
+    
+    let size = 1u32 << (32 - prefix);
+    let base = u32::from(address) & !(size - 1);
+    let (first, last) = if size > 2 { (1, size - 1) } else { (0, size) };
+    
+    Ok((first..last).map(|offset| Ipv4Addr::from(base + offset).to_string()).collect())
+}
+
+/// فحص اتصال TCP متوازي وإرجاع الأزواج (مضيف، منفذ) المفتوحة بترتيب المواصفة
+pub async fn probe_live(spec: &TargetSpec, default_port: u16, timeout: Duration) -> Vec<(String, u16)> {
+    let ports = if spec.ports.is_empty() { vec![default_port] } else { spec.ports.clone() };
+    let semaphore = Arc::new(Semaphore::new(PROBE_CONCURRENCY));
+    let mut tasks = JoinSet::new();
+    
+    for (index, host) in spec.hosts.iter().enumerate() {
+        for &port in &ports {
+            let host = host.clone();
+            let semaphore = Arc::clone(&semaphore);
+            
+            tasks.spawn(async move {
+                let _permit = semaphore.acquire_owned().await.ok()?;
+                let connect = TcpStream::connect((host.as_str(), port));
+                
+                matches!(tokio::time::timeout(timeout, connect).await, Ok(Ok(_)))
+                    .then_some((index, host, port))
+            });
+        }
+    }
+    
+    let mut live = Vec::new();
+    while let Some(result) = tasks.join_next().await {
+        if let Ok(Some(target)) = result {
+            live.push(target);
+        }
+    }
+    
+    live.sort_by_key(|(index, _, port)| (*index, *port));
+    live.into_iter().map(|(_, host, port)| (host, port)).collect()
+}
+
+/// بناء رابط هدف لمضيف ومنفذ من قالب الرابط (أو من البروتوكول عند غيابه)
+pub fn target_url(template: Option<&str>, protocol: Protocol, host: &str, port: u16) -> String {
+    if let Some(mut url) = template.and_then(|t| Url::parse(t).ok()) {
+        if url.set_host(Some(host)).is_ok() && url.set_port(Some(port)).is_ok() {
+            return url.to_string();
+        }
+    }
+    
+    let scheme = match protocol {
+        Protocol::Http if matches!(port, 443 | 8443) => "https".to_string(),
+        other => other.to_string(),
+    };
+    format!("{}://{}:{}", scheme, host, port)
+}
+
+/// توسيع --targets: ملف روابط (سطر لكل هدف) أو مواصفة CIDR تُفحص بحثًا عن المضيفين الحيين
+pub async fn expand_targets(
+    input: &str,
+    template: Option<&str>,
+    protocol: Protocol,
+    timeout: Duration,
+) -> Result<Vec<String>> {
+    if Path::new(input).exists() {
+        return parse_input(input).await;
+    }
+    
+    let spec: TargetSpec = input.parse()?;
+    let default_port = template
+        .and_then(|t| Url::parse(t).ok())
+        .and_then(|url| url.port_or_known_default())
+        .unwrap_or_else(|| protocol.default_port());
+    
+    let live = probe_live(&spec, default_port, timeout).await;
+    
+    Ok(live
+        .iter()
+        .map(|(host, port)| target_url(template, protocol, host, *port))
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_target_spec_expansion() {
+        let spec: TargetSpec = "10.0.0.0/30:8080,443,9000-9002".parse().unwrap();
+        
+        assert_eq!(spec.hosts, ["10.0.0.1", "10.0.0.2"]);
+        assert_eq!(spec.ports, [8080, 443, 9000, 9001, 9002]);
+        assert!("10.0.0.0/8".parse::<TargetSpec>().is_err());
+        assert_eq!(
+            target_url(Some("https://x/login"), Protocol::Http, "10.0.0.1", 8443),
+            "https://10.0.0.1:8443/login"
+        );
+        assert_eq!(target_url(None, Protocol::Ssh, "10.0.0.1", 22), "ssh://10.0.0.1:22");
+    }
+}
@@ -0,0 +1,100 @@
+//! أخطاء المكتبة
+//! نوع أخطاء مصنف تتفرع عليه التطبيقات المضمنة (anyhow يبقى للتفاصيل الداخلية وسطر الأوامر)
+
+use std::path::PathBuf;
+use std::time::Duration;
+
+/// نتيجة واجهات المكتبة العامة
+pub type Result<T, E = RedFoxError> = std::result::Result<T, E>;
+
+/// سبب فشل عملية في المكتبة
+#[derive(Debug, thiserror::Error)]
+pub enum RedFoxError {
+    /// تعذر الاتصال بالهدف (رفض، مهلة، DNS)
+    #[error("الهدف غير قابل للوصول: {0}")]
+    TargetUnreachable(String),
+    
+    /// ملف قائمة كلمات غير موجود في المسار ولا في المسارات الشائعة
+    #[error("قائمة الكلمات غير موجودة: {}", .0.display())]
+    WordlistNotFound(PathBuf),
+    
+    /// الهدف يقيد المعدل (HTTP 429)
+    #[error("تم تقييد المعدل من الهدف{}", .retry_after.map(|d| format!(" (إعادة المحاولة بعد {}ث)", d.as_secs())).unwrap_or_default())]
+    RateLimited {
+        /// المدة المطلوبة قبل إعادة المحاولة (ترويسة Retry-After)
+        retry_after: Option<Duration>,
+    },
+    
+    /// رسالة قفل حساب معروفة في الاستجابة
+    #[error("تم اكتشاف قفل بواسطة {0}")]
+    LockoutDetected(String),
+    
+    /// البروكسي غير صالح أو لا يستجيب
+    #[error("فشل البروكسي {proxy}: {reason}")]
+    ProxyFailure {
+        /// عنوان البروكسي
+        proxy: String,
+        /// سبب الفشل
+        reason: String,
+    },
+    
+    /// إعدادات غير صالحة أو ناقصة
+    #[error("إعدادات غير صالحة: {0}")]
+    InvalidConfig(String),
+    
+    /// خطأ غير مصنف (التفاصيل في السلسلة)
+    #[error(transparent)]
+    Other(anyhow::Error),
+}
+
+impl From<anyhow::Error> for RedFoxError {
+    /// تصنيف خطأ داخلي: خطأ مصنف في السلسلة أولًا، ثم أخطاء الشبكة كهدف غير قابل للوصول
+    fn from(error: anyhow::Error) -> Self {
+        let error = match error.downcast::<RedFoxError>() {
+            Ok(classified) => return classified,
+            Err(error) => error,
+        };
+        
+        if error.chain().any(is_network_failure) {
+            return RedFoxError::TargetUnreachable(format!("{:#}", error));
+        }
+        
+        RedFoxError::Other(error)
+    }
+}
+
+/// هل السبب فشل اتصال شبكي؟ (رفض، مهلة، انقطاع)
+pub fn is_network_failure(cause: &(dyn std::error::Error + 'static)) -> bool {
+    if let Some(e) = cause.downcast_ref::<reqwest::Error>() {
+        return e.is_connect() || e.is_timeout();
+    }
+    if let Some(e) = cause.downcast_ref::<std::io::Error>() {
+        return matches!(
+            e.kind(),
+            std::io::ErrorKind::ConnectionRefused
+                | std::io::ErrorKind::ConnectionReset
+                | std::io::ErrorKind::ConnectionAborted
+                | std::io::ErrorKind::TimedOut
+                | std::io::ErrorKind::AddrNotAvailable
+        );
+    }
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Context;
+    
+    #[test]
+    fn test_classify_keeps_typed_error_through_context() {
+        let typed: anyhow::Result<()> = Err(RedFoxError::WordlistNotFound("rockyou.txt".into()).into());
+        let error = RedFoxError::from(typed.context("فشل في تحليل كلمات المرور").unwrap_err());
+        assert!(matches!(error, RedFoxError::WordlistNotFound(path) if path == std::path::Path::new("rockyou.txt")));
+        
+        let io = std::io::Error::new(std::io::ErrorKind::ConnectionRefused, "refused");
+        assert!(matches!(RedFoxError::from(anyhow::Error::new(io)), RedFoxError::TargetUnreachable(_)));
+        
+        assert!(matches!(RedFoxError::from(anyhow::anyhow!("other")), RedFoxError::Other(_)));
+    }
+}
@@ -0,0 +1,75 @@
+//! خطافات أحداث الفحص
+//! تمرير أحداث النجاح والفشل والأخطاء والتباطؤ والتقدم إلى منطق المضيف دون تعديل حلقات الفحص
+
+use std::time::Duration;
+
+use crate::scanner::ScanResult;
+
+/// حدث أثناء الفحص
+#[derive(Debug, Clone, Copy)]
+pub enum ScanEvent<'a> {
+    /// بيانات اعتماد صالحة
+    Success(&'a ScanResult),
+    /// محاولة مرفوضة
+    Failure(&'a ScanResult),
+    /// محاولة لم تكتمل (مهلة، قفل، خطأ اتصال)
+    Error(&'a ScanResult),
+    /// تأخير مقصود قبل المحاولة التالية
+    Throttle {
+        /// مدة التأخير
+        delay: Duration,
+        /// سببه (jitter، throttle، rate-limit...)
+        reason: &'static str,
+    },
+    /// تقدم الفحص بعد كل نتيجة
+    Progress {
+        /// المحاولات المكتملة
+        completed: usize,
+        /// إجمالي المحاولات المتوقعة
+        total: usize,
+    },
+}
+
+impl<'a> ScanEvent<'a> {
+    /// حدث النتيجة حسب حالتها
+    pub fn from_result(result: &'a ScanResult) -> Self {
+        if result.success {
+            ScanEvent::Success(result)
+        } else if result.error.is_some() {
+            ScanEvent::Error(result)
+        } else {
+            ScanEvent::Failure(result)
+        }
+    }
+}
+
+/// خطاف يستقبل أحداث الفحص (يُستدعى من مهام الفحص المتزامنة فيجب أن يكون سريعًا)
+pub trait EventHook: Send + Sync {
+    /// استقبال حدث واحد
+    fn on_event(&self, event: &ScanEvent<'_>);
+}
+
+impl<F> EventHook for F
+where
+    F: Fn(&ScanEvent<'_>) + Send + Sync,
+{
+    fn on_event(&self, event: &ScanEvent<'_>) {
+        self(event)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_event_from_result_state() {
+        let success = ScanResult { success: true, ..Default::default() };
+        let error = ScanResult { error: Some("timeout".to_string()), ..Default::default() };
+        let failure = ScanResult::default();
+        
+        assert!(matches!(ScanEvent::from_result(&success), ScanEvent::Success(_)));
+        assert!(matches!(ScanEvent::from_result(&error), ScanEvent::Error(_)));
+        assert!(matches!(ScanEvent::from_result(&failure), ScanEvent::Failure(_)));
+    }
+}
@@ -0,0 +1,96 @@
+//! رموز الخروج
+//! رموز خروج موثقة ليتفرع عليها CI والسكربتات المغلفة
+
+use crate::error::{is_network_failure, RedFoxError};
+use crate::scanner::ScanResult;
+
+/// نتيجة التنفيذ كرمز خروج
+///
+/// | الرمز | المعنى |
+/// |---|---|
+/// | 0 | عُثر على بيانات اعتماد (أو نجح الأمر) |
+/// | 1 | اكتمل الفحص دون إصابات |
+/// | 2 | خطأ في الاستخدام أو الإعدادات |
+/// | 3 | الهدف غير قابل للوصول |
+/// | 4 | أُلغي الفحص (Ctrl+C) |
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExitStatus {
+    /// عُثر على بيانات اعتماد أو نجح الأمر
+    Success = 0,
+    /// اكتمل الفحص دون إصابات
+    NoHits = 1,
+    /// خطأ في الاستخدام أو الإعدادات (وأخطاء clap)
+    Usage = 2,
+    /// الهدف غير قابل للوصول
+    Unreachable = 3,
+    /// أُلغي الفحص
+    Aborted = 4,
+}
+
+impl ExitStatus {
+    /// الرمز الرقمي
+    pub fn code(self) -> i32 {
+        self as i32
+    }
+    
+    /// الحالة من نتائج فحص مكتمل (فشل كل المحاولات بأخطاء اتصال يعني هدفًا غير قابل للوصول)
+    pub fn from_results(results: &[ScanResult]) -> Self {
+        if results.iter().any(|r| r.success) {
+            Self::Success
+        } else if !results.is_empty() && results.iter().all(|r| r.error.is_some()) {
+            Self::Unreachable
+        } else {
+            Self::NoHits
+        }
+    }
+    
+    /// الحالة المجمعة لعدة أهداف (إصابة واحدة تكفي للنجاح، وعدم الوصول يعني أن كل الأهداف غير قابلة للوصول)
+    pub fn combine(statuses: &[Self]) -> Self {
+        if statuses.contains(&Self::Success) {
+            Self::Success
+        } else if !statuses.is_empty() && statuses.iter().all(|s| *s == Self::Unreachable) {
+            Self::Unreachable
+        } else {
+            Self::NoHits
+        }
+    }
+    
+    /// الحالة من خطأ أوقف التنفيذ (أخطاء الشبكة في السلسلة تعني هدفًا غير قابل للوصول)
+    pub fn from_error(error: &anyhow::Error) -> Self {
+        let unreachable = error.chain().any(|cause| {
+            matches!(cause.downcast_ref::<RedFoxError>(), Some(RedFoxError::TargetUnreachable(_)))
+                || is_network_failure(cause)
+        });
+        
+        if unreachable {
+            Self::Unreachable
+        } else {
+            Self::Usage
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_status_from_results_and_errors() {
+        let failed = ScanResult::default();
+        let errored = ScanResult { error: Some("connection refused".to_string()), ..ScanResult::default() };
+        let found = ScanResult { success: true, ..ScanResult::default() };
+        
+        assert_eq!(ExitStatus::from_results(&[failed.clone(), found]), ExitStatus::Success);
+        assert_eq!(ExitStatus::from_results(&[failed, errored.clone()]), ExitStatus::NoHits);
+        assert_eq!(ExitStatus::from_results(&[errored]), ExitStatus::Unreachable);
+        
+        let unreachable = [ExitStatus::Unreachable, ExitStatus::Unreachable];
+        assert_eq!(ExitStatus::combine(&unreachable), ExitStatus::Unreachable);
+        assert_eq!(ExitStatus::combine(&[ExitStatus::Unreachable, ExitStatus::NoHits]), ExitStatus::NoHits);
+        assert_eq!(ExitStatus::combine(&[ExitStatus::Unreachable, ExitStatus::Success]), ExitStatus::Success);
+        
+        let refused = anyhow::Error::from(std::io::Error::from(std::io::ErrorKind::ConnectionRefused));
+        assert_eq!(ExitStatus::from_error(&refused.context("فشل")), ExitStatus::Unreachable);
+        assert_eq!(ExitStatus::from_error(&anyhow::anyhow!("ملف غير موجود")), ExitStatus::Usage);
+    }
+}
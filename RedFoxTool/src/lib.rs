@@ -2,52 +2,123 @@
 //! توفر واجهة برمجية لاستخدام الأداة كمكتبة
 
 #![warn(missing_docs)]
-#![warn(clippy::all)]
 
 pub mod scanner;
 pub mod bruteforcer;
+pub mod calibration;
+pub mod config;
+pub mod captcha;
+pub mod crawler;
+pub mod csrf;
 pub mod http_client;
+pub mod login_body;
+pub mod matcher;
+pub mod notifiers;
+pub mod output;
+pub mod ntlm;
 pub mod parser;
 pub mod validator;
+pub mod potfile;
 pub mod progress;
 pub mod reporter;
+pub mod proxy_check;
+pub mod racer;
+pub mod raw_request;
+pub mod results_store;
+pub mod session;
+pub mod events;
+pub mod telemetry;
+pub mod shell;
+pub mod plugins;
+pub mod queue;
+pub mod agent;
+pub mod server;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+pub mod discovery;
+pub mod error;
+pub mod exit_status;
+pub mod throttle;
+pub mod vault;
+pub mod wordlist;
 pub mod modules;
 pub mod utils;
 
 // إعادة تصدير الأنواع الأساسية
-pub use scanner::{RedFoxScanner, ScanResult, ScanOptions};
+pub use scanner::{RedFoxScanner, ScanResult, ScanOptions, ScannerBuilder};
+pub use events::{EventHook, ScanEvent};
+pub use progress::{ProgressObserver, ProgressSnapshot};
+pub use error::RedFoxError;
+pub use modules::{AttemptOutcome, AuthModule, LoginTarget};
 pub use bruteforcer::{Bruteforcer, AttackMode};
 pub use http_client::HttpClient;
 pub use validator::ValidationResult;
 
 /// تهيئة الأداة
 pub fn init() {
-    // تهيئة المسجل
-    utils::logger::init();
+    // تهيئة المشترك (tracing) بالسياسة الافتراضية
+    telemetry::init(&output::policy(), Vec::new());
     
     // التحقق من المتطلبات
     utils::system::check_requirements();
 }
 
-/// تنفيذ فحص سريع
+/// تنفيذ فحص سريع (10 خيوط، مهلة 30 ثانية، الوضع العادي)
 pub async fn quick_scan(
     url: &str,
     username: &str,
     passwords: &[&str],
-) -> anyhow::Result<Vec<ScanResult>> {
-    let scanner = RedFoxScanner::new(
-        url,
-        username,
-        "",
-        10,
-        30,
-        "normal",
-        None,
-    )
-    .await?;
+) -> error::Result<Vec<ScanResult>> {
+    let options = ScanOptions {
+        threads: 10,
+        ..ScanOptions::default()
+    };
     
-    let results = scanner.scan_specific_passwords(passwords).await?;
-    Ok(results)
+    quick_scan_with(url, [username], passwords.iter().copied(), options).await
+}
+
+/// تنفيذ فحص سريع بخيارات مخصصة (التوازي، المهلة، الوضع، شروط الإيقاف...)
+pub async fn quick_scan_with<U, P>(
+    url: &str,
+    users: U,
+    passwords: P,
+    options: ScanOptions,
+) -> error::Result<Vec<ScanResult>>
+where
+    U: IntoIterator,
+    U::Item: Into<String>,
+    P: IntoIterator,
+    P::Item: Into<String>,
+{
+    let scanner = ScannerBuilder::new()
+        .target(url)
+        .users(users)
+        .passwords(passwords)
+        .options(options)
+        .build()
+        .await?;
+    
+    scanner.scan(false).await
+}
+
+/// تنفيذ فحص سريع من قوائم كلمات (اسم مستخدم أو ملف مستخدمين، وملف كلمات مرور)
+pub async fn quick_scan_wordlists(
+    url: &str,
+    users: &str,
+    password_file: &str,
+    options: ScanOptions,
+) -> error::Result<Vec<ScanResult>> {
+    let scanner = ScannerBuilder::new()
+        .target(url)
+        .load_users(users)
+        .await?
+        .load_passwords(password_file)
+        .await?
+        .options(options)
+        .build()
+        .await?;
+    
+    scanner.scan(false).await
 }
 
 /// توليد تقرير
@@ -55,15 +126,15 @@ pub async fn generate_report(
     results: &[ScanResult],
     format: &str,
     output_path: &str,
-) -> anyhow::Result<String> {
+) -> error::Result<String> {
     let reporter = reporter::ReportGenerator::new();
     let path = reporter.generate(results, output_path, format).await?;
     Ok(path)
 }
 
 /// التحقق من صحة الهدف
-pub async fn validate_target(url: &str) -> anyhow::Result<ValidationResult> {
-    validator::validate_url(url).await
+pub async fn validate_target(url: &str) -> error::Result<ValidationResult> {
+    Ok(validator::validate_url(url).await?)
 }
 
 /// معلومات الإصدار
@@ -0,0 +1,246 @@
+//! قوالب جسم طلب تسجيل الدخول
+//! توليد جسم الطلب (form-urlencoded أو JSON أو XML/SOAP) لكل محاولة من قالب يحتوي ^USER^ و ^PASS^ و ^CSRF^
+
+use anyhow::{Result, Context};
+
+/// العنصر النائب لاسم المستخدم في القالب
+pub const USER_PLACEHOLDER: &str = "^USER^";
+
+/// العنصر النائب لكلمة المرور في القالب
+pub const PASS_PLACEHOLDER: &str = "^PASS^";
+
+/// العنصر النائب لرمز CSRF في القالب
+pub const CSRF_PLACEHOLDER: &str = "^CSRF^";
+
+
+/// تنسيق جسم الطلب
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BodyFormat {
+    /// application/x-www-form-urlencoded
+    #[default]
+    Form,
+    /// application/json
+    Json,
+    /// text/xml (SOAP)
+    Xml,
+}
+
+impl std::str::FromStr for BodyFormat {
+    type Err = anyhow::Error;
+    
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "form" => Ok(BodyFormat::Form),
+            "json" => Ok(BodyFormat::Json),
+            "xml" | "soap" => Ok(BodyFormat::Xml),
+            _ => Err(anyhow::anyhow!("تنسيق جسم غير مدعوم: {} (form, json, xml)", s)),
+        }
+    }
+}
+
+impl BodyFormat {
+    /// نوع المحتوى المقابل
+    pub fn content_type(self) -> &'static str {
+        match self {
+            BodyFormat::Form => "application/x-www-form-urlencoded",
+            BodyFormat::Json => "application/json",
+            BodyFormat::Xml => "text/xml; charset=utf-8",
+        }
+    }
+}
+
+/// حقول الجسم الافتراضي (عند عدم تحديد قالب)
+#[derive(Debug, Clone)]
+pub struct FormFields {
+    /// حقل اسم المستخدم
+    pub username: String,
+    /// حقل كلمة المرور
+    pub password: String,
+    /// حقول إضافية ثابتة (من --data)
+    pub extra: Vec<(String, String)>,
+}
+
+impl Default for FormFields {
+    fn default() -> Self {
+        Self {
+            username: "username".to_string(),
+            password: "password".to_string(),
+            extra: Vec::new(),
+        }
+    }
+}
+
+/// جسم طلب تسجيل الدخول
+#[derive(Debug, Clone, Default)]
+pub struct LoginBody {
+    format: BodyFormat,
+    template: Option<String>,
+    fields: FormFields,
+}
+
+impl LoginBody {
+    /// إنشاء جسم بتنسيق وقالب اختياري (يتم التحقق من صلاحية قوالب JSON و XML)
+    pub fn new(format: BodyFormat, template: Option<String>) -> Result<Self> {
+        if let Some(template) = &template {
+            if !template.contains(USER_PLACEHOLDER) && !template.contains(PASS_PLACEHOLDER) {
+                tracing::warn!("القالب لا يحتوي {} أو {}", USER_PLACEHOLDER, PASS_PLACEHOLDER);
+            }
+        }
+        
+        if format == BodyFormat::Xml && template.is_none() {
+            return Err(anyhow::anyhow!("تنسيق XML يتطلب قالبًا (--body-template)"));
+        }
+        
+        let body = Self {
+            format,
+            template,
+            fields: FormFields::default(),
+        };
+        let (_, sample) = body.render("user", "pass", None);
+        
+        match format {
+            BodyFormat::Json => {
+                serde_json::from_str::<serde_json::Value>(&sample)
+                    .context("قالب JSON غير صالح")?;
+            }
+            BodyFormat::Xml => {
+                sxd_document::parser::parse(&sample)
+                    .map_err(|e| anyhow::anyhow!("قالب XML غير صالح: {:?}", e))?;
+            }
+            BodyFormat::Form => {}
+        }
+        
+        Ok(body)
+    }
+    
+    /// التنسيق
+    pub fn format(&self) -> BodyFormat {
+        self.format
+    }
+    
+    /// تعيين حقول الجسم الافتراضي (لا تؤثر على القوالب المخصصة)
+    pub fn set_fields(&mut self, fields: FormFields) {
+        if self.template.is_some() && !fields.extra.is_empty() {
+            tracing::warn!("الحقول الإضافية (--data) تُتجاهل مع قالب جسم مخصص");
+        }
+        self.fields = fields;
+    }
+    
+    /// توليد الجسم لمحاولة واحدة: (نوع المحتوى، الجسم)
+    ///
+    /// يُحقن رمز CSRF (الحقل، القيمة) في النموذج الافتراضي أو مكان ^CSRF^ في القالب.
+    pub fn render(
+        &self,
+        username: &str,
+        password: &str,
+        csrf: Option<(&str, &str)>,
+    ) -> (&'static str, String) {
+        let token = csrf.map_or("", |(_, value)| value);
+        
+        let body = match (self.format, &self.template) {
+            (BodyFormat::Json, Some(template)) => {
+                fill(template, &json_escape(username), &json_escape(password), &json_escape(token))
+            }
+            (BodyFormat::Json, None) => {
+                let mut object = serde_json::Map::new();
+                object.insert(self.fields.username.clone(), username.into());
+                object.insert(self.fields.password.clone(), password.into());
+                for (key, value) in &self.fields.extra {
+                    object.insert(key.clone(), value.as_str().into());
+                }
+                
+                serde_json::Value::Object(object).to_string()
+            }
+            (BodyFormat::Xml, template) => fill(
+                template.as_deref().unwrap_or_default(),
+                &xml_escape(username),
+                &xml_escape(password),
+                &xml_escape(token),
+            ),
+            (BodyFormat::Form, Some(template)) => {
+                fill(template, &form_escape(username), &form_escape(password), &form_escape(token))
+            }
+            (BodyFormat::Form, None) => {
+                let (csrf_field, csrf_value) = csrf.unwrap_or(("csrf_token", "test")); // يمكن تعديله حسب الحاجة
+                
+                let mut form = url::form_urlencoded::Serializer::new(String::new());
+                form.append_pair(&self.fields.username, username)
+                    .append_pair(&self.fields.password, password)
+                    .extend_pairs(&self.fields.extra)
+                    .append_pair("submit", "Login")
+                    .append_pair(csrf_field, csrf_value);
+                form.finish()
+            }
+        };
+        
+        (self.format.content_type(), body)
+    }
+}
+
+/// توليد سلسلة استعلام من قالب (مثال: `u=^USER^&p=^PASS^`) مع ترميز القيم
+pub fn render_query(template: &str, username: &str, password: &str, csrf: Option<(&str, &str)>) -> String {
+    let token = csrf.map_or("", |(_, value)| value);
+    fill(template, &form_escape(username), &form_escape(password), &form_escape(token))
+}
+
+/// استبدال العناصر النائبة بالقيم
+fn fill(template: &str, username: &str, password: &str, csrf: &str) -> String {
+    template
+        .replace(USER_PLACEHOLDER, username)
+        .replace(PASS_PLACEHOLDER, password)
+        .replace(CSRF_PLACEHOLDER, csrf)
+}
+
+/// تهريب قيمة لإدراجها داخل سلسلة JSON
+pub(crate) fn json_escape(value: &str) -> String {
+    let quoted = serde_json::Value::String(value.to_string()).to_string();
+    quoted[1..quoted.len() - 1].to_string()
+}
+
+/// تهريب قيمة لإدراجها في مستند XML
+pub(crate) fn xml_escape(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+/// ترميز قيمة form-urlencoded
+pub(crate) fn form_escape(value: &str) -> String {
+    url::form_urlencoded::byte_serialize(value.as_bytes()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_json_template_escapes_values() {
+        let body = LoginBody::new(
+            BodyFormat::Json,
+            Some(r#"{"user":"^USER^","pass":"^PASS^"}"#.to_string()),
+        )
+        .unwrap();
+        
+        let (content_type, rendered) = body.render("admin", r#"p"a\ss"#, None);
+        let value: serde_json::Value = serde_json::from_str(&rendered).unwrap();
+        
+        assert_eq!(content_type, "application/json");
+        assert_eq!(value["pass"], r#"p"a\ss"#);
+    }
+    
+    #[test]
+    fn test_query_template_is_url_encoded() {
+        let query = render_query("u=^USER^&p=^PASS^", "admin", "a&b=c", None);
+        
+        assert_eq!(query, "u=admin&p=a%26b%3Dc");
+    }
+    
+    #[test]
+    fn test_invalid_json_template_is_rejected() {
+        assert!(LoginBody::new(BodyFormat::Json, Some("{user: ^USER^}".to_string())).is_err());
+    }
+}
@@ -3,107 +3,422 @@
 //! الإصدار: 1.0.0
 
 #![warn(missing_docs)]
-#![warn(clippy::all)]
-#![warn(clippy::pedantic)]
 
+use std::path::{Path, PathBuf};
 use std::process;
-use std::time::Instant;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 use colored::Colorize;
 use anyhow::{Result, Context};
 use tokio::runtime::Runtime;
 
 // استيراد الموديولات
 mod cli;
-mod scanner;
-mod bruteforcer;
-mod http_client;
-mod parser;
-mod validator;
-mod progress;
-mod reporter;
-mod modules;
-mod utils;
 
-use cli::{Cli, Command};
-use scanner::RedFoxScanner;
+use redfox_tool::{
+    scanner, config, crawler, http_client, login_body, matcher, notifiers, output, parser, validator,
+    potfile, progress, reporter, proxy_check, results_store, session, telemetry, shell, plugins, queue,
+    agent, server, discovery, exit_status, throttle, vault, wordlist, modules, utils,
+};
+
+use cli::{Cli, Command, ConfigCommand, ReportCommand, SessionsCommand, VaultCommand, WordlistCommand};
+use http_client::{AuthType, TransportOptions};
+use matcher::SuccessCriteria;
+use notifiers::{DiscordConfig, Notifications, NotifiersConfig, SlackConfig, WebhookConfig};
+use exit_status::ExitStatus;
+use modules::{audit_file, defaults, hashcrack, jwt, Protocol, TlsMode};
+use scanner::{RedFoxScanner, ScanOptions, ScannerBuilder};
+use potfile::{Potfile, DEFAULT_POTFILE};
+use session::{ScanSession, SessionConfig, SessionRecorder, DEFAULT_CHECKPOINT_INTERVAL};
 use reporter::ReportGenerator;
-use utils::logger::Logger;
+use telemetry::Logger;
 
-/// دالة رئيسية غير متزامنة
-async fn async_main() -> Result<()> {
-    // عرض البانر
-    show_banner();
-    
+/// ملف سجل أوامر الوضع التفاعلي
+const SHELL_HISTORY: &str = "~/.redfox/shell_history";
+
+/// دالة رئيسية غير متزامنة (تعيد رمز الخروج الموثق)
+async fn async_main() -> Result<ExitStatus> {
     // تحليل سطر الأوامر
     let cli = Cli::parse();
     
+    // سكربت الإكمال يُكتب إلى stdout دون بانر أو سجلات
+    if let Command::Completions { shell } = cli.command {
+        Cli::print_completions(shell);
+        return Ok(ExitStatus::Success);
+    }
+    
+    let policy = output::OutputPolicy {
+        format: cli.output_format,
+        quiet: cli.quiet,
+        verbosity: cli.verbosity(),
+        log_format: cli.log_format,
+    };
+    output::init(policy);
+    
+    // طبقات إضافية للمشترك (تصدير OTLP اختياري)
+    let mut layers = Vec::new();
+    let otlp_error = match cli.otlp_endpoint.as_deref().map(telemetry::otlp_layer) {
+        Some(Ok(layer)) => {
+            layers.push(layer);
+            None
+        }
+        Some(Err(e)) => Some(e),
+        None => None,
+    };
+    telemetry::init(&policy, layers);
+    if let Some(e) = otlp_error {
+        tracing::warn!("تعطيل تصدير OTLP: {:#}", e);
+    }
+    
     // تهيئة المسجل
-    let logger = Logger::new(cli.verbose);
+    let logger = Logger;
+    
+    // عرض البانر (لا بانر في الوضع الهادئ ومخرجات JSON)
+    if !output::is_quiet() {
+        show_banner(&logger);
+    }
     logger.info("بدء RedFoxTool");
     
     // التحقق من المتطلبات
-    if cli.requires_root && !utils::system::is_root() {
+    if cli.requires_root() && !utils::system::is_root() {
         logger.error("يجب تشغيل الأداة كـ root!");
-        process::exit(1);
+        return Ok(ExitStatus::Usage);
     }
     
+    let mut status = ExitStatus::Success;
+    // -v يعرض أيضًا شريط التقدم والمحاولات الفاشلة
+    let verbose = cli.verbose > 0;
+    let plaintext = cli.plaintext;
+    
     match cli.command {
         Command::Scan {
             url,
+            targets,
+            probe_timeout,
+            parallel_targets,
             user,
             password_file,
+            combo_file,
+            rules,
             threads,
             timeout,
             output,
             format,
-            verbose,
+            template,
+            redact,
             proxy,
             mode,
             rate_limit,
-            ..
+            proxy_file,
+            rate_limit_per_proxy,
+            race_connections,
+            stop_on_success,
+            stop_per_user,
+            success_match,
+            failure_match,
+            success_status,
+            success_redirect,
+            length_delta,
+            success_header,
+            success_xpath,
+            min_delay,
+            max_delay,
+            jitter,
+            http_version,
+            insecure,
+            tls_min,
+            tls_max,
+            ca_cert,
+            host_header,
+            resolve,
+            sni,
+            auth_type,
+            secret_header,
+            client_id,
+            client_secret,
+            scope,
+            body_format,
+            method,
+            query,
+            raw_request,
+            body_template,
+            csrf_field,
+            csrf_selector,
+            protocol,
+            service_tls,
+            ldap_user_template,
+            snmp_version,
+            snmp_auth,
+            udp_timeout,
+            udp_retries,
+            preset,
+            evasion,
+            halt_on_captcha,
+            quick_defaults,
+            calibration_samples,
+            session,
+            no_session,
+            no_pot,
+            no_vault,
+            output_stream,
+            queue,
+            batch_size,
+            plugin,
+            webhook,
+            webhook_secret,
+            slack_webhook,
+            slack_token,
+            slack_channel,
+            discord_webhook,
+            email_to,
+            no_notify,
+            profile,
+            username_field,
+            password_field,
+            cookies,
+            headers,
+            data,
         } => {
-            let start_time = Instant::now();
+            // الملف الشخصي من ملف الإعدادات يكمل ما لم يُحدد في سطر الأوامر
+            let app_config = config::AppConfig::load(cli.config.as_deref()).await?;
+            let defaults = app_config.profile(profile.as_deref())?;
+            if let Some(name) = app_config.profile_name(profile.as_deref()) {
+                logger.info(&format!("الملف الشخصي: {}", name));
+            }
+            let url = url.or(defaults.url.clone());
+            let headers = defaults.merge_headers(headers)?;
+            let base = defaults.scan_options();
             
-            logger.info(&format!("بدء الفحص على: {}", url));
-            logger.info(&format!("المستخدمون: {}", user));
-            logger.info(&format!("خيوط المعالجة: {}", threads));
+            // --targets يُوسع إلى الأهداف الحية فقط، وإلا فالهدف الوحيد من --url
+            let targets = match &targets {
+                Some(spec) => {
+                    let template_protocol = protocol
+                        .or_else(|| url.as_deref().and_then(Protocol::from_target))
+                        .unwrap_or_default();
+                    let live = discovery::expand_targets(
+                        spec,
+                        url.as_deref(),
+                        template_protocol,
+                        Duration::from_millis(probe_timeout),
+                    )
+                    .await
+                    .context("فشل في توسيع الأهداف")?;
+                    
+                    logger.info(&format!("{} هدف حي من: {}", live.len(), spec));
+                    live
+                }
+                None => vec![url.ok_or_else(|| {
+                    anyhow::anyhow!("حدد --url أو --targets (أو url في الملف الشخصي)")
+                })?],
+            };
+            let Some(first) = targets.first().cloned() else {
+                logger.warn("لا توجد أهداف حية للفحص");
+                return Ok(ExitStatus::Unreachable);
+            };
             
-            // إنشاء الماسح
-            let scanner = RedFoxScanner::new(
-                &url,
-                &user,
-                &password_file,
-                threads,
-                timeout,
-                mode,
-                rate_limit,
-            )
-            .await
-            .context("فشل في تهيئة الماسح")?;
+            // البروتوكول المحدد أو المستنتج من مخطط الهدف (ssh://...)
+            let protocol = protocol
+                .or_else(|| Protocol::from_target(&first))
+                .unwrap_or_default();
+            let service_tls = service_tls
+                .or_else(|| TlsMode::from_target(&first))
+                .unwrap_or_default();
             
-            // تعيين البروكسي إذا وجد
-            if let Some(proxy_url) = proxy {
-                scanner.set_proxy(&proxy_url).await?;
+            // إشعارات الملف الشخصي من ملف الإعدادات، وخيارات سطر الأوامر تتقدم عليها
+            let configured = if no_notify {
+                NotifiersConfig::default()
+            } else {
+                app_config.notifiers(profile.as_deref())?
+            };
+            let mut notifiers = configured.merge(NotifiersConfig {
+                webhook: webhook.map(|url| WebhookConfig { url, secret: webhook_secret }),
+                slack: (slack_webhook.is_some() || slack_token.is_some()).then_some(SlackConfig {
+                    webhook_url: slack_webhook,
+                    bot_token: slack_token,
+                    channel: slack_channel,
+                }),
+                discord: discord_webhook.map(|webhook_url| DiscordConfig { webhook_url, username: None }),
+                email: None,
+            });
+            if !email_to.is_empty() {
+                let email = notifiers.email.as_mut().ok_or_else(|| {
+                    anyhow::anyhow!("--email-to يتطلب قسم [notifiers.email] في ملف الإعدادات")
+                })?;
+                email.to = email_to;
             }
             
-            // تشغيل الفحص
-            let results = scanner
-                .scan(verbose)
-                .await
-                .context("فشل في تنفيذ الفحص")?;
+            let config = SessionConfig {
+                url: first,
+                protocol,
+                service_tls,
+                ldap_user_template,
+                snmp_version,
+                snmp_auth,
+                udp_timeout,
+                udp_retries,
+                preset,
+                user,
+                password_file,
+                rules,
+                combo_file,
+                threads: threads.unwrap_or(base.threads),
+                timeout: timeout.unwrap_or(base.timeout.as_secs()),
+                mode: mode.unwrap_or_else(|| base.mode.name().to_string()),
+                rate_limit: rate_limit.or(base.rate_limit),
+                output,
+                format,
+                template,
+                redact,
+                proxy: proxy.or(defaults.proxy),
+                proxy_file,
+                rate_limit_per_proxy,
+                race_connections,
+                stop_on_success,
+                stop_per_user,
+                calibration_samples,
+                success_criteria: SuccessCriteria {
+                    body_match: success_match,
+                    body_not_match: failure_match,
+                    status_codes: success_status,
+                    redirect_match: success_redirect,
+                    length_delta,
+                    header: success_header,
+                    xpath: success_xpath,
+                }
+                .or(base.success_criteria),
+                halt_on_captcha,
+                quick_defaults,
+                evasion,
+                min_delay,
+                max_delay,
+                jitter,
+                http_version,
+                insecure,
+                tls_min,
+                tls_max,
+                ca_cert,
+                host_header,
+                resolve,
+                sni,
+                auth_type,
+                secret_header,
+                client_id,
+                client_secret,
+                scope,
+                body_format,
+                method,
+                query,
+                raw_request,
+                body_template,
+                username_field,
+                password_field,
+                cookies,
+                headers,
+                data,
+                csrf_field,
+                csrf_selector,
+                no_pot,
+                no_vault,
+                plaintext,
+                output_stream,
+                queue,
+                batch_size,
+                plugin,
+                notifiers,
+            };
             
-            // حساب الوقت المستغرق
-            let duration = start_time.elapsed();
+            status = if targets.len() > 1 {
+                run_targets(config, &targets, parallel_targets, verbose, &logger).await?
+            } else {
+                let session_path = (!no_session).then_some(session);
+                let results = run_scan(config, session_path, None, None, verbose, &logger).await?;
+                ExitStatus::from_results(&results)
+            };
+        }
+        
+        Command::Resume { session } => {
+            logger.info(&format!("استئناف الجلسة: {}", session.display()));
             
-            // عرض النتائج
-            display_results(&results, verbose, &logger);
+            let state = ScanSession::load(&session)
+                .await
+                .context("فشل في تحميل الجلسة")?;
             
-            // إظهار الإحصائيات
-            show_statistics(&results, duration, &logger);
+            if state.finished {
+                logger.success(&format!(
+                    "الجلسة مكتملة مسبقًا ({} بيانات اعتماد مكتشفة)",
+                    state.found.len()
+                ));
+                display_results(&state.found, verbose, &logger);
+                return Ok(ExitStatus::from_results(&state.found));
+            }
             
-            // حفظ النتائج
-            if let Some(output_path) = output {
-                save_results(&results, &output_path, format, &logger).await?;
+            let results = run_scan(state.config.clone(), Some(session), Some(state), None, verbose, &logger).await?;
+            status = ExitStatus::from_results(&results);
+        }
+        
+        Command::Sessions { action } => {
+            match action {
+                SessionsCommand::List => {
+                    let sessions = session::registered();
+                    if sessions.is_empty() {
+                        logger.warn("لا توجد جلسات مسجلة");
+                    }
+                    
+                    for (i, path) in sessions.iter().enumerate() {
+                        match ScanSession::load(path).await {
+                            Ok(state) => {
+                                let status = if state.finished {
+                                    "مكتملة".bright_green()
+                                } else {
+                                    format!("{:5.1}%", state.progress()).bright_yellow()
+                                };
+                                logger.print(&format!(
+                                    "{:3}. {:<40} {:>8} {:>4} إصابة  {}  {}",
+                                    i + 1,
+                                    state.config.url.bright_blue(),
+                                    status,
+                                    state.found.len(),
+                                    state.updated_at.with_timezone(&chrono::Local).format("%Y-%m-%d %H:%M"),
+                                    path.display().to_string().bright_black()
+                                ));
+                            }
+                            Err(e) => logger.print(&format!("{:3}. {} {}", i + 1, path.display(), format!("({})", e).bright_red())),
+                        }
+                    }
+                }
+                SessionsCommand::Show { session: reference, show } => {
+                    let path = session::resolve(&reference);
+                    let state = ScanSession::load(&path).await.context("فشل في تحميل الجلسة")?;
+                    
+                    logger.print(&format!("{:<18} {}", "الملف:", path.display()));
+                    logger.print(&format!("{:<18} {}", "الهدف:", state.config.url.bright_blue()));
+                    logger.print(&format!("{:<18} {:?}", "البروتوكول:", state.config.protocol));
+                    logger.print(&format!("{:<18} {}", "المستخدمون:", state.config.user.as_deref().unwrap_or("-")));
+                    logger.print(&format!("{:<18} {}", "كلمات المرور:", state.config.password_file.as_deref().unwrap_or("-")));
+                    logger.print(&format!("{:<18} {}", "أُنشئت:", state.created_at.with_timezone(&chrono::Local).format("%Y-%m-%d %H:%M:%S")));
+                    logger.print(&format!("{:<18} {}", "آخر حفظ:", state.updated_at.with_timezone(&chrono::Local).format("%Y-%m-%d %H:%M:%S")));
+                    logger.print(&format!(
+                        "{:<18} {}/{} ({:.1}%){}",
+                        "التقدم:",
+                        state.completed_attempts(),
+                        state.total_attempts,
+                        state.progress(),
+                        if state.finished { " - مكتملة" } else { "" }
+                    ));
+                    
+                    if state.found.is_empty() {
+                        logger.warn("لم تُكتشف بيانات اعتماد بعد");
+                    }
+                    for (i, result) in state.found.iter().enumerate() {
+                        let password = if show { result.password.clone() } else { "*".repeat(8) };
+                        logger.print(&format!("{:3}. {:<20} {}", i + 1, result.username.bright_cyan(), password.bright_yellow()));
+                    }
+                }
+                SessionsCommand::Delete { session: reference } => {
+                    let path = session::resolve(&reference);
+                    session::delete(&path)?;
+                    logger.success(&format!("تم حذف الجلسة: {}", path.display()));
+                }
             }
         }
         
@@ -117,7 +432,7 @@ async fn async_main() -> Result<()> {
             logger.info("بدء اختبار الأداء");
             
             // تنفيذ اختبار الأداء
-            modules::benchmark::run(
+            let report = modules::benchmark::run(
                 &url,
                 &users_file,
                 &passwords_file,
@@ -126,32 +441,116 @@ async fn async_main() -> Result<()> {
             )
             .await
             .context("فشل في اختبار الأداء")?;
+            
+            for (i, run) in report.runs.iter().enumerate() {
+                logger.print(&format!(
+                    "{}. {} محاولة في {:.2?} ({:.0} محاولة/ثانية)",
+                    i + 1,
+                    run.attempts,
+                    run.elapsed,
+                    run.rate()
+                ));
+            }
+            logger.success(&format!(
+                "المتوسط: {:.0} محاولة/ثانية، الأفضل: {:.0} ({} خيط)",
+                report.average_rate(),
+                report.best_rate(),
+                threads
+            ));
         }
         
         Command::Generate {
             wordlist,
             size,
             patterns,
+            mask,
+            charsets,
+            min_len,
+            max_len,
+            chars,
+            start_index,
+            base,
+            rules,
+            leet,
+            case,
+            arabic,
+            mut suffixes,
+            append_years,
+            append_symbols,
+            max_expansion,
+            crawl,
+            depth,
+            max_pages,
+            min_word_len,
+            users_output,
+            prince,
+            elem_cnt_min,
+            elem_cnt_max,
+            pw_min,
+            pw_max,
+            limit,
         } => {
             logger.info("توليد قائمة كلمات");
             
-            modules::generator::generate(
-                &wordlist,
-                size,
-                patterns.as_deref(),
-            )
-            .await
+            let start = u128::from(start_index);
+            let written = match (mask, min_len, base, crawl.as_deref()) {
+                (Some(mask), ..) => {
+                    let mask = modules::generator::Mask::parse(&mask, &charsets)
+                        .context(format!("قناع غير صالح: {}", mask))?;
+                    modules::generator::generate_mask(&wordlist, &mask, start).await
+                }
+                (None, Some(min_len), ..) => {
+                    let sequence = modules::generator::Sequence::new(&chars, min_len, max_len.unwrap_or(min_len))?;
+                    modules::generator::generate_sequence(&wordlist, &sequence, start).await
+                }
+                (None, None, Some(base), _) => {
+                    let year = chrono::Datelike::year(&chrono::Local::now());
+                    suffixes.extend(modules::generator::mutate::date_suffixes(append_years, append_symbols, year));
+                    let rules = match rules {
+                        Some(path) => Some(modules::generator::RuleSet::load(&path).await?),
+                        None => None,
+                    };
+                    let mutator = modules::generator::Mutator { leet, case, arabic, suffixes, max_expansion };
+                    modules::generator::generate_mutations(&wordlist, &base, rules, &mutator).await
+                }
+                (None, None, None, Some(url)) => {
+                    logger.info(&format!("حصاد كلمات {} (العمق: {}، الحد: {} صفحة)", url, depth, max_pages));
+                    let options = crawler::CrawlOptions {
+                        max_depth: depth,
+                        max_pages,
+                        timeout: Duration::from_secs(10),
+                    };
+                    modules::generator::generate_harvest(&wordlist, users_output.as_deref(), url, &options, min_word_len)
+                        .await
+                }
+                (None, None, None, None) if !prince.is_empty() => {
+                    let mut elements = Vec::new();
+                    for list in &prince {
+                        elements.extend(parser::parse_input(list).await?);
+                    }
+                    let combinator = modules::generator::Combinator::new(elements, (elem_cnt_min, elem_cnt_max), (pw_min, pw_max))?;
+                    modules::generator::generate_prince(&wordlist, &combinator, limit).await
+                }
+                (None, None, None, None) => modules::generator::generate(&wordlist, size, patterns.as_deref()).await,
+            }
             .context("فشل في توليد القائمة")?;
+            
+            logger.success(&format!("تم توليد {} كلمة في {}", written, wordlist));
+            
+            if let (Some(url), Some(users)) = (&crawl, &users_output) {
+                logger.info(&format!("للهجوم: redfox scan --url {} -U {} -P {}", url, users, wordlist));
+            }
         }
         
         Command::Validate { url } => {
             logger.info("التحقق من الهدف");
             
-            let is_valid = validator::validate_url(&url)
+            let validation = validator::validate_url(&url)
                 .await
                 .context("فشل في التحقق")?;
+            validation.display();
             
-            if is_valid {
+            if validation.is_valid {
                 logger.success("الهدف صالح للفحص");
             } else {
                 logger.error("الهدف غير صالح");
@@ -166,26 +565,803 @@ async fn async_main() -> Result<()> {
                 logger.warn("لا توجد قوائم كلمات متاحة");
             } else {
                 for (i, wordlist) in wordlists.iter().enumerate() {
-                    println!("{}. {}", i + 1, wordlist.green());
+                    logger.print(&format!("{}. {}", i + 1, wordlist.green()));
+                }
+            }
+        }
+        
+        Command::Plugins => {
+            let plugins = plugins::discover();
+            if plugins.is_empty() {
+                logger.warn(&format!("لا توجد إضافات في {}", plugins::DEFAULT_PLUGIN_DIR));
+            }
+            
+            for (i, path) in plugins.iter().enumerate() {
+                match plugins::PluginRuntime::load(path) {
+                    Ok(runtime) => logger.print(&format!("{}. {} ({})", i + 1, runtime.name().green(), path.display())),
+                    Err(e) => logger.print(&format!("{}. {} ({:#})", i + 1, path.display().to_string().red(), e)),
+                }
+            }
+        }
+        
+        Command::Defaults {
+            url,
+            vendor,
+            list,
+            threads,
+            timeout,
+            output,
+            format,
+        } => {
+            logger.info(&format!("أخذ بصمة الجهاز: {}", url));
+            
+            let fingerprint = defaults::Fingerprint::probe(&url, timeout)
+                .await
+                .context("فشل في أخذ بصمة الجهاز")?;
+            logger.info(&format!("البصمة: {}", fingerprint));
+            
+            let devices = match &vendor {
+                Some(vendor) => defaults::by_vendor(vendor),
+                None => fingerprint.matches(),
+            };
+            
+            if devices.is_empty() {
+                logger.warn("لم يتم التعرف على الجهاز، استخدم --vendor لتحديده يدويًا");
+                return Ok(ExitStatus::NoHits);
+            }
+            
+            for device in &devices {
+                logger.success(&format!("جهاز مطابق: {} {}", device.vendor, device.product));
+            }
+            
+            let pairs = defaults::credential_pairs(&devices);
+            if list {
+                for (username, password) in &pairs {
+                    logger.print(&format!("{}:{}", username, password));
+                }
+                return Ok(ExitStatus::Success);
+            }
+            
+            let start_time = Instant::now();
+            let mut scanner = ScannerBuilder::new()
+                .target(url.as_str())
+                .combos(pairs)
+                .options(ScanOptions {
+                    threads,
+                    timeout: Duration::from_secs(timeout),
+                    stop_on_success_global: true,
+                    ..ScanOptions::default()
+                })
+                .build()
+                .await
+                .context("فشل في تهيئة الماسح")?;
+            
+            // الأجهزة المدمجة تستخدم غالبًا شهادات موقعة ذاتيًا
+            scanner.set_transport(TransportOptions {
+                insecure: true,
+                ..TransportOptions::default()
+            })?;
+            
+            // نطاق WWW-Authenticate يعني أن الجهاز يستخدم HTTP Basic بدل النموذج
+            if fingerprint.realm.is_some() {
+                scanner.set_auth_type(AuthType::Basic);
+            }
+            
+            let results = scanner.scan(verbose).await.context("فشل في تنفيذ الفحص")?;
+            
+            display_results(&results, verbose, &logger);
+            show_statistics(&results, start_time.elapsed(), &logger);
+            
+            if let Some(output_path) = &output {
+                save_results(&results, output_path, format, None, !plaintext, &logger).await?;
+            }
+            if results.iter().any(|r| r.success) {
+                if let Err(e) = store_in_vault(&url, &results, &logger).await {
+                    logger.warn(&format!("لم تُحفظ النتائج في الخزنة: {}", e));
                 }
             }
+            status = ExitStatus::from_results(&results);
         }
         
+        Command::Discover {
+            url,
+            depth,
+            max_pages,
+            timeout,
+            output,
+        } => {
+            logger.info(&format!("زحف {} (العمق: {}، الحد: {} صفحة)", url, depth, max_pages));
+            
+            let options = crawler::CrawlOptions {
+                max_depth: depth,
+                max_pages,
+                timeout: Duration::from_secs(timeout),
+            };
+            let candidates = crawler::crawl(&url, &options)
+                .await
+                .context("فشل في زحف الموقع")?;
+            
+            if candidates.is_empty() {
+                logger.warn("لم يتم العثور على صفحات دخول");
+                return Ok(ExitStatus::NoHits);
+            }
+            
+            for (i, candidate) in candidates.iter().enumerate() {
+                logger.print(&format!(
+                    "{:3}. {:<6} {} {}",
+                    i + 1,
+                    candidate.method.bright_yellow(),
+                    candidate.target.bright_cyan(),
+                    candidate.scan_hint().dimmed()
+                ));
+            }
+            logger.success(&format!("تم العثور على {} هدف محتمل", candidates.len()));
+            
+            if let Some(output_path) = &output {
+                let content: String = candidates.iter().map(|c| format!("{}\n", c.target)).collect();
+                tokio::fs::write(output_path, content)
+                    .await
+                    .context(format!("فشل في كتابة الملف: {}", output_path))?;
+                logger.info(&format!("للفحص: redfox scan --targets {} ...", output_path));
+            }
+        }
+        
+        Command::JwtCrack {
+            token,
+            password_file,
+            threads,
+        } => {
+            let token = jwt::JwtToken::parse(&token).context("فشل في تحليل رمز JWT")?;
+            let candidates = parser::parse_input(&password_file)
+                .await
+                .context("فشل في تحميل قائمة الأسرار")?;
+            
+            logger.info(&format!(
+                "كسر سر {} من {} مرشح على {} خيط",
+                token.algorithm,
+                candidates.len(),
+                threads
+            ));
+            
+            let pool = rayon::ThreadPoolBuilder::new()
+                .num_threads(threads.max(1))
+                .build()
+                .context("فشل في إنشاء مجمع الخيوط")?;
+            let start = Instant::now();
+            let pb = progress::new_bar(candidates.len());
+            let found = pool.install(|| jwt::crack(&token, &candidates, Some(&pb)));
+            pb.finish_and_clear();
+            
+            match found {
+                Some(secret) => logger.success(&format!(
+                    "تم العثور على السر: {} (في {:.2?})",
+                    secret.bright_green().bold(),
+                    start.elapsed()
+                )),
+                None => {
+                    logger.warn(&format!("لم يُعثر على السر في {:.2?}", start.elapsed()));
+                    status = ExitStatus::NoHits;
+                }
+            }
+        }
+        
+        Command::HashCrack {
+            hashes,
+            password_file,
+            hash_type,
+            threads,
+            output,
+            format,
+        } => {
+            let entries = hashcrack::load(&hashes, hash_type).await?;
+            if entries.is_empty() {
+                return Err(anyhow::anyhow!("لا توجد تجزئات معروفة في: {}", hashes));
+            }
+            
+            status = run_offline_crack(&entries, &password_file, threads, output, format, plaintext, verbose, &logger).await?;
+        }
+        
+        Command::AuditFile {
+            file,
+            password_file,
+            file_format,
+            threads,
+            output,
+            format,
+        } => {
+            logger.info(&format!("تدقيق ملف كلمات المرور: {}", file));
+            
+            let entries = audit_file::load(&file, file_format).await?;
+            if entries.is_empty() {
+                return Err(anyhow::anyhow!("لا توجد تجزئات قابلة للتدقيق في: {}", file));
+            }
+            
+            status = run_offline_crack(&entries, &password_file, threads, output, format, plaintext, verbose, &logger).await?;
+        }
+        
+        Command::ProxyCheck {
+            input,
+            output,
+            check_url,
+            timeout,
+            threads,
+            min_anonymity,
+        } => {
+            logger.info(&format!("فحص البروكسيات من: {}", input));
+            
+            let results = proxy_check::check_all(&input, &check_url, timeout, threads)
+                .await
+                .context("فشل في فحص البروكسيات")?;
+            
+            proxy_check::display_results(&results);
+            
+            if let Some(output_path) = output {
+                let written = proxy_check::write_proxy_file(&results, &output_path, min_anonymity)
+                    .await?;
+                logger.success(&format!("تم حفظ {} بروكسي في: {}", written, output_path));
+            }
+        }
+        
+        Command::Report { action } => {
+            match action {
+                ReportCommand::Regenerate { input, output, format, template, redact } => {
+                    logger.info(&format!("تحميل النتائج من: {}", input));
+                    
+                    let results = results_store::load_results(&input)
+                        .await
+                        .context("فشل في تحميل النتائج")?;
+                    logger.info(&format!("تم تحميل {} نتيجة", results.len()));
+                    
+                    save_results(&results, &output, Some(format), template.as_deref(), redact, &logger).await?;
+                }
+                
+                ReportCommand::Merge { inputs, output, format, template, redact } => {
+                    let mut sources = Vec::new();
+                    for input in &inputs {
+                        let results = results_store::load_results(input)
+                            .await
+                            .context(format!("فشل في تحميل النتائج: {}", input))?;
+                        logger.info(&format!("تم تحميل {} نتيجة من: {}", results.len(), input));
+                        sources.push(results);
+                    }
+                    
+                    let total: usize = sources.iter().map(Vec::len).sum();
+                    let results = results_store::merge_results(sources);
+                    logger.info(&format!(
+                        "{} نتيجة بعد الدمج ({} مكررة)",
+                        results.len(),
+                        total - results.len()
+                    ));
+                    
+                    save_results(&results, &output, Some(format), template.as_deref(), redact, &logger).await?;
+                }
+            }
+        }
+        
+        Command::Vault { action } => {
+            let passphrase = vault::passphrase(false)?
+                .ok_or_else(|| anyhow::anyhow!("عبارة مرور الخزنة مطلوبة ({})", vault::PASSPHRASE_ENV))?;
+            let store = vault::Vault::open(vault::DEFAULT_VAULT, passphrase)
+                .await
+                .context("فشل في فتح الخزنة")?;
+            
+            match action {
+                VaultCommand::List { target, show } => {
+                    let entries: Vec<_> = store
+                        .entries()
+                        .iter()
+                        .filter(|e| target.as_deref().map_or(true, |t| e.target.contains(t)))
+                        .collect();
+                    
+                    if entries.is_empty() {
+                        logger.warn("الخزنة فارغة");
+                    }
+                    for (i, entry) in entries.iter().enumerate() {
+                        let password = if show { entry.password.clone() } else { "*".repeat(8) };
+                        logger.print(&format!(
+                            "{:3}. {:<40} {:<20} {:<20} {}",
+                            i + 1,
+                            entry.target.bright_blue(),
+                            entry.username.bright_cyan(),
+                            password.bright_yellow(),
+                            entry.found_at.format("%Y-%m-%d %H:%M")
+                        ));
+                    }
+                }
+                VaultCommand::Export { output } => {
+                    vault::export(store.entries(), &output)
+                        .await
+                        .context("فشل في تصدير الخزنة")?;
+                    logger.warn(&format!(
+                        "تم تصدير {} بيانات اعتماد كنص صريح إلى: {}",
+                        store.entries().len(),
+                        output.display()
+                    ));
+                }
+            }
+        }
+        
+        Command::Wordlist { action } => {
+            match action {
+                WordlistCommand::Merge { inputs, output, dedupe, bloom } => {
+                    logger.info(&format!("دمج {} قائمة", inputs.len()));
+                    
+                    let mode = match (dedupe, bloom) {
+                        (false, _) => wordlist::DedupeMode::None,
+                        (true, None) => wordlist::DedupeMode::Exact,
+                        (true, Some(false_positive_rate)) => wordlist::DedupeMode::Bloom { false_positive_rate },
+                    };
+                    let written = wordlist::merge(&inputs, &output, mode)
+                        .await
+                        .context("فشل في دمج القوائم")?;
+                    logger.success(&format!("تمت كتابة {} إدخال في: {}", written, output));
+                }
+                WordlistCommand::Dedupe { input, output } => {
+                    logger.info(&format!("إزالة التكرارات من: {}", input));
+                    
+                    let written = wordlist::dedupe(&input, &output)
+                        .await
+                        .context("فشل في إزالة التكرارات")?;
+                    logger.success(&format!("تمت كتابة {} إدخال فريد في: {}", written, output));
+                }
+                WordlistCommand::Sort { input, output, unique } => {
+                    logger.info(&format!("فرز: {}", input));
+                    
+                    let written = wordlist::sort(&input, &output, unique)
+                        .await
+                        .context("فشل في فرز القائمة")?;
+                    logger.success(&format!("تمت كتابة {} إدخال في: {}", written, output));
+                }
+                WordlistCommand::Filter { input, output, policy } => {
+                    logger.info(&format!("تصفية: {}", input));
+                    
+                    let written = wordlist::filter(&input, &output, &policy)
+                        .await
+                        .context("فشل في تصفية القائمة")?;
+                    logger.success(&format!("تمت كتابة {} إدخال مطابق في: {}", written, output));
+                }
+                WordlistCommand::Stats { input, rps, users } => {
+                    logger.info(&format!("تحليل: {}", input));
+                    
+                    let stats = wordlist::stats(&input)
+                        .await
+                        .context("فشل في حساب الإحصائيات")?;
+                    stats.display(users, rps);
+                }
+                WordlistCommand::Split { input, parts, bytes, output_dir } => {
+                    logger.info(&format!("تقسيم: {}", input));
+                    
+                    let mode = match bytes {
+                        Some(bytes) => wordlist::SplitMode::Bytes(bytes),
+                        None => wordlist::SplitMode::Parts(parts.unwrap_or(1)),
+                    };
+                    let (manifest, manifest_path) = wordlist::split(&input, output_dir.as_deref(), mode)
+                        .await
+                        .context("فشل في تقسيم القائمة")?;
+                    
+                    for part in &manifest.parts {
+                        logger.print(&format!("  {:<40} {:>10} إدخال {:>12} بايت", part.path.display(), part.entries, part.bytes));
+                    }
+                    logger.success(&format!(
+                        "{} إدخال في {} جزء، البيان: {}",
+                        manifest.total_entries,
+                        manifest.parts.len(),
+                        manifest_path.display()
+                    ));
+                }
+            }
+        }
+        
+        Command::Shell => {
+            run_shell(&logger).await?;
+        }
+        
+        Command::Worker { queue } => {
+            queue::work(&queue).await?;
+        }
+        
+        Command::Serve { listen, token, agents_listen, cert, key, client_ca } => {
+            let agents = match (agents_listen, cert, key, client_ca) {
+                (Some(address), Some(cert), Some(key), Some(ca)) => {
+                    Some((address, agent::TlsFiles { cert, key, ca }))
+                }
+                _ => None,
+            };
+            server::serve(listen, token, agents).await?;
+        }
+        
+        Command::Agent { join, cert, key, ca, name, capacity } => {
+            let name = name
+                .or_else(|| std::env::var("HOSTNAME").ok())
+                .unwrap_or_else(|| "redfox-agent".to_string());
+            agent::join(&join, &name, capacity, &agent::TlsFiles { cert, key, ca }).await?;
+        }
+        
+        Command::Config { action } => {
+            let path = config::config_path(cli.config.as_deref());
+            
+            match action {
+                ConfigCommand::Init { force } => {
+                    config::init(&path, force).await.context("فشل في إنشاء ملف الإعدادات")?;
+                    logger.success(&format!("تم إنشاء ملف الإعدادات: {}", path.display()));
+                }
+                ConfigCommand::Show { profile } => {
+                    let app_config = config::AppConfig::load(Some(&path)).await?;
+                    let active = app_config.profile_name(profile.as_deref()).map(str::to_string);
+                    
+                    logger.print(&format!("{} {}", "ملف الإعدادات:".bright_white().bold(), path.display()));
+                    for name in app_config.profiles.keys() {
+                        let marker = if Some(name) == active.as_ref() { "*" } else { " " };
+                        logger.print(&format!(" {} {}", marker.bright_green(), name.bright_cyan()));
+                    }
+                    
+                    if let Some(name) = &active {
+                        let effective = app_config.profile(Some(name))?;
+                        logger.print(&format!("\n[profiles.{}]", name.bright_yellow()));
+                        logger.print(&toml::to_string_pretty(&effective).context("فشل في ترميز الملف الشخصي")?);
+                    }
+                }
+                ConfigCommand::Use { profile } => {
+                    config::use_profile(&path, &profile).await?;
+                    logger.success(&format!("الملف الشخصي الفعال: {}", profile));
+                }
+            }
+        }
+        
+        // عولج قبل عرض البانر
+        Command::Completions { .. } => {}
+        
         Command::Update => {
             logger.info("التحقق من التحديثات");
             
-            utils::updater::check_for_updates()
+            let latest = utils::updater::check_for_updates()
                 .await
                 .context("فشل في التحقق من التحديثات")?;
+            match latest {
+                Some(version) => logger.success(&format!(
+                    "إصدار جديد متاح: {} (الحالي {}) - حدّث عبر scripts/update.sh",
+                    version,
+                    env!("CARGO_PKG_VERSION")
+                )),
+                None => logger.success("أنت تستخدم أحدث إصدار"),
+            }
         }
     }
     
     logger.info("اكتمل التنفيذ بنجاح");
+    Ok(status)
+}
+
+/// حفظ النتائج الناجحة في الخزنة المشفرة
+async fn store_in_vault(
+    target: &str,
+    results: &[crate::scanner::ScanResult],
+    logger: &Logger,
+) -> Result<()> {
+    let passphrase = vault::passphrase(!vault::exists(vault::DEFAULT_VAULT))?
+        .ok_or_else(|| anyhow::anyhow!("لا توجد عبارة مرور (عيّن {})", vault::PASSPHRASE_ENV))?;
+    
+    let mut store = vault::Vault::open(vault::DEFAULT_VAULT, passphrase).await?;
+    let added = store.add_results(target, results);
+    if added > 0 {
+        store.save().await?;
+        logger.success(&format!("تم حفظ {} بيانات اعتماد في الخزنة: {}", added, store.path().display()));
+    }
+    
     Ok(())
 }
 
+/// كسر تجزئات محملة دون اتصال وعرض النتائج وحفظها كنتائج فحص عادية
+async fn run_offline_crack(
+    entries: &[hashcrack::HashEntry],
+    password_file: &str,
+    threads: usize,
+    output: Option<String>,
+    format: Option<String>,
+    plaintext: bool,
+    verbose: bool,
+    logger: &Logger,
+) -> Result<ExitStatus> {
+    let candidates = parser::parse_input(password_file)
+        .await
+        .context("فشل في تحميل قائمة كلمات المرور")?;
+    logger.info(&format!(
+        "كسر {} تجزئة بـ {} كلمة على {} خيط",
+        entries.len(),
+        candidates.len(),
+        threads
+    ));
+    
+    let pool = rayon::ThreadPoolBuilder::new()
+        .num_threads(threads.max(1))
+        .build()
+        .context("فشل في إنشاء مجمع الخيوط")?;
+    let start_time = Instant::now();
+    let pb = progress::new_bar(hashcrack::work_size(entries, candidates.len()));
+    let results = pool.install(|| hashcrack::crack(entries, &candidates, Some(&pb)));
+    pb.finish_and_clear();
+    
+    display_results(&results, verbose, logger);
+    show_statistics(&results, start_time.elapsed(), logger);
+    
+    if let Some(output_path) = &output {
+        save_results(&results, output_path, format, None, !plaintext, logger).await?;
+    }
+    
+    Ok(ExitStatus::from_results(&results))
+}
+
+/// فحص عدة أهداف (بدون جلسات، حتى `parallel` في آن واحد) وحفظ نتائجها المجمعة مرة واحدة
+///
+/// حد المعدل والتزامن يُتتبعان لكل مضيف: الأهداف على نفس السلطة تتقاسم حدًا واحدًا،
+/// ورمز الخروج يجمع حالة كل هدف على حدة
+async fn run_targets(
+    config: SessionConfig,
+    targets: &[String],
+    parallel: usize,
+    verbose: bool,
+    logger: &Logger,
+) -> Result<ExitStatus> {
+    use futures::stream::{self, StreamExt};
+    
+    let limits = throttle::HostLimiters::new(config.rate_limit, config.threads);
+    let mut results = Vec::new();
+    let mut statuses = Vec::with_capacity(targets.len());
+    
+    let mut scans = stream::iter(targets.iter().enumerate())
+        .map(|(i, target)| {
+            logger.info(&format!("[{}/{}] الهدف: {}", i + 1, targets.len(), target));
+            
+            let target_config = SessionConfig {
+                url: target.clone(),
+                output: None,
+                ..config.clone()
+            };
+            let limits = &limits;
+            async move {
+                (target, run_scan(target_config, None, None, Some(limits), verbose, logger).await)
+            }
+        })
+        .buffer_unordered(parallel.max(1));
+    
+    while let Some((target, outcome)) = scans.next().await {
+        match outcome {
+            Ok(target_results) => {
+                statuses.push(ExitStatus::from_results(&target_results));
+                results.extend(target_results);
+            }
+            Err(e) => {
+                logger.error(&format!("فشل فحص {}: {}", target, e));
+                statuses.push(ExitStatus::from_error(&e));
+            }
+        }
+    }
+    
+    if let Some(output_path) = &config.output {
+        let template = config.template.as_deref();
+        save_results(&results, output_path, config.format.clone(), template, config.redacts(), logger).await?;
+    }
+    
+    Ok(ExitStatus::combine(&statuses))
+}
+
+/// الوضع التفاعلي: حلقة قراءة وتنفيذ تحتفظ بالخيارات والنتائج بين الأوامر
+async fn run_shell(logger: &Logger) -> Result<()> {
+    let mut editor = rustyline::DefaultEditor::new().context("فشل في تهيئة الطرفية التفاعلية")?;
+    let history = PathBuf::from(shellexpand::tilde(SHELL_HISTORY).as_ref());
+    editor.load_history(&history).ok();
+    
+    let mut state = shell::ShellState::new();
+    logger.print(&"اكتب help لعرض الأوامر، و exit للخروج".bright_black().to_string());
+    
+    loop {
+        let line = match editor.readline("redfox > ") {
+            Ok(line) => line,
+            Err(rustyline::error::ReadlineError::Interrupted) => continue,
+            Err(rustyline::error::ReadlineError::Eof) => break,
+            Err(e) => return Err(e.into()),
+        };
+        editor.add_history_entry(line.as_str()).ok();
+        
+        let command = match shell::ShellCommand::parse(&line) {
+            Ok(Some(command)) => command,
+            Ok(None) => continue,
+            Err(e) => {
+                logger.error(&e.to_string());
+                continue;
+            }
+        };
+        
+        let outcome = match command {
+            shell::ShellCommand::Set { key, value } => state.set(&key, &value).map(|()| {
+                logger.print(&format!("{} => {}", key.bright_cyan(), value));
+            }),
+            shell::ShellCommand::Unset(key) => state.unset(&key),
+            shell::ShellCommand::ShowOptions => {
+                for (key, value) in state.options() {
+                    logger.print(&format!("  {:<35} {}", key.bright_cyan(), value));
+                }
+                Ok(())
+            }
+            shell::ShellCommand::ShowResults => {
+                if state.results.is_empty() {
+                    logger.warn("لا توجد نتائج بعد (run)");
+                } else {
+                    display_results(&state.results, false, logger);
+                }
+                Ok(())
+            }
+            shell::ShellCommand::Run => match state.config() {
+                Ok(config) => run_scan(config, None, None, None, false, logger)
+                    .await
+                    .map(|results| state.results = results),
+                Err(e) => Err(e),
+            },
+            shell::ShellCommand::Save { output, format } => {
+                save_results(&state.results, &output, format, None, false, logger).await.map(|_| ())
+            }
+            shell::ShellCommand::Help => {
+                logger.print(shell::HELP);
+                Ok(())
+            }
+            shell::ShellCommand::Exit => break,
+        };
+        
+        if let Err(e) = outcome {
+            logger.error(&format!("{:#}", e));
+        }
+    }
+    
+    if let Some(parent) = history.parent() {
+        std::fs::create_dir_all(parent).ok();
+    }
+    editor.save_history(&history).ok();
+    Ok(())
+}
+
+/// تشغيل فحص كامل (جديد أو مستأنف من جلسة)
+async fn run_scan(
+    config: SessionConfig,
+    session_path: Option<PathBuf>,
+    existing: Option<ScanSession>,
+    limits: Option<&throttle::HostLimiters>,
+    verbose: bool,
+    logger: &Logger,
+) -> Result<Vec<scanner::ScanResult>> {
+    let start_time = Instant::now();
+    
+    let url = config.login_url()?;
+    
+    logger.info(&format!("بدء الفحص على: {}", url));
+    logger.info(&format!("خيوط المعالجة: {}", config.threads));
+    
+    let mut scanner = RedFoxScanner::from_config(&config).await?;
+    if let Some(limits) = limits {
+        scanner.attach_host_limiter(limits.get(&url));
+    }
+    
+    // بيانات الاعتماد المعروفة مسبقًا لهذا الهدف لا يُعاد تخمينها
+    let mut potfile = if config.no_pot {
+        None
+    } else {
+        match Potfile::open(DEFAULT_POTFILE).await {
+            Ok(potfile) => Some(potfile),
+            Err(e) => {
+                logger.warn(&format!("تعذر تحميل potfile: {}", e));
+                None
+            }
+        }
+    };
+    if let Some(known) = potfile.as_ref().map(|p| p.known(&url)).filter(|k| !k.is_empty()) {
+        for (username, password) in known {
+            logger.success(&format!("معروفة مسبقًا في potfile: {}:{}", username, password));
+        }
+        scanner.skip_known(known);
+    }
+    
+    if let Some(stream_path) = &config.output_stream {
+        let stream = results_store::ResultStream::open(stream_path)?;
+        scanner.attach_stream(Arc::new(stream));
+    }
+    
+    let backends = config.notifiers.build()?;
+    let notifications = if backends.is_empty() {
+        None
+    } else {
+        let notifications = Arc::new(Notifications::new(backends, &url)?);
+        scanner.attach_notifications(Arc::clone(&notifications));
+        Some(notifications)
+    };
+    
+    // ربط الجلسة لحفظ التقدم دوريًا
+    let recorder = session_path.map(|path| {
+        if let Err(e) = session::register(&path) {
+            logger.warn(&format!("فشل في تسجيل الجلسة في الفهرس: {}", e));
+        }
+        let state = existing.unwrap_or_else(|| ScanSession::new(config.clone()));
+        Arc::new(SessionRecorder::new(path, state, DEFAULT_CHECKPOINT_INTERVAL))
+    });
+    if let Some(recorder) = &recorder {
+        scanner.attach_session(Arc::clone(recorder));
+    }
+    
+    // تشغيل الفحص مع حفظ الجلسة عند المقاطعة
+    let results = tokio::select! {
+        results = async {
+            match &config.queue {
+                Some(queue) => queue::coordinate(queue, &config, scanner.pair_stream(), config.batch_size).await,
+                None => Ok(scanner.scan(verbose).await?),
+            }
+        } => results.context("فشل في تنفيذ الفحص")?,
+        _ = tokio::signal::ctrl_c() => {
+            if let Some(recorder) = &recorder {
+                recorder.checkpoint().context("فشل في حفظ الجلسة")?;
+                logger.warn(&format!(
+                    "تمت مقاطعة الفحص، للاستئناف: redfox resume {}",
+                    recorder.path().display()
+                ));
+            } else {
+                logger.warn("تمت مقاطعة الفحص");
+            }
+            tokio::task::block_in_place(telemetry::shutdown);
+            process::exit(ExitStatus::Aborted.code());
+        }
+    };
+    
+    // حساب الوقت المستغرق
+    let duration = start_time.elapsed();
+    
+    if let Some(notifications) = &notifications {
+        notifications.finish(&results, duration).await;
+    }
+    
+    // عرض النتائج
+    display_results(&results, verbose, logger);
+    
+    // إظهار الإحصائيات
+    show_statistics(&results, duration, logger);
+    
+    // حفظ النتائج
+    let report_path = match &config.output {
+        Some(output_path) => {
+            let template = config.template.as_deref();
+            Some(save_results(&results, output_path, config.format.clone(), template, config.redacts(), logger).await?)
+        }
+        None => None,
+    };
+    
+    if let Some(email) = &config.notifiers.email {
+        let report = report_path.as_deref().map(Path::new);
+        match notifiers::email::send_summary(email, &url, &results, duration, report).await {
+            Ok(()) => logger.info(&format!("تم إرسال الملخص إلى: {}", email.to.join(", "))),
+            Err(e) => logger.warn(&format!("فشل في إرسال البريد: {}", e)),
+        }
+    }
+    
+    if !config.no_vault && results.iter().any(|r| r.success) {
+        if let Err(e) = store_in_vault(&url, &results, logger).await {
+            logger.warn(&format!("لم تُحفظ النتائج في الخزنة: {}", e));
+        }
+    }
+    
+    // potfile نص واضح، فلا تُضاف إليه بيانات الاعتماد إلا مع --plaintext
+    if let Some(potfile) = potfile.as_mut().filter(|_| config.plaintext) {
+        match potfile.record(&url, &results).await {
+            Ok(0) => {}
+            Ok(added) => logger.info(&format!(
+                "تمت إضافة {} بيانات اعتماد إلى: {}",
+                added,
+                potfile.path().display()
+            )),
+            Err(e) => logger.warn(&format!("فشل في تحديث potfile: {}", e)),
+        }
+    }
+    
+    Ok(results)
+}
+
 /// عرض البانر
-fn show_banner() {
+fn show_banner(logger: &Logger) {
     let banner = r#"
     ██████╗ ███████╗██████╗ ███████╗ ██████╗ ██╗  ██╗
     ██╔══██╗██╔════╝██╔══██╗██╔════╝██╔═══██╗╚██╗██╔╝
@@ -198,11 +1374,16 @@ fn show_banner() {
     ===============================================
     "#.bright_red();
     
-    println!("{}", banner);
+    logger.print(&banner.to_string());
 }
 
 /// عرض النتائج
 fn display_results(results: &[crate::scanner::ScanResult], verbose: bool, logger: &Logger) {
+    if output::is_json() {
+        results.iter().filter(|r| r.success).for_each(|r| output::emit(&output::credential(r)));
+        return;
+    }
+    
     if results.is_empty() {
         logger.warn("لم يتم العثور على نتائج");
         return;
@@ -211,36 +1392,36 @@ fn display_results(results: &[crate::scanner::ScanResult], verbose: bool, logger
     let successes: Vec<_> = results.iter().filter(|r| r.success).collect();
     
     if !successes.is_empty() {
-        println!("\n{}", "نتائج ناجحة:".bright_green().bold());
-        println!("{}", "-".repeat(60).bright_blue());
+        logger.print(&format!("\n{}", "نتائج ناجحة:".bright_green().bold()));
+        logger.print(&"-".repeat(60).bright_blue().to_string());
         
         for (i, result) in successes.iter().enumerate() {
-            println!(
+            logger.print(&format!(
                 "{:3}. {:<20} {:<30} [{}] {:.2?}",
                 i + 1,
                 result.username.bright_cyan(),
                 result.password.bright_yellow(),
                 result.status_code,
                 result.response_time
-            );
+            ));
         }
     }
     
     if verbose {
         let failures: Vec<_> = results.iter().filter(|r| !r.success).collect();
         if !failures.is_empty() {
-            println!("\n{}", "محاولات فاشلة:".bright_yellow().bold());
+            logger.print(&format!("\n{}", "محاولات فاشلة:".bright_yellow().bold()));
             for result in failures.iter().take(10) {
-                println!(
+                logger.print(&format!(
                     "✗ {:<20} {:<30} - {}",
                     result.username,
                     result.password,
                     result.error.as_deref().unwrap_or("غير معروف")
-                );
+                ));
             }
             
             if failures.len() > 10 {
-                println!("... و {} محاولة أخرى", failures.len() - 10);
+                logger.print(&format!("... و {} محاولة أخرى", failures.len() - 10));
             }
         }
     }
@@ -248,22 +1429,30 @@ fn display_results(results: &[crate::scanner::ScanResult], verbose: bool, logger
 
 /// عرض الإحصائيات
 fn show_statistics(results: &[crate::scanner::ScanResult], duration: std::time::Duration, logger: &Logger) {
+    if output::is_json() {
+        output::emit(&output::summary(results, duration));
+        return;
+    }
+    if output::is_quiet() {
+        return;
+    }
+    
     let total = results.len();
     let successes = results.iter().filter(|r| r.success).count();
     let failures = total - successes;
     let rps = total as f64 / duration.as_secs_f64();
     
-    println!("\n{}", "إحصائيات الفحص:".bright_magenta().bold());
-    println!("{}", "=".repeat(60).bright_blue());
-    println!("الوقت المستغرق:          {:.2?}", duration);
-    println!("إجمالي المحاولات:       {}", total);
-    println!("المحاولات الناجحة:      {}", successes.to_string().bright_green());
-    println!("المحاولات الفاشلة:      {}", failures.to_string().bright_red());
-    println!("معدل المحاولات/ثانية:  {:.2}", rps.to_string().bright_yellow());
+    logger.print(&format!("\n{}", "إحصائيات الفحص:".bright_magenta().bold()));
+    logger.print(&"=".repeat(60).bright_blue().to_string());
+    logger.print(&format!("الوقت المستغرق:          {:.2?}", duration));
+    logger.print(&format!("إجمالي المحاولات:       {}", total));
+    logger.print(&format!("المحاولات الناجحة:      {}", successes.to_string().bright_green()));
+    logger.print(&format!("المحاولات الفاشلة:      {}", failures.to_string().bright_red()));
+    logger.print(&format!("معدل المحاولات/ثانية:  {:.2}", rps.to_string().bright_yellow()));
     
     if successes > 0 {
         let success_rate = (successes as f64 / total as f64) * 100.0;
-        println!("معدل النجاح:            {:.2}%", success_rate);
+        logger.print(&format!("معدل النجاح:            {:.2}%", success_rate));
     }
 }
 
@@ -272,9 +1461,21 @@ async fn save_results(
     results: &[crate::scanner::ScanResult],
     output_path: &str,
     format: Option<String>,
+    template: Option<&Path>,
+    redact: bool,
     logger: &Logger,
-) -> Result<()> {
-    let generator = ReportGenerator::new();
+) -> Result<String> {
+    let mut generator = ReportGenerator::new();
+    if let Some(template) = template {
+        generator = generator.with_template(template);
+    }
+    if redact {
+        let key = reporter::redaction_key();
+        if key.is_none() {
+            logger.info(&format!("كلمات المرور مخفية بلا تجزئة (اضبط {} لإضافة HMAC)", reporter::REDACTION_KEY_ENV));
+        }
+        generator = generator.with_redaction(key.as_deref());
+    }
     let format = format.unwrap_or_else(|| "json".to_string());
     
     let report_path = generator
@@ -283,7 +1484,7 @@ async fn save_results(
         .context("فشل في إنشاء التقرير")?;
     
     logger.success(&format!("تم حفظ التقرير في: {}", report_path));
-    Ok(())
+    Ok(report_path)
 }
 
 /// نقطة الدخول الرئيسية
@@ -291,21 +1492,28 @@ fn main() {
     // إنشاء وقت تشغيل Tokio
     let rt = Runtime::new().unwrap_or_else(|e| {
         eprintln!("فشل في إنشاء وقت التشغيل: {}", e);
-        process::exit(1);
+        process::exit(ExitStatus::Usage.code());
     });
     
-    // تشغيل الدالة الرئيسية
-    if let Err(e) = rt.block_on(async_main()) {
-        eprintln!("{}: {}", "خطأ".bright_red(), e);
-        
-        // عرض التفاصيل في الوضع التفصيلي
-        if std::env::var("RUST_BACKTRACE").is_ok() {
-            eprintln!("\nتفاصيل الخطأ:");
-            for cause in e.chain() {
-                eprintln!("  - {}", cause);
+    // تشغيل الدالة الرئيسية ثم تفريغ التتبعات المعلقة
+    let outcome = rt.block_on(async_main());
+    telemetry::shutdown();
+    
+    match outcome {
+        Ok(status) => process::exit(status.code()),
+        Err(e) => {
+            let logger = Logger;
+            logger.error(&e.to_string());
+            
+            // عرض التفاصيل في الوضع التفصيلي
+            if std::env::var("RUST_BACKTRACE").is_ok() {
+                logger.error("تفاصيل الخطأ:");
+                for cause in e.chain() {
+                    logger.error(&format!("  - {}", cause));
+                }
             }
+            
+            process::exit(ExitStatus::from_error(&e).code());
         }
-        
-        process::exit(1);
     }
 }
\ No newline at end of file
@@ -0,0 +1,352 @@
+//! قواعد كشف النجاح والفشل
+//! معايير يحددها المستخدم (تعابير نمطية، رموز حالة، تحويل، طول، ترويسات، XPath) تُترجم إلى مطابق مُجمّع
+
+use regex::{Regex, RegexBuilder};
+use reqwest::header::HeaderMap;
+use reqwest::Response;
+use anyhow::{Result, Context};
+
+use crate::calibration::{FailureBaseline, ResponseFingerprint, FAILURE_INDICATORS};
+
+/// مؤشرات النجاح الافتراضية (عند غياب القواعد وخط الأساس)
+const SUCCESS_INDICATORS: [&str; 8] = [
+    "welcome", "dashboard", "home", "logout", "profile",
+    "success", "logged in", "redirecting",
+];
+
+/// معايير النجاح كما يحددها المستخدم
+#[derive(Debug, Clone, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct SuccessCriteria {
+    /// تعبير نمطي يجب أن يطابق المحتوى عند النجاح
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub body_match: Option<String>,
+    
+    /// تعبير نمطي يدل على الفشل إذا طابق المحتوى
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub body_not_match: Option<String>,
+    
+    /// رموز الحالة المتوقعة عند النجاح
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub status_codes: Vec<u16>,
+    
+    /// تعبير نمطي لوجهة التحويل عند النجاح
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub redirect_match: Option<String>,
+    
+    /// الحد الأدنى لفرق الطول عن استجابة الفشل المعايرة (بايت)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub length_delta: Option<usize>,
+    
+    /// ترويسة يجب وجودها عند النجاح (NAME أو NAME:VALUE)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub header: Option<String>,
+    
+    /// تعبير XPath يجب أن يتحقق في استجابة XML/SOAP
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub xpath: Option<String>,
+}
+
+impl SuccessCriteria {
+    /// هل حدد المستخدم أي قاعدة؟
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+    
+    /// إكمال القواعد غير المحددة من قواعد احتياطية (ملف شخصي مثلًا)
+    pub fn or(self, fallback: Self) -> Self {
+        Self {
+            body_match: self.body_match.or(fallback.body_match),
+            body_not_match: self.body_not_match.or(fallback.body_not_match),
+            status_codes: if self.status_codes.is_empty() { fallback.status_codes } else { self.status_codes },
+            redirect_match: self.redirect_match.or(fallback.redirect_match),
+            length_delta: self.length_delta.or(fallback.length_delta),
+            header: self.header.or(fallback.header),
+            xpath: self.xpath.or(fallback.xpath),
+        }
+    }
+}
+
+/// استجابة مقروءة بالكامل لتقييم القواعد
+#[derive(Debug, Clone)]
+pub struct CapturedResponse {
+    /// رمز الحالة
+    pub status: u16,
+    
+    /// الترويسات
+    pub headers: HeaderMap,
+    
+    /// وجهة التحويل (ترويسة Location أو الرابط النهائي بعد التحويل)
+    pub redirect: Option<String>,
+    
+    /// المحتوى
+    pub body: String,
+}
+
+impl CapturedResponse {
+    /// قراءة الاستجابة (يستهلك المحتوى)
+    pub async fn capture(response: Response, request_url: &str) -> Self {
+        let status = response.status().as_u16();
+        let headers = response.headers().clone();
+        
+        let redirect = headers
+            .get("Location")
+            .and_then(|l| l.to_str().ok())
+            .map(str::to_string)
+            .or_else(|| {
+                let final_url = response.url().as_str();
+                (final_url.trim_end_matches('/') != request_url.trim_end_matches('/'))
+                    .then(|| final_url.to_string())
+            });
+        
+        let body = response.text().await.unwrap_or_default();
+        
+        Self { status, headers, redirect, body }
+    }
+    
+    /// بصمة الاستجابة لمقارنتها بخط أساس الفشل
+    pub fn fingerprint(&self) -> ResponseFingerprint {
+        ResponseFingerprint::from_parts(self.status, self.redirect.clone(), &self.body)
+    }
+}
+
+/// مطابق النجاح المُجمّع
+#[derive(Debug, Clone, Default)]
+pub struct SuccessMatcher {
+    body_match: Option<Regex>,
+    body_not_match: Option<Regex>,
+    status_codes: Vec<u16>,
+    redirect_match: Option<Regex>,
+    length_delta: Option<usize>,
+    header: Option<(String, Option<String>)>,
+    xpath: Option<String>,
+}
+
+impl SuccessMatcher {
+    /// تجميع المعايير إلى مطابق
+    pub fn compile(criteria: &SuccessCriteria) -> Result<Self> {
+        let regex = |pattern: &Option<String>, name: &str| -> Result<Option<Regex>> {
+            pattern
+                .as_deref()
+                .map(|p| {
+                    RegexBuilder::new(p)
+                        .case_insensitive(true)
+                        .build()
+                        .context(format!("تعبير نمطي غير صالح لـ {}: {}", name, p))
+                })
+                .transpose()
+        };
+        
+        let header = criteria.header.as_deref().map(|h| match h.split_once(':') {
+            Some((name, value)) => (name.trim().to_lowercase(), Some(value.trim().to_string())),
+            None => (h.trim().to_lowercase(), None),
+        });
+        
+        // التحقق من صحة XPath مبكرًا (يُبنى عند كل تقييم لأنه غير قابل للمشاركة بين الخيوط)
+        if let Some(expr) = &criteria.xpath {
+            build_xpath(expr).context(format!("تعبير XPath غير صالح: {}", expr))?;
+        }
+        
+        Ok(Self {
+            body_match: regex(&criteria.body_match, "--success-match")?,
+            body_not_match: regex(&criteria.body_not_match, "--failure-match")?,
+            status_codes: criteria.status_codes.clone(),
+            redirect_match: regex(&criteria.redirect_match, "--success-redirect")?,
+            length_delta: criteria.length_delta,
+            header,
+            xpath: criteria.xpath.clone(),
+        })
+    }
+    
+    /// هل يحتوي المطابق على قواعد من المستخدم؟
+    pub fn has_rules(&self) -> bool {
+        self.body_match.is_some()
+            || self.body_not_match.is_some()
+            || !self.status_codes.is_empty()
+            || self.redirect_match.is_some()
+            || self.length_delta.is_some()
+            || self.header.is_some()
+            || self.xpath.is_some()
+    }
+    
+    /// تقييم الاستجابة
+    ///
+    /// عند وجود قواعد يجب أن تتحقق جميعها؛ وإلا يُستخدم خط أساس الفشل
+    /// إن وجد، ثم مؤشرات الكلمات المفتاحية كحل أخير.
+    pub fn evaluate(&self, response: &CapturedResponse, baseline: Option<&FailureBaseline>) -> bool {
+        if !self.has_rules() {
+            return match baseline {
+                Some(baseline) => !baseline.is_failure(&response.fingerprint()),
+                None => keyword_heuristic(response),
+            };
+        }
+        
+        if let Some(re) = &self.body_not_match {
+            if re.is_match(&response.body) {
+                return false;
+            }
+        }
+        
+        if let Some(re) = &self.body_match {
+            if !re.is_match(&response.body) {
+                return false;
+            }
+        }
+        
+        if !self.status_codes.is_empty() && !self.status_codes.contains(&response.status) {
+            return false;
+        }
+        
+        if let Some(re) = &self.redirect_match {
+            match &response.redirect {
+                Some(location) if re.is_match(location) => {}
+                _ => return false,
+            }
+        }
+        
+        if let Some((name, expected)) = &self.header {
+            let values: Vec<&str> = response
+                .headers
+                .get_all(name.as_str())
+                .iter()
+                .filter_map(|v| v.to_str().ok())
+                .collect();
+            
+            let found = match expected {
+                Some(expected) => values.iter().any(|v| v.contains(expected.as_str())),
+                None => !values.is_empty(),
+            };
+            if !found {
+                return false;
+            }
+        }
+        
+        if let Some(expr) = &self.xpath {
+            if !xpath_matches(expr, &response.body) {
+                return false;
+            }
+        }
+        
+        if let Some(delta) = self.length_delta {
+            match baseline {
+                Some(baseline) => {
+                    if baseline.length_distance(response.body.len()) < delta {
+                        return false;
+                    }
+                }
+                None => tracing::warn!("قاعدة فرق الطول تتطلب المعايرة، تم تجاهلها"),
+            }
+        }
+        
+        true
+    }
+}
+
+/// بناء تعبير XPath
+fn build_xpath(expr: &str) -> Result<sxd_xpath::XPath> {
+    sxd_xpath::Factory::new()
+        .build(expr)
+        .map_err(|e| anyhow::anyhow!("{:?}", e))?
+        .ok_or_else(|| anyhow::anyhow!("تعبير XPath فارغ"))
+}
+
+/// تقييم XPath على محتوى XML (القيمة الصحيحة: منطقية صادقة، عقد غير فارغة، نص غير فارغ، رقم غير صفري)
+fn xpath_matches(expr: &str, body: &str) -> bool {
+    use sxd_xpath::Value;
+    
+    let Ok(package) = sxd_document::parser::parse(body) else {
+        return false;
+    };
+    let Ok(xpath) = build_xpath(expr) else {
+        return false;
+    };
+    
+    let document = package.as_document();
+    match xpath.evaluate(&sxd_xpath::Context::new(), document.root()) {
+        Ok(Value::Boolean(b)) => b,
+        Ok(Value::Number(n)) => n != 0.0 && !n.is_nan(),
+        Ok(Value::String(s)) => !s.is_empty(),
+        Ok(Value::Nodeset(nodes)) => nodes.size() > 0,
+        Err(_) => false,
+    }
+}
+
+/// التصنيف بالكلمات المفتاحية (السلوك الافتراضي القديم)
+fn keyword_heuristic(response: &CapturedResponse) -> bool {
+    if (200..300).contains(&response.status) {
+        return true;
+    }
+    
+    // في بعض الأنظمة، التحويل قد يعني النجاح
+    if (300..400).contains(&response.status) {
+        if let Some(location) = &response.redirect {
+            return !location.contains("login")
+                && !location.contains("error")
+                && !location.contains("fail");
+        }
+    }
+    
+    let body_lower = response.body.to_lowercase();
+    
+    // حساب النقاط
+    let failure_points: usize = FAILURE_INDICATORS
+        .iter()
+        .map(|indicator| body_lower.matches(indicator).count())
+        .sum();
+    
+    let success_points: usize = SUCCESS_INDICATORS
+        .iter()
+        .map(|indicator| body_lower.matches(indicator).count())
+        .sum();
+    
+    success_points > failure_points
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    fn response(status: u16, redirect: Option<&str>, body: &str) -> CapturedResponse {
+        CapturedResponse {
+            status,
+            headers: HeaderMap::new(),
+            redirect: redirect.map(str::to_string),
+            body: body.to_string(),
+        }
+    }
+    
+    #[test]
+    fn test_xpath_on_soap_response() {
+        let matcher = SuccessMatcher::compile(&SuccessCriteria {
+            xpath: Some("//*[local-name()='LoginResult']='true'".to_string()),
+            ..SuccessCriteria::default()
+        })
+        .unwrap();
+        
+        let soap = |result: &str| {
+            format!(
+                r#"<soap:Envelope xmlns:soap="http://schemas.xmlsoap.org/soap/envelope/"><soap:Body><LoginResponse><LoginResult>{}</LoginResult></LoginResponse></soap:Body></soap:Envelope>"#,
+                result
+            )
+        };
+        
+        assert!(matcher.evaluate(&response(200, None, &soap("true")), None));
+        assert!(!matcher.evaluate(&response(200, None, &soap("false")), None));
+        assert!(!matcher.evaluate(&response(200, None, "not xml"), None));
+    }
+    
+    #[test]
+    fn test_all_rules_must_hold() {
+        let matcher = SuccessMatcher::compile(&SuccessCriteria {
+            status_codes: vec![302],
+            redirect_match: Some("/dashboard".to_string()),
+            body_not_match: Some("locked".to_string()),
+            ..SuccessCriteria::default()
+        })
+        .unwrap();
+        
+        assert!(matcher.evaluate(&response(302, Some("/dashboard"), ""), None));
+        assert!(!matcher.evaluate(&response(302, Some("/login"), ""), None));
+        assert!(!matcher.evaluate(&response(200, Some("/dashboard"), ""), None));
+        assert!(!matcher.evaluate(&response(302, Some("/dashboard"), "Account LOCKED"), None));
+    }
+}
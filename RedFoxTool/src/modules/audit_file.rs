@@ -0,0 +1,122 @@
+//! تدقيق ملفات كلمات المرور
+//! تحليل ملفات Apache htpasswd و Unix shadow واختبار تجزئاتها دون اتصال
+
+use anyhow::{Context, Result};
+use tokio::fs as tokio_fs;
+
+use crate::modules::hashcrack::{HashEntry, HashKind};
+use crate::parser::normalize_line;
+
+/// عدد حقول سطر shadow
+const SHADOW_FIELDS: usize = 9;
+
+/// تنسيق ملف كلمات المرور
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum FileFormat {
+    /// كشف تلقائي لكل سطر من عدد الحقول
+    #[default]
+    Auto,
+    /// Apache htpasswd (user:hash)
+    Htpasswd,
+    /// Unix shadow (user:hash:lastchg:min:max:warn:inactive:expire:)
+    Shadow,
+}
+
+impl std::str::FromStr for FileFormat {
+    type Err = anyhow::Error;
+    
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "auto" => Ok(FileFormat::Auto),
+            "htpasswd" => Ok(FileFormat::Htpasswd),
+            "shadow" => Ok(FileFormat::Shadow),
+            _ => Err(anyhow::anyhow!("تنسيق ملف غير مدعوم: {} (auto, htpasswd, shadow)", s)),
+        }
+    }
+}
+
+impl std::fmt::Display for FileFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let name = match self {
+            FileFormat::Auto => "auto",
+            FileFormat::Htpasswd => "htpasswd",
+            FileFormat::Shadow => "shadow",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// تحميل ملف htpasswd أو shadow كتجزئات جاهزة للكسر
+pub async fn load(filepath: &str, format: FileFormat) -> Result<Vec<HashEntry>> {
+    let content = tokio_fs::read_to_string(filepath)
+        .await
+        .context(format!("فشل في قراءة الملف: {}", filepath))?;
+    
+    Ok(parse(&content, format))
+}
+
+/// تحليل محتوى الملف (الحسابات المقفلة والتجزئات غير المدعومة تُتجاهل مع تحذير)
+pub fn parse(content: &str, format: FileFormat) -> Vec<HashEntry> {
+    let mut entries = Vec::new();
+    
+    for line in content.lines().filter_map(normalize_line) {
+        let fields: Vec<&str> = line.split(':').collect();
+        let [user, hash, ..] = fields[..] else {
+            tracing::warn!("سطر غير صالح، تم تجاهله: {}", line);
+            continue;
+        };
+        
+        let shadow = match format {
+            FileFormat::Auto => fields.len() >= SHADOW_FIELDS,
+            other => other == FileFormat::Shadow,
+        };
+        
+        if shadow && (hash.starts_with('!') || hash.starts_with('*')) {
+            tracing::debug!(username = %user, "حساب مقفل");
+            continue;
+        }
+        if hash.is_empty() {
+            tracing::warn!("حساب بدون كلمة مرور: {}", user);
+            continue;
+        }
+        
+        match HashKind::detect(hash).or_else(|| is_des_crypt(hash).then_some(HashKind::Crypt)) {
+            Some(kind) => entries.push(HashEntry {
+                user: Some(user.to_string()),
+                hash: hash.to_string(),
+                kind,
+            }),
+            None => tracing::warn!("تجزئة غير مدعومة للمستخدم {}: {}", user, hash),
+        }
+    }
+    
+    entries
+}
+
+/// تجزئة DES crypt التقليدية (13 حرفًا من أبجدية crypt)
+fn is_des_crypt(hash: &str) -> bool {
+    hash.len() == 13 && hash.bytes().all(|b| b.is_ascii_alphanumeric() || b == b'.' || b == b'/')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_parse_htpasswd_and_shadow() {
+        let content = "\
+admin:$apr1$abcdefgh$FBwExRW4dCc8aL.OvjpIE1
+web:{SHA}W6ph5Mm5Pz8GgiULbPgzG37mj9g=
+root:$6$salt$hash:19000:0:99999:7:::
+daemon:*:19000:0:99999:7:::
+nobody:!:19000:0:99999:7:::
+";
+        let entries = parse(content, FileFormat::Auto);
+        
+        let kinds: Vec<_> = entries.iter().map(|e| (e.label(), e.kind)).collect();
+        assert_eq!(
+            kinds,
+            [("admin", HashKind::Apr1), ("web", HashKind::HtSha), ("root", HashKind::Crypt)]
+        );
+    }
+}
@@ -0,0 +1,114 @@
+//! اختبار الأداء
+//! تكرار فحص كامل على هدف تجريبي بعدد خيوط ثابت وقياس المحاولات في الثانية لكل تكرار
+
+use std::time::{Duration, Instant};
+use anyhow::{bail, Context, Result};
+
+use crate::scanner::RedFoxScanner;
+
+/// مهلة كل محاولة أثناء الاختبار (ثوانٍ)
+const BENCHMARK_TIMEOUT: u64 = 30;
+
+/// نتيجة تكرار واحد
+#[derive(Debug, Clone, Copy)]
+pub struct BenchmarkRun {
+    /// عدد المحاولات المنفذة
+    pub attempts: usize,
+    /// مدة الفحص (بدون تحميل القوائم)
+    pub elapsed: Duration,
+}
+
+impl BenchmarkRun {
+    /// المحاولات في الثانية
+    pub fn rate(&self) -> f64 {
+        let seconds = self.elapsed.as_secs_f64();
+        if seconds > 0.0 {
+            self.attempts as f64 / seconds
+        } else {
+            0.0
+        }
+    }
+}
+
+/// نتائج كل التكرارات
+#[derive(Debug, Clone, Default)]
+pub struct BenchmarkReport {
+    /// التكرارات بالترتيب
+    pub runs: Vec<BenchmarkRun>,
+}
+
+impl BenchmarkReport {
+    /// متوسط المحاولات في الثانية
+    pub fn average_rate(&self) -> f64 {
+        if self.runs.is_empty() {
+            return 0.0;
+        }
+        
+        self.runs.iter().map(BenchmarkRun::rate).sum::<f64>() / self.runs.len() as f64
+    }
+    
+    /// أفضل معدل بين التكرارات
+    pub fn best_rate(&self) -> f64 {
+        self.runs.iter().map(BenchmarkRun::rate).fold(0.0, f64::max)
+    }
+}
+
+/// تنفيذ الفحص عدة مرات على نفس القوائم وقياس المعدل
+pub async fn run(
+    url: &str,
+    users_file: &str,
+    passwords_file: &str,
+    iterations: u32,
+    threads: usize,
+) -> Result<BenchmarkReport> {
+    if iterations == 0 {
+        bail!("عدد التكرارات يجب أن يكون 1 على الأقل");
+    }
+    
+    let mut report = BenchmarkReport::default();
+    
+    for iteration in 1..=iterations {
+        let scanner = RedFoxScanner::new(
+            url,
+            users_file,
+            passwords_file,
+            threads,
+            BENCHMARK_TIMEOUT,
+            "normal",
+            None,
+        )
+        .await?;
+        
+        let start = Instant::now();
+        let results = scanner
+            .scan(false)
+            .await
+            .context(format!("فشل التكرار {}", iteration))?;
+        let run = BenchmarkRun { attempts: results.len(), elapsed: start.elapsed() };
+        
+        tracing::debug!(iteration, attempts = run.attempts, rate = run.rate(), "اكتمل تكرار");
+        report.runs.push(run);
+    }
+    
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_report_rates() {
+        let report = BenchmarkReport {
+            runs: vec![
+                BenchmarkRun { attempts: 100, elapsed: Duration::from_secs(1) },
+                BenchmarkRun { attempts: 300, elapsed: Duration::from_secs(1) },
+                BenchmarkRun { attempts: 10, elapsed: Duration::ZERO },
+            ],
+        };
+        
+        assert_eq!(report.best_rate(), 300.0);
+        assert!((report.average_rate() - 400.0 / 3.0).abs() < 1e-9);
+        assert_eq!(BenchmarkReport::default().average_rate(), 0.0);
+    }
+}
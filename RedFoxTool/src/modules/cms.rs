@@ -0,0 +1,67 @@
+//! إعدادات أنظمة إدارة المحتوى
+//! Joomla و Drupal و Magento: مسارات لوحات الإدارة وحقول النماذج ورموز CSRF ورسائل الفشل
+
+use super::presets::Preset;
+
+/// Joomla (لوحة الإدارة)
+///
+/// رمز CSRF هو اسم حقل مخفي عشوائي قيمته 1، لذا يُستخرج الاسم ويُحقن بصيغة ^CSRF^=1.
+/// التحويل بعد الدخول يعيد نموذج الدخول في الحالتين، فيُعتمد على غياب رسالة الفشل.
+pub const JOOMLA: Preset = Preset {
+    name: "joomla",
+    description: "Joomla (administrator/index.php)",
+    login_path: "/administrator/index.php",
+    default_port: None,
+    body_template: "username=^USER^&passwd=^PASS^&option=com_login&task=login&^CSRF^=1",
+    cookies: None,
+    csrf: Some(("token", Some(r#"re:name="([a-f0-9]{32})"\s+value="1""#))),
+    success_redirect: None,
+    success_match: None,
+    failure_match: Some(r#"do not match|invalid (?:security )?token|type="(?:danger|warning)"|alert-(?:error|danger|warning)"#),
+    success_header: None,
+    lockout_markers: &[
+        ("too many failed login attempts", "Joomla Brute Force Protection"),
+        ("your ip address has been blocked", "Admin Tools"),
+    ],
+    probe: None,
+};
+
+/// Drupal 8+ (نموذج user/login مع form_build_id)
+pub const DRUPAL: Preset = Preset {
+    name: "drupal",
+    description: "Drupal (user/login)",
+    login_path: "/user/login",
+    default_port: None,
+    body_template: "name=^USER^&pass=^PASS^&form_id=user_login_form&form_build_id=^CSRF^&op=Log+in",
+    cookies: None,
+    csrf: Some(("form_build_id", None)),
+    // النجاح يحول إلى صفحة المستخدم مع check_logged_in=1
+    success_redirect: Some(r"check_logged_in=1|/user/\d+"),
+    success_match: None,
+    failure_match: Some("unrecognized username or password"),
+    success_header: None,
+    lockout_markers: &[
+        ("there have been more than 5 failed login attempts", "Drupal Flood Control"),
+        ("temporarily blocked", "Drupal Flood Control"),
+    ],
+    probe: None,
+};
+
+/// Magento 2 (لوحة الإدارة مع form_key)
+pub const MAGENTO: Preset = Preset {
+    name: "magento",
+    description: "Magento 2 (admin)",
+    login_path: "/admin",
+    default_port: None,
+    body_template: "login%5Busername%5D=^USER^&login%5Bpassword%5D=^PASS^&form_key=^CSRF^",
+    cookies: None,
+    csrf: Some(("form_key", None)),
+    success_redirect: None,
+    success_match: None,
+    failure_match: Some(r"sign-in was incorrect|invalid form key"),
+    success_header: None,
+    lockout_markers: &[
+        ("your account is temporarily disabled", "Magento Admin Lockout"),
+    ],
+    probe: None,
+};
@@ -0,0 +1,366 @@
+//! قاعدة بيانات بيانات الاعتماد الافتراضية للأجهزة المدمجة
+//! بصمات الموجهات وأجهزة IoT (ترويسة Server، العنوان، نطاق المصادقة) مع كلمات مرورها الافتراضية
+
+use std::collections::HashSet;
+use std::time::Duration;
+use anyhow::{Result, Context};
+use once_cell::sync::Lazy;
+use regex::Regex;
+use reqwest::header::{HeaderMap, SERVER, WWW_AUTHENTICATE};
+
+/// جهاز معروف مع علامات بصمته وبيانات اعتماده الافتراضية
+#[derive(Debug)]
+pub struct DeviceDefaults {
+    /// الشركة المصنعة
+    pub vendor: &'static str,
+    /// فئة الجهاز أو المنتج
+    pub product: &'static str,
+    /// أجزاء من ترويسة Server
+    pub server: &'static [&'static str],
+    /// أجزاء من عنوان الصفحة (<title>)
+    pub title: &'static [&'static str],
+    /// أجزاء من نطاق WWW-Authenticate (realm)
+    pub realm: &'static [&'static str],
+    /// بيانات الاعتماد الافتراضية (مرتبة حسب الشيوع)
+    pub credentials: &'static [(&'static str, &'static str)],
+}
+
+/// الأجهزة المعروفة
+pub static DEVICES: &[DeviceDefaults] = &[
+    DeviceDefaults {
+        vendor: "TP-Link",
+        product: "Router/Access Point",
+        server: &["TP-LINK"],
+        title: &["TP-LINK", "TL-WR", "Archer"],
+        realm: &["TP-LINK"],
+        credentials: &[("admin", "admin")],
+    },
+    DeviceDefaults {
+        vendor: "D-Link",
+        product: "Router",
+        server: &["D-Link", "DIR-"],
+        title: &["D-LINK", "DIR-"],
+        realm: &["DIR-", "DSL-"],
+        credentials: &[("admin", ""), ("admin", "admin"), ("user", "")],
+    },
+    DeviceDefaults {
+        vendor: "Netgear",
+        product: "Router",
+        server: &["NETGEAR"],
+        title: &["NETGEAR"],
+        realm: &["NETGEAR"],
+        credentials: &[("admin", "password"), ("admin", "1234")],
+    },
+    DeviceDefaults {
+        vendor: "Linksys",
+        product: "Router",
+        server: &["Linksys"],
+        title: &["Linksys"],
+        realm: &["Linksys", "WRT54G"],
+        credentials: &[("admin", "admin"), ("", "admin")],
+    },
+    DeviceDefaults {
+        vendor: "ASUS",
+        product: "Router",
+        server: &["httpd/2.0"],
+        title: &["ASUS", "RT-AC", "RT-N"],
+        realm: &["RT-AC", "RT-N"],
+        credentials: &[("admin", "admin")],
+    },
+    DeviceDefaults {
+        vendor: "MikroTik",
+        product: "RouterOS",
+        server: &["MikroTik"],
+        title: &["RouterOS", "MikroTik"],
+        realm: &[],
+        credentials: &[("admin", "")],
+    },
+    DeviceDefaults {
+        vendor: "Ubiquiti",
+        product: "AirOS",
+        server: &["Ubiquiti"],
+        title: &["airOS", "Ubiquiti"],
+        realm: &[],
+        credentials: &[("ubnt", "ubnt")],
+    },
+    DeviceDefaults {
+        vendor: "ZTE",
+        product: "ONT/DSL Router",
+        server: &["ZTE"],
+        title: &["ZTE", "ZXHN", "F660", "F670"],
+        realm: &["ZXHN"],
+        credentials: &[("admin", "admin"), ("user", "user"), ("support", "support")],
+    },
+    DeviceDefaults {
+        vendor: "Huawei",
+        product: "ONT/HG Router",
+        server: &["Huawei", "HuaweiHomeGateway"],
+        title: &["HG8245", "HG532", "EchoLife", "HUAWEI"],
+        realm: &[],
+        credentials: &[("telecomadmin", "admintelecom"), ("root", "admin"), ("admin", "admin")],
+    },
+    DeviceDefaults {
+        vendor: "Zyxel",
+        product: "Router/Gateway",
+        server: &["ZyXEL", "RomPager"],
+        title: &["ZyXEL", "Zyxel"],
+        realm: &["ZyXEL"],
+        credentials: &[("admin", "1234"), ("admin", "admin")],
+    },
+    DeviceDefaults {
+        vendor: "Tenda",
+        product: "Router",
+        server: &["Tenda"],
+        title: &["Tenda"],
+        realm: &[],
+        credentials: &[("admin", "admin"), ("admin", "")],
+    },
+    DeviceDefaults {
+        vendor: "Cisco",
+        product: "IOS HTTP/Small Business",
+        server: &["cisco-IOS"],
+        title: &["Cisco"],
+        realm: &["level_15_access", "level 15 access"],
+        credentials: &[("cisco", "cisco"), ("admin", "admin"), ("admin", "cisco")],
+    },
+    DeviceDefaults {
+        vendor: "Hikvision",
+        product: "IP Camera/NVR",
+        server: &["App-webs", "DNVRS-Webs", "Hikvision-Webs"],
+        title: &["Hikvision"],
+        realm: &["Hikvision"],
+        credentials: &[("admin", "12345"), ("admin", "admin")],
+    },
+    DeviceDefaults {
+        vendor: "Dahua",
+        product: "IP Camera/DVR",
+        server: &["DH-", "Dahua"],
+        title: &["WEB SERVICE", "Dahua"],
+        realm: &["Dahua"],
+        credentials: &[("admin", "admin"), ("888888", "888888"), ("666666", "666666")],
+    },
+    DeviceDefaults {
+        vendor: "Axis",
+        product: "IP Camera",
+        server: &["Boa/", "AXIS"],
+        title: &["AXIS"],
+        realm: &["AXIS_"],
+        credentials: &[("root", "pass"), ("root", "root")],
+    },
+    DeviceDefaults {
+        vendor: "HP",
+        product: "Printer (EWS)",
+        server: &["HP HTTP Server", "HP-ChaiSOE"],
+        title: &["HP LaserJet", "HP Color LaserJet", "HP OfficeJet"],
+        realm: &[],
+        credentials: &[("admin", ""), ("admin", "admin")],
+    },
+    DeviceDefaults {
+        vendor: "Raspberry Pi",
+        product: "Pi-hole",
+        server: &["lighttpd"],
+        title: &["Pi-hole"],
+        realm: &[],
+        credentials: &[("pi", "raspberry")],
+    },
+    DeviceDefaults {
+        vendor: "Synology",
+        product: "DiskStation NAS",
+        server: &["nginx"],
+        title: &["Synology", "DiskStation"],
+        realm: &[],
+        credentials: &[("admin", ""), ("admin", "admin")],
+    },
+];
+
+/// أكثر 50 زوج افتراضي شيوعًا (لـ --quick-defaults قبل القائمة الرئيسية)
+pub static QUICK_DEFAULTS: &[(&str, &str)] = &[
+    ("admin", "admin"),
+    ("admin", "password"),
+    ("admin", ""),
+    ("admin", "1234"),
+    ("admin", "12345"),
+    ("admin", "123456"),
+    ("root", "root"),
+    ("root", ""),
+    ("root", "toor"),
+    ("root", "admin"),
+    ("root", "password"),
+    ("root", "123456"),
+    ("administrator", "administrator"),
+    ("administrator", "password"),
+    ("administrator", ""),
+    ("user", "user"),
+    ("user", "password"),
+    ("guest", "guest"),
+    ("guest", ""),
+    ("test", "test"),
+    ("admin", "admin123"),
+    ("admin", "Admin@123"),
+    ("admin", "changeme"),
+    ("admin", "default"),
+    ("admin", "pass"),
+    ("admin", "letmein"),
+    ("admin", "welcome"),
+    ("admin", "qwerty"),
+    ("admin", "secret"),
+    ("admin", "P@ssw0rd"),
+    ("support", "support"),
+    ("manager", "manager"),
+    ("operator", "operator"),
+    ("service", "service"),
+    ("sysadmin", "sysadmin"),
+    ("demo", "demo"),
+    ("ubnt", "ubnt"),
+    ("pi", "raspberry"),
+    ("cisco", "cisco"),
+    ("tomcat", "tomcat"),
+    ("tomcat", "s3cret"),
+    ("postgres", "postgres"),
+    ("sa", ""),
+    ("sa", "sa"),
+    ("oracle", "oracle"),
+    ("ftp", "ftp"),
+    ("anonymous", "anonymous"),
+    ("webadmin", "webadmin"),
+    ("super", "super"),
+    ("supervisor", "supervisor"),
+];
+
+/// بصمة جهاز مستخرجة من الاستجابة الأولى
+#[derive(Debug, Clone, Default)]
+pub struct Fingerprint {
+    /// ترويسة Server
+    pub server: Option<String>,
+    /// عنوان الصفحة
+    pub title: Option<String>,
+    /// نطاق WWW-Authenticate (يعني أن الجهاز يستخدم HTTP Basic)
+    pub realm: Option<String>,
+}
+
+#[allow(clippy::unwrap_used)] // نمط ثابت لا يفشل تجميعه
+static TITLE_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?is)<title[^>]*>(.*?)</title>").unwrap()
+});
+
+#[allow(clippy::unwrap_used)] // نمط ثابت لا يفشل تجميعه
+static REALM_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"(?i)realm\s*=\s*"([^"]*)""#).unwrap()
+});
+
+impl Fingerprint {
+    /// جلب الصفحة الرئيسية للجهاز واستخراج بصمته
+    pub async fn probe(url: &str, timeout_secs: u64) -> Result<Self> {
+        // الأجهزة المدمجة تستخدم غالبًا شهادات موقعة ذاتيًا
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(timeout_secs))
+            .danger_accept_invalid_certs(true)
+            .build()
+            .context("فشل في إنشاء عميل HTTP")?;
+        
+        let response = client
+            .get(url)
+            .send()
+            .await
+            .context(format!("فشل في الاتصال بالجهاز: {}", url))?;
+        
+        let headers = response.headers().clone();
+        let body = response.text().await.unwrap_or_default();
+        
+        Ok(Self::from_response(&headers, &body))
+    }
+    
+    /// استخراج البصمة من ترويسات وجسم الاستجابة
+    pub fn from_response(headers: &HeaderMap, body: &str) -> Self {
+        let header = |name| {
+            headers
+                .get(name)
+                .and_then(|v| v.to_str().ok())
+                .map(str::to_string)
+        };
+        
+        Self {
+            server: header(SERVER),
+            title: TITLE_RE
+                .captures(body)
+                .map(|c| c[1].trim().to_string())
+                .filter(|t| !t.is_empty()),
+            realm: header(WWW_AUTHENTICATE)
+                .and_then(|auth| REALM_RE.captures(&auth).map(|c| c[1].to_string())),
+        }
+    }
+    
+    /// الأجهزة المطابقة لهذه البصمة
+    pub fn matches(&self) -> Vec<&'static DeviceDefaults> {
+        let hit = |value: &Option<String>, markers: &[&str]| {
+            value.as_deref().is_some_and(|value| {
+                let value = value.to_lowercase();
+                markers.iter().any(|m| value.contains(&m.to_lowercase()))
+            })
+        };
+        
+        DEVICES
+            .iter()
+            .filter(|d| {
+                hit(&self.server, d.server) || hit(&self.title, d.title) || hit(&self.realm, d.realm)
+            })
+            .collect()
+    }
+}
+
+impl std::fmt::Display for Fingerprint {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let field = |value: &Option<String>| value.clone().unwrap_or_else(|| "-".to_string());
+        write!(
+            f,
+            "Server: {}, Title: {}, Realm: {}",
+            field(&self.server),
+            field(&self.title),
+            field(&self.realm)
+        )
+    }
+}
+
+/// الأجهزة التابعة لشركة مصنعة (بدون حساسية لحالة الأحرف)
+pub fn by_vendor(vendor: &str) -> Vec<&'static DeviceDefaults> {
+    DEVICES
+        .iter()
+        .filter(|d| d.vendor.eq_ignore_ascii_case(vendor))
+        .collect()
+}
+
+/// أزواج بيانات الاعتماد للأجهزة المحددة (بدون تكرار مع الحفاظ على الترتيب)
+pub fn credential_pairs(devices: &[&DeviceDefaults]) -> Vec<(String, String)> {
+    let mut seen = HashSet::new();
+    
+    devices
+        .iter()
+        .flat_map(|d| d.credentials.iter())
+        .filter(|pair| seen.insert(**pair))
+        .map(|(u, p)| (u.to_string(), p.to_string()))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::header::HeaderValue;
+    
+    #[test]
+    fn test_fingerprint_matches_realm_and_title() {
+        let mut headers = HeaderMap::new();
+        headers.insert(WWW_AUTHENTICATE, HeaderValue::from_static("Basic realm=\"NETGEAR R7000\""));
+        
+        let fingerprint = Fingerprint::from_response(&headers, "<html><TITLE> Login </TITLE></html>");
+        assert_eq!(fingerprint.realm.as_deref(), Some("NETGEAR R7000"));
+        assert_eq!(fingerprint.title.as_deref(), Some("Login"));
+        
+        let devices = fingerprint.matches();
+        assert_eq!(devices.len(), 1);
+        assert_eq!(devices[0].vendor, "Netgear");
+        
+        let pairs = credential_pairs(&[devices[0], devices[0]]);
+        assert_eq!(pairs.len(), devices[0].credentials.len());
+        assert!(Fingerprint::default().matches().is_empty());
+    }
+}
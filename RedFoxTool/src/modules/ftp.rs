@@ -0,0 +1,113 @@
+//! وحدة FTP
+//! اختبار تسجيل الدخول على خوادم FTP/FTPS (TLS ضمني أو صريح عبر AUTH TLS)
+
+use anyhow::{Result, Context};
+
+use super::tcp::TextConnection;
+use super::{split_host_port, AttemptOutcome, AuthModule, ServiceOptions, TlsMode};
+
+/// المنفذ الافتراضي لـ FTPS الضمني
+const IMPLICIT_FTPS_PORT: u16 = 990;
+
+/// وحدة اختبار تسجيل الدخول عبر FTP
+pub struct FtpModule {
+    host: String,
+    port: u16,
+    options: ServiceOptions,
+}
+
+impl FtpModule {
+    /// إنشاء وحدة FTP لهدف (host:port أو ftp://host:port)
+    pub fn new(target: &str, options: &ServiceOptions) -> Result<Self> {
+        let default_port = match options.tls {
+            TlsMode::Implicit => IMPLICIT_FTPS_PORT,
+            _ => 21,
+        };
+        let (host, port) = split_host_port(target, default_port).context("هدف FTP غير صالح")?;
+        
+        Ok(Self {
+            host,
+            port,
+            options: options.clone(),
+        })
+    }
+    
+    /// عنوان الهدف
+    pub fn address(&self) -> String {
+        format!("{}:{}", self.host, self.port)
+    }
+    
+    /// تجربة بيانات اعتماد واحدة (اتصال مستقل لكل محاولة)
+    pub async fn attempt(&self, username: &str, password: &str) -> Result<AttemptOutcome> {
+        let user_command = control_command("USER", username)?;
+        let pass_command = control_command("PASS", password)?;
+        
+        let mut conn = TextConnection::connect(
+            &self.host,
+            self.port,
+            self.options.timeout,
+            self.options.tls == TlsMode::Implicit,
+        )
+        .await?;
+        
+        let banner = conn.read_reply().await?;
+        if banner.code != 220 {
+            return Err(anyhow::anyhow!("رد ترحيب FTP غير متوقع: {} {}", banner.code, banner.text));
+        }
+        
+        // TLS صريح
+        if self.options.tls == TlsMode::Explicit {
+            let reply = conn.command("AUTH TLS").await?;
+            if reply.code != 234 {
+                return Err(anyhow::anyhow!("الخادم لا يدعم AUTH TLS: {} {}", reply.code, reply.text));
+            }
+            conn = conn.upgrade_tls().await?;
+        }
+        
+        let mut reply = conn.command(&user_command).await?;
+        if reply.code == 331 || reply.code == 332 {
+            reply = conn.command(&pass_command).await?;
+        }
+        
+        let _ = conn.command("QUIT").await;
+        
+        Ok(AttemptOutcome {
+            success: reply.code == 230 || reply.code == 202,
+            status_code: reply.code,
+            banner: Some(banner.text),
+            ..Default::default()
+        })
+    }
+}
+
+/// أمر على قناة التحكم، مع رفض CR/LF في الوسيط حتى لا يحقن سطر من القائمة أوامر إضافية
+fn control_command(verb: &str, argument: &str) -> Result<String> {
+    if argument.contains(['\r', '\n']) {
+        return Err(anyhow::anyhow!("وسيط {} يحتوي CR/LF، تم تخطيه", verb));
+    }
+    
+    Ok(format!("{} {}", verb, argument))
+}
+
+#[async_trait::async_trait]
+impl AuthModule for FtpModule {
+    fn endpoint(&self) -> String {
+        self.address()
+    }
+    
+    async fn try_credential(&self, username: &str, password: &str) -> Result<AttemptOutcome> {
+        self.attempt(username, password).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_control_command_rejects_line_breaks() {
+        assert_eq!(control_command("USER", "admin").unwrap(), "USER admin");
+        assert!(control_command("PASS", "x\r\nDELE important.txt").is_err());
+        assert!(control_command("PASS", "x\nQUIT").is_err());
+    }
+}
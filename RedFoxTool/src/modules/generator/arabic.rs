@@ -0,0 +1,277 @@
+//! التوليد الواعي بالعربية
+//! الأسماء العربية وتهجئاتها اللاتينية (mohamed/muhammad/mhmd) والعربيزي وأخطاء تخطيط لوحة المفاتيح العربية
+
+use std::collections::HashSet;
+
+/// أقصى عدد تهجئات لاتينية عامة لكلمة عربية واحدة (قبل إضافة البادئات)
+pub const MAX_TRANSLITERATIONS: usize = 16;
+
+/// أسماء شائعة وتهجئاتها المعروفة (الصيغة العربية أولًا)
+const NAMES: &[&[&str]] = &[
+    &["محمد", "mohamed", "mohammed", "muhammad", "mohammad", "muhammed", "mohamad", "mhmd", "mohd", "mo7amed", "m7md"],
+    &["أحمد", "ahmed", "ahmad", "ahmd", "a7med", "a7mad"],
+    &["محمود", "mahmoud", "mahmud", "mahmood", "ma7moud"],
+    &["مصطفى", "mostafa", "mustafa", "moustafa", "mustapha", "mostfa"],
+    &["علي", "ali", "aly", "alee", "3li", "3ly"],
+    &["عمر", "omar", "umar", "omer", "3omar", "3mr"],
+    &["عبدالله", "abdullah", "abdallah", "abdulla", "abdalla", "abdellah", "abdo", "3bdallah"],
+    &["عبدالرحمن", "abdelrahman", "abdulrahman", "abdurrahman", "abdalrahman", "abdo"],
+    &["خالد", "khaled", "khalid", "5aled", "5alid"],
+    &["يوسف", "youssef", "yousef", "yusuf", "yousif", "youssif", "yossef"],
+    &["إبراهيم", "ibrahim", "ebrahim", "ibraheem", "brahim"],
+    &["حسن", "hassan", "hasan", "7asan", "7assan"],
+    &["حسين", "hussein", "hussain", "hosein", "husain", "7ussein"],
+    &["سعيد", "said", "saeed", "saied", "sa3eed"],
+    &["طارق", "tarek", "tariq", "tarik"],
+    &["ياسر", "yasser", "yaser", "yasir"],
+    &["كريم", "karim", "kareem"],
+    &["هشام", "hisham", "hesham"],
+    &["وليد", "walid", "waleed"],
+    &["فيصل", "faisal", "faysal"],
+    &["فاطمة", "fatima", "fatma", "fatimah", "fatmah"],
+    &["عائشة", "aisha", "aysha", "aicha", "3aisha"],
+    &["مريم", "mariam", "maryam", "meriem"],
+    &["زينب", "zainab", "zeinab", "zaynab"],
+    &["نور", "nour", "noor", "nur"],
+    &["سارة", "sara", "sarah"],
+];
+
+/// تخطيط لوحة المفاتيح العربية القياسي (المفتاح اللاتيني ← الحرف العربي)
+const KEYBOARD: &[(char, &str)] = &[
+    ('q', "ض"), ('w', "ص"), ('e', "ث"), ('r', "ق"), ('t', "ف"), ('y', "غ"), ('u', "ع"), ('i', "ه"),
+    ('o', "خ"), ('p', "ح"), ('[', "ج"), (']', "د"), ('a', "ش"), ('s', "س"), ('d', "ي"), ('f', "ب"),
+    ('g', "ل"), ('h', "ا"), ('j', "ت"), ('k', "ن"), ('l', "م"), (';', "ك"), ('\'', "ط"), ('z', "ئ"),
+    ('x', "ء"), ('c', "ؤ"), ('v', "ر"), ('b', "لا"), ('n', "ى"), ('m', "ة"), (',', "و"), ('.', "ز"),
+    ('/', "ظ"), ('`', "ذ"), ('H', "أ"), ('Y', "إ"), ('N', "آ"),
+];
+
+/// هل الحرف من النطاق العربي
+fn is_arabic(c: char) -> bool {
+    matches!(c, '\u{0600}'..='\u{06FF}' | '\u{0750}'..='\u{077F}')
+}
+
+/// هل النص يحتوي أحرفًا عربية
+pub fn contains_arabic(text: &str) -> bool {
+    text.chars().any(is_arabic)
+}
+
+/// توحيد النص العربي للمقارنة: حذف التشكيل والتطويل وتوحيد الهمزات والتاء المربوطة والألف المقصورة
+pub fn normalize(text: &str) -> String {
+    text.chars()
+        .filter(|c| !matches!(c, '\u{064B}'..='\u{0652}' | '\u{0640}'))
+        .map(|c| match c {
+            'أ' | 'إ' | 'آ' => 'ا',
+            'ة' => 'ه',
+            'ى' => 'ي',
+            c => c,
+        })
+        .collect::<String>()
+        .to_lowercase()
+}
+
+/// تهجئات حرف عربي: اللاتينية (الأولى هي الأشيع) ثم العربيزي
+fn letter(c: char) -> (&'static [&'static str], Option<&'static str>) {
+    match c {
+        'ا' | 'أ' | 'آ' | 'ى' => (&["a"], None),
+        'إ' => (&["e", "i"], None),
+        'ب' => (&["b"], None),
+        'ت' => (&["t"], None),
+        'ث' => (&["th", "s"], None),
+        'ج' => (&["j", "g"], None),
+        'ح' => (&["h"], Some("7")),
+        'خ' => (&["kh"], Some("5")),
+        'د' => (&["d"], None),
+        'ذ' => (&["z", "th"], None),
+        'ر' => (&["r"], None),
+        'ز' => (&["z"], None),
+        'س' => (&["s"], None),
+        'ش' => (&["sh"], None),
+        'ص' => (&["s"], Some("9")),
+        'ض' => (&["d", "dh"], None),
+        'ط' => (&["t"], Some("6")),
+        'ظ' => (&["z", "dh"], None),
+        'ع' => (&["a", ""], Some("3")),
+        'غ' => (&["gh"], None),
+        'ف' => (&["f"], None),
+        'ق' => (&["q", "k"], Some("8")),
+        'ك' => (&["k"], None),
+        'ل' => (&["l"], None),
+        'م' => (&["m"], None),
+        'ن' => (&["n"], None),
+        'ه' => (&["h"], None),
+        'ة' => (&["a", "ah"], None),
+        'و' => (&["o", "w", "u"], None),
+        'ي' => (&["y", "i", "ee"], None),
+        'ء' => (&[""], Some("2")),
+        'ئ' => (&["e"], Some("2")),
+        'ؤ' => (&["o"], Some("2")),
+        _ => (&[], None),
+    }
+}
+
+/// تهجئات لاتينية عامة لكلمة عربية: تراكيب بدائل الأحرف (بحد MAX_TRANSLITERATIONS) ثم العربيزي، مع بادئة al/el لـ "ال"
+pub fn transliterate(word: &str) -> Vec<String> {
+    let word: String = word.chars().filter(|c| !matches!(c, '\u{064B}'..='\u{0652}' | '\u{0640}')).collect();
+    let (prefixes, stem): (&[&str], &str) = match word.strip_prefix("ال") {
+        Some(stem) if !stem.is_empty() => (&["al", "el", ""], stem),
+        _ => (&[""], word.as_str()),
+    };
+    
+    let mut stems = vec![String::new()];
+    let mut arabizi = String::new();
+    for c in stem.chars() {
+        let (latin, chat) = letter(c);
+        if latin.is_empty() {
+            // الأحرف غير العربية (أرقام، لاتينية) تبقى كما هي
+            stems.iter_mut().for_each(|s| s.push(c));
+            arabizi.push(c);
+            continue;
+        }
+        stems = stems
+            .iter()
+            .flat_map(|s| latin.iter().map(move |l| format!("{}{}", s, l)))
+            .take(MAX_TRANSLITERATIONS)
+            .collect();
+        arabizi.push_str(chat.unwrap_or(latin[0]));
+    }
+    stems.push(arabizi);
+    
+    let mut seen = HashSet::new();
+    prefixes
+        .iter()
+        .flat_map(|prefix| stems.iter().map(move |stem| format!("{}{}", prefix, stem)))
+        .filter(|candidate| !candidate.is_empty() && seen.insert(candidate.clone()))
+        .collect()
+}
+
+/// ما يُكتب فعلًا عند كتابة نص عربي ولوحة المفاتيح على الإنجليزية (None إن لم يحتو حرفًا قابلًا للتحويل)
+pub fn to_english_layout(arabic: &str) -> Option<String> {
+    let mut output = String::new();
+    let mut converted = false;
+    let mut chars = arabic.chars().peekable();
+    
+    while let Some(c) = chars.next() {
+        // "لا" حرف واحد على المفتاح b
+        if c == 'ل' && chars.peek() == Some(&'ا') {
+            chars.next();
+            output.push('b');
+            converted = true;
+            continue;
+        }
+        match KEYBOARD.iter().find(|(_, arabic)| arabic.chars().eq(std::iter::once(c))) {
+            Some((key, _)) => {
+                output.push(*key);
+                converted = true;
+            }
+            None => output.push(c),
+        }
+    }
+    
+    converted.then_some(output)
+}
+
+/// ما يُكتب فعلًا عند كتابة نص لاتيني ولوحة المفاتيح على العربية (None إن لم يحتو مفتاحًا قابلًا للتحويل)
+pub fn to_arabic_layout(latin: &str) -> Option<String> {
+    let mut converted = false;
+    let output = latin
+        .chars()
+        .map(|c| {
+            let key = c.to_ascii_lowercase();
+            match KEYBOARD.iter().find(|(k, _)| *k == key) {
+                Some((_, arabic)) => {
+                    converted = true;
+                    arabic.to_string()
+                }
+                None => c.to_string(),
+            }
+        })
+        .collect();
+    
+    converted.then_some(output)
+}
+
+/// مجموعة الأسماء المعروفة التي تطابق الكلمة (بأي تهجئة)
+fn name_group(word: &str) -> Option<&'static [&'static str]> {
+    let key = normalize(word);
+    NAMES.iter().copied().find(|group| group.iter().any(|spelling| normalize(spelling) == key))
+}
+
+/// التهجئة اللاتينية الأشيع لكلمة عربية (من جدول الأسماء، وإلا حرفًا بحرف)؛ الكلمات اللاتينية تبقى كما هي
+pub fn romanize(word: &str) -> String {
+    if !contains_arabic(word) {
+        return word.to_string();
+    }
+    
+    name_group(word)
+        .and_then(|group| group.get(1))
+        .map(|spelling| spelling.to_string())
+        .or_else(|| transliterate(word).into_iter().next())
+        .unwrap_or_else(|| word.to_string())
+}
+
+/// كل المتغيرات المحلية لكلمة (بأي خط): تهجئات الاسم المعروفة، التهجئات العامة، وأخطاء تخطيط لوحة المفاتيح
+pub fn variants(word: &str) -> Vec<String> {
+    let mut seen = HashSet::from([word.to_string()]);
+    let mut variants = Vec::new();
+    let mut push = |variant: String| {
+        if !variant.is_empty() && seen.insert(variant.clone()) {
+            variants.push(variant);
+        }
+    };
+    
+    let group = name_group(word);
+    for spelling in group.unwrap_or_default() {
+        push(spelling.to_string());
+    }
+    
+    // الكلمات العربية غير المعروفة تُهجأ حرفًا بحرف
+    let arabic_form = match group {
+        Some(group) => group.first().map(|s| s.to_string()),
+        None => contains_arabic(word).then(|| word.to_string()),
+    };
+    if group.is_none() {
+        if let Some(arabic) = &arabic_form {
+            transliterate(arabic).into_iter().for_each(&mut push);
+        }
+    }
+    
+    // أخطاء التخطيط: العربية مكتوبة على الإنجليزية، واللاتينية مكتوبة على العربية
+    if let Some(keys) = arabic_form.as_deref().and_then(to_english_layout) {
+        push(keys);
+    }
+    if !contains_arabic(word) {
+        if let Some(arabic) = to_arabic_layout(word) {
+            push(arabic);
+        }
+    }
+    
+    variants
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_arabic_name_variants() {
+        let from_arabic = variants("مُحَمَّد");
+        for expected in ["محمد", "mohamed", "muhammad", "mhmd", "lpl]"] {
+            assert!(from_arabic.iter().any(|v| v == expected), "{}", expected);
+        }
+        
+        let from_latin = variants("Mohammed");
+        for expected in ["محمد", "mohamed", "mhmd", "lpl]", "ةخاشةةثي"] {
+            assert!(from_latin.iter().any(|v| v == expected), "{}", expected);
+        }
+        
+        let unknown = transliterate("الشمري");
+        for expected in ["alshmry", "elshmri", "shmry"] {
+            assert!(unknown.iter().any(|v| v == expected), "{}", expected);
+        }
+        assert!(transliterate("خالد").iter().any(|v| v == "5ald"));
+        
+        assert_eq!(to_arabic_layout("password").as_deref(), Some("حشسسصخقي"));
+        assert_eq!(to_english_layout("سلام").as_deref(), Some("sbl"));
+        assert_eq!(romanize("أحمد"), "ahmed");
+    }
+}
@@ -0,0 +1,217 @@
+//! حصاد كلمات الموقع المستهدف
+//! زحف بأسلوب CeWL يستخرج الكلمات والبريد والبيانات الوصفية لبناء قائمة كلمات وأسماء مستخدمين خاصة بالهدف
+
+use std::collections::HashMap;
+use anyhow::Result;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use scraper::{Html, Node, Selector};
+use url::Url;
+
+use crate::crawler::{self, CrawlOptions, Page};
+
+use super::arabic;
+
+/// أقل طول افتراضي للكلمة المحصودة (كما في CeWL)
+pub const DEFAULT_MIN_WORD_LEN: usize = 3;
+
+/// عناصر لا يُحصد نصها
+const SKIPPED_ELEMENTS: &[&str] = &["script", "style", "noscript", "template"];
+
+#[allow(clippy::unwrap_used)] // نمط ثابت لا يفشل تجميعه
+static EMAIL: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"[A-Za-z0-9._%+-]+@[A-Za-z0-9-]+(?:\.[A-Za-z0-9-]+)*\.[A-Za-z]{2,}").unwrap());
+#[allow(clippy::unwrap_used)] // محدد ثابت لا يفشل تحليله
+static META_SELECTOR: Lazy<Selector> = Lazy::new(|| Selector::parse("meta[content]").unwrap());
+#[allow(clippy::unwrap_used)] // محدد ثابت لا يفشل تحليله
+static ATTRIBUTE_SELECTOR: Lazy<Selector> = Lazy::new(|| Selector::parse("[alt], [title], [placeholder]").unwrap());
+
+/// ما جُمع من الموقع: الكلمات بتكرارها، البريد، وأسماء المؤلفين
+#[derive(Debug, Default)]
+pub struct Harvest {
+    min_word_len: usize,
+    words: HashMap<String, usize>,
+    emails: Vec<String>,
+    authors: Vec<String>,
+    pages: usize,
+}
+
+impl Harvest {
+    /// حصاد فارغ بأقل طول للكلمات
+    pub fn new(min_word_len: usize) -> Self {
+        Self { min_word_len: min_word_len.max(1), ..Default::default() }
+    }
+    
+    /// عدد الصفحات المحللة
+    pub fn pages(&self) -> usize {
+        self.pages
+    }
+    
+    /// عناوين البريد المكتشفة بترتيب ظهورها
+    pub fn emails(&self) -> &[String] {
+        &self.emails
+    }
+    
+    /// إضافة صفحة HTML إلى الحصاد وإرجاع روابطها للمتابعة
+    pub fn add_page(&mut self, page: &Url, html: &str) -> Vec<Url> {
+        let document = Html::parse_document(html);
+        self.pages += 1;
+        
+        for email in EMAIL.find_iter(html) {
+            push_unique(&mut self.emails, email.as_str().to_lowercase());
+        }
+        
+        // النص المرئي فقط (بدون السكربتات والأنماط)
+        let texts: Vec<String> = document
+            .root_element()
+            .descendants()
+            .filter_map(|node| {
+                let Node::Text(text) = node.value() else { return None };
+                let skipped = node
+                    .parent()
+                    .and_then(|parent| parent.value().as_element().map(|e| e.name()))
+                    .is_some_and(|name| SKIPPED_ELEMENTS.contains(&name));
+                (!skipped).then(|| (**text).to_string())
+            })
+            .collect();
+        for text in &texts {
+            self.add_words(text);
+        }
+        
+        for element in document.select(&ATTRIBUTE_SELECTOR) {
+            for attribute in ["alt", "title", "placeholder"] {
+                if let Some(value) = element.value().attr(attribute) {
+                    self.add_words(value);
+                }
+            }
+        }
+        
+        // البيانات الوصفية: المؤلف مصدر لأسماء المستخدمين، والبقية (الوصف، الكلمات المفتاحية) كلمات
+        for meta in document.select(&META_SELECTOR) {
+            let name = meta.value().attr("name").or_else(|| meta.value().attr("property")).unwrap_or("");
+            let content = meta.value().attr("content").unwrap_or("").trim();
+            if name.eq_ignore_ascii_case("author") && !content.is_empty() {
+                push_unique(&mut self.authors, content.to_string());
+            }
+            if !name.is_empty() && !content.starts_with("http") {
+                self.add_words(content);
+            }
+        }
+        
+        crawler::page_links(page, &document)
+    }
+    
+    /// تقسيم نص إلى كلمات (أحرف وأرقام بأي لغة) وعدها
+    fn add_words(&mut self, text: &str) {
+        for word in text.split(|c: char| !c.is_alphanumeric()) {
+            if word.chars().count() >= self.min_word_len && word.chars().any(char::is_alphabetic) {
+                *self.words.entry(word.to_string()).or_insert(0) += 1;
+            }
+        }
+    }
+    
+    /// الكلمات مرتبة بالتكرار (الأكثر أولًا ثم أبجديًا)
+    pub fn words(&self) -> Vec<String> {
+        let mut words: Vec<(&String, &usize)> = self.words.iter().collect();
+        words.sort_by(|a, b| b.1.cmp(a.1).then_with(|| a.0.cmp(b.0)));
+        words.into_iter().map(|(word, _)| word.clone()).collect()
+    }
+    
+    /// أسماء مستخدمين مرشحة: البريد كاملًا، الجزء المحلي منه، وصيغ أسماء المؤلفين (first.last, flast...) بعد تهجئة العربية منها
+    pub fn usernames(&self) -> Vec<String> {
+        let mut usernames = Vec::new();
+        
+        for email in &self.emails {
+            push_unique(&mut usernames, email.clone());
+            if let Some((local, _)) = email.split_once('@') {
+                push_unique(&mut usernames, local.to_string());
+            }
+        }
+        
+        for author in &self.authors {
+            let parts: Vec<String> = author
+                .split(|c: char| !c.is_alphanumeric())
+                .filter(|part| !part.is_empty())
+                .map(|part| arabic::romanize(part).to_lowercase())
+                .collect();
+            match parts.as_slice() {
+                [] => {}
+                [single] => push_unique(&mut usernames, single.clone()),
+                [first, .., last] => {
+                    let initial: String = first.chars().take(1).collect();
+                    for candidate in [
+                        format!("{}.{}", first, last),
+                        format!("{}{}", initial, last),
+                        format!("{}{}", first, last),
+                        format!("{}_{}", first, last),
+                        first.clone(),
+                        last.clone(),
+                    ] {
+                        push_unique(&mut usernames, candidate);
+                    }
+                }
+            }
+        }
+        
+        usernames
+    }
+}
+
+/// إضافة قيمة إلى قائمة إن لم تكن موجودة (مع حفظ ترتيب الظهور)
+fn push_unique(values: &mut Vec<String>, value: String) {
+    if !values.contains(&value) {
+        values.push(value);
+    }
+}
+
+/// زحف الموقع وحصاد كلماته (صفحات HTML فقط على نفس المضيف)
+pub async fn harvest(start: &str, options: &CrawlOptions, min_word_len: usize) -> Result<Harvest> {
+    let mut harvest = Harvest::new(min_word_len);
+    
+    crawler::walk(start, options, |page, content| match content {
+        Page::Html(body) => harvest.add_page(page, &body),
+        Page::Challenge(_) => Vec::new(),
+    })
+    .await?;
+    
+    Ok(harvest)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_harvest_words_emails_and_usernames() {
+        let page = Url::parse("https://acme.com/about").unwrap();
+        let html = r#"
+            <html><head>
+                <title>Acme Rockets</title>
+                <meta name="author" content="John Smith">
+                <meta name="keywords" content="rockets, propulsion">
+                <script>var ignored = "javascript";</script>
+            </head><body>
+                <p>Acme builds rockets. Rockets go up! Contact sales@acme.com</p>
+                <img src="/logo.png" alt="Falcon logo">
+                <a href="/team">Team</a><a href="mailto:j.doe@acme.com">mail</a>
+            </body></html>
+        "#;
+        
+        let mut harvest = Harvest::new(DEFAULT_MIN_WORD_LEN);
+        let links = harvest.add_page(&page, html);
+        let words = harvest.words();
+        
+        assert_eq!(links, [Url::parse("https://acme.com/team").unwrap()]);
+        assert_eq!(words[..3], ["Acme", "Rockets", "rockets"]);
+        for expected in ["Acme", "propulsion", "Falcon", "Team"] {
+            assert!(words.iter().any(|w| w == expected), "{}", expected);
+        }
+        assert!(!words.iter().any(|w| w == "javascript" || w == "up"));
+        assert_eq!(harvest.emails(), ["sales@acme.com", "j.doe@acme.com"]);
+        
+        let usernames = harvest.usernames();
+        for expected in ["sales@acme.com", "sales", "j.doe", "john.smith", "jsmith", "john"] {
+            assert!(usernames.iter().any(|u| u == expected), "{}", expected);
+        }
+    }
+}
@@ -0,0 +1,225 @@
+//! أقنعة بأسلوب hashcat
+//! تحليل الأقنعة (?u?l?l?l?d?d) والمجموعات المخصصة ?1..?4 وتعداد فضاء المفاتيح بترتيب ثابت
+
+use anyhow::{bail, Result};
+use rand::Rng;
+
+/// عدد المجموعات المخصصة المدعومة (?1..?4)
+pub const MAX_CUSTOM_CHARSETS: usize = 4;
+
+/// الأحرف الخاصة (?s) كما يعرّفها hashcat
+const SPECIAL: &str = " !\"#$%&'()*+,-./:;<=>?@[\\]^_`{|}~";
+
+/// أحرف مجموعة مدمجة
+fn builtin(name: char) -> Option<String> {
+    let charset = match name {
+        'l' => ('a'..='z').collect(),
+        'u' => ('A'..='Z').collect(),
+        'd' => ('0'..='9').collect(),
+        'h' => "0123456789abcdef".to_string(),
+        'H' => "0123456789ABCDEF".to_string(),
+        's' => SPECIAL.to_string(),
+        'a' => ['l', 'u', 'd', 's'].into_iter().filter_map(builtin).collect(),
+        _ => return None,
+    };
+    
+    Some(charset)
+}
+
+/// توسيع تعريف مجموعة (أحرف حرفية ومجموعات مدمجة ومخصصة) مع إزالة التكرار وحفظ الترتيب
+pub(crate) fn expand_charset(definition: &str, custom: &[Vec<char>]) -> Result<Vec<char>> {
+    let mut chars: Vec<char> = Vec::new();
+    let mut input = definition.chars();
+    
+    while let Some(c) = input.next() {
+        let expanded = match c {
+            '?' => match input.next() {
+                Some('?') => vec!['?'],
+                Some(name @ '1'..='4') => {
+                    let index = name as usize - '1' as usize;
+                    match custom.get(index) {
+                        Some(set) => set.clone(),
+                        None => bail!("المجموعة المخصصة ?{} غير معرّفة", name),
+                    }
+                }
+                Some(name) => match builtin(name) {
+                    Some(set) => set.chars().collect(),
+                    None => bail!("مجموعة أحرف غير معروفة: ?{}", name),
+                },
+                None => bail!("'?' في نهاية التعريف بدون اسم مجموعة: {}", definition),
+            },
+            literal => vec![literal],
+        };
+        
+        for c in expanded {
+            if !chars.contains(&c) {
+                chars.push(c);
+            }
+        }
+    }
+    
+    Ok(chars)
+}
+
+/// قناع محلل: مجموعة أحرف لكل موضع
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Mask {
+    positions: Vec<Vec<char>>,
+}
+
+impl Mask {
+    /// تحليل قناع مع تعريفات المجموعات المخصصة بالترتيب (?1, ?2, ...)
+    pub fn parse(mask: &str, charsets: &[String]) -> Result<Self> {
+        if charsets.len() > MAX_CUSTOM_CHARSETS {
+            bail!("الحد الأقصى {} مجموعات مخصصة", MAX_CUSTOM_CHARSETS);
+        }
+        
+        // كل مجموعة مخصصة يمكنها الإشارة إلى المجموعات المعرّفة قبلها
+        let mut custom: Vec<Vec<char>> = Vec::with_capacity(charsets.len());
+        for definition in charsets {
+            let set = expand_charset(definition, &custom)?;
+            if set.is_empty() {
+                bail!("مجموعة مخصصة فارغة: ?{}", custom.len() + 1);
+            }
+            custom.push(set);
+        }
+        
+        let mut positions = Vec::new();
+        let mut input = mask.chars().peekable();
+        while let Some(c) = input.next() {
+            let token = match (c, input.peek()) {
+                ('?', Some(&name)) => {
+                    input.next();
+                    format!("?{}", name)
+                }
+                _ => c.to_string(),
+            };
+            positions.push(expand_charset(&token, &custom)?);
+        }
+        
+        if positions.is_empty() {
+            bail!("قناع فارغ");
+        }
+        
+        Ok(Self { positions })
+    }
+    
+    /// قناع من مجموعة أحرف واحدة مكررة لعدد من المواضع
+    pub fn uniform(charset: &[char], length: usize) -> Self {
+        Self { positions: vec![charset.to_vec(); length] }
+    }
+    
+    /// طول المرشحين (عدد المواضع)
+    pub fn len(&self) -> usize {
+        self.positions.len()
+    }
+    
+    /// هل القناع بلا مواضع
+    pub fn is_empty(&self) -> bool {
+        self.positions.is_empty()
+    }
+    
+    /// حجم فضاء المفاتيح (مشبع عند تجاوز u128)
+    pub fn keyspace(&self) -> u128 {
+        self.positions
+            .iter()
+            .fold(1u128, |total, set| total.saturating_mul(set.len() as u128))
+    }
+    
+    /// المرشح عند فهرس معين (الموضع الأخير هو الأسرع تغيرًا)
+    pub fn candidate(&self, index: u128) -> Option<String> {
+        self.digits(index)
+            .map(|digits| digits.iter().zip(&self.positions).map(|(&i, set)| set[i]).collect())
+    }
+    
+    /// مرشح عشوائي من فضاء المفاتيح
+    pub fn sample<R: Rng>(&self, rng: &mut R) -> String {
+        self.positions
+            .iter()
+            .map(|set| set[rng.gen_range(0..set.len())])
+            .collect()
+    }
+    
+    /// مكرر على كل المرشحين بدءًا من فهرس (للاستئناف)
+    pub fn iter_from(&self, start: u128) -> MaskIter<'_> {
+        MaskIter { mask: self, digits: self.digits(start) }
+    }
+    
+    /// مكرر على كل المرشحين
+    pub fn iter(&self) -> MaskIter<'_> {
+        self.iter_from(0)
+    }
+    
+    /// تفكيك فهرس إلى فهارس أحرف لكل موضع (None إن تجاوز فضاء المفاتيح)
+    fn digits(&self, mut index: u128) -> Option<Vec<usize>> {
+        if index >= self.keyspace() {
+            return None;
+        }
+        
+        let mut digits = vec![0; self.positions.len()];
+        for (digit, set) in digits.iter_mut().zip(&self.positions).rev() {
+            let radix = set.len() as u128;
+            *digit = (index % radix) as usize;
+            index /= radix;
+        }
+        
+        Some(digits)
+    }
+}
+
+/// مكرر عداد (odometer) على مرشحي القناع
+pub struct MaskIter<'a> {
+    mask: &'a Mask,
+    digits: Option<Vec<usize>>,
+}
+
+impl Iterator for MaskIter<'_> {
+    type Item = String;
+    
+    fn next(&mut self) -> Option<String> {
+        let digits = self.digits.as_mut()?;
+        let candidate = digits
+            .iter()
+            .zip(&self.mask.positions)
+            .map(|(&i, set)| set[i])
+            .collect();
+        
+        // زيادة العداد من الموضع الأخير مع الحمل
+        let mut carried = true;
+        for (digit, set) in digits.iter_mut().zip(&self.mask.positions).rev() {
+            *digit += 1;
+            if *digit < set.len() {
+                carried = false;
+                break;
+            }
+            *digit = 0;
+        }
+        if carried {
+            self.digits = None;
+        }
+        
+        Some(candidate)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_mask_keyspace_and_enumeration() {
+        let mask = Mask::parse("?u?d", &[]).unwrap();
+        assert_eq!(mask.keyspace(), 260);
+        assert_eq!(mask.candidate(0).as_deref(), Some("A0"));
+        assert_eq!(mask.candidate(259).as_deref(), Some("Z9"));
+        assert_eq!(mask.iter().count(), 260);
+        assert_eq!(mask.iter_from(258).collect::<Vec<_>>(), ["Z8", "Z9"]);
+        
+        let custom = Mask::parse("?1x??", &["ab?d".to_string()]).unwrap();
+        assert_eq!(custom.keyspace(), 12);
+        assert_eq!(custom.iter().next().as_deref(), Some("ax?"));
+        
+        assert!(Mask::parse("?2", &["ab".to_string()]).is_err());
+        assert!(Mask::parse("?q", &[]).is_err());
+    }
+}
@@ -0,0 +1,164 @@
+//! مولد قوائم الكلمات
+//! قوائم عشوائية بحجم ثابت من أنماط، تعداد كامل لأقنعة بأسلوب hashcat وتسلسلات بأسلوب crunch، تحوير قوائم أساس بالقواعد، تراكيب PRINCE، التهجئات العربية، وحصاد كلمات الموقع
+
+pub mod arabic;
+pub mod harvest;
+pub mod mask;
+pub mod mutate;
+pub mod prince;
+pub mod rules;
+pub mod sequence;
+
+pub use mask::Mask;
+pub use mutate::Mutator;
+pub use prince::Combinator;
+pub use rules::RuleSet;
+pub use sequence::Sequence;
+
+use std::collections::HashSet;
+use std::sync::Arc;
+use anyhow::{Context, Result};
+use futures::TryStreamExt;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use tokio::fs::{File, OpenOptions};
+use tokio::io::{AsyncWriteExt, BufWriter};
+
+use crate::crawler::CrawlOptions;
+use crate::parser::WordSource;
+
+/// أنماط التوليد الافتراضية (أقنعة شائعة لكلمات المرور البشرية)
+const DEFAULT_PATTERNS: [&str; 4] = ["?u?l?l?l?l?d?d", "?l?l?l?l?l?l?d?d", "?u?l?l?l?l?l?d?d?s", "?l?l?l?l?d?d?d?d"];
+
+/// عدد المحاولات لكل مرشح فريد قبل التوقف (حين يكون فضاء الأنماط أصغر من الحجم المطلوب)
+const SAMPLE_ATTEMPTS_PER_ENTRY: usize = 10;
+
+/// فتح ملف الإخراج للكتابة (إلحاقًا عند الاستئناف)
+async fn open_output(output: &str, append: bool) -> Result<BufWriter<File>> {
+    let file = OpenOptions::new()
+        .create(true)
+        .write(true)
+        .append(append)
+        .truncate(!append)
+        .open(output)
+        .await
+        .context(format!("فشل في إنشاء الملف: {}", output))?;
+    
+    Ok(BufWriter::new(file))
+}
+
+/// كتابة دفعة مرشحين سطرًا سطرًا وإرجاع عددهم
+async fn write_lines(writer: &mut BufWriter<File>, candidates: impl Iterator<Item = String>) -> Result<u64> {
+    let mut written = 0u64;
+    
+    for candidate in candidates {
+        writer.write_all(candidate.as_bytes()).await?;
+        writer.write_all(b"\n").await?;
+        written += 1;
+    }
+    
+    Ok(written)
+}
+
+/// كتابة المرشحين في ملف (إلحاقًا عند الاستئناف) وإرجاع عددهم
+async fn write_candidates(output: &str, candidates: impl Iterator<Item = String>, append: bool) -> Result<u64> {
+    let mut writer = open_output(output, append).await?;
+    let written = write_lines(&mut writer, candidates).await?;
+    writer.flush().await?;
+    
+    Ok(written)
+}
+
+/// توليد قائمة عشوائية فريدة بحجم ثابت من أنماط (أقنعة) وكتابتها في ملف
+pub async fn generate(output: &str, size: usize, patterns: Option<&[String]>) -> Result<u64> {
+    let masks = match patterns {
+        Some(patterns) if !patterns.is_empty() => patterns
+            .iter()
+            .map(|pattern| Mask::parse(pattern, &[]).context(format!("نمط غير صالح: {}", pattern)))
+            .collect::<Result<Vec<_>>>()?,
+        _ => DEFAULT_PATTERNS.iter().map(|pattern| Mask::parse(pattern, &[])).collect::<Result<Vec<_>>>()?,
+    };
+    
+    let mut rng = StdRng::from_entropy();
+    let mut seen = HashSet::with_capacity(size);
+    let mut candidates = Vec::with_capacity(size);
+    
+    for mask in masks.iter().cycle().take(size.saturating_mul(SAMPLE_ATTEMPTS_PER_ENTRY)) {
+        if candidates.len() >= size {
+            break;
+        }
+        let candidate = mask.sample(&mut rng);
+        if seen.insert(candidate.clone()) {
+            candidates.push(candidate);
+        }
+    }
+    
+    if candidates.len() < size {
+        tracing::warn!("الأنماط لا تكفي إلا لـ {} مرشح فريد من أصل {}", candidates.len(), size);
+    }
+    
+    write_candidates(output, candidates.into_iter(), false).await
+}
+
+/// تعداد مرشحي القناع من فهرس بداية وكتابتهم في ملف (يُلحق بالملف عند الاستئناف)
+pub async fn generate_mask(output: &str, mask: &Mask, start: u128) -> Result<u64> {
+    tracing::info!("فضاء المفاتيح: {} مرشح بطول {}", mask.keyspace(), mask.len());
+    
+    write_candidates(output, mask.iter_from(start), start > 0).await
+}
+
+/// تحوير قائمة أساس (ملف، رابط أو stdin) بالبث وكتابة المتغيرات في ملف (قواعد hashcat أولًا إن وُجدت ثم المحوّر)
+pub async fn generate_mutations(output: &str, base: &str, rules: Option<RuleSet>, mutator: &Mutator) -> Result<u64> {
+    let source = WordSource::locate(base)
+        .await
+        .context(format!("فشل في قراءة القائمة الأساس: {}", base))?;
+    let mut words = match rules {
+        Some(rules) => source.with_rules(Arc::new(rules)).stream(),
+        None => source.stream(),
+    };
+    let mut writer = open_output(output, false).await?;
+    let (mut read, mut written) = (0u64, 0u64);
+    
+    while let Some(word) = words.try_next().await? {
+        read += 1;
+        written += write_lines(&mut writer, mutator.mutate(&word).into_iter()).await?;
+    }
+    writer.flush().await?;
+    
+    tracing::info!("{} كلمة أساس ← {} متغير", read, written);
+    Ok(written)
+}
+
+/// حصاد كلمات الموقع بالزحف وكتابتها في ملف، وأسماء المستخدمين المرشحة في ملف ثانٍ إن طُلب
+pub async fn generate_harvest(
+    output: &str,
+    users_output: Option<&str>,
+    start: &str,
+    options: &CrawlOptions,
+    min_word_len: usize,
+) -> Result<u64> {
+    let harvest = harvest::harvest(start, options, min_word_len).await?;
+    tracing::info!("{} صفحة، {} بريد", harvest.pages(), harvest.emails().len());
+    
+    if let Some(users_output) = users_output {
+        let users = write_candidates(users_output, harvest.usernames().into_iter(), false).await?;
+        tracing::info!("{} اسم مستخدم مرشح في {}", users, users_output);
+    }
+    
+    write_candidates(output, harvest.words().into_iter(), false).await
+}
+
+/// تراكيب PRINCE من قوائم عناصر (كلمات، أرقام، فواصل) وكتابتها في ملف، بحد أقصى اختياري للعدد
+pub async fn generate_prince(output: &str, combinator: &Combinator, limit: Option<u64>) -> Result<u64> {
+    tracing::info!("فضاء المفاتيح: {} مرشح في {} سلسلة أطوال", combinator.keyspace(), combinator.chains());
+    
+    let limit = limit.map_or(usize::MAX, |limit| usize::try_from(limit).unwrap_or(usize::MAX));
+    write_candidates(output, combinator.iter().take(limit), false).await
+}
+
+/// تعداد تسلسل بأسلوب crunch من فهرس بداية وكتابته في ملف (يُلحق بالملف عند الاستئناف)
+pub async fn generate_sequence(output: &str, sequence: &Sequence, start: u128) -> Result<u64> {
+    tracing::info!("فضاء المفاتيح: {} كلمة، البداية من الفهرس {}", sequence.keyspace(), start);
+    
+    write_candidates(output, sequence.iter_from(start), start > 0).await
+}
@@ -0,0 +1,191 @@
+//! محرك التحوير
+//! توليد متغيرات كل كلمة أساس (استبدالات leet، حالة الأحرف، لواحق) بحد أقصى لعامل التوسع
+
+use std::collections::HashSet;
+
+use super::arabic;
+
+/// الحد الافتراضي لعدد المتغيرات الناتجة عن كلمة أساس واحدة
+pub const DEFAULT_MAX_EXPANSION: usize = 50;
+
+/// أول سنة في لواحق السنوات
+pub const FIRST_YEAR: i32 = 1990;
+
+/// لواحق الرموز الأكثر شيوعًا في كلمات المرور المؤسسية
+pub const SYMBOL_SUFFIXES: [&str; 3] = ["!", "@", "123"];
+
+/// استبدالات leet الشائعة لكل حرف (تُطبق على كل تكرارات الحرف معًا)
+const LEET: [(char, &[char]); 9] = [
+    ('a', &['4', '@']),
+    ('e', &['3']),
+    ('i', &['1', '!']),
+    ('o', &['0']),
+    ('s', &['5', '$']),
+    ('t', &['7']),
+    ('l', &['1']),
+    ('g', &['9']),
+    ('b', &['8']),
+];
+
+/// إعدادات التحوير
+#[derive(Debug, Clone)]
+pub struct Mutator {
+    /// تطبيق استبدالات leet
+    pub leet: bool,
+    /// توليد متغيرات حالة الأحرف (صغيرة، كبيرة، أول حرف كبير، معكوسة)
+    pub case: bool,
+    /// توليد التهجئات العربية/اللاتينية وأخطاء تخطيط لوحة المفاتيح العربية
+    pub arabic: bool,
+    /// لواحق تُضاف إلى كل متغير
+    pub suffixes: Vec<String>,
+    /// أقصى عدد متغيرات لكل كلمة أساس (بما فيها الكلمة نفسها)
+    pub max_expansion: usize,
+}
+
+impl Default for Mutator {
+    fn default() -> Self {
+        Self {
+            leet: false,
+            case: false,
+            arabic: false,
+            suffixes: Vec::new(),
+            max_expansion: DEFAULT_MAX_EXPANSION,
+        }
+    }
+}
+
+/// متغيرات حالة الأحرف لكلمة
+fn case_variants(word: &str) -> Vec<String> {
+    let lower = word.to_lowercase();
+    let mut capitalized = lower.chars();
+    let capitalized = match capitalized.next() {
+        Some(first) => first.to_uppercase().chain(capitalized).collect(),
+        None => String::new(),
+    };
+    let toggled = word
+        .chars()
+        .map(|c| if c.is_uppercase() { c.to_lowercase().collect::<String>() } else { c.to_uppercase().collect() })
+        .collect();
+    
+    vec![lower, word.to_uppercase(), capitalized, toggled]
+}
+
+/// لواحق السنوات والتواريخ والرموز (الأحدث أولًا لأنها الأعلى مردودًا عند بلوغ حد التوسع)
+pub fn date_suffixes(years: bool, symbols: bool, current_year: i32) -> Vec<String> {
+    let mut suffixes = Vec::new();
+    let recent: Vec<i32> = (FIRST_YEAR..=current_year).rev().collect();
+    
+    if years {
+        // السنة الحالية والسابقة بشهر (MMYY) كما في سياسات التدوير الدورية
+        for &year in recent.iter().take(2) {
+            suffixes.extend((1..=12).map(|month| format!("{:02}{:02}", month, year % 100)));
+        }
+        for &year in &recent {
+            suffixes.push(year.to_string());
+            suffixes.push(format!("{:02}", year % 100));
+        }
+    }
+    
+    if symbols {
+        suffixes.extend(SYMBOL_SUFFIXES.iter().map(|s| s.to_string()));
+    }
+    
+    // سنة متبوعة برمز (Password2024!)
+    if years && symbols {
+        for &year in &recent {
+            suffixes.extend(SYMBOL_SUFFIXES.iter().take(2).map(|s| format!("{}{}", year, s)));
+        }
+    }
+    
+    suffixes
+}
+
+impl Mutator {
+    /// متغيرات كلمة أساس مرتبة (الكلمة الأصلية أولًا) وبدون تكرار، مقيدة بـ max_expansion
+    pub fn mutate(&self, word: &str) -> Vec<String> {
+        let cap = self.max_expansion.max(1);
+        let mut seen = HashSet::new();
+        let mut variants = Vec::new();
+        let mut push = |variant: String, variants: &mut Vec<String>| {
+            if variants.len() < cap && seen.insert(variant.clone()) {
+                variants.push(variant);
+            }
+        };
+        
+        push(word.to_string(), &mut variants);
+        if self.arabic {
+            for variant in arabic::variants(word) {
+                push(variant, &mut variants);
+            }
+        }
+        if self.case {
+            for base in variants.clone() {
+                for variant in case_variants(&base) {
+                    push(variant, &mut variants);
+                }
+            }
+        }
+        
+        // كل فئة leet تضاعف المتغيرات الحالية حتى بلوغ الحد
+        if self.leet {
+            for (letter, replacements) in LEET {
+                let current = variants.clone();
+                for variant in current.iter().filter(|v| v.contains(letter)) {
+                    for &replacement in replacements {
+                        push(variant.replace(letter, &replacement.to_string()), &mut variants);
+                    }
+                }
+            }
+        }
+        
+        let bases = variants.clone();
+        for suffix in &self.suffixes {
+            for base in &bases {
+                push(format!("{}{}", base, suffix), &mut variants);
+            }
+        }
+        
+        variants
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_mutations_are_bounded_and_unique() {
+        let mutator = Mutator {
+            leet: true,
+            case: true,
+            arabic: false,
+            suffixes: vec!["!".to_string()],
+            max_expansion: 1000,
+        };
+        let variants = mutator.mutate("Password");
+        
+        assert_eq!(variants[0], "Password");
+        for expected in ["password", "PASSWORD", "pASSWORD", "p@ssword", "p455w0rd", "Password!"] {
+            assert!(variants.iter().any(|v| v == expected), "{}", expected);
+        }
+        assert_eq!(variants.len(), variants.iter().collect::<HashSet<_>>().len());
+        
+        let capped = Mutator { max_expansion: 5, ..mutator };
+        assert_eq!(capped.mutate("Password").len(), 5);
+    }
+    
+    #[test]
+    fn test_date_suffixes_recent_first() {
+        let suffixes = date_suffixes(true, true, 2025);
+        
+        assert_eq!(suffixes[0], "0125");
+        let position = |suffix: &str| suffixes.iter().position(|s| s == suffix).unwrap();
+        assert!(position("2025") < position("1990"));
+        for expected in ["1225", "0124", "90", "!", "@", "123", "2025!", "1990@"] {
+            assert!(suffixes.iter().any(|s| s == expected), "{}", expected);
+        }
+        
+        assert_eq!(date_suffixes(false, true, 2025), ["!", "@", "123"]);
+        assert!(date_suffixes(false, false, 2025).is_empty());
+    }
+}
@@ -0,0 +1,168 @@
+//! التوليد التركيبي بأسلوب PRINCE
+//! مرشحون من سلاسل 2-3 عناصر (كلمات، أرقام، فواصل) تُبنى فقط للأطوال المسموحة بدل الجداء الكامل
+
+use std::collections::{BTreeMap, HashSet};
+use anyhow::{bail, Result};
+
+/// أقل عدد عناصر افتراضي في المرشح
+pub const DEFAULT_MIN_ELEMENTS: usize = 2;
+
+/// أقصى عدد عناصر افتراضي في المرشح
+pub const DEFAULT_MAX_ELEMENTS: usize = 3;
+
+/// أقصى طول افتراضي للمرشح (كما في princeprocessor)
+pub const DEFAULT_MAX_LENGTH: usize = 16;
+
+/// مولد تركيبي: العناصر مجمعة حسب طولها وكل سلسلة أطوال تُعدد جداءها فقط
+#[derive(Debug, Clone)]
+pub struct Combinator {
+    buckets: BTreeMap<usize, Vec<String>>,
+    chains: Vec<Vec<usize>>,
+}
+
+impl Combinator {
+    /// مولد من عناصر (بدون تكرار) بعدد عناصر وطول مرشح محددين
+    pub fn new(
+        elements: impl IntoIterator<Item = String>,
+        elements_range: (usize, usize),
+        length_range: (usize, usize),
+    ) -> Result<Self> {
+        let (min_elements, max_elements) = elements_range;
+        let (min_len, max_len) = length_range;
+        if min_elements == 0 || min_elements > max_elements {
+            bail!("عدد عناصر غير صالح: {}..{}", min_elements, max_elements);
+        }
+        if min_len > max_len {
+            bail!("أطوال غير صالحة: {}..{}", min_len, max_len);
+        }
+        
+        let mut buckets: BTreeMap<usize, Vec<String>> = BTreeMap::new();
+        let mut seen = HashSet::new();
+        for element in elements {
+            let len = element.chars().count();
+            if len > 0 && len <= max_len && seen.insert(element.clone()) {
+                buckets.entry(len).or_default().push(element);
+            }
+        }
+        if buckets.is_empty() {
+            bail!("لا توجد عناصر بطول مسموح");
+        }
+        
+        // سلاسل الأطوال: الأقصر أولًا ثم الأقل عناصر
+        let lengths: Vec<usize> = buckets.keys().copied().collect();
+        let mut chains = Vec::new();
+        for total in min_len.max(1)..=max_len {
+            for count in min_elements..=max_elements {
+                compositions(total, count, &lengths, &mut Vec::new(), &mut chains);
+            }
+        }
+        
+        Ok(Self { buckets, chains })
+    }
+    
+    /// عدد سلاسل الأطوال الصالحة
+    pub fn chains(&self) -> usize {
+        self.chains.len()
+    }
+    
+    /// حجم فضاء المفاتيح (مشبع عند تجاوز u128)
+    pub fn keyspace(&self) -> u128 {
+        self.chains
+            .iter()
+            .map(|chain| {
+                chain
+                    .iter()
+                    .fold(1u128, |total, len| total.saturating_mul(self.bucket(*len).len() as u128))
+            })
+            .fold(0u128, u128::saturating_add)
+    }
+    
+    /// مكرر على كل المرشحين (سلسلة بعد سلسلة)
+    pub fn iter(&self) -> impl Iterator<Item = String> + '_ {
+        self.chains.iter().flat_map(move |chain| Product::new(chain.iter().map(|len| self.bucket(*len)).collect()))
+    }
+    
+    /// عناصر طول معين
+    fn bucket(&self, len: usize) -> &[String] {
+        self.buckets.get(&len).map_or(&[], Vec::as_slice)
+    }
+}
+
+/// كل تقسيمات total إلى count أطوال متاحة (مرتبة، والترتيب مهم لأن "ab"+"c" غير "c"+"ab")
+fn compositions(total: usize, count: usize, lengths: &[usize], current: &mut Vec<usize>, out: &mut Vec<Vec<usize>>) {
+    if count == 0 {
+        if total == 0 {
+            out.push(current.clone());
+        }
+        return;
+    }
+    
+    for &len in lengths.iter().take_while(|&&len| len <= total) {
+        current.push(len);
+        compositions(total - len, count - 1, lengths, current, out);
+        current.pop();
+    }
+}
+
+/// جداء قوائم بعداد (odometer)، العنصر الأخير هو الأسرع تغيرًا
+struct Product<'a> {
+    lists: Vec<&'a [String]>,
+    digits: Option<Vec<usize>>,
+}
+
+impl<'a> Product<'a> {
+    fn new(lists: Vec<&'a [String]>) -> Self {
+        let digits = lists.iter().all(|list| !list.is_empty()).then(|| vec![0; lists.len()]);
+        Self { lists, digits }
+    }
+}
+
+impl Iterator for Product<'_> {
+    type Item = String;
+    
+    fn next(&mut self) -> Option<String> {
+        let digits = self.digits.as_mut()?;
+        let candidate = digits.iter().zip(&self.lists).map(|(&i, list)| list[i].as_str()).collect();
+        
+        let mut carried = true;
+        for (digit, list) in digits.iter_mut().zip(&self.lists).rev() {
+            *digit += 1;
+            if *digit < list.len() {
+                carried = false;
+                break;
+            }
+            *digit = 0;
+        }
+        if carried {
+            self.digits = None;
+        }
+        
+        Some(candidate)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_combinator_respects_lengths() {
+        let elements = ["acme", "summer", "2024", "!", "1"].map(String::from);
+        let combinator = Combinator::new(elements, (2, 3), (6, 10)).unwrap();
+        let candidates: Vec<String> = combinator.iter().collect();
+        
+        assert_eq!(combinator.keyspace(), candidates.len() as u128);
+        assert!(candidates.iter().all(|c| (6..=10).contains(&c.chars().count())));
+        for expected in ["acme2024", "summer2024", "acme!", "summer!", "acme2024!", "summer1!"] {
+            let expected_len = expected.chars().count();
+            assert_eq!(candidates.iter().any(|c| c == expected), (6..=10).contains(&expected_len), "{}", expected);
+        }
+        assert!(!candidates.iter().any(|c| c == "acmesummer2024"));
+        
+        // الأقصر أولًا
+        let lengths: Vec<usize> = candidates.iter().map(|c| c.chars().count()).collect();
+        assert!(lengths.windows(2).all(|w| w[0] <= w[1]));
+        
+        assert!(Combinator::new(["x".to_string()], (3, 2), (1, 8)).is_err());
+    }
+}
@@ -0,0 +1,406 @@
+//! محرك قواعد hashcat
+//! مفسر لصيغة قواعد hashcat/John (best64, dive) يطبقها على قائمة أساس عند التوليد أو أثناء الهجوم
+
+use std::collections::HashSet;
+use std::path::Path;
+use std::str::Chars;
+use anyhow::{anyhow, bail, Context, Result};
+use tokio::fs as tokio_fs;
+
+/// أقصى طول لكلمة ناتجة (كما في hashcat؛ الأطول تُرفض)
+pub const MAX_WORD_LEN: usize = 256;
+
+/// دالة واحدة من دوال القاعدة
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Op {
+    Noop,
+    Lower,
+    Upper,
+    Capitalize,
+    InvertCapitalize,
+    ToggleAll,
+    ToggleAt(usize),
+    Reverse,
+    Duplicate,
+    DuplicateTimes(usize),
+    Reflect,
+    RotateLeft,
+    RotateRight,
+    Append(char),
+    Prepend(char),
+    DeleteFirst,
+    DeleteLast,
+    DeleteAt(usize),
+    Extract(usize, usize),
+    Omit(usize, usize),
+    Insert(usize, char),
+    Overwrite(usize, char),
+    Truncate(usize),
+    Replace(char, char),
+    Purge(char),
+    DuplicateFirst(usize),
+    DuplicateLast(usize),
+    DuplicateEach,
+    SwapFront,
+    SwapBack,
+    Swap(usize, usize),
+    ShiftLeft(usize),
+    ShiftRight(usize),
+    Increment(usize),
+    Decrement(usize),
+    ReplaceWithNext(usize),
+    ReplaceWithPrior(usize),
+    DuplicateBlockFront(usize),
+    DuplicateBlockBack(usize),
+    Title(char),
+    Memorize,
+    AppendMemory,
+    PrependMemory,
+    InsertMemory(usize, usize, usize),
+    RejectLonger(usize),
+    RejectShorter(usize),
+    RejectLengthNot(usize),
+    RejectContains(char),
+    RejectMissing(char),
+    RejectFirstNot(char),
+    RejectLastNot(char),
+    RejectCharAtNot(usize, char),
+    RejectCountBelow(usize, char),
+    RejectUnchanged,
+}
+
+/// فك ترميز موضع (0-9 ثم A-Z لـ 10-35)
+fn position(c: char) -> Option<usize> {
+    match c {
+        '0'..='9' => Some(c as usize - '0' as usize),
+        'A'..='Z' => Some(c as usize - 'A' as usize + 10),
+        _ => None,
+    }
+}
+
+/// الوسيط الحرفي التالي لدالة
+fn next_char(chars: &mut Chars, name: char) -> Result<char> {
+    chars.next().ok_or_else(|| anyhow!("وسيط ناقص للدالة '{}'", name))
+}
+
+/// وسيط الموضع التالي لدالة
+fn next_position(chars: &mut Chars, name: char) -> Result<usize> {
+    let c = next_char(chars, name)?;
+    position(c).ok_or_else(|| anyhow!("موضع غير صالح '{}' للدالة '{}'", c, name))
+}
+
+/// تعديل حرف ASCII برمزه (الأحرف غير ASCII تبقى كما هي)
+fn map_ascii(c: char, f: impl Fn(u8) -> u8) -> char {
+    if c.is_ascii() { char::from(f(c as u8) & 0x7f) } else { c }
+}
+
+/// عكس حالة حرف
+fn toggle(c: char) -> char {
+    if c.is_lowercase() {
+        c.to_uppercase().next().unwrap_or(c)
+    } else {
+        c.to_lowercase().next().unwrap_or(c)
+    }
+}
+
+/// قاعدة واحدة (سطر من ملف .rule)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rule {
+    ops: Vec<Op>,
+}
+
+impl Rule {
+    /// تحليل سطر قاعدة (المسافات بين الدوال تُتجاهل)
+    pub fn parse(line: &str) -> Result<Self> {
+        let mut chars = line.chars();
+        let mut ops = Vec::new();
+        
+        while let Some(name) = chars.next() {
+            let pos = |chars: &mut Chars| next_position(chars, name);
+            let arg = |chars: &mut Chars| next_char(chars, name);
+            
+            let op = match name {
+                ' ' | '\t' => continue,
+                ':' => Op::Noop,
+                'l' => Op::Lower,
+                'u' => Op::Upper,
+                'c' => Op::Capitalize,
+                'C' => Op::InvertCapitalize,
+                't' => Op::ToggleAll,
+                'T' => Op::ToggleAt(pos(&mut chars)?),
+                'r' => Op::Reverse,
+                'd' => Op::Duplicate,
+                'p' => Op::DuplicateTimes(pos(&mut chars)?),
+                'f' => Op::Reflect,
+                '{' => Op::RotateLeft,
+                '}' => Op::RotateRight,
+                '$' => Op::Append(arg(&mut chars)?),
+                '^' => Op::Prepend(arg(&mut chars)?),
+                '[' => Op::DeleteFirst,
+                ']' => Op::DeleteLast,
+                'D' => Op::DeleteAt(pos(&mut chars)?),
+                'x' => Op::Extract(pos(&mut chars)?, pos(&mut chars)?),
+                'O' => Op::Omit(pos(&mut chars)?, pos(&mut chars)?),
+                'i' => Op::Insert(pos(&mut chars)?, arg(&mut chars)?),
+                'o' => Op::Overwrite(pos(&mut chars)?, arg(&mut chars)?),
+                '\'' => Op::Truncate(pos(&mut chars)?),
+                's' => Op::Replace(arg(&mut chars)?, arg(&mut chars)?),
+                '@' => Op::Purge(arg(&mut chars)?),
+                'z' => Op::DuplicateFirst(pos(&mut chars)?),
+                'Z' => Op::DuplicateLast(pos(&mut chars)?),
+                'q' => Op::DuplicateEach,
+                'k' => Op::SwapFront,
+                'K' => Op::SwapBack,
+                '*' => Op::Swap(pos(&mut chars)?, pos(&mut chars)?),
+                'L' => Op::ShiftLeft(pos(&mut chars)?),
+                'R' => Op::ShiftRight(pos(&mut chars)?),
+                '+' => Op::Increment(pos(&mut chars)?),
+                '-' => Op::Decrement(pos(&mut chars)?),
+                '.' => Op::ReplaceWithNext(pos(&mut chars)?),
+                ',' => Op::ReplaceWithPrior(pos(&mut chars)?),
+                'y' => Op::DuplicateBlockFront(pos(&mut chars)?),
+                'Y' => Op::DuplicateBlockBack(pos(&mut chars)?),
+                'E' => Op::Title(' '),
+                'e' => Op::Title(arg(&mut chars)?),
+                'M' => Op::Memorize,
+                '4' => Op::AppendMemory,
+                '6' => Op::PrependMemory,
+                'X' => Op::InsertMemory(pos(&mut chars)?, pos(&mut chars)?, pos(&mut chars)?),
+                '<' => Op::RejectLonger(pos(&mut chars)?),
+                '>' => Op::RejectShorter(pos(&mut chars)?),
+                '_' => Op::RejectLengthNot(pos(&mut chars)?),
+                '!' => Op::RejectContains(arg(&mut chars)?),
+                '/' => Op::RejectMissing(arg(&mut chars)?),
+                '(' => Op::RejectFirstNot(arg(&mut chars)?),
+                ')' => Op::RejectLastNot(arg(&mut chars)?),
+                '=' => Op::RejectCharAtNot(pos(&mut chars)?, arg(&mut chars)?),
+                '%' => Op::RejectCountBelow(pos(&mut chars)?, arg(&mut chars)?),
+                'Q' => Op::RejectUnchanged,
+                other => bail!("دالة قاعدة غير مدعومة: '{}'", other),
+            };
+            ops.push(op);
+        }
+        
+        if ops.is_empty() {
+            bail!("قاعدة فارغة");
+        }
+        
+        Ok(Self { ops })
+    }
+    
+    /// تطبيق القاعدة على كلمة (None إن رفضتها إحدى دوال الرفض أو تجاوزت MAX_WORD_LEN)
+    pub fn apply(&self, word: &str) -> Option<String> {
+        let mut w: Vec<char> = word.chars().collect();
+        let mut memory = w.clone();
+        
+        for op in &self.ops {
+            let len = w.len();
+            match *op {
+                Op::Noop => {}
+                Op::Lower => w = w.iter().flat_map(|c| c.to_lowercase()).collect(),
+                Op::Upper => w = w.iter().flat_map(|c| c.to_uppercase()).collect(),
+                Op::Capitalize | Op::InvertCapitalize => {
+                    let first_upper = matches!(op, Op::Capitalize);
+                    w = w
+                        .iter()
+                        .enumerate()
+                        .flat_map(|(i, c)| -> Vec<char> {
+                            if (i == 0) == first_upper { c.to_uppercase().collect() } else { c.to_lowercase().collect() }
+                        })
+                        .collect();
+                }
+                Op::ToggleAll => w.iter_mut().for_each(|c| *c = toggle(*c)),
+                Op::ToggleAt(n) => {
+                    if let Some(c) = w.get_mut(n) {
+                        *c = toggle(*c);
+                    }
+                }
+                Op::Reverse => w.reverse(),
+                Op::Duplicate => w = w.repeat(2),
+                Op::DuplicateTimes(n) => w = w.repeat(n + 1),
+                Op::Reflect => w.extend(w.clone().into_iter().rev()),
+                Op::RotateLeft if len > 0 => w.rotate_left(1),
+                Op::RotateRight if len > 0 => w.rotate_right(1),
+                Op::Append(c) => w.push(c),
+                Op::Prepend(c) => w.insert(0, c),
+                Op::DeleteFirst if len > 0 => {
+                    w.remove(0);
+                }
+                Op::DeleteLast => {
+                    w.pop();
+                }
+                Op::DeleteAt(n) if n < len => {
+                    w.remove(n);
+                }
+                Op::Extract(n, m) if n < len => w = w.iter().skip(n).take(m).copied().collect(),
+                Op::Omit(n, m) if n < len => {
+                    w.drain(n..(n + m).min(len));
+                }
+                Op::Insert(n, c) if n <= len => w.insert(n, c),
+                Op::Overwrite(n, c) if n < len => w[n] = c,
+                Op::Truncate(n) => w.truncate(n),
+                Op::Replace(from, to) => w.iter_mut().filter(|c| **c == from).for_each(|c| *c = to),
+                Op::Purge(c) => w.retain(|x| *x != c),
+                Op::DuplicateFirst(n) if len > 0 => {
+                    let first = w[0];
+                    w.splice(0..0, std::iter::repeat(first).take(n));
+                }
+                Op::DuplicateLast(n) if len > 0 => w.extend(std::iter::repeat(w[len - 1]).take(n)),
+                Op::DuplicateEach => w = w.iter().flat_map(|&c| [c, c]).collect(),
+                Op::SwapFront if len > 1 => w.swap(0, 1),
+                Op::SwapBack if len > 1 => w.swap(len - 2, len - 1),
+                Op::Swap(a, b) if a < len && b < len => w.swap(a, b),
+                Op::ShiftLeft(n) if n < len => w[n] = map_ascii(w[n], |b| b << 1),
+                Op::ShiftRight(n) if n < len => w[n] = map_ascii(w[n], |b| b >> 1),
+                Op::Increment(n) if n < len => w[n] = map_ascii(w[n], |b| b.wrapping_add(1)),
+                Op::Decrement(n) if n < len => w[n] = map_ascii(w[n], |b| b.wrapping_sub(1)),
+                Op::ReplaceWithNext(n) if n + 1 < len => w[n] = w[n + 1],
+                Op::ReplaceWithPrior(n) if n > 0 && n < len => w[n] = w[n - 1],
+                Op::DuplicateBlockFront(n) if n <= len => {
+                    let block: Vec<char> = w[..n].to_vec();
+                    w.splice(0..0, block);
+                }
+                Op::DuplicateBlockBack(n) if n <= len => w.extend(w[len - n..].to_vec()),
+                Op::Title(separator) => {
+                    let mut upper_next = true;
+                    w = w
+                        .iter()
+                        .flat_map(|&c| {
+                            let mapped: Vec<char> =
+                                if upper_next { c.to_uppercase().collect() } else { c.to_lowercase().collect() };
+                            upper_next = c == separator;
+                            mapped
+                        })
+                        .collect();
+                }
+                Op::Memorize => memory = w.clone(),
+                Op::AppendMemory => w.extend(memory.iter().copied()),
+                Op::PrependMemory => {
+                    w.splice(0..0, memory.iter().copied());
+                }
+                Op::InsertMemory(start, count, at) if start + count <= memory.len() && at <= len => {
+                    w.splice(at..at, memory[start..start + count].iter().copied());
+                }
+                Op::RejectLonger(n) if len > n => return None,
+                Op::RejectShorter(n) if len < n => return None,
+                Op::RejectLengthNot(n) if len != n => return None,
+                Op::RejectContains(c) if w.contains(&c) => return None,
+                Op::RejectMissing(c) if !w.contains(&c) => return None,
+                Op::RejectFirstNot(c) if w.first() != Some(&c) => return None,
+                Op::RejectLastNot(c) if w.last() != Some(&c) => return None,
+                Op::RejectCharAtNot(n, c) if w.get(n) != Some(&c) => return None,
+                Op::RejectCountBelow(n, c) if w.iter().filter(|x| **x == c).count() < n => return None,
+                Op::RejectUnchanged if w == memory => return None,
+                // المواضع خارج الكلمة وشروط الرفض غير المتحققة لا تغير شيئًا
+                _ => {}
+            }
+            
+            if w.len() > MAX_WORD_LEN {
+                return None;
+            }
+        }
+        
+        Some(w.into_iter().collect())
+    }
+}
+
+/// مجموعة قواعد محملة من ملف .rule
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RuleSet {
+    rules: Vec<Rule>,
+}
+
+impl RuleSet {
+    /// تحليل محتوى ملف قواعد (الأسطر الفارغة والتعليقات # تُتجاهل) وإرجاع عدد القواعد غير المدعومة المتخطاة
+    pub fn parse(content: &str) -> (Self, usize) {
+        let mut rules = Vec::new();
+        let mut skipped = 0;
+        
+        for line in content.lines() {
+            let line = line.trim_end_matches('\r');
+            if line.trim().is_empty() || line.starts_with('#') {
+                continue;
+            }
+            match Rule::parse(line) {
+                Ok(rule) => rules.push(rule),
+                Err(e) => {
+                    tracing::debug!(rule = line, error = %e, "قاعدة متخطاة");
+                    skipped += 1;
+                }
+            }
+        }
+        
+        (Self { rules }, skipped)
+    }
+    
+    /// تحميل ملف قواعد
+    pub async fn load(path: &Path) -> Result<Self> {
+        let content = tokio_fs::read_to_string(path)
+            .await
+            .context(format!("فشل في قراءة ملف القواعد: {}", path.display()))?;
+        
+        let (rules, skipped) = Self::parse(&content);
+        if skipped > 0 {
+            tracing::warn!("تم تخطي {} قاعدة غير مدعومة في {}", skipped, path.display());
+        }
+        if rules.is_empty() {
+            bail!("لا توجد قواعد صالحة في {}", path.display());
+        }
+        
+        Ok(rules)
+    }
+    
+    /// عدد القواعد
+    pub fn len(&self) -> usize {
+        self.rules.len()
+    }
+    
+    /// هل المجموعة فارغة
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+    
+    /// ناتج كل القواعد على كلمة بالترتيب وبدون تكرار (القواعد الرافضة لا تنتج شيئًا)
+    pub fn apply(&self, word: &str) -> Vec<String> {
+        let mut seen = HashSet::new();
+        
+        self.rules
+            .iter()
+            .filter_map(|rule| rule.apply(word))
+            .filter(|candidate| seen.insert(candidate.clone()))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_best64_style_rules() {
+        let apply = |rule: &str, word: &str| Rule::parse(rule).unwrap().apply(word);
+        
+        assert_eq!(apply(":", "password").as_deref(), Some("password"));
+        assert_eq!(apply("c $1 $2 $3", "password").as_deref(), Some("Password123"));
+        assert_eq!(apply("u", "abc").as_deref(), Some("ABC"));
+        assert_eq!(apply("r", "abc").as_deref(), Some("cba"));
+        assert_eq!(apply("d", "ab").as_deref(), Some("abab"));
+        assert_eq!(apply("f", "ab").as_deref(), Some("abba"));
+        assert_eq!(apply("sa@ so0", "password").as_deref(), Some("p@ssw0rd"));
+        assert_eq!(apply("^1 ]", "abc").as_deref(), Some("1ab"));
+        assert_eq!(apply("x13", "password").as_deref(), Some("ass"));
+        assert_eq!(apply("O02 i0X", "password").as_deref(), Some("Xssword"));
+        assert_eq!(apply("T0 T7", "password").as_deref(), Some("PassworD"));
+        assert_eq!(apply("M r 4", "ab").as_deref(), Some("baab"));
+        assert_eq!(apply(">9", "password"), None);
+        assert_eq!(apply("/z", "password"), None);
+        assert_eq!(apply("$!", "A!"), Some("A!!".to_string()));
+        
+        assert!(Rule::parse("w").is_err());
+        assert!(Rule::parse("$").is_err());
+        
+        let (rules, skipped) = RuleSet::parse("# best64\n:\nc\n\nw\nl\n");
+        assert_eq!((rules.len(), skipped), (3, 1));
+        assert_eq!(rules.apply("Word"), ["Word", "word"]);
+    }
+}
@@ -0,0 +1,64 @@
+//! التوليد التسلسلي
+//! تعداد كل تركيبات مجموعة أحرف بين طولين (بأسلوب crunch) مع الاستئناف من فهرس بداية
+
+use anyhow::{bail, Result};
+
+use super::mask::{expand_charset, Mask};
+
+/// تسلسل كل الكلمات من مجموعة أحرف بأطوال من min إلى max (الأقصر أولًا)
+#[derive(Debug, Clone)]
+pub struct Sequence {
+    masks: Vec<Mask>,
+}
+
+impl Sequence {
+    /// تسلسل جديد (المجموعة تقبل أحرفًا حرفية ومجموعات مدمجة مثل ?d?l)
+    pub fn new(charset: &str, min_len: usize, max_len: usize) -> Result<Self> {
+        if min_len == 0 || min_len > max_len {
+            bail!("أطوال غير صالحة: {}..{}", min_len, max_len);
+        }
+        
+        let chars = expand_charset(charset, &[])?;
+        if chars.is_empty() {
+            bail!("مجموعة أحرف فارغة");
+        }
+        
+        Ok(Self {
+            masks: (min_len..=max_len).map(|length| Mask::uniform(&chars, length)).collect(),
+        })
+    }
+    
+    /// حجم فضاء المفاتيح لكل الأطوال (مشبع عند تجاوز u128)
+    pub fn keyspace(&self) -> u128 {
+        self.masks
+            .iter()
+            .fold(0u128, |total, mask| total.saturating_add(mask.keyspace()))
+    }
+    
+    /// مكرر على الكلمات بدءًا من فهرس عام (للاستئناف بعد انقطاع)
+    pub fn iter_from(&self, mut start: u128) -> impl Iterator<Item = String> + '_ {
+        self.masks.iter().flat_map(move |mask| {
+            let skip = start.min(mask.keyspace());
+            start -= skip;
+            mask.iter_from(skip)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_sequence_spans_lengths_and_resumes() {
+        let pins = Sequence::new("?d", 4, 6).unwrap();
+        assert_eq!(pins.keyspace(), 10_000 + 100_000 + 1_000_000);
+        
+        let tiny = Sequence::new("ab", 1, 2).unwrap();
+        assert_eq!(tiny.iter_from(0).collect::<Vec<_>>(), ["a", "b", "aa", "ab", "ba", "bb"]);
+        assert_eq!(tiny.iter_from(3).collect::<Vec<_>>(), ["ab", "ba", "bb"]);
+        assert_eq!(tiny.iter_from(6).count(), 0);
+        
+        assert!(Sequence::new("?d", 5, 4).is_err());
+    }
+}
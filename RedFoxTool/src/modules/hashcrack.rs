@@ -0,0 +1,375 @@
+//! كسر التجزئات دون اتصال
+//! هجوم قاموس على تجزئات MD5, SHA-1, SHA-256, NTLM, bcrypt وتجزئات htpasswd/shadow الملتقطة أثناء المهمة
+
+use std::collections::{HashMap, HashSet};
+use std::time::{Duration, Instant};
+use anyhow::{Context, Result};
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use indicatif::ProgressBar;
+use md4::{Digest, Md4};
+use md5::Md5;
+use rayon::prelude::*;
+use sha1::Sha1;
+use sha2::Sha256;
+use tokio::fs as tokio_fs;
+
+use crate::parser::normalize_line;
+use crate::scanner::ScanResult;
+
+/// نوع التجزئة
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum HashKind {
+    /// MD5 (32 خانة ست عشرية)
+    Md5,
+    /// SHA-1 (40 خانة)
+    Sha1,
+    /// SHA-256 (64 خانة)
+    Sha256,
+    /// تجزئة NT لـ Windows: MD4(UTF-16LE)
+    Ntlm,
+    /// bcrypt ($2a$, $2b$, $2y$)
+    Bcrypt,
+    /// APR1-MD5 الخاص بـ Apache ($apr1$)
+    Apr1,
+    /// SHA-1 بترميز base64 في htpasswd ({SHA})
+    HtSha,
+    /// Unix crypt: md5-crypt ($1$), sha256-crypt ($5$), sha512-crypt ($6$) و DES
+    Crypt,
+}
+
+impl std::str::FromStr for HashKind {
+    type Err = anyhow::Error;
+    
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "md5" => Ok(HashKind::Md5),
+            "sha1" | "sha-1" => Ok(HashKind::Sha1),
+            "sha256" | "sha-256" => Ok(HashKind::Sha256),
+            "ntlm" | "nt" => Ok(HashKind::Ntlm),
+            "bcrypt" => Ok(HashKind::Bcrypt),
+            "apr1" => Ok(HashKind::Apr1),
+            "htsha" => Ok(HashKind::HtSha),
+            "crypt" => Ok(HashKind::Crypt),
+            _ => Err(anyhow::anyhow!(
+                "نوع تجزئة غير مدعوم: {} (md5, sha1, sha256, ntlm, bcrypt, apr1, htsha, crypt)",
+                s
+            )),
+        }
+    }
+}
+
+impl std::fmt::Display for HashKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let name = match self {
+            HashKind::Md5 => "md5",
+            HashKind::Sha1 => "sha1",
+            HashKind::Sha256 => "sha256",
+            HashKind::Ntlm => "ntlm",
+            HashKind::Bcrypt => "bcrypt",
+            HashKind::Apr1 => "apr1",
+            HashKind::HtSha => "htsha",
+            HashKind::Crypt => "crypt",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+impl HashKind {
+    /// تخمين النوع من شكل التجزئة (32 خانة تُعتبر MD5، استخدم --hash-type ntlm لتجزئات NT)
+    pub fn detect(hash: &str) -> Option<Self> {
+        if hash.starts_with("$2a$") || hash.starts_with("$2b$") || hash.starts_with("$2y$") {
+            return Some(HashKind::Bcrypt);
+        }
+        if hash.starts_with("$apr1$") {
+            return Some(HashKind::Apr1);
+        }
+        if hash.starts_with("{SHA}") {
+            return Some(HashKind::HtSha);
+        }
+        if ["$1$", "$5$", "$6$"].iter().any(|prefix| hash.starts_with(prefix)) {
+            return Some(HashKind::Crypt);
+        }
+        
+        if !hash.bytes().all(|b| b.is_ascii_hexdigit()) {
+            return None;
+        }
+        
+        match hash.len() {
+            32 => Some(HashKind::Md5),
+            40 => Some(HashKind::Sha1),
+            64 => Some(HashKind::Sha256),
+            _ => None,
+        }
+    }
+    
+    /// هل يمكن فهرسة التجزئة (غير مملحة وبالست عشري)
+    pub fn indexable(self) -> bool {
+        matches!(self, HashKind::Md5 | HashKind::Sha1 | HashKind::Sha256 | HashKind::Ntlm)
+    }
+    
+    /// تجزئة سريعة بالست عشري الصغير (None للأنواع غير القابلة للفهرسة)
+    pub fn digest(self, candidate: &str) -> Option<String> {
+        let bytes: Vec<u8> = match self {
+            HashKind::Md5 => Md5::digest(candidate.as_bytes()).to_vec(),
+            HashKind::Sha1 => Sha1::digest(candidate.as_bytes()).to_vec(),
+            HashKind::Sha256 => Sha256::digest(candidate.as_bytes()).to_vec(),
+            HashKind::Ntlm => {
+                let utf16: Vec<u8> = candidate.encode_utf16().flat_map(u16::to_le_bytes).collect();
+                Md4::digest(utf16).to_vec()
+            }
+            _ => return None,
+        };
+        
+        Some(bytes.iter().map(|b| format!("{:02x}", b)).collect())
+    }
+    
+    /// التحقق من مرشح مقابل تجزئة
+    pub fn verify(self, hash: &str, candidate: &str) -> bool {
+        match self {
+            HashKind::Bcrypt => bcrypt::verify(candidate, hash).unwrap_or(false),
+            HashKind::Apr1 => apr1_verify(hash, candidate),
+            HashKind::HtSha => hash
+                .strip_prefix("{SHA}")
+                .is_some_and(|encoded| BASE64.encode(Sha1::digest(candidate.as_bytes())) == encoded),
+            HashKind::Crypt => pwhash::unix::verify(candidate, hash),
+            _ => self.digest(candidate).is_some_and(|digest| digest.eq_ignore_ascii_case(hash)),
+        }
+    }
+}
+
+/// تجزئة محملة من ملف (مع اسم المستخدم إن وجد)
+#[derive(Debug, Clone)]
+pub struct HashEntry {
+    /// اسم المستخدم (صيغة user:hash)
+    pub user: Option<String>,
+    
+    /// التجزئة
+    pub hash: String,
+    
+    /// نوع التجزئة
+    pub kind: HashKind,
+}
+
+impl HashEntry {
+    /// التسمية المعروضة في النتائج
+    pub fn label(&self) -> &str {
+        self.user.as_deref().unwrap_or(&self.hash)
+    }
+}
+
+/// تحميل ملف تجزئات (سطر لكل تجزئة أو user:hash)
+pub async fn load(filepath: &str, forced: Option<HashKind>) -> Result<Vec<HashEntry>> {
+    let content = tokio_fs::read_to_string(filepath)
+        .await
+        .context(format!("فشل في قراءة ملف التجزئات: {}", filepath))?;
+    
+    Ok(parse_hashes(&content, forced))
+}
+
+/// تحليل محتوى ملف التجزئات (الأسطر غير المعروفة تُتجاهل مع تحذير)
+pub fn parse_hashes(content: &str, forced: Option<HashKind>) -> Vec<HashEntry> {
+    let mut entries = Vec::new();
+    
+    for line in content.lines().filter_map(normalize_line) {
+        // bcrypt يحتوي "$" وليس ":"، فالفاصل الأخير يفصل المستخدم عن التجزئة
+        let (user, hash) = match line.rsplit_once(':') {
+            Some((user, hash)) => (Some(user.to_string()), hash.trim()),
+            None => (None, line),
+        };
+        
+        match forced.or_else(|| HashKind::detect(hash)) {
+            Some(kind) => entries.push(HashEntry { user, hash: hash.to_string(), kind }),
+            None => tracing::warn!("تجزئة غير معروفة، تم تجاهلها: {}", line),
+        }
+    }
+    
+    entries
+}
+
+/// كسر التجزئات بقائمة كلمات وإرجاع نتيجة لكل تجزئة
+pub fn crack(entries: &[HashEntry], candidates: &[String], progress: Option<&ProgressBar>) -> Vec<ScanResult> {
+    let start = Instant::now();
+    let mut cracked: HashMap<usize, String> = HashMap::new();
+    
+    // التجزئات السريعة: تجزئة كل مرشح مرة واحدة لكل نوع والبحث في فهرس
+    let mut fast: HashMap<HashKind, HashMap<String, Vec<usize>>> = HashMap::new();
+    for (index, entry) in entries.iter().enumerate() {
+        if entry.kind.indexable() {
+            fast.entry(entry.kind)
+                .or_default()
+                .entry(entry.hash.to_lowercase())
+                .or_default()
+                .push(index);
+        }
+    }
+    
+    for (kind, targets) in &fast {
+        let hits: Vec<(&Vec<usize>, &String)> = candidates
+            .par_iter()
+            .filter_map(|candidate| {
+                if let Some(pb) = progress {
+                    pb.inc(1);
+                }
+                targets.get(&kind.digest(candidate)?).map(|indices| (indices, candidate))
+            })
+            .collect();
+        
+        for (indices, candidate) in hits {
+            for index in indices {
+                cracked.entry(*index).or_insert_with(|| candidate.clone());
+            }
+        }
+    }
+    
+    // التجزئات المملحة (bcrypt, crypt...): كل تجزئة على حدة مع التوقف عند أول تطابق
+    for (index, entry) in entries.iter().enumerate().filter(|(_, e)| !e.kind.indexable()) {
+        let found = candidates.par_iter().find_any(|candidate| {
+            if let Some(pb) = progress {
+                pb.inc(1);
+            }
+            entry.kind.verify(&entry.hash, candidate)
+        });
+        
+        if let Some(password) = found {
+            cracked.insert(index, password.clone());
+        }
+    }
+    
+    let elapsed = start.elapsed();
+    entries
+        .iter()
+        .enumerate()
+        .map(|(index, entry)| hash_result(entry, cracked.remove(&index), elapsed))
+        .collect()
+}
+
+/// عدد عمليات التجزئة المتوقعة (لشريط التقدم)
+pub fn work_size(entries: &[HashEntry], candidates: usize) -> usize {
+    let kinds: HashSet<HashKind> = entries
+        .iter()
+        .filter(|e| e.kind.indexable())
+        .map(|e| e.kind)
+        .collect();
+    
+    let salted = entries.iter().filter(|e| !e.kind.indexable()).count();
+    (kinds.len() + salted) * candidates
+}
+
+/// أبجدية ترميز crypt
+const CRYPT_ALPHABET: &[u8] = b"./0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz";
+
+/// التحقق من تجزئة APR1 ($apr1$salt$digest)
+fn apr1_verify(hash: &str, candidate: &str) -> bool {
+    let Some(salt) = hash.strip_prefix("$apr1$").and_then(|rest| rest.split('$').next()) else {
+        return false;
+    };
+    
+    md5_crypt(candidate.as_bytes(), salt.as_bytes(), "$apr1$") == hash
+}
+
+/// خوارزمية md5-crypt (Poul-Henning Kamp) بمقدمة قابلة للتغيير
+fn md5_crypt(password: &[u8], salt: &[u8], magic: &str) -> String {
+    let salt = &salt[..salt.len().min(8)];
+    let alternate = Md5::new().chain_update(password).chain_update(salt).chain_update(password).finalize();
+    
+    let mut ctx = Md5::new().chain_update(password).chain_update(magic).chain_update(salt);
+    for chunk in (0..password.len()).step_by(16) {
+        ctx.update(&alternate[..(password.len() - chunk).min(16)]);
+    }
+    let mut length = password.len();
+    while length > 0 {
+        if length & 1 == 1 {
+            ctx.update([0u8]);
+        } else {
+            ctx.update(&password[..1]);
+        }
+        length >>= 1;
+    }
+    let mut digest = ctx.finalize();
+    
+    for round in 0..1000 {
+        let odd = round & 1 == 1;
+        let mut ctx = Md5::new();
+        
+        ctx.update(if odd { password } else { &digest[..] });
+        if round % 3 != 0 {
+            ctx.update(salt);
+        }
+        if round % 7 != 0 {
+            ctx.update(password);
+        }
+        ctx.update(if odd { &digest[..] } else { password });
+        
+        digest = ctx.finalize();
+    }
+    
+    let mut encoded = String::new();
+    let mut push = |mut value: u32, count: usize| {
+        for _ in 0..count {
+            encoded.push(CRYPT_ALPHABET[(value & 0x3f) as usize] as char);
+            value >>= 6;
+        }
+    };
+    for (a, b, c) in [(0, 6, 12), (1, 7, 13), (2, 8, 14), (3, 9, 15), (4, 10, 5)] {
+        push(u32::from(digest[a]) << 16 | u32::from(digest[b]) << 8 | u32::from(digest[c]), 4);
+    }
+    push(u32::from(digest[11]), 2);
+    
+    format!("{}{}${}", magic, String::from_utf8_lossy(salt), encoded)
+}
+
+/// بناء نتيجة فحص لتجزئة (لإعادة استخدام المُبلغ وحفظ النتائج)
+pub fn hash_result(entry: &HashEntry, password: Option<String>, elapsed: Duration) -> ScanResult {
+    ScanResult {
+        username: entry.label().to_string(),
+        success: password.is_some(),
+        password: password.unwrap_or_default(),
+        status_code: 0,
+        response_time: elapsed,
+        error: None,
+        timestamp: chrono::Utc::now(),
+        metadata: None,
+        captcha_detected: false,
+        banner: Some(entry.kind.to_string()),
+        http_version: None,
+        ..Default::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_cracks_fast_hashes_by_kind() {
+        let entries = parse_hashes(
+            "admin:5f4dcc3b5aa765d61d8327deb882cf99\n\
+             5baa61e4c9b93f3f0682250b6cf8331b7ee68fd8\n\
+             # تعليق\n",
+            None,
+        );
+        let ntlm = parse_hashes("bob:8846F7EAEE8FB117AD06BDD830B7586C", Some(HashKind::Ntlm));
+        let candidates = vec!["123456".to_string(), "password".to_string()];
+        
+        assert_eq!(entries[0].kind, HashKind::Md5);
+        assert_eq!(entries[1].kind, HashKind::Sha1);
+        
+        let results = crack(&[entries, ntlm].concat(), &candidates, None);
+        
+        assert!(results.iter().all(|r| r.success && r.password == "password"));
+        assert_eq!(results[0].username, "admin");
+        assert_eq!(results[2].banner.as_deref(), Some("ntlm"));
+    }
+    
+    #[test]
+    fn test_htpasswd_hash_kinds() {
+        let apr1 = "$apr1$abcdefgh$FBwExRW4dCc8aL.OvjpIE1";
+        let sha = "{SHA}W6ph5Mm5Pz8GgiULbPgzG37mj9g=";
+        
+        assert_eq!(HashKind::detect(apr1), Some(HashKind::Apr1));
+        assert!(HashKind::Apr1.verify(apr1, "password"));
+        assert!(!HashKind::Apr1.verify(apr1, "Password"));
+        assert!(HashKind::HtSha.verify(sha, "password"));
+    }
+}
@@ -0,0 +1,154 @@
+//! كسر أسرار JWT دون اتصال
+//! تخمين مفتاح توقيع HS256/HS384/HS512 محليًا من قائمة كلمات بالتوازي (بدون أي طلبات شبكة)
+
+use anyhow::{Context, Result};
+use base64::engine::general_purpose::URL_SAFE_NO_PAD as BASE64URL;
+use base64::Engine;
+use hmac::digest::KeyInit;
+use hmac::{Hmac, Mac};
+use indicatif::ProgressBar;
+use rayon::prelude::*;
+use serde_json::Value;
+use sha2::{Sha256, Sha384, Sha512};
+
+/// عدد الكلمات في كل دفعة بين تحديثات شريط التقدم
+const CHUNK_SIZE: usize = 4096;
+
+/// خوارزمية توقيع HMAC
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JwtAlgorithm {
+    /// HMAC-SHA256
+    Hs256,
+    /// HMAC-SHA384
+    Hs384,
+    /// HMAC-SHA512
+    Hs512,
+}
+
+impl std::str::FromStr for JwtAlgorithm {
+    type Err = anyhow::Error;
+    
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_uppercase().as_str() {
+            "HS256" => Ok(JwtAlgorithm::Hs256),
+            "HS384" => Ok(JwtAlgorithm::Hs384),
+            "HS512" => Ok(JwtAlgorithm::Hs512),
+            _ => Err(anyhow::anyhow!("خوارزمية JWT غير مدعومة للكسر: {} (HS256, HS384, HS512)", s)),
+        }
+    }
+}
+
+impl std::fmt::Display for JwtAlgorithm {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let name = match self {
+            JwtAlgorithm::Hs256 => "HS256",
+            JwtAlgorithm::Hs384 => "HS384",
+            JwtAlgorithm::Hs512 => "HS512",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// رمز JWT مفكك جاهز للتحقق من التوقيع
+#[derive(Debug, Clone)]
+pub struct JwtToken {
+    /// خوارزمية التوقيع من الترويسة
+    pub algorithm: JwtAlgorithm,
+    
+    /// الحمولة بعد فك الترميز
+    pub claims: Value,
+    
+    /// الجزء الموقع (header.payload)
+    signing_input: String,
+    
+    /// التوقيع الخام
+    signature: Vec<u8>,
+}
+
+impl JwtToken {
+    /// تفكيك رمز بصيغة header.payload.signature
+    pub fn parse(token: &str) -> Result<Self> {
+        let parts: Vec<&str> = token.trim().split('.').collect();
+        let [header, payload, signature] = parts[..] else {
+            return Err(anyhow::anyhow!("رمز JWT غير صالح: يجب أن يتكون من ثلاثة أجزاء"));
+        };
+        
+        let header_json = decode_json(header).context("ترويسة JWT غير صالحة")?;
+        let algorithm = header_json
+            .get("alg")
+            .and_then(Value::as_str)
+            .ok_or_else(|| anyhow::anyhow!("ترويسة JWT لا تحتوي alg"))?
+            .parse()?;
+        
+        Ok(Self {
+            algorithm,
+            claims: decode_json(payload).context("حمولة JWT غير صالحة")?,
+            signing_input: format!("{}.{}", header, payload),
+            signature: BASE64URL
+                .decode(signature.trim_end_matches('='))
+                .context("توقيع JWT ليس base64url صالحًا")?,
+        })
+    }
+    
+    /// التحقق من التوقيع بسر مرشح
+    pub fn verify(&self, secret: &[u8]) -> bool {
+        let input = self.signing_input.as_bytes();
+        
+        match self.algorithm {
+            JwtAlgorithm::Hs256 => hmac_matches::<Hmac<Sha256>>(secret, input, &self.signature),
+            JwtAlgorithm::Hs384 => hmac_matches::<Hmac<Sha384>>(secret, input, &self.signature),
+            JwtAlgorithm::Hs512 => hmac_matches::<Hmac<Sha512>>(secret, input, &self.signature),
+        }
+    }
+}
+
+/// تخمين السر بالتوازي على كل الأنوية (يتوقف عند أول تطابق)
+pub fn crack(token: &JwtToken, candidates: &[String], progress: Option<&ProgressBar>) -> Option<String> {
+    candidates.par_chunks(CHUNK_SIZE).find_map_any(|chunk| {
+        let found = chunk.iter().find(|secret| token.verify(secret.as_bytes())).cloned();
+        
+        if let Some(pb) = progress {
+            pb.inc(chunk.len() as u64);
+        }
+        
+        found
+    })
+}
+
+/// فك ترميز جزء base64url يحتوي JSON
+fn decode_json(part: &str) -> Result<Value> {
+    let bytes = BASE64URL.decode(part.trim_end_matches('=')).context("ليس base64url صالحًا")?;
+    serde_json::from_slice(&bytes).context("ليس JSON صالحًا")
+}
+
+/// مقارنة توقيع HMAC بزمن ثابت
+fn hmac_matches<M: Mac + KeyInit>(secret: &[u8], input: &[u8], signature: &[u8]) -> bool {
+    let Ok(mut mac) = <M as Mac>::new_from_slice(secret) else {
+        return false;
+    };
+    mac.update(input);
+    mac.verify_slice(signature).is_ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_cracks_known_hs256_secret() {
+        let token = JwtToken::parse(
+            "eyJhbGciOiJIUzI1NiIsInR5cCI6IkpXVCJ9.\
+             eyJzdWIiOiIxMjM0NTY3ODkwIiwibmFtZSI6IkpvaG4gRG9lIiwiaWF0IjoxNTE2MjM5MDIyfQ.\
+             SflKxwRJSMeKKF2QT4fwpMeJf36POk6yJV_adQssw5c",
+        )
+        .unwrap();
+        let candidates: Vec<String> = ["secret", "password", "your-256-bit-secret"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        
+        assert_eq!(token.algorithm, JwtAlgorithm::Hs256);
+        assert_eq!(crack(&token, &candidates, None).as_deref(), Some("your-256-bit-secret"));
+        assert!(crack(&token, &candidates[..2], None).is_none());
+    }
+}
@@ -0,0 +1,127 @@
+//! وحدة LDAP
+//! اختبار الربط البسيط (simple bind) على LDAP و Active Directory عبر ldap أو ldaps أو StartTLS
+
+use anyhow::{Result, Context};
+use ldap3::{LdapConnAsync, LdapConnSettings};
+
+use super::{split_host_port, AttemptOutcome, AuthModule, ServiceOptions, TlsMode};
+use crate::login_body::USER_PLACEHOLDER;
+
+/// المنفذ الافتراضي لـ LDAPS
+const LDAPS_PORT: u16 = 636;
+
+/// رمز نتيجة LDAP لبيانات اعتماد غير صالحة
+const INVALID_CREDENTIALS: u32 = 49;
+
+/// وحدة اختبار الربط عبر LDAP
+pub struct LdapModule {
+    host: String,
+    port: u16,
+    options: ServiceOptions,
+}
+
+impl LdapModule {
+    /// إنشاء وحدة LDAP لهدف (host:port أو ldap://host:port)
+    pub fn new(target: &str, options: &ServiceOptions) -> Result<Self> {
+        let default_port = match options.tls {
+            TlsMode::Implicit => LDAPS_PORT,
+            _ => 389,
+        };
+        let (host, port) = split_host_port(target, default_port).context("هدف LDAP غير صالح")?;
+        
+        Ok(Self {
+            host,
+            port,
+            options: options.clone(),
+        })
+    }
+    
+    /// عنوان الهدف
+    pub fn address(&self) -> String {
+        format!("{}:{}", self.host, self.port)
+    }
+    
+    /// اسم الربط: DN أو UPN كما هو، أو عبر القالب (مثال: ^USER^@corp.local)
+    pub fn bind_name(&self, username: &str) -> String {
+        if username.contains('=') || username.contains('@') || username.contains('\\') {
+            return username.to_string();
+        }
+        
+        match &self.options.ldap_user_template {
+            Some(template) => template.replace(USER_PLACEHOLDER, username),
+            None => username.to_string(),
+        }
+    }
+    
+    /// تجربة بيانات اعتماد واحدة (اتصال مستقل لكل محاولة)
+    pub async fn attempt(&self, username: &str, password: &str) -> Result<AttemptOutcome> {
+        // كلمة مرور فارغة تعني ربطًا مجهولًا يقبله الخادم دائمًا
+        if password.is_empty() {
+            return Ok(AttemptOutcome::default());
+        }
+        
+        let scheme = if self.options.tls == TlsMode::Implicit { "ldaps" } else { "ldap" };
+        let url = format!("{}://{}", scheme, self.address());
+        
+        let settings = LdapConnSettings::new()
+            .set_conn_timeout(self.options.timeout)
+            .set_starttls(self.options.tls == TlsMode::Explicit)
+            .set_no_tls_verify(true);
+        
+        let (conn, mut ldap) = LdapConnAsync::with_settings(settings, &url)
+            .await
+            .context(format!("فشل في الاتصال بخادم LDAP: {}", url))?;
+        ldap3::drive!(conn);
+        
+        ldap.with_timeout(self.options.timeout);
+        let result = ldap
+            .simple_bind(&self.bind_name(username), password)
+            .await
+            .context("فشل في تنفيذ الربط")?;
+        
+        let _ = ldap.unbind().await;
+        
+        match result.rc {
+            0 => Ok(AttemptOutcome {
+                success: true,
+                ..Default::default()
+            }),
+            INVALID_CREDENTIALS => Ok(AttemptOutcome {
+                status_code: INVALID_CREDENTIALS as u16,
+                banner: (!result.text.is_empty()).then_some(result.text),
+                ..Default::default()
+            }),
+            rc => Err(anyhow::anyhow!("رد LDAP غير متوقع ({}): {}", rc, result.text)),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl AuthModule for LdapModule {
+    fn endpoint(&self) -> String {
+        self.address()
+    }
+    
+    async fn try_credential(&self, username: &str, password: &str) -> Result<AttemptOutcome> {
+        self.attempt(username, password).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_bind_name_formats() {
+        let options = ServiceOptions {
+            ldap_user_template: Some("^USER^@corp.local".to_string()),
+            ..Default::default()
+        };
+        let module = LdapModule::new("dc01", &options).unwrap();
+        
+        assert_eq!(module.bind_name("jdoe"), "jdoe@corp.local");
+        assert_eq!(module.bind_name("cn=jdoe,dc=corp,dc=local"), "cn=jdoe,dc=corp,dc=local");
+        assert_eq!(module.bind_name("CORP\\jdoe"), "CORP\\jdoe");
+        assert_eq!(module.address(), "dc01:389");
+    }
+}
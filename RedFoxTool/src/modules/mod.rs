@@ -0,0 +1,478 @@
+//! وحدات البروتوكولات
+//! توجيه محاولات تسجيل الدخول إلى HTTP أو إلى وحدة البروتوكول المناسبة (SSH, FTP, SMTP...)
+
+pub mod audit_file;
+pub mod benchmark;
+pub mod cms;
+pub mod defaults;
+pub mod ftp;
+pub mod generator;
+pub mod hashcrack;
+pub mod jwt;
+pub mod ldap;
+pub mod mongodb;
+pub mod panels;
+pub mod presets;
+pub mod smtp;
+pub mod snmp;
+pub mod ssh;
+pub mod tcp;
+pub mod wordpress;
+
+use std::sync::Arc;
+use std::time::Duration;
+use anyhow::Result;
+
+use crate::captcha::CaptchaKind;
+use crate::error::RedFoxError;
+use crate::http_client::HttpClient;
+
+/// اسم المستخدم المسجل لنتيجة الوصول بدون مصادقة
+pub const ANONYMOUS_USER: &str = "<anonymous>";
+
+/// مهلة رد UDP الافتراضية بالمللي ثانية
+pub const DEFAULT_UDP_TIMEOUT_MS: u64 = 1000;
+
+/// عدد مرات إعادة إرسال UDP الافتراضي
+pub const DEFAULT_UDP_RETRIES: u32 = 2;
+
+/// بروتوكول الهدف
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Protocol {
+    /// نموذج أو مصادقة HTTP(S)
+    #[default]
+    Http,
+    /// خادم SSH
+    Ssh,
+    /// خادم FTP/FTPS
+    Ftp,
+    /// خادم بريد SMTP
+    Smtp,
+    /// قاعدة بيانات MongoDB
+    Mongodb,
+    /// خادم دليل LDAP / Active Directory
+    Ldap,
+    /// وكيل SNMP (UDP)
+    Snmp,
+}
+
+impl Protocol {
+    /// المنفذ الافتراضي للبروتوكول
+    pub fn default_port(&self) -> u16 {
+        match self {
+            Protocol::Http => 80,
+            Protocol::Ssh => 22,
+            Protocol::Ftp => 21,
+            Protocol::Smtp => 25,
+            Protocol::Mongodb => 27017,
+            Protocol::Ldap => 389,
+            Protocol::Snmp => 161,
+        }
+    }
+    
+    /// استنتاج البروتوكول من مخطط الهدف (مثال: ssh://host:22)
+    pub fn from_target(target: &str) -> Option<Self> {
+        let (scheme, _) = target.split_once("://")?;
+        scheme.parse().ok()
+    }
+}
+
+impl std::str::FromStr for Protocol {
+    type Err = anyhow::Error;
+    
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "http" | "https" => Ok(Protocol::Http),
+            "ssh" => Ok(Protocol::Ssh),
+            "ftp" | "ftps" => Ok(Protocol::Ftp),
+            "smtp" | "smtps" => Ok(Protocol::Smtp),
+            "mongodb" | "mongo" => Ok(Protocol::Mongodb),
+            "ldap" | "ldaps" => Ok(Protocol::Ldap),
+            "snmp" => Ok(Protocol::Snmp),
+            _ => Err(anyhow::anyhow!("بروتوكول غير مدعوم: {} (http, ssh, ftp, smtp, mongodb, ldap, snmp)", s)),
+        }
+    }
+}
+
+impl std::fmt::Display for Protocol {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Protocol::Http => "http",
+            Protocol::Ssh => "ssh",
+            Protocol::Ftp => "ftp",
+            Protocol::Smtp => "smtp",
+            Protocol::Mongodb => "mongodb",
+            Protocol::Ldap => "ldap",
+            Protocol::Snmp => "snmp",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// نتيجة محاولة تسجيل دخول واحدة بغض النظر عن البروتوكول
+#[derive(Debug, Clone, Default)]
+pub struct AttemptOutcome {
+    /// هل نجحت المصادقة؟
+    pub success: bool,
+    
+    /// رمز الحالة (HTTP فقط، صفر لبقية البروتوكولات)
+    pub status_code: u16,
+    
+    /// نوع CAPTCHA المكتشف (HTTP فقط)
+    pub captcha: Option<CaptchaKind>,
+    
+    /// رسالة ترحيب الخادم (FTP, SMTP مع آليات AUTH المعلنة...)
+    pub banner: Option<String>,
+    
+    /// إصدار HTTP المتفاوض عليه (HTTP فقط)
+    pub http_version: Option<String>,
+    
+    /// وجهة إعادة التوجيه (HTTP فقط)
+    pub redirect: Option<String>,
+}
+
+/// وضع TLS لوحدات البروتوكولات
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TlsMode {
+    /// بدون تشفير
+    #[default]
+    None,
+    /// ترقية صريحة بعد الاتصال (AUTH TLS / STARTTLS)
+    Explicit,
+    /// TLS ضمني منذ بداية الاتصال (FTPS/SMTPS)
+    Implicit,
+}
+
+impl TlsMode {
+    /// استنتاج TLS الضمني من مخطط الهدف (ftps://, smtps://, ldaps://)
+    pub fn from_target(target: &str) -> Option<Self> {
+        let (scheme, _) = target.split_once("://")?;
+        matches!(scheme.to_lowercase().as_str(), "ftps" | "smtps" | "ldaps").then_some(TlsMode::Implicit)
+    }
+}
+
+impl std::str::FromStr for TlsMode {
+    type Err = anyhow::Error;
+    
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "none" => Ok(TlsMode::None),
+            "explicit" | "starttls" => Ok(TlsMode::Explicit),
+            "implicit" => Ok(TlsMode::Implicit),
+            _ => Err(anyhow::anyhow!("وضع TLS غير مدعوم: {} (none, explicit, implicit)", s)),
+        }
+    }
+}
+
+impl std::fmt::Display for TlsMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            TlsMode::None => "none",
+            TlsMode::Explicit => "explicit",
+            TlsMode::Implicit => "implicit",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// إعدادات الاتصال المشتركة لوحدات البروتوكولات
+#[derive(Debug, Clone)]
+pub struct ServiceOptions {
+    /// مهلة الاتصال وكل عملية قراءة/كتابة
+    pub timeout: Duration,
+    
+    /// وضع TLS
+    pub tls: TlsMode,
+    
+    /// قالب اسم الربط في LDAP (مثال: ^USER^@corp.local أو uid=^USER^,ou=people,dc=corp)
+    pub ldap_user_template: Option<String>,
+    
+    /// مهلة انتظار كل رد UDP (أقصر من مهلة TCP لأن الرفض يكون بالصمت)
+    pub udp_timeout: Duration,
+    
+    /// عدد مرات إعادة إرسال طلب UDP بدون رد
+    pub udp_retries: u32,
+    
+    /// إصدار SNMP
+    pub snmp_version: snmp::SnmpVersion,
+    
+    /// بروتوكول مصادقة SNMPv3
+    pub snmp_auth: snmp::SnmpAuthProtocol,
+}
+
+impl Default for ServiceOptions {
+    fn default() -> Self {
+        Self {
+            timeout: Duration::from_secs(30),
+            tls: TlsMode::None,
+            ldap_user_template: None,
+            udp_timeout: Duration::from_millis(DEFAULT_UDP_TIMEOUT_MS),
+            udp_retries: DEFAULT_UDP_RETRIES,
+            snmp_version: snmp::SnmpVersion::default(),
+            snmp_auth: snmp::SnmpAuthProtocol::default(),
+        }
+    }
+}
+
+/// وحدة مصادقة قابلة للتوصيل: تنفذ محاولة واحدة وتترك التنسيق (الخيوط، المعدل، التقارير) للماسح
+///
+/// ```ignore
+/// struct Radius { /* ... */ }
+///
+/// #[async_trait::async_trait]
+/// impl AuthModule for Radius {
+///     fn endpoint(&self) -> String { "10.0.0.1:1812".into() }
+///     async fn try_credential(&self, user: &str, pass: &str) -> anyhow::Result<AttemptOutcome> { /* ... */ }
+/// }
+///
+/// let scanner = ScannerBuilder::new().target("radius://10.0.0.1").users(["admin"]).passwords(["admin"])
+///     .build().await?
+///     .with_module(Radius { /* ... */ });
+/// ```
+#[async_trait::async_trait]
+pub trait AuthModule: Send + Sync {
+    /// عنوان الهدف كما يظهر في السجلات
+    fn endpoint(&self) -> String;
+    
+    /// فحص الوصول بدون مصادقة قبل التخمين (Some عند السماح بالوصول)
+    async fn precheck(&self) -> Result<Option<AttemptOutcome>> {
+        Ok(None)
+    }
+    
+    /// تجربة بيانات اعتماد واحدة (الخطأ يعني تعذر الحكم وليس كلمة مرور خاطئة)
+    async fn try_credential(&self, username: &str, password: &str) -> Result<AttemptOutcome>;
+}
+
+/// وحدة بروتوكول غير HTTP
+pub enum ServiceModule {
+    /// وحدة SSH
+    Ssh(ssh::SshModule),
+    /// وحدة FTP
+    Ftp(ftp::FtpModule),
+    /// وحدة SMTP
+    Smtp(smtp::SmtpModule),
+    /// وحدة MongoDB
+    Mongodb(mongodb::MongoModule),
+    /// وحدة LDAP
+    Ldap(ldap::LdapModule),
+    /// وحدة SNMP
+    Snmp(snmp::SnmpModule),
+}
+
+impl ServiceModule {
+    /// إنشاء وحدة البروتوكول للهدف (None لـ HTTP)
+    pub fn for_protocol(protocol: Protocol, target: &str, options: &ServiceOptions) -> Result<Option<Self>> {
+        match protocol {
+            Protocol::Http => Ok(None),
+            Protocol::Ssh => Ok(Some(ServiceModule::Ssh(ssh::SshModule::new(target, options)?))),
+            Protocol::Ftp => Ok(Some(ServiceModule::Ftp(ftp::FtpModule::new(target, options)?))),
+            Protocol::Smtp => Ok(Some(ServiceModule::Smtp(smtp::SmtpModule::new(target, options)?))),
+            Protocol::Mongodb => Ok(Some(ServiceModule::Mongodb(mongodb::MongoModule::new(target, options)?))),
+            Protocol::Ldap => Ok(Some(ServiceModule::Ldap(ldap::LdapModule::new(target, options)?))),
+            Protocol::Snmp => Ok(Some(ServiceModule::Snmp(snmp::SnmpModule::new(target, options)?))),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl AuthModule for ServiceModule {
+    fn endpoint(&self) -> String {
+        match self {
+            ServiceModule::Ssh(module) => module.endpoint(),
+            ServiceModule::Ftp(module) => module.endpoint(),
+            ServiceModule::Smtp(module) => module.endpoint(),
+            ServiceModule::Mongodb(module) => module.endpoint(),
+            ServiceModule::Ldap(module) => module.endpoint(),
+            ServiceModule::Snmp(module) => module.endpoint(),
+        }
+    }
+    
+    async fn precheck(&self) -> Result<Option<AttemptOutcome>> {
+        match self {
+            ServiceModule::Mongodb(module) => module.precheck().await,
+            _ => Ok(None),
+        }
+    }
+    
+    async fn try_credential(&self, username: &str, password: &str) -> Result<AttemptOutcome> {
+        match self {
+            ServiceModule::Ssh(module) => module.try_credential(username, password).await,
+            ServiceModule::Ftp(module) => module.try_credential(username, password).await,
+            ServiceModule::Smtp(module) => module.try_credential(username, password).await,
+            ServiceModule::Mongodb(module) => module.try_credential(username, password).await,
+            ServiceModule::Ldap(module) => module.try_credential(username, password).await,
+            ServiceModule::Snmp(module) => module.try_credential(username, password).await,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl AuthModule for HttpClient {
+    fn endpoint(&self) -> String {
+        self.base_url.clone()
+    }
+    
+    async fn try_credential(&self, username: &str, password: &str) -> Result<AttemptOutcome> {
+        let response = self.test_login(username, password).await?;
+        let status_code = response.status().as_u16();
+        let http_version = format!("{:?}", response.version());
+        let redirect = response
+            .headers()
+            .get(reqwest::header::LOCATION)
+            .and_then(|location| location.to_str().ok())
+            .map(str::to_string);
+        
+        // تقييد المعدل من الهدف: خطأ يستدعي الإبطاء وليس كلمة مرور خاطئة
+        if status_code == 429 {
+            let retry_after = response
+                .headers()
+                .get(reqwest::header::RETRY_AFTER)
+                .and_then(|value| value.to_str().ok())
+                .and_then(|value| value.trim().parse().ok())
+                .map(Duration::from_secs);
+            return Err(RedFoxError::RateLimited { retry_after }.into());
+        }
+        
+        let verdict = self.evaluate_response(response).await;
+        
+        // رسالة قفل معروفة: خطأ وليست محاولة فاشلة
+        if let Some(mechanism) = verdict.lockout {
+            return Err(RedFoxError::LockoutDetected(mechanism.to_string()).into());
+        }
+        
+        Ok(AttemptOutcome {
+            success: verdict.success,
+            status_code,
+            captcha: verdict.captcha,
+            banner: None,
+            http_version: Some(http_version),
+            redirect,
+        })
+    }
+}
+
+/// هدف المحاولات الذي يوزع عليه الماسح
+#[derive(Clone)]
+pub enum LoginTarget {
+    /// عميل HTTP
+    Http(Arc<HttpClient>),
+    /// وحدة بروتوكول أخرى
+    Service(Arc<ServiceModule>),
+    /// وحدة مصادقة خارجية (بروتوكولات خاصة من خارج المكتبة)
+    Module(Arc<dyn AuthModule>),
+}
+
+impl LoginTarget {
+    /// الوحدة التي تنفذ المحاولة
+    fn module(&self) -> &dyn AuthModule {
+        match self {
+            LoginTarget::Http(client) => client.as_ref(),
+            LoginTarget::Service(module) => module.as_ref(),
+            LoginTarget::Module(module) => module.as_ref(),
+        }
+    }
+    
+    /// تجربة بيانات اعتماد واحدة وتصنيف النتيجة
+    #[tracing::instrument(
+        name = "attempt",
+        level = "debug",
+        skip(self, password),
+        fields(username = %username, status = tracing::field::Empty, success = tracing::field::Empty)
+    )]
+    pub async fn attempt(&self, username: &str, password: &str) -> Result<AttemptOutcome> {
+        let outcome = self.module().try_credential(username, password).await;
+        
+        // نتيجة المحاولة كحقول في النطاق (تظهر في التتبع المصدر عبر OTLP)
+        if let Ok(outcome) = &outcome {
+            tracing::Span::current()
+                .record("status", outcome.status_code)
+                .record("success", outcome.success);
+        }
+        
+        outcome
+    }
+}
+
+#[async_trait::async_trait]
+impl AuthModule for LoginTarget {
+    fn endpoint(&self) -> String {
+        self.module().endpoint()
+    }
+    
+    async fn precheck(&self) -> Result<Option<AttemptOutcome>> {
+        self.module().precheck().await
+    }
+    
+    async fn try_credential(&self, username: &str, password: &str) -> Result<AttemptOutcome> {
+        self.module().try_credential(username, password).await
+    }
+}
+
+/// تقسيم الهدف إلى مضيف ومنفذ مع إزالة المخطط إن وجد
+pub fn split_host_port(target: &str, default_port: u16) -> Result<(String, u16)> {
+    let target = target.split_once("://").map_or(target, |(_, rest)| rest);
+    let target = target.trim_end_matches('/');
+    
+    // IPv6 بين أقواس: [::1]:22
+    if let Some(rest) = target.strip_prefix('[') {
+        let (host, port) = rest
+            .split_once(']')
+            .ok_or_else(|| anyhow::anyhow!("عنوان IPv6 غير صالح: {}", target))?;
+        let port = match port.strip_prefix(':') {
+            Some(p) => p.parse().map_err(|_| anyhow::anyhow!("منفذ غير صالح: {}", p))?,
+            None => default_port,
+        };
+        return Ok((host.to_string(), port));
+    }
+    
+    match target.rsplit_once(':') {
+        Some((host, port)) => {
+            let port = port.parse().map_err(|_| anyhow::anyhow!("منفذ غير صالح: {}", port))?;
+            Ok((host.to_string(), port))
+        }
+        None if target.is_empty() => Err(anyhow::anyhow!("هدف فارغ")),
+        None => Ok((target.to_string(), default_port)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_split_host_port() {
+        assert_eq!(split_host_port("ssh://10.0.0.5:2222", 22).unwrap(), ("10.0.0.5".to_string(), 2222));
+        assert_eq!(split_host_port("host", 22).unwrap(), ("host".to_string(), 22));
+        assert_eq!(split_host_port("[::1]:22", 21).unwrap(), ("::1".to_string(), 22));
+        assert_eq!(Protocol::from_target("ssh://host"), Some(Protocol::Ssh));
+        assert!(split_host_port("host:abc", 22).is_err());
+    }
+    
+    #[tokio::test]
+    async fn test_login_target_dispatches_to_custom_module() {
+        struct Fixed;
+        
+        #[async_trait::async_trait]
+        impl AuthModule for Fixed {
+            fn endpoint(&self) -> String {
+                "fixed:1".to_string()
+            }
+            
+            async fn try_credential(&self, username: &str, password: &str) -> Result<AttemptOutcome> {
+                Ok(AttemptOutcome {
+                    success: username == "admin" && password == "secret",
+                    ..Default::default()
+                })
+            }
+        }
+        
+        let target = LoginTarget::Module(Arc::new(Fixed));
+        
+        assert!(target.attempt("admin", "secret").await.unwrap().success);
+        assert!(!target.attempt("admin", "wrong").await.unwrap().success);
+        assert!(target.precheck().await.unwrap().is_none());
+        assert_eq!(target.endpoint(), "fixed:1");
+    }
+}
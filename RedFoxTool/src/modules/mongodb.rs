@@ -0,0 +1,106 @@
+//! وحدة MongoDB
+//! اختبار مصادقة SCRAM-SHA-1/SCRAM-SHA-256 مع فحص مسبق للوصول بدون مصادقة
+
+use anyhow::{Result, Context};
+use mongodb::bson::doc;
+use mongodb::error::ErrorKind;
+use mongodb::options::{ClientOptions, Credential};
+use mongodb::Client;
+
+use super::{split_host_port, AttemptOutcome, AuthModule, ServiceOptions};
+
+/// قاعدة بيانات المصادقة الافتراضية
+const AUTH_SOURCE: &str = "admin";
+
+/// رمز خطأ MongoDB لعدم التفويض
+const UNAUTHORIZED_CODE: i32 = 13;
+
+/// وحدة اختبار مصادقة MongoDB
+pub struct MongoModule {
+    host: String,
+    port: u16,
+    options: ServiceOptions,
+}
+
+impl MongoModule {
+    /// إنشاء وحدة MongoDB لهدف (host:port أو mongodb://host:port)
+    pub fn new(target: &str, options: &ServiceOptions) -> Result<Self> {
+        let (host, port) = split_host_port(target, 27017).context("هدف MongoDB غير صالح")?;
+        
+        Ok(Self {
+            host,
+            port,
+            options: options.clone(),
+        })
+    }
+    
+    /// عنوان الهدف
+    pub fn address(&self) -> String {
+        format!("{}:{}", self.host, self.port)
+    }
+    
+    /// إعدادات العميل (اتصال مباشر بدون اكتشاف المجموعة المتماثلة)
+    async fn client_options(&self, credential: Option<Credential>) -> Result<ClientOptions> {
+        let uri = format!("mongodb://{}/?directConnection=true", self.address());
+        let mut options = ClientOptions::parse(&uri)
+            .await
+            .context("فشل في تحليل عنوان MongoDB")?;
+        
+        options.connect_timeout = Some(self.options.timeout);
+        options.server_selection_timeout = Some(self.options.timeout);
+        options.tls = None;
+        options.credential = credential;
+        
+        Ok(options)
+    }
+    
+    /// فحص الوصول بدون مصادقة (نتيجة حرجة بحد ذاتها)
+    pub async fn precheck(&self) -> Result<Option<AttemptOutcome>> {
+        let client = Client::with_options(self.client_options(None).await?)?;
+        
+        match client.list_database_names().await {
+            Ok(databases) => Ok(Some(AttemptOutcome {
+                success: true,
+                banner: Some(format!("قواعد البيانات: {}", databases.join(", "))),
+                ..Default::default()
+            })),
+            Err(e) if matches!(*e.kind, ErrorKind::Command(ref c) if c.code == UNAUTHORIZED_CODE) => Ok(None),
+            Err(e) => Err(e).context("فشل في فحص الوصول بدون مصادقة"),
+        }
+    }
+    
+    /// تجربة بيانات اعتماد واحدة (يتفاوض العميل على SCRAM-SHA-256 أو SCRAM-SHA-1)
+    pub async fn attempt(&self, username: &str, password: &str) -> Result<AttemptOutcome> {
+        let credential = Credential::builder()
+            .username(username.to_string())
+            .password(password.to_string())
+            .source(AUTH_SOURCE.to_string())
+            .build();
+        
+        let client = Client::with_options(self.client_options(Some(credential)).await?)?;
+        
+        match client.database(AUTH_SOURCE).run_command(doc! { "ping": 1 }).await {
+            Ok(_) => Ok(AttemptOutcome {
+                success: true,
+                ..Default::default()
+            }),
+            Err(e) if matches!(*e.kind, ErrorKind::Authentication { .. }) => Ok(AttemptOutcome::default()),
+            Err(e) => Err(e).context(format!("فشل في الاتصال بـ MongoDB: {}", self.address())),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl AuthModule for MongoModule {
+    fn endpoint(&self) -> String {
+        self.address()
+    }
+    
+    async fn precheck(&self) -> Result<Option<AttemptOutcome>> {
+        MongoModule::precheck(self).await
+    }
+    
+    async fn try_credential(&self, username: &str, password: &str) -> Result<AttemptOutcome> {
+        self.attempt(username, password).await
+    }
+}
@@ -0,0 +1,81 @@
+//! إعدادات لوحات الاستضافة والبريد
+//! cPanel/WHM و Webmin و Plesk و Roundcube: المنافذ والمسارات وحقول النماذج وعلامات النجاح
+
+use super::presets::Preset;
+
+/// cPanel (المنفذ 2083، رد JSON مع login_only=1)
+pub const CPANEL: Preset = Preset {
+    name: "cpanel",
+    description: "cPanel (login_only JSON)",
+    login_path: "/login/?login_only=1",
+    default_port: Some(2083),
+    body_template: "user=^USER^&pass=^PASS^",
+    cookies: None,
+    csrf: None,
+    success_redirect: None,
+    success_match: Some(r#""status"\s*:\s*1"#),
+    failure_match: None,
+    success_header: None,
+    lockout_markers: &[("brute force protection", "cPHulk")],
+    probe: None,
+};
+
+/// WHM (نفس نموذج cPanel على المنفذ 2087)
+pub const WHM: Preset = Preset {
+    name: "whm",
+    description: "WHM (login_only JSON)",
+    default_port: Some(2087),
+    ..CPANEL
+};
+
+/// Webmin (المنفذ 10000، يتطلب كوكي testing)
+pub const WEBMIN: Preset = Preset {
+    name: "webmin",
+    description: "Webmin (session_login.cgi)",
+    login_path: "/session_login.cgi",
+    default_port: Some(10000),
+    body_template: "user=^USER^&pass=^PASS^",
+    cookies: Some("testing=1"),
+    csrf: None,
+    // النجاح يحول إلى الصفحة الرئيسية، والفشل يعيد صفحة الدخول بدون تحويل
+    success_redirect: Some("."),
+    success_match: None,
+    failure_match: Some("login failed"),
+    success_header: None,
+    lockout_markers: &[("blocked due to too many", "Webmin Failed Login Blocking")],
+    probe: None,
+};
+
+/// Plesk (المنفذ 8443)
+pub const PLESK: Preset = Preset {
+    name: "plesk",
+    description: "Plesk (login_up.php)",
+    login_path: "/login_up.php",
+    default_port: Some(8443),
+    body_template: "login_name=^USER^&passwd=^PASS^",
+    cookies: None,
+    csrf: None,
+    success_redirect: Some("/(?:admin|smb)/"),
+    success_match: None,
+    failure_match: Some("incorrect user ?name or password"),
+    success_header: None,
+    lockout_markers: &[("ip address has been blocked", "Plesk IP Ban")],
+    probe: None,
+};
+
+/// Roundcube (رمز _token من نموذج الدخول)
+pub const ROUNDCUBE: Preset = Preset {
+    name: "roundcube",
+    description: "Roundcube Webmail",
+    login_path: "/?_task=login",
+    default_port: None,
+    body_template: "_task=login&_action=login&_timezone=UTC&_user=^USER^&_pass=^PASS^&_token=^CSRF^",
+    cookies: None,
+    csrf: Some(("_token", None)),
+    success_redirect: Some("_task=mail"),
+    success_match: None,
+    failure_match: Some("login failed"),
+    success_header: None,
+    lockout_markers: &[],
+    probe: None,
+};
@@ -0,0 +1,141 @@
+//! إعدادات الأهداف الجاهزة
+//! سجل الأنظمة المعروفة (المنفذ، مسار الدخول، حقول النموذج، رموز CSRF، معايير النجاح، رسائل القفل)
+
+use anyhow::Result;
+
+use crate::matcher::SuccessCriteria;
+use super::{cms, panels, wordpress};
+
+/// فحص إضافي خاص بالإعداد الجاهز
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PresetProbe {
+    /// كشف XML-RPC system.multicall في ووردبريس
+    XmlRpcMulticall,
+}
+
+/// إعداد جاهز لنظام معروف
+#[derive(Debug)]
+pub struct Preset {
+    /// اسم الإعداد (قيمة --preset)
+    pub name: &'static str,
+    
+    /// وصف مختصر
+    pub description: &'static str,
+    
+    /// مسار صفحة تسجيل الدخول
+    pub login_path: &'static str,
+    
+    /// المنفذ الافتراضي (إن لم يحدده الرابط)
+    pub default_port: Option<u16>,
+    
+    /// قالب جسم النموذج (^USER^ و ^PASS^ و ^CSRF^)
+    pub body_template: &'static str,
+    
+    /// كوكيز ثابتة يتطلبها النموذج
+    pub cookies: Option<&'static str>,
+    
+    /// حقل رمز CSRF ومحدده الاختياري
+    pub csrf: Option<(&'static str, Option<&'static str>)>,
+    
+    /// تعبير نمطي لوجهة التحويل عند النجاح
+    pub success_redirect: Option<&'static str>,
+    
+    /// تعبير نمطي للمحتوى عند النجاح
+    pub success_match: Option<&'static str>,
+    
+    /// تعبير نمطي للمحتوى عند الفشل
+    pub failure_match: Option<&'static str>,
+    
+    /// ترويسة النجاح (NAME أو NAME:VALUE)
+    pub success_header: Option<&'static str>,
+    
+    /// رسائل القفل (العلامة بأحرف صغيرة، اسم الإضافة أو الآلية)
+    pub lockout_markers: &'static [(&'static str, &'static str)],
+    
+    /// فحص إضافي قبل التخمين
+    pub probe: Option<PresetProbe>,
+}
+
+/// جميع الإعدادات الجاهزة
+pub static PRESETS: &[&Preset] = &[
+    &wordpress::PRESET,
+    &cms::JOOMLA,
+    &cms::DRUPAL,
+    &cms::MAGENTO,
+    &panels::CPANEL,
+    &panels::WHM,
+    &panels::WEBMIN,
+    &panels::PLESK,
+    &panels::ROUNDCUBE,
+];
+
+/// البحث عن إعداد جاهز بالاسم
+pub fn find(name: &str) -> Result<&'static Preset> {
+    PRESETS
+        .iter()
+        .copied()
+        .find(|p| p.name.eq_ignore_ascii_case(name))
+        .ok_or_else(|| {
+            let names: Vec<&str> = PRESETS.iter().map(|p| p.name).collect();
+            anyhow::anyhow!("إعداد جاهز غير معروف: {} ({})", name, names.join(", "))
+        })
+}
+
+impl Preset {
+    /// رابط صفحة الدخول
+    ///
+    /// يُضاف المنفذ الافتراضي إن لم يُحدد، ويُضاف المسار إذا كان الرابط جذر الموقع فقط.
+    pub fn login_url(&self, base: &str) -> String {
+        let Ok(mut url) = url::Url::parse(base) else {
+            return base.to_string();
+        };
+        
+        if let (None, Some(port)) = (url.port(), self.default_port) {
+            let _ = url.set_port(Some(port));
+        }
+        
+        if url.path() == "/" && url.query().is_none() {
+            if let Ok(login) = url.join(self.login_path) {
+                url = login;
+            }
+        }
+        
+        url.to_string()
+    }
+    
+    /// معايير النجاح الخاصة بالإعداد
+    pub fn success_criteria(&self) -> SuccessCriteria {
+        SuccessCriteria {
+            body_match: self.success_match.map(str::to_string),
+            body_not_match: self.failure_match.map(str::to_string),
+            redirect_match: self.success_redirect.map(str::to_string),
+            header: self.success_header.map(str::to_string),
+            ..Default::default()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::csrf::CsrfExtractor;
+    use crate::matcher::SuccessMatcher;
+    
+    #[test]
+    fn test_find_and_login_url() {
+        let preset = find("WordPress").unwrap();
+        
+        assert_eq!(preset.login_url("https://blog.example.com/"), "https://blog.example.com/wp-login.php");
+        assert_eq!(preset.login_url("https://example.com/blog/wp-login.php"), "https://example.com/blog/wp-login.php");
+        assert!(find("unknown").is_err());
+        assert_eq!(find("whm").unwrap().login_url("https://host"), "https://host:2087/login/?login_only=1");
+        assert_eq!(find("webmin").unwrap().login_url("https://host:8443/"), "https://host:8443/session_login.cgi");
+        
+        for preset in PRESETS {
+            SuccessMatcher::compile(&preset.success_criteria()).unwrap();
+            if let Some((field, selector)) = preset.csrf {
+                CsrfExtractor::new(field, selector).unwrap();
+            }
+        }
+    }
+}
@@ -0,0 +1,232 @@
+//! وحدة SMTP
+//! اختبار SMTP AUTH (PLAIN, LOGIN, CRAM-MD5) مع STARTTLS أو TLS ضمني
+
+use anyhow::{Result, Context};
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+
+use super::tcp::{Reply, TextConnection};
+use super::{split_host_port, AttemptOutcome, AuthModule, ServiceOptions, TlsMode};
+use crate::ntlm::hmac_md5;
+
+/// المنفذ الافتراضي لـ SMTPS الضمني
+const IMPLICIT_SMTPS_PORT: u16 = 465;
+
+/// اسم العميل المرسل في EHLO
+const EHLO_NAME: &str = "redfox.local";
+
+/// آلية مصادقة SMTP
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SmtpMechanism {
+    /// AUTH PLAIN
+    Plain,
+    /// AUTH LOGIN
+    Login,
+    /// AUTH CRAM-MD5
+    CramMd5,
+}
+
+impl SmtpMechanism {
+    /// الاسم كما يعلنه الخادم
+    pub fn name(&self) -> &'static str {
+        match self {
+            SmtpMechanism::Plain => "PLAIN",
+            SmtpMechanism::Login => "LOGIN",
+            SmtpMechanism::CramMd5 => "CRAM-MD5",
+        }
+    }
+    
+    /// اختيار آلية من الآليات المعلنة (بترتيب التفضيل)
+    pub fn select(advertised: &[String]) -> Option<Self> {
+        [SmtpMechanism::Plain, SmtpMechanism::Login, SmtpMechanism::CramMd5]
+            .into_iter()
+            .find(|m| advertised.iter().any(|a| a.eq_ignore_ascii_case(m.name())))
+    }
+}
+
+/// وحدة اختبار SMTP AUTH
+pub struct SmtpModule {
+    host: String,
+    port: u16,
+    options: ServiceOptions,
+}
+
+impl SmtpModule {
+    /// إنشاء وحدة SMTP لهدف (host:port أو smtp://host:port)
+    pub fn new(target: &str, options: &ServiceOptions) -> Result<Self> {
+        let default_port = match options.tls {
+            TlsMode::Implicit => IMPLICIT_SMTPS_PORT,
+            _ => 25,
+        };
+        let (host, port) = split_host_port(target, default_port).context("هدف SMTP غير صالح")?;
+        
+        Ok(Self {
+            host,
+            port,
+            options: options.clone(),
+        })
+    }
+    
+    /// عنوان الهدف
+    pub fn address(&self) -> String {
+        format!("{}:{}", self.host, self.port)
+    }
+    
+    /// تجربة بيانات اعتماد واحدة (اتصال مستقل لكل محاولة)
+    pub async fn attempt(&self, username: &str, password: &str) -> Result<AttemptOutcome> {
+        let mut conn = TextConnection::connect(
+            &self.host,
+            self.port,
+            self.options.timeout,
+            self.options.tls == TlsMode::Implicit,
+        )
+        .await?;
+        
+        let greeting = conn.read_reply().await?;
+        if greeting.code != 220 {
+            return Err(anyhow::anyhow!("رد ترحيب SMTP غير متوقع: {} {}", greeting.code, greeting.text));
+        }
+        
+        let mut capabilities = ehlo(&mut conn).await?;
+        
+        // STARTTLS ثم إعادة EHLO للحصول على الآليات المتاحة بعد التشفير
+        if self.options.tls == TlsMode::Explicit {
+            let reply = conn.command("STARTTLS").await?;
+            if reply.code != 220 {
+                return Err(anyhow::anyhow!("الخادم لا يدعم STARTTLS: {} {}", reply.code, reply.text));
+            }
+            conn = conn.upgrade_tls().await?;
+            capabilities = ehlo(&mut conn).await.context("فشل EHLO بعد STARTTLS")?;
+        }
+        
+        let mechanisms = auth_mechanisms(&capabilities);
+        let banner = format!("{} [AUTH {}]", greeting.text, mechanisms.join(" "));
+        
+        let mechanism = SmtpMechanism::select(&mechanisms)
+            .ok_or_else(|| anyhow::anyhow!("لا توجد آلية AUTH مدعومة ({})", banner))?;
+        
+        let reply = authenticate(&mut conn, mechanism, username, password).await?;
+        let _ = conn.command("QUIT").await;
+        
+        Ok(AttemptOutcome {
+            success: reply.code == 235,
+            status_code: reply.code,
+            banner: Some(banner),
+            ..Default::default()
+        })
+    }
+}
+
+#[async_trait::async_trait]
+impl AuthModule for SmtpModule {
+    fn endpoint(&self) -> String {
+        self.address()
+    }
+    
+    async fn try_credential(&self, username: &str, password: &str) -> Result<AttemptOutcome> {
+        self.attempt(username, password).await
+    }
+}
+
+/// إرسال EHLO
+async fn ehlo(conn: &mut TextConnection) -> Result<Reply> {
+    let reply = conn.command(&format!("EHLO {}", EHLO_NAME)).await?;
+    if reply.code != 250 {
+        return Err(anyhow::anyhow!("رفض الخادم EHLO: {} {}", reply.code, reply.text));
+    }
+    Ok(reply)
+}
+
+/// استخراج آليات AUTH من رد EHLO (AUTH PLAIN LOGIN أو AUTH=PLAIN)
+fn auth_mechanisms(ehlo: &Reply) -> Vec<String> {
+    ehlo.lines()
+        .filter_map(|line| {
+            let upper = line.to_uppercase();
+            upper
+                .strip_prefix("AUTH ")
+                .or_else(|| upper.strip_prefix("AUTH="))
+                .map(|rest| rest.split_whitespace().map(str::to_string).collect::<Vec<_>>())
+        })
+        .flatten()
+        .fold(Vec::new(), |mut acc, m| {
+            if !acc.contains(&m) {
+                acc.push(m);
+            }
+            acc
+        })
+}
+
+/// تنفيذ المصادقة بالآلية المختارة وإرجاع الرد النهائي
+async fn authenticate(
+    conn: &mut TextConnection,
+    mechanism: SmtpMechanism,
+    username: &str,
+    password: &str,
+) -> Result<Reply> {
+    match mechanism {
+        SmtpMechanism::Plain => {
+            let token = BASE64.encode(format!("\0{}\0{}", username, password));
+            conn.command(&format!("AUTH PLAIN {}", token)).await
+        }
+        SmtpMechanism::Login => {
+            let reply = conn.command("AUTH LOGIN").await?;
+            if reply.code != 334 {
+                return Ok(reply);
+            }
+            let reply = conn.command(&BASE64.encode(username)).await?;
+            if reply.code != 334 {
+                return Ok(reply);
+            }
+            conn.command(&BASE64.encode(password)).await
+        }
+        SmtpMechanism::CramMd5 => {
+            let reply = conn.command("AUTH CRAM-MD5").await?;
+            if reply.code != 334 {
+                return Ok(reply);
+            }
+            let challenge = BASE64
+                .decode(reply.text.trim())
+                .context("تحدي CRAM-MD5 غير صالح")?;
+            conn.command(&cram_md5_response(username, password, &challenge)).await
+        }
+    }
+}
+
+/// رد CRAM-MD5: base64(user + " " + hex(HMAC-MD5(password, challenge)))
+fn cram_md5_response(username: &str, password: &str, challenge: &[u8]) -> String {
+    let digest: String = hmac_md5(password.as_bytes(), &[challenge])
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect();
+    
+    BASE64.encode(format!("{} {}", username, digest))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_cram_md5_rfc2195_vector() {
+        let response = cram_md5_response(
+            "tim",
+            "tanstaaftanstaaf",
+            b"<1896.697170952@postoffice.reston.mci.net>",
+        );
+        
+        assert_eq!(response, "dGltIGI5MTNhNjAyYzdlZGE3YTQ5NWI0ZTZlNzMzNGQzODkw");
+    }
+    
+    #[test]
+    fn test_auth_mechanisms_from_ehlo() {
+        let ehlo = Reply {
+            code: 250,
+            text: "mail.example.com\nAUTH LOGIN PLAIN\nAUTH=LOGIN\nSTARTTLS".to_string(),
+        };
+        
+        let mechanisms = auth_mechanisms(&ehlo);
+        
+        assert_eq!(mechanisms, vec!["LOGIN", "PLAIN"]);
+        assert_eq!(SmtpMechanism::select(&mechanisms), Some(SmtpMechanism::Plain));
+    }
+}
@@ -0,0 +1,566 @@
+//! وحدة SNMP
+//! تخمين سلاسل المجتمع (v1/v2c) وبيانات مستخدمي v3 (USM) عبر UDP مع إعادة المحاولة
+
+use anyhow::{Result, Context};
+use hmac::{Hmac, Mac};
+use md5::{Digest, Md5};
+use sha1::Sha1;
+use tokio::net::UdpSocket;
+use tokio::time::timeout;
+
+use super::{split_host_port, AttemptOutcome, AuthModule, ServiceOptions};
+
+/// معرف sysDescr.0 المستخدم في طلبات GET
+const SYS_DESCR_OID: &[u32] = &[1, 3, 6, 1, 2, 1, 1, 1, 0];
+
+/// معرف عداد usmStatsUnknownUserNames
+const USM_UNKNOWN_USER_OID: &[u32] = &[1, 3, 6, 1, 6, 3, 15, 1, 1, 3, 0];
+
+/// طول معاملات المصادقة في HMAC-96
+const AUTH_PARAMS_LEN: usize = 12;
+
+/// حجم البيانات المستخدم في توليد المفتاح من كلمة المرور (RFC 3414)
+const PASSWORD_TO_KEY_LEN: usize = 1_048_576;
+
+/// أقصى حجم لاستجابة UDP
+const MAX_DATAGRAM: usize = 65_507;
+
+/// وسوم BER المستخدمة
+const TAG_INTEGER: u8 = 0x02;
+const TAG_OCTET_STRING: u8 = 0x04;
+const TAG_NULL: u8 = 0x05;
+const TAG_OID: u8 = 0x06;
+const TAG_SEQUENCE: u8 = 0x30;
+const TAG_GET_REQUEST: u8 = 0xA0;
+const TAG_GET_RESPONSE: u8 = 0xA2;
+const TAG_REPORT: u8 = 0xA8;
+
+/// إصدار SNMP
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SnmpVersion {
+    /// SNMPv1 (سلسلة مجتمع)
+    V1,
+    /// SNMPv2c (سلسلة مجتمع)
+    #[default]
+    V2c,
+    /// SNMPv3 (مستخدم وكلمة مرور، authNoPriv)
+    V3,
+}
+
+impl std::str::FromStr for SnmpVersion {
+    type Err = anyhow::Error;
+    
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "1" | "v1" => Ok(SnmpVersion::V1),
+            "2c" | "v2c" => Ok(SnmpVersion::V2c),
+            "3" | "v3" => Ok(SnmpVersion::V3),
+            _ => Err(anyhow::anyhow!("إصدار SNMP غير مدعوم: {} (1, 2c, 3)", s)),
+        }
+    }
+}
+
+impl std::fmt::Display for SnmpVersion {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            SnmpVersion::V1 => "1",
+            SnmpVersion::V2c => "2c",
+            SnmpVersion::V3 => "3",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// بروتوكول مصادقة SNMPv3
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SnmpAuthProtocol {
+    /// HMAC-MD5-96
+    Md5,
+    /// HMAC-SHA-96
+    #[default]
+    Sha,
+}
+
+impl std::str::FromStr for SnmpAuthProtocol {
+    type Err = anyhow::Error;
+    
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "md5" => Ok(SnmpAuthProtocol::Md5),
+            "sha" | "sha1" => Ok(SnmpAuthProtocol::Sha),
+            _ => Err(anyhow::anyhow!("بروتوكول مصادقة SNMP غير مدعوم: {} (md5, sha)", s)),
+        }
+    }
+}
+
+impl std::fmt::Display for SnmpAuthProtocol {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            SnmpAuthProtocol::Md5 => "md5",
+            SnmpAuthProtocol::Sha => "sha",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// معلمات المحرك المرجعي لـ SNMPv3 (من رد الاكتشاف)
+#[derive(Debug, Clone)]
+struct EngineInfo {
+    id: Vec<u8>,
+    boots: i64,
+    time: i64,
+}
+
+/// وحدة اختبار SNMP
+pub struct SnmpModule {
+    host: String,
+    port: u16,
+    options: ServiceOptions,
+}
+
+impl SnmpModule {
+    /// إنشاء وحدة SNMP لهدف (host:port أو snmp://host:port)
+    pub fn new(target: &str, options: &ServiceOptions) -> Result<Self> {
+        let (host, port) = split_host_port(target, 161).context("هدف SNMP غير صالح")?;
+        
+        Ok(Self {
+            host,
+            port,
+            options: options.clone(),
+        })
+    }
+    
+    /// عنوان الهدف
+    pub fn address(&self) -> String {
+        format!("{}:{}", self.host, self.port)
+    }
+    
+    /// تجربة بيانات اعتماد واحدة
+    ///
+    /// في v1/v2c تُعامل كلمة المرور كسلسلة مجتمع ويُتجاهل اسم المستخدم.
+    pub async fn attempt(&self, username: &str, password: &str) -> Result<AttemptOutcome> {
+        match self.options.snmp_version {
+            SnmpVersion::V1 | SnmpVersion::V2c => self.attempt_community(password).await,
+            SnmpVersion::V3 => self.attempt_usm(username, password).await,
+        }
+    }
+    
+    /// تجربة سلسلة مجتمع (الوكيل يتجاهل السلاسل الخاطئة بصمت)
+    async fn attempt_community(&self, community: &str) -> Result<AttemptOutcome> {
+        let version = if self.options.snmp_version == SnmpVersion::V1 { 0 } else { 1 };
+        let request = sequence(&[
+            integer(version),
+            octet_string(community.as_bytes()),
+            get_request(rand::random::<u16>().into(), &[SYS_DESCR_OID]),
+        ]);
+        
+        let Some(response) = self.exchange(&request).await? else {
+            return Ok(AttemptOutcome::default());
+        };
+        
+        let message = Tlv::parse(&response)?.0.children()?;
+        let pdu = message.get(2).ok_or_else(|| anyhow::anyhow!("رد SNMP غير مكتمل"))?;
+        
+        Ok(AttemptOutcome {
+            success: pdu.tag == TAG_GET_RESPONSE,
+            banner: first_varbind_value(pdu),
+            ..Default::default()
+        })
+    }
+    
+    /// تجربة مستخدم v3 بمصادقة authNoPriv
+    async fn attempt_usm(&self, username: &str, password: &str) -> Result<AttemptOutcome> {
+        let engine = self.discover_engine().await?;
+        let key = localize_key(self.options.snmp_auth, password.as_bytes(), &engine.id);
+        
+        let placeholder = [0u8; AUTH_PARAMS_LEN];
+        let mut request = v3_message(&engine, username.as_bytes(), &placeholder, &[SYS_DESCR_OID]);
+        
+        // حساب HMAC على الرسالة كاملة ووضعه مكان المعاملات الصفرية
+        let offset = find_auth_params(&request)
+            .ok_or_else(|| anyhow::anyhow!("تعذر تحديد موقع معاملات المصادقة"))?;
+        let digest = hmac_96(self.options.snmp_auth, &key, &request);
+        request[offset..offset + AUTH_PARAMS_LEN].copy_from_slice(&digest);
+        
+        let Some(response) = self.exchange(&request).await? else {
+            return Err(anyhow::anyhow!("لا يوجد رد من وكيل SNMPv3"));
+        };
+        
+        let pdu = v3_pdu(&response)?;
+        match pdu.tag {
+            TAG_GET_RESPONSE => Ok(AttemptOutcome {
+                success: true,
+                banner: first_varbind_value(&pdu),
+                ..Default::default()
+            }),
+            TAG_REPORT if first_varbind_oid(&pdu).as_deref() == Some(USM_UNKNOWN_USER_OID) => {
+                Ok(AttemptOutcome {
+                    banner: Some("مستخدم غير معروف".to_string()),
+                    ..Default::default()
+                })
+            }
+            // usmStatsWrongDigests وغيرها: كلمة مرور خاطئة
+            _ => Ok(AttemptOutcome::default()),
+        }
+    }
+    
+    /// اكتشاف معرف المحرك وعداداته (طلب بدون مستخدم)
+    async fn discover_engine(&self) -> Result<EngineInfo> {
+        let empty = EngineInfo {
+            id: Vec::new(),
+            boots: 0,
+            time: 0,
+        };
+        let request = v3_message(&empty, b"", &[], &[]);
+        
+        let response = self
+            .exchange(&request)
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("لا يوجد رد على اكتشاف محرك SNMPv3"))?;
+        
+        let message = Tlv::parse(&response)?.0.children()?;
+        let security = message
+            .get(2)
+            .ok_or_else(|| anyhow::anyhow!("رد SNMPv3 بدون معاملات أمان"))?;
+        let params = Tlv::parse(&security.value)?.0.children()?;
+        
+        match params.as_slice() {
+            [id, boots, time, ..] => Ok(EngineInfo {
+                id: id.value.clone(),
+                boots: boots.as_integer(),
+                time: time.as_integer(),
+            }),
+            _ => Err(anyhow::anyhow!("معاملات أمان SNMPv3 غير صالحة")),
+        }
+    }
+    
+    /// إرسال طلب UDP مع إعادة المحاولة عند انتهاء المهلة (None عند عدم الرد)
+    async fn exchange(&self, request: &[u8]) -> Result<Option<Vec<u8>>> {
+        let bind = if self.host.contains(':') { "[::]:0" } else { "0.0.0.0:0" };
+        let socket = UdpSocket::bind(bind).await.context("فشل في فتح مقبس UDP")?;
+        socket
+            .connect((self.host.as_str(), self.port))
+            .await
+            .context(format!("فشل في الاتصال بـ {}", self.address()))?;
+        
+        let mut buffer = vec![0u8; MAX_DATAGRAM];
+        for _ in 0..=self.options.udp_retries {
+            socket.send(request).await.context("فشل في إرسال طلب SNMP")?;
+            
+            match timeout(self.options.udp_timeout, socket.recv(&mut buffer)).await {
+                Ok(Ok(len)) => return Ok(Some(buffer[..len].to_vec())),
+                Ok(Err(e)) => return Err(e).context("فشل في استقبال رد SNMP"),
+                Err(_) => continue,
+            }
+        }
+        
+        Ok(None)
+    }
+}
+
+#[async_trait::async_trait]
+impl AuthModule for SnmpModule {
+    fn endpoint(&self) -> String {
+        self.address()
+    }
+    
+    async fn try_credential(&self, username: &str, password: &str) -> Result<AttemptOutcome> {
+        self.attempt(username, password).await
+    }
+}
+
+/// بناء رسالة SNMPv3 (authNoPriv عند وجود معاملات مصادقة، وإلا اكتشاف)
+fn v3_message(engine: &EngineInfo, username: &[u8], auth_params: &[u8], oids: &[&[u32]]) -> Vec<u8> {
+    let msg_id = i64::from(rand::random::<u16>());
+    // reportable دائمًا، و auth عند وجود معاملات مصادقة
+    let flags = if auth_params.is_empty() { 0x04 } else { 0x05 };
+    
+    let global = sequence(&[
+        integer(msg_id),
+        integer(MAX_DATAGRAM as i64),
+        octet_string(&[flags]),
+        integer(3),
+    ]);
+    let security = sequence(&[
+        octet_string(&engine.id),
+        integer(engine.boots),
+        integer(engine.time),
+        octet_string(username),
+        octet_string(auth_params),
+        octet_string(&[]),
+    ]);
+    let scoped_pdu = sequence(&[
+        octet_string(&engine.id),
+        octet_string(&[]),
+        get_request(msg_id, oids),
+    ]);
+    
+    sequence(&[integer(3), global, octet_string(&security), scoped_pdu])
+}
+
+/// استخراج PDU من رد SNMPv3 (بدون تشفير)
+fn v3_pdu(response: &[u8]) -> Result<Tlv> {
+    let message = Tlv::parse(response)?.0.children()?;
+    let scoped = message
+        .get(3)
+        .ok_or_else(|| anyhow::anyhow!("رد SNMPv3 بدون PDU"))?;
+    
+    scoped
+        .children()?
+        .into_iter()
+        .nth(2)
+        .ok_or_else(|| anyhow::anyhow!("رد SNMPv3 بدون PDU"))
+}
+
+/// موقع معاملات المصادقة الصفرية في الرسالة
+fn find_auth_params(message: &[u8]) -> Option<usize> {
+    let mut pattern = vec![TAG_OCTET_STRING, AUTH_PARAMS_LEN as u8];
+    pattern.extend_from_slice(&[0u8; AUTH_PARAMS_LEN]);
+    
+    message
+        .windows(pattern.len())
+        .position(|w| w == pattern.as_slice())
+        .map(|pos| pos + 2)
+}
+
+/// توليد المفتاح المحلي من كلمة المرور ومعرف المحرك (RFC 3414 A.2)
+fn localize_key(protocol: SnmpAuthProtocol, password: &[u8], engine_id: &[u8]) -> Vec<u8> {
+    fn derive<D: Digest>(password: &[u8], engine_id: &[u8]) -> Vec<u8> {
+        let mut hasher = D::new();
+        if !password.is_empty() {
+            let expanded: Vec<u8> = password.iter().copied().cycle().take(PASSWORD_TO_KEY_LEN).collect();
+            hasher.update(&expanded);
+        }
+        let ku = hasher.finalize();
+        
+        let mut hasher = D::new();
+        hasher.update(&ku);
+        hasher.update(engine_id);
+        hasher.update(&ku);
+        hasher.finalize().to_vec()
+    }
+    
+    match protocol {
+        SnmpAuthProtocol::Md5 => derive::<Md5>(password, engine_id),
+        SnmpAuthProtocol::Sha => derive::<Sha1>(password, engine_id),
+    }
+}
+
+/// HMAC-96 (أول 12 بايت من HMAC)
+fn hmac_96(protocol: SnmpAuthProtocol, key: &[u8], message: &[u8]) -> Vec<u8> {
+    let digest = match protocol {
+        SnmpAuthProtocol::Md5 => {
+            #[allow(clippy::expect_used)] // لا يفشل: HMAC يقبل مفاتيح بأي طول
+            let mut mac = Hmac::<Md5>::new_from_slice(key).expect("HMAC يقبل مفاتيح بأي طول");
+            mac.update(message);
+            mac.finalize().into_bytes().to_vec()
+        }
+        SnmpAuthProtocol::Sha => {
+            #[allow(clippy::expect_used)] // لا يفشل: HMAC يقبل مفاتيح بأي طول
+            let mut mac = Hmac::<Sha1>::new_from_slice(key).expect("HMAC يقبل مفاتيح بأي طول");
+            mac.update(message);
+            mac.finalize().into_bytes().to_vec()
+        }
+    };
+    
+    digest[..AUTH_PARAMS_LEN].to_vec()
+}
+
+/// طلب GET لقائمة معرفات (قيم NULL)
+fn get_request(request_id: i64, oids: &[&[u32]]) -> Vec<u8> {
+    let varbinds: Vec<Vec<u8>> = oids
+        .iter()
+        .map(|oid| sequence(&[object_id(oid), tlv(TAG_NULL, &[])]))
+        .collect();
+    
+    tlv(
+        TAG_GET_REQUEST,
+        &[integer(request_id), integer(0), integer(0), sequence(&varbinds)].concat(),
+    )
+}
+
+/// قيمة أول متغير في PDU كنص (مثل sysDescr)
+fn first_varbind_value(pdu: &Tlv) -> Option<String> {
+    first_varbind(pdu)
+        .and_then(|vb| vb.get(1).cloned())
+        .filter(|value| value.tag == TAG_OCTET_STRING)
+        .map(|value| String::from_utf8_lossy(&value.value).trim().to_string())
+}
+
+/// معرف أول متغير في PDU
+fn first_varbind_oid(pdu: &Tlv) -> Option<Vec<u32>> {
+    first_varbind(pdu)
+        .and_then(|vb| vb.first().cloned())
+        .filter(|oid| oid.tag == TAG_OID)
+        .map(|oid| decode_oid(&oid.value))
+}
+
+/// عناصر أول متغير (المعرف والقيمة)
+fn first_varbind(pdu: &Tlv) -> Option<Vec<Tlv>> {
+    let fields = pdu.children().ok()?;
+    let varbinds = fields.get(3)?.children().ok()?;
+    varbinds.first()?.children().ok()
+}
+
+/// عنصر BER (وسم وقيمة)
+#[derive(Debug, Clone)]
+struct Tlv {
+    tag: u8,
+    value: Vec<u8>,
+}
+
+impl Tlv {
+    /// تحليل عنصر واحد وإرجاع الباقي
+    fn parse(data: &[u8]) -> Result<(Self, &[u8])> {
+        let (&tag, rest) = data.split_first().ok_or_else(|| anyhow::anyhow!("BER: بيانات فارغة"))?;
+        let (&first, rest) = rest.split_first().ok_or_else(|| anyhow::anyhow!("BER: طول مفقود"))?;
+        
+        let (len, rest) = if first & 0x80 == 0 {
+            (first as usize, rest)
+        } else {
+            let count = (first & 0x7F) as usize;
+            if count == 0 || count > 4 || rest.len() < count {
+                return Err(anyhow::anyhow!("BER: طول غير صالح"));
+            }
+            let len = rest[..count].iter().fold(0usize, |acc, b| (acc << 8) | *b as usize);
+            (len, &rest[count..])
+        };
+        
+        if rest.len() < len {
+            return Err(anyhow::anyhow!("BER: بيانات مقتطعة"));
+        }
+        
+        Ok((
+            Self {
+                tag,
+                value: rest[..len].to_vec(),
+            },
+            &rest[len..],
+        ))
+    }
+    
+    /// العناصر الفرعية لعنصر مركب (تسلسل أو PDU)
+    fn children(&self) -> Result<Vec<Self>> {
+        let mut items = Vec::new();
+        let mut rest = self.value.as_slice();
+        while !rest.is_empty() {
+            let (item, next) = Self::parse(rest)?;
+            items.push(item);
+            rest = next;
+        }
+        Ok(items)
+    }
+    
+    /// قيمة عدد صحيح
+    fn as_integer(&self) -> i64 {
+        let negative = self.value.first().is_some_and(|b| b & 0x80 != 0);
+        self.value
+            .iter()
+            .fold(if negative { -1 } else { 0 }, |acc, b| (acc << 8) | i64::from(*b))
+    }
+}
+
+/// ترميز عنصر BER
+fn tlv(tag: u8, content: &[u8]) -> Vec<u8> {
+    let mut out = vec![tag];
+    let len = content.len();
+    
+    if len < 0x80 {
+        out.push(len as u8);
+    } else {
+        let bytes: Vec<u8> = len.to_be_bytes().into_iter().skip_while(|b| *b == 0).collect();
+        out.push(0x80 | bytes.len() as u8);
+        out.extend(bytes);
+    }
+    
+    out.extend_from_slice(content);
+    out
+}
+
+/// ترميز تسلسل
+fn sequence(items: &[Vec<u8>]) -> Vec<u8> {
+    tlv(TAG_SEQUENCE, &items.concat())
+}
+
+/// ترميز عدد صحيح (أقصر تمثيل بالمتمم الثنائي)
+fn integer(value: i64) -> Vec<u8> {
+    let bytes = value.to_be_bytes();
+    let mut start = 0;
+    while start < 7
+        && ((bytes[start] == 0x00 && bytes[start + 1] & 0x80 == 0)
+            || (bytes[start] == 0xFF && bytes[start + 1] & 0x80 != 0))
+    {
+        start += 1;
+    }
+    tlv(TAG_INTEGER, &bytes[start..])
+}
+
+/// ترميز سلسلة ثمانية
+fn octet_string(value: &[u8]) -> Vec<u8> {
+    tlv(TAG_OCTET_STRING, value)
+}
+
+/// ترميز معرف كائن
+fn object_id(oid: &[u32]) -> Vec<u8> {
+    let mut content = vec![(oid[0] * 40 + oid[1]) as u8];
+    
+    for &arc in &oid[2..] {
+        let mut chunk = vec![(arc & 0x7F) as u8];
+        let mut rest = arc >> 7;
+        while rest > 0 {
+            chunk.push(0x80 | (rest & 0x7F) as u8);
+            rest >>= 7;
+        }
+        content.extend(chunk.into_iter().rev());
+    }
+    
+    tlv(TAG_OID, &content)
+}
+
+/// فك ترميز معرف كائن
+fn decode_oid(content: &[u8]) -> Vec<u32> {
+    let Some((&first, rest)) = content.split_first() else {
+        return Vec::new();
+    };
+    
+    let mut oid = vec![u32::from(first / 40), u32::from(first % 40)];
+    let mut arc = 0u32;
+    for &b in rest {
+        arc = (arc << 7) | u32::from(b & 0x7F);
+        if b & 0x80 == 0 {
+            oid.push(arc);
+            arc = 0;
+        }
+    }
+    oid
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_localize_key_rfc3414_vectors() {
+        let engine_id = [0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 2];
+        
+        let md5 = localize_key(SnmpAuthProtocol::Md5, b"maplesyrup", &engine_id);
+        let sha = localize_key(SnmpAuthProtocol::Sha, b"maplesyrup", &engine_id);
+        
+        let hex = |b: &[u8]| b.iter().map(|x| format!("{:02x}", x)).collect::<String>();
+        assert_eq!(hex(&md5), "526f5eed9fcce26f8964c2930787d82b");
+        assert_eq!(hex(&sha), "6695febc9288e36282235fc7151f128497b38f3f");
+    }
+    
+    #[test]
+    fn test_ber_roundtrip() {
+        let encoded = object_id(SYS_DESCR_OID);
+        let (parsed, rest) = Tlv::parse(&encoded).unwrap();
+        
+        assert!(rest.is_empty());
+        assert_eq!(decode_oid(&parsed.value), SYS_DESCR_OID);
+        assert_eq!(integer(128), vec![TAG_INTEGER, 2, 0x00, 0x80]);
+        assert_eq!(Tlv::parse(&integer(-2)).unwrap().0.as_integer(), -2);
+    }
+}
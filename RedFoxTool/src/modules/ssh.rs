@@ -0,0 +1,92 @@
+//! وحدة SSH
+//! اختبار مصادقة كلمة المرور على خوادم SSH باستخدام russh
+
+use std::sync::Arc;
+use std::time::Duration;
+use anyhow::{Result, Context};
+use tokio::time::timeout;
+
+use super::{split_host_port, AttemptOutcome, AuthModule, ServiceOptions};
+
+/// معالج العميل (يقبل أي مفتاح خادم، فالهدف هو اختبار بيانات الاعتماد فقط)
+struct AcceptAnyKey;
+
+#[async_trait::async_trait]
+impl russh::client::Handler for AcceptAnyKey {
+    type Error = russh::Error;
+    
+    async fn check_server_key(
+        &mut self,
+        _server_public_key: &russh::keys::PublicKey,
+    ) -> Result<bool, Self::Error> {
+        Ok(true)
+    }
+}
+
+/// وحدة اختبار تسجيل الدخول عبر SSH
+pub struct SshModule {
+    host: String,
+    port: u16,
+    timeout: Duration,
+    config: Arc<russh::client::Config>,
+}
+
+impl SshModule {
+    /// إنشاء وحدة SSH لهدف (host:port أو ssh://host:port)
+    pub fn new(target: &str, options: &ServiceOptions) -> Result<Self> {
+        let (host, port) = split_host_port(target, 22).context("هدف SSH غير صالح")?;
+        
+        let config = russh::client::Config {
+            inactivity_timeout: Some(options.timeout),
+            ..Default::default()
+        };
+        
+        Ok(Self {
+            host,
+            port,
+            timeout: options.timeout,
+            config: Arc::new(config),
+        })
+    }
+    
+    /// عنوان الهدف
+    pub fn address(&self) -> String {
+        format!("{}:{}", self.host, self.port)
+    }
+    
+    /// تجربة بيانات اعتماد واحدة (اتصال مستقل لكل محاولة)
+    pub async fn attempt(&self, username: &str, password: &str) -> Result<AttemptOutcome> {
+        let mut session = timeout(
+            self.timeout,
+            russh::client::connect(Arc::clone(&self.config), (self.host.as_str(), self.port), AcceptAnyKey),
+        )
+        .await
+        .context("مهلة الاتصال بخادم SSH انتهت")?
+        .context(format!("فشل في الاتصال بخادم SSH: {}", self.address()))?;
+        
+        let authenticated = timeout(self.timeout, session.authenticate_password(username, password))
+            .await
+            .context("مهلة مصادقة SSH انتهت")?
+            .context("فشل في مصادقة SSH")?;
+        
+        let _ = session
+            .disconnect(russh::Disconnect::ByApplication, "", "en")
+            .await;
+        
+        Ok(AttemptOutcome {
+            success: authenticated,
+            ..Default::default()
+        })
+    }
+}
+
+#[async_trait::async_trait]
+impl AuthModule for SshModule {
+    fn endpoint(&self) -> String {
+        self.address()
+    }
+    
+    async fn try_credential(&self, username: &str, password: &str) -> Result<AttemptOutcome> {
+        self.attempt(username, password).await
+    }
+}
@@ -0,0 +1,144 @@
+//! اتصالات نصية فوق TCP
+//! قراءة ردود الخوادم النصية (FTP/SMTP) مع دعم TLS الضمني والترقية عبر AUTH TLS/STARTTLS
+
+use std::time::Duration;
+use anyhow::{Result, Context};
+use tokio::io::{AsyncBufReadExt, AsyncRead, AsyncWrite, AsyncWriteExt, BufReader};
+use tokio::net::TcpStream;
+use tokio::time::timeout;
+
+/// تيار قابل للقراءة والكتابة (TCP عادي أو TLS)
+trait AsyncStream: AsyncRead + AsyncWrite + Unpin + Send {}
+
+impl<T: AsyncRead + AsyncWrite + Unpin + Send> AsyncStream for T {}
+
+/// رد خادم نصي (رمز من ثلاثة أرقام ونص)
+#[derive(Debug, Clone)]
+pub struct Reply {
+    /// رمز الرد
+    pub code: u16,
+    
+    /// نص الرد (جميع الأسطر في الردود متعددة الأسطر)
+    pub text: String,
+}
+
+impl Reply {
+    /// هل الرد إيجابي (2xx أو 3xx)؟
+    pub fn is_positive(&self) -> bool {
+        (200..400).contains(&self.code)
+    }
+    
+    /// أسطر الرد بدون الرموز
+    pub fn lines(&self) -> impl Iterator<Item = &str> {
+        self.text.lines()
+    }
+}
+
+/// اتصال نصي بخادم (سطر لكل أمر)
+pub struct TextConnection {
+    reader: BufReader<Box<dyn AsyncStream>>,
+    host: String,
+    timeout: Duration,
+}
+
+impl TextConnection {
+    /// فتح اتصال (مع TLS ضمني اختياري)
+    pub async fn connect(host: &str, port: u16, io_timeout: Duration, implicit_tls: bool) -> Result<Self> {
+        let tcp = timeout(io_timeout, TcpStream::connect((host, port)))
+            .await
+            .context(format!("مهلة الاتصال بـ {}:{} انتهت", host, port))?
+            .context(format!("فشل في الاتصال بـ {}:{}", host, port))?;
+        
+        let stream: Box<dyn AsyncStream> = if implicit_tls {
+            Box::new(tls_handshake(host, tcp, io_timeout).await?)
+        } else {
+            Box::new(tcp)
+        };
+        
+        Ok(Self {
+            reader: BufReader::new(stream),
+            host: host.to_string(),
+            timeout: io_timeout,
+        })
+    }
+    
+    /// ترقية الاتصال إلى TLS بعد قبول الخادم (AUTH TLS أو STARTTLS)
+    pub async fn upgrade_tls(self) -> Result<Self> {
+        let stream = self.reader.into_inner();
+        let tls = tls_handshake(&self.host, stream, self.timeout).await?;
+        
+        Ok(Self {
+            reader: BufReader::new(Box::new(tls)),
+            host: self.host,
+            timeout: self.timeout,
+        })
+    }
+    
+    /// قراءة رد كامل (يدعم الردود متعددة الأسطر "250-...")
+    pub async fn read_reply(&mut self) -> Result<Reply> {
+        let mut text = Vec::new();
+        
+        loop {
+            let mut line = String::new();
+            let read = timeout(self.timeout, self.reader.read_line(&mut line))
+                .await
+                .context("مهلة انتظار رد الخادم انتهت")?
+                .context("فشل في قراءة رد الخادم")?;
+            
+            if read == 0 {
+                return Err(anyhow::anyhow!("أغلق الخادم الاتصال"));
+            }
+            
+            let line = line.trim_end();
+            let code = line
+                .get(..3)
+                .and_then(|c| c.parse::<u16>().ok())
+                .ok_or_else(|| anyhow::anyhow!("رد غير صالح من الخادم: {}", line))?;
+            
+            text.push(line.get(4..).unwrap_or_default().to_string());
+            
+            // السطر الأخير: الرمز متبوع بمسافة
+            if line.as_bytes().get(3) != Some(&b'-') {
+                return Ok(Reply {
+                    code,
+                    text: text.join("\n"),
+                });
+            }
+        }
+    }
+    
+    /// إرسال أمر وقراءة رده
+    pub async fn command(&mut self, line: &str) -> Result<Reply> {
+        if line.contains(['\r', '\n']) {
+            return Err(anyhow::anyhow!("الأمر يحتوي على محارف سطر جديد"));
+        }
+        
+        let stream = self.reader.get_mut();
+        timeout(self.timeout, async {
+            stream.write_all(format!("{}\r\n", line).as_bytes()).await?;
+            stream.flush().await
+        })
+        .await
+        .context("مهلة إرسال الأمر انتهت")?
+        .context("فشل في إرسال الأمر")?;
+        
+        self.read_reply().await
+    }
+}
+
+/// مصافحة TLS (بدون التحقق من الشهادة، فالهدف اختبار بيانات الاعتماد فقط)
+async fn tls_handshake<S>(host: &str, stream: S, io_timeout: Duration) -> Result<tokio_native_tls::TlsStream<S>>
+where
+    S: AsyncRead + AsyncWrite + Unpin,
+{
+    let connector = native_tls::TlsConnector::builder()
+        .danger_accept_invalid_certs(true)
+        .danger_accept_invalid_hostnames(true)
+        .build()
+        .context("فشل في إنشاء موصل TLS")?;
+    
+    timeout(io_timeout, tokio_native_tls::TlsConnector::from(connector).connect(host, stream))
+        .await
+        .context("مهلة مصافحة TLS انتهت")?
+        .context("فشل في مصافحة TLS")
+}
@@ -0,0 +1,81 @@
+//! إعداد ووردبريس
+//! نموذج wp-login.php، وكشف تضخيم XML-RPC (system.multicall)، ورسائل إضافات القفل
+
+use std::time::Duration;
+use anyhow::{Result, Context};
+use reqwest::header::CONTENT_TYPE;
+
+use super::presets::{Preset, PresetProbe};
+
+/// إعداد ووردبريس الجاهز
+pub const PRESET: Preset = Preset {
+    name: "wordpress",
+    description: "WordPress (wp-login.php)",
+    login_path: "/wp-login.php",
+    default_port: None,
+    body_template: "log=^USER^&pwd=^PASS^&wp-submit=Log+In",
+    cookies: None,
+    csrf: None,
+    // النجاح يحول إلى لوحة التحكم، والفشل يعيد النموذج مع login_error
+    success_redirect: Some("wp-admin"),
+    success_match: None,
+    failure_match: Some(r#"id=["']login_error"#),
+    success_header: None,
+    lockout_markers: LOCKOUT_MARKERS,
+    probe: Some(PresetProbe::XmlRpcMulticall),
+};
+
+/// رسائل إضافات الحماية من التخمين في ووردبريس
+const LOCKOUT_MARKERS: &[(&str, &str)] = &[
+    ("too many failed login attempts", "Limit Login Attempts"),
+    ("generated by wordfence", "Wordfence"),
+    ("you have been locked out", "Solid Security (iThemes)"),
+    ("access from your ip address has been blocked", "All In One WP Security"),
+    ("exceeded maximum login retries", "Loginizer"),
+];
+
+/// طلب قائمة الطرق المتاحة عبر XML-RPC
+const LIST_METHODS_REQUEST: &str = "<?xml version=\"1.0\"?>\
+<methodCall><methodName>system.listMethods</methodName><params></params></methodCall>";
+
+/// رابط xmlrpc.php من رابط صفحة الدخول
+fn xmlrpc_url(login_url: &str) -> String {
+    match login_url.rsplit_once("/wp-login.php") {
+        Some((root, _)) => format!("{}/xmlrpc.php", root),
+        None => format!("{}/xmlrpc.php", login_url.trim_end_matches('/')),
+    }
+}
+
+/// هل يتيح الموقع system.multicall (تجربة مئات كلمات المرور في طلب واحد)؟
+pub async fn xmlrpc_multicall_available(login_url: &str, timeout_secs: u64) -> Result<bool> {
+    let client = reqwest::Client::builder()
+        .timeout(Duration::from_secs(timeout_secs))
+        .build()
+        .context("فشل في بناء عميل HTTP")?;
+    
+    let response = client
+        .post(xmlrpc_url(login_url))
+        .header(CONTENT_TYPE, "text/xml")
+        .body(LIST_METHODS_REQUEST)
+        .send()
+        .await
+        .context("فشل في الاتصال بـ xmlrpc.php")?;
+    
+    if !response.status().is_success() {
+        return Ok(false);
+    }
+    
+    let body = response.text().await.unwrap_or_default();
+    Ok(body.contains("system.multicall"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_xmlrpc_url() {
+        assert_eq!(xmlrpc_url("https://example.com/blog/wp-login.php"), "https://example.com/blog/xmlrpc.php");
+        assert_eq!(xmlrpc_url("https://example.com/"), "https://example.com/xmlrpc.php");
+    }
+}
@@ -0,0 +1,124 @@
+//! إشعارات Discord
+//! ملخصات منسقة كـ Embeds عبر Discord Webhook
+
+use anyhow::{Result, Context};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use super::{Event, Notifier};
+
+/// لون Embed لبيانات الاعتماد الصالحة (أحمر)
+const FOUND_COLOR: u32 = 0xE0_3C_31;
+
+/// لون Embed لاكتمال الفحص (أزرق)
+const COMPLETED_COLOR: u32 = 0x34_98_DB;
+
+/// أقصى طول لقيمة حقل في Embed
+const MAX_FIELD_LEN: usize = 1024;
+
+/// إعدادات Discord
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DiscordConfig {
+    /// رابط Webhook القناة
+    pub webhook_url: String,
+    
+    /// اسم المرسل الظاهر بدل اسم Webhook
+    #[serde(default)]
+    pub username: Option<String>,
+}
+
+/// خلفية Discord
+pub struct DiscordNotifier {
+    url: reqwest::Url,
+    username: Option<String>,
+}
+
+impl DiscordNotifier {
+    /// إنشاء الخلفية من الإعدادات
+    pub fn new(config: &DiscordConfig) -> Result<Self> {
+        let url = reqwest::Url::parse(&config.webhook_url)
+            .context(format!("رابط Discord غير صالح: {}", config.webhook_url))?;
+        
+        Ok(Self { url, username: config.username.clone() })
+    }
+}
+
+impl Notifier for DiscordNotifier {
+    fn name(&self) -> &'static str {
+        "Discord"
+    }
+    
+    fn request(&self, client: &reqwest::Client, event: &Event) -> Result<reqwest::RequestBuilder> {
+        let mut message = json!({ "embeds": [embed(event)] });
+        if let Some(username) = &self.username {
+            message["username"] = json!(username);
+        }
+        
+        Ok(client.post(self.url.clone()).json(&message))
+    }
+}
+
+/// Embed منسق للحدث
+fn embed(event: &Event) -> Value {
+    match event {
+        Event::CredentialFound { target, result } => json!({
+            "title": "بيانات اعتماد صالحة",
+            "description": target,
+            "color": FOUND_COLOR,
+            "fields": [
+                { "name": "المستخدم", "value": format!("`{}`", result.username), "inline": true },
+                { "name": "كلمة المرور", "value": format!("`{}`", result.password), "inline": true },
+                { "name": "رمز الحالة", "value": result.status_code.to_string(), "inline": true },
+            ],
+            "timestamp": result.timestamp.to_rfc3339(),
+        }),
+        Event::ScanCompleted { target, total_attempts, credentials, duration } => {
+            let mut found = if credentials.is_empty() {
+                "لا يوجد".to_string()
+            } else {
+                credentials
+                    .iter()
+                    .map(|(username, password)| format!("`{}:{}`", username, password))
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            };
+            if found.chars().count() > MAX_FIELD_LEN {
+                found = found.chars().take(MAX_FIELD_LEN - 1).collect::<String>() + "…";
+            }
+            
+            json!({
+                "title": "اكتمل الفحص",
+                "description": target,
+                "color": COMPLETED_COLOR,
+                "fields": [
+                    { "name": "المحاولات", "value": total_attempts.to_string(), "inline": true },
+                    { "name": "المدة", "value": format!("{:.1} ثانية", duration.as_secs_f64()), "inline": true },
+                    { "name": format!("بيانات الاعتماد ({})", credentials.len()), "value": found, "inline": false },
+                ],
+                "timestamp": chrono::Utc::now().to_rfc3339(),
+            })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+    
+    #[test]
+    fn test_completion_embed_truncates_long_credential_list() {
+        let event = Event::ScanCompleted {
+            target: "https://target.com/login".to_string(),
+            total_attempts: 5000,
+            credentials: (0..200).map(|i| (format!("user{}", i), "password".to_string())).collect(),
+            duration: Duration::from_secs(60),
+        };
+        
+        let embed = embed(&event);
+        
+        assert_eq!(embed["color"], json!(COMPLETED_COLOR));
+        assert_eq!(embed["fields"][2]["name"], json!("بيانات الاعتماد (200)"));
+        assert_eq!(embed["fields"][2]["value"].as_str().unwrap().chars().count(), MAX_FIELD_LEN);
+    }
+}
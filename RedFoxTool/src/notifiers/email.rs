@@ -0,0 +1,198 @@
+//! إشعارات البريد الإلكتروني
+//! إرسال ملخص الفحص (مع التقرير المولد كمرفق) عبر SMTP عند الاكتمال
+
+use std::path::Path;
+use std::time::Duration;
+use anyhow::{Result, Context};
+use lettre::message::header::ContentType;
+use lettre::message::{Attachment, MultiPart, SinglePart};
+use lettre::transport::smtp::authentication::Credentials;
+use lettre::{AsyncSmtpTransport, AsyncTransport, Message, Tokio1Executor};
+use serde::{Deserialize, Serialize};
+
+use crate::scanner::ScanResult;
+
+/// وضع تشفير اتصال SMTP
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SmtpTls {
+    /// ترقية الاتصال بـ STARTTLS (المنفذ 587)
+    #[default]
+    StartTls,
+    /// TLS مباشر (المنفذ 465)
+    Tls,
+    /// بدون تشفير (للخوادم المحلية فقط)
+    None,
+}
+
+/// إعدادات SMTP والمستلمين
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EmailConfig {
+    /// خادم SMTP
+    pub smtp_host: String,
+    
+    /// المنفذ (الافتراضي حسب وضع التشفير)
+    #[serde(default)]
+    pub smtp_port: Option<u16>,
+    
+    /// وضع التشفير
+    #[serde(default)]
+    pub tls: SmtpTls,
+    
+    /// اسم مستخدم SMTP
+    #[serde(default)]
+    pub username: Option<String>,
+    
+    /// كلمة مرور SMTP
+    #[serde(default)]
+    pub password: Option<String>,
+    
+    /// المرسل
+    pub from: String,
+    
+    /// المستلمون
+    pub to: Vec<String>,
+    
+    /// إرفاق التقرير المولد (إن وجد)
+    #[serde(default = "default_attach_report")]
+    pub attach_report: bool,
+}
+
+fn default_attach_report() -> bool {
+    true
+}
+
+/// إرسال ملخص الفحص إلى المستلمين
+pub async fn send_summary(
+    config: &EmailConfig,
+    target: &str,
+    results: &[ScanResult],
+    duration: Duration,
+    report: Option<&Path>,
+) -> Result<()> {
+    if config.to.is_empty() {
+        return Err(anyhow::anyhow!("لا يوجد مستلمون للبريد"));
+    }
+    
+    let (subject, body) = summary(target, results, duration);
+    
+    let mut builder = Message::builder()
+        .from(config.from.parse().context(format!("عنوان مرسل غير صالح: {}", config.from))?)
+        .subject(subject);
+    for recipient in &config.to {
+        builder = builder.to(recipient.parse().context(format!("عنوان مستلم غير صالح: {}", recipient))?);
+    }
+    
+    let text = SinglePart::plain(body);
+    let message = match report.filter(|_| config.attach_report) {
+        Some(path) => {
+            let content = tokio::fs::read(path)
+                .await
+                .context(format!("فشل في قراءة التقرير: {}", path.display()))?;
+            let filename = path
+                .file_name()
+                .map(|name| name.to_string_lossy().into_owned())
+                .unwrap_or_else(|| "report".to_string());
+            let content_type = ContentType::parse(content_type(path))?;
+            
+            builder.multipart(
+                MultiPart::mixed()
+                    .singlepart(text)
+                    .singlepart(Attachment::new(filename).body(content, content_type)),
+            )?
+        }
+        None => builder.singlepart(text)?,
+    };
+    
+    let mut transport = match config.tls {
+        SmtpTls::StartTls => AsyncSmtpTransport::<Tokio1Executor>::starttls_relay(&config.smtp_host)?,
+        SmtpTls::Tls => AsyncSmtpTransport::<Tokio1Executor>::relay(&config.smtp_host)?,
+        SmtpTls::None => AsyncSmtpTransport::<Tokio1Executor>::builder_dangerous(&config.smtp_host),
+    };
+    if let Some(port) = config.smtp_port {
+        transport = transport.port(port);
+    }
+    if let Some(username) = &config.username {
+        transport = transport.credentials(Credentials::new(
+            username.clone(),
+            config.password.clone().unwrap_or_default(),
+        ));
+    }
+    
+    transport
+        .build()
+        .send(message)
+        .await
+        .context(format!("فشل في الإرسال عبر {}", config.smtp_host))?;
+    
+    Ok(())
+}
+
+/// عنوان ونص الملخص
+fn summary(target: &str, results: &[ScanResult], duration: Duration) -> (String, String) {
+    let found: Vec<&ScanResult> = results.iter().filter(|r| r.success).collect();
+    
+    let subject = format!("[RedFoxTool] {} - {} بيانات اعتماد صالحة", target, found.len());
+    
+    let mut body = format!(
+        "الهدف: {}\nالمحاولات: {}\nالمدة: {:.1} ثانية\nبيانات الاعتماد الصالحة: {}\n",
+        target,
+        results.len(),
+        duration.as_secs_f64(),
+        found.len()
+    );
+    if !found.is_empty() {
+        body.push('\n');
+        for result in found {
+            body.push_str(&format!("  {}:{}\n", result.username, result.password));
+        }
+    }
+    
+    (subject, body)
+}
+
+/// نوع المحتوى من امتداد التقرير
+fn content_type(path: &Path) -> &'static str {
+    match path.extension().and_then(|e| e.to_str()).unwrap_or("") {
+        "html" => "text/html",
+        "json" => "application/json",
+        "csv" => "text/csv",
+        "xml" => "application/xml",
+        "pdf" => "application/pdf",
+        "txt" | "md" => "text/plain",
+        _ => "application/octet-stream",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_summary_lists_only_successful_credentials() {
+        let result = |username: &str, success| ScanResult {
+            username: username.to_string(),
+            password: "secret".to_string(),
+            success,
+            status_code: 200,
+            response_time: Duration::from_millis(10),
+            error: None,
+            timestamp: chrono::Utc::now(),
+            metadata: None,
+            captcha_detected: false,
+            banner: None,
+            http_version: None,
+            ..Default::default()
+        };
+        
+        let (subject, body) = summary(
+            "https://target.com",
+            &[result("admin", true), result("guest", false)],
+            Duration::from_secs(2),
+        );
+        
+        assert!(subject.contains("1 بيانات اعتماد"));
+        assert!(body.contains("admin:secret"));
+        assert!(!body.contains("guest"));
+    }
+}
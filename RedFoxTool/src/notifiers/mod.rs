@@ -0,0 +1,216 @@
+//! إشعارات الفحص
+//! توزيع أحداث اكتشاف بيانات الاعتماد واكتمال الفحص على خلفيات الإشعار (Webhook، Slack، Discord، البريد)
+
+pub mod discord;
+pub mod email;
+pub mod slack;
+pub mod webhook;
+
+use std::time::Duration;
+use anyhow::{Result, Context};
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+
+use crate::scanner::ScanResult;
+
+pub use discord::{DiscordConfig, DiscordNotifier};
+pub use email::EmailConfig;
+pub use slack::{SlackConfig, SlackNotifier};
+pub use webhook::{WebhookConfig, WebhookNotifier};
+
+/// عدد محاولات الإرسال لكل حدث
+const MAX_ATTEMPTS: u32 = 4;
+
+/// التأخير الأولي بين المحاولات (يتضاعف بعد كل فشل)
+const INITIAL_BACKOFF: Duration = Duration::from_secs(1);
+
+/// مهلة كل طلب
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// حدث إشعار
+#[derive(Debug, Clone)]
+pub enum Event {
+    /// بيانات اعتماد صالحة
+    CredentialFound {
+        /// الهدف
+        target: String,
+        /// النتيجة الناجحة
+        result: Box<ScanResult>,
+    },
+    /// اكتمال الفحص
+    ScanCompleted {
+        /// الهدف
+        target: String,
+        /// إجمالي المحاولات
+        total_attempts: usize,
+        /// بيانات الاعتماد الصالحة (مستخدم، كلمة مرور)
+        credentials: Vec<(String, String)>,
+        /// مدة الفحص
+        duration: Duration,
+    },
+}
+
+/// خلفية إشعار تبني طلب HTTP لكل حدث
+pub trait Notifier: Send + Sync {
+    /// اسم الخلفية (للسجلات)
+    fn name(&self) -> &'static str;
+    
+    /// بناء طلب إرسال الحدث
+    fn request(&self, client: &reqwest::Client, event: &Event) -> Result<reqwest::RequestBuilder>;
+    
+    /// التحقق من قبول الحدث في جسم استجابة ناجحة
+    fn check_response(&self, _body: &str) -> Result<()> {
+        Ok(())
+    }
+}
+
+/// إعدادات الإشعارات (قسم [notifiers] في ملف الإعدادات أو خيارات سطر الأوامر)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct NotifiersConfig {
+    /// Webhook عام
+    #[serde(default)]
+    pub webhook: Option<WebhookConfig>,
+    
+    /// Slack
+    #[serde(default)]
+    pub slack: Option<SlackConfig>,
+    
+    /// Discord
+    #[serde(default)]
+    pub discord: Option<DiscordConfig>,
+    
+    /// ملخص بالبريد عند الاكتمال (يُرسل بعد توليد التقرير)
+    #[serde(default)]
+    pub email: Option<EmailConfig>,
+}
+
+impl NotifiersConfig {
+    /// دمج إعدادات أخرى (الخلفيات المحددة فيها تتقدم)
+    pub fn merge(self, other: NotifiersConfig) -> Self {
+        Self {
+            webhook: other.webhook.or(self.webhook),
+            slack: other.slack.or(self.slack),
+            discord: other.discord.or(self.discord),
+            email: other.email.or(self.email),
+        }
+    }
+    
+    /// بناء خلفيات HTTP المفعلة (البريد يُرسل منفصلًا بعد حفظ التقرير)
+    pub fn build(&self) -> Result<Vec<Box<dyn Notifier>>> {
+        let mut backends: Vec<Box<dyn Notifier>> = Vec::new();
+        
+        if let Some(webhook) = &self.webhook {
+            backends.push(Box::new(WebhookNotifier::new(webhook)?));
+        }
+        if let Some(slack) = &self.slack {
+            backends.push(Box::new(SlackNotifier::new(slack)?));
+        }
+        if let Some(discord) = &self.discord {
+            backends.push(Box::new(DiscordNotifier::new(discord)?));
+        }
+        
+        Ok(backends)
+    }
+}
+
+/// موزع الأحداث على الخلفيات في الخلفية (الإرسال لا يوقف الفحص)
+pub struct Notifications {
+    target: String,
+    sender: Mutex<Option<mpsc::UnboundedSender<Event>>>,
+    worker: Mutex<Option<JoinHandle<()>>>,
+}
+
+impl Notifications {
+    /// إنشاء موزع لهدف الفحص وتشغيل عامل الإرسال
+    pub fn new(backends: Vec<Box<dyn Notifier>>, target: &str) -> Result<Self> {
+        let client = reqwest::Client::builder()
+            .timeout(REQUEST_TIMEOUT)
+            .build()
+            .context("فشل في إنشاء عميل HTTP")?;
+        
+        let (sender, mut receiver) = mpsc::unbounded_channel::<Event>();
+        let worker = tokio::spawn(async move {
+            while let Some(event) = receiver.recv().await {
+                for backend in &backends {
+                    if let Err(e) = deliver(&client, backend.as_ref(), &event).await {
+                        tracing::warn!("فشل في إرسال إشعار {}: {}", backend.name(), e);
+                    }
+                }
+            }
+        });
+        
+        Ok(Self {
+            target: target.to_string(),
+            sender: Mutex::new(Some(sender)),
+            worker: Mutex::new(Some(worker)),
+        })
+    }
+    
+    /// حدث بيانات اعتماد صالحة
+    pub fn credential_found(&self, result: &ScanResult) {
+        self.send(Event::CredentialFound {
+            target: self.target.clone(),
+            result: Box::new(result.clone()),
+        });
+    }
+    
+    /// حدث اكتمال الفحص، ثم انتظار إرسال كل الأحداث المعلقة
+    pub async fn finish(&self, results: &[ScanResult], duration: Duration) {
+        self.send(Event::ScanCompleted {
+            target: self.target.clone(),
+            total_attempts: results.len(),
+            credentials: results
+                .iter()
+                .filter(|r| r.success)
+                .map(|r| (r.username.clone(), r.password.clone()))
+                .collect(),
+            duration,
+        });
+        
+        // إغلاق القناة لينتهي العامل بعد تفريغها
+        self.sender.lock().take();
+        let worker = self.worker.lock().take();
+        if let Some(worker) = worker {
+            worker.await.ok();
+        }
+    }
+    
+    /// وضع حدث في طابور الإرسال
+    fn send(&self, event: Event) {
+        if let Some(sender) = self.sender.lock().as_ref() {
+            sender.send(event).ok();
+        }
+    }
+}
+
+/// إرسال حدث مع إعادة المحاولة عند أخطاء الشبكة و 429 و 5xx
+async fn deliver(client: &reqwest::Client, backend: &dyn Notifier, event: &Event) -> Result<()> {
+    let mut backoff = INITIAL_BACKOFF;
+    
+    for attempt in 1..=MAX_ATTEMPTS {
+        let retryable = match backend.request(client, event)?.send().await {
+            Ok(response) if response.status().is_success() => {
+                let body = response.text().await.unwrap_or_default();
+                return backend.check_response(&body);
+            }
+            Ok(response) => {
+                let status = response.status();
+                if !(status.is_server_error() || status.as_u16() == 429) {
+                    return Err(anyhow::anyhow!("رفض الخادم الحدث: {}", status));
+                }
+                format!("HTTP {}", status)
+            }
+            Err(e) => e.to_string(),
+        };
+        
+        if attempt == MAX_ATTEMPTS {
+            return Err(anyhow::anyhow!("فشل بعد {} محاولات: {}", MAX_ATTEMPTS, retryable));
+        }
+        tokio::time::sleep(backoff).await;
+        backoff *= 2;
+    }
+    
+    Ok(())
+}
@@ -0,0 +1,165 @@
+//! إشعارات Slack
+//! رسائل منسقة عبر Incoming Webhook أو رمز بوت (chat.postMessage)
+
+use anyhow::{Result, Context};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use super::{Event, Notifier};
+
+/// نقطة إرسال الرسائل برمز البوت
+const POST_MESSAGE_URL: &str = "https://slack.com/api/chat.postMessage";
+
+/// إعدادات Slack (رابط Incoming Webhook أو رمز بوت مع قناة)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SlackConfig {
+    /// رابط Incoming Webhook
+    #[serde(default)]
+    pub webhook_url: Option<String>,
+    
+    /// رمز البوت (xoxb-...)
+    #[serde(default)]
+    pub bot_token: Option<String>,
+    
+    /// القناة عند استخدام رمز البوت
+    #[serde(default)]
+    pub channel: Option<String>,
+}
+
+/// وجهة الرسائل
+enum Destination {
+    Incoming(reqwest::Url),
+    Bot { token: String, channel: String },
+}
+
+/// خلفية Slack
+pub struct SlackNotifier {
+    destination: Destination,
+}
+
+impl SlackNotifier {
+    /// إنشاء الخلفية من الإعدادات (Incoming Webhook يتقدم على رمز البوت)
+    pub fn new(config: &SlackConfig) -> Result<Self> {
+        let destination = match (&config.webhook_url, &config.bot_token) {
+            (Some(url), _) => Destination::Incoming(
+                reqwest::Url::parse(url).context(format!("رابط Slack غير صالح: {}", url))?,
+            ),
+            (None, Some(token)) => Destination::Bot {
+                token: token.clone(),
+                channel: config
+                    .channel
+                    .clone()
+                    .ok_or_else(|| anyhow::anyhow!("رمز بوت Slack يتطلب تحديد القناة"))?,
+            },
+            (None, None) => {
+                return Err(anyhow::anyhow!("إعدادات Slack تتطلب webhook_url أو bot_token"));
+            }
+        };
+        
+        Ok(Self { destination })
+    }
+}
+
+impl Notifier for SlackNotifier {
+    fn name(&self) -> &'static str {
+        "Slack"
+    }
+    
+    fn request(&self, client: &reqwest::Client, event: &Event) -> Result<reqwest::RequestBuilder> {
+        let mut message = message(event);
+        
+        Ok(match &self.destination {
+            Destination::Incoming(url) => client.post(url.clone()).json(&message),
+            Destination::Bot { token, channel } => {
+                message["channel"] = json!(channel);
+                client.post(POST_MESSAGE_URL).bearer_auth(token).json(&message)
+            }
+        })
+    }
+    
+    fn check_response(&self, body: &str) -> Result<()> {
+        // واجهة chat.postMessage تعيد 200 مع ok=false عند الخطأ
+        if let Destination::Bot { .. } = self.destination {
+            let response: Value = serde_json::from_str(body).context("استجابة Slack غير صالحة")?;
+            if response["ok"] != json!(true) {
+                return Err(anyhow::anyhow!(
+                    "رفض Slack الرسالة: {}",
+                    response["error"].as_str().unwrap_or("unknown")
+                ));
+            }
+        }
+        
+        Ok(())
+    }
+}
+
+/// رسالة Slack منسقة (نص بديل + كتل mrkdwn)
+fn message(event: &Event) -> Value {
+    let (text, fields) = match event {
+        Event::CredentialFound { target, result } => (
+            format!(":unlock: بيانات اعتماد صالحة على {}", target),
+            vec![
+                format!("*المستخدم:*\n`{}`", result.username),
+                format!("*كلمة المرور:*\n`{}`", result.password),
+                format!("*رمز الحالة:*\n{}", result.status_code),
+                format!("*الوقت:*\n{}", result.timestamp.to_rfc3339()),
+            ],
+        ),
+        Event::ScanCompleted { target, total_attempts, credentials, duration } => {
+            let found = if credentials.is_empty() {
+                "لا يوجد".to_string()
+            } else {
+                credentials
+                    .iter()
+                    .map(|(username, password)| format!("`{}:{}`", username, password))
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            };
+            
+            (
+                format!(":checkered_flag: اكتمل فحص {}", target),
+                vec![
+                    format!("*المحاولات:*\n{}", total_attempts),
+                    format!("*المدة:*\n{:.1} ثانية", duration.as_secs_f64()),
+                    format!("*بيانات الاعتماد ({}):*\n{}", credentials.len(), found),
+                ],
+            )
+        }
+    };
+    
+    json!({
+        "text": text,
+        "blocks": [
+            { "type": "header", "text": { "type": "plain_text", "text": text, "emoji": true } },
+            {
+                "type": "section",
+                "fields": fields
+                    .iter()
+                    .map(|field| json!({ "type": "mrkdwn", "text": field }))
+                    .collect::<Vec<_>>(),
+            },
+        ],
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+    
+    #[test]
+    fn test_completion_message_lists_credentials() {
+        let event = Event::ScanCompleted {
+            target: "https://target.com/login".to_string(),
+            total_attempts: 120,
+            credentials: vec![("admin".to_string(), "secret".to_string())],
+            duration: Duration::from_secs(3),
+        };
+        
+        let message = message(&event);
+        
+        assert!(message["text"].as_str().unwrap().contains("https://target.com/login"));
+        assert!(message["blocks"][1]["fields"][2]["text"].as_str().unwrap().contains("`admin:secret`"));
+        assert!(SlackNotifier::new(&SlackConfig { bot_token: Some("xoxb".into()), ..SlackConfig::default() }).is_err());
+    }
+}
@@ -0,0 +1,103 @@
+//! إشعارات Webhook
+//! إرسال حمولة JSON لكل بيانات اعتماد صالحة وعند اكتمال الفحص، مع توقيع HMAC-SHA256
+
+use anyhow::{Result, Context};
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use sha2::Sha256;
+
+use super::{Event, Notifier};
+
+/// ترويسة التوقيع (sha256=<hex>)
+pub const SIGNATURE_HEADER: &str = "X-RedFox-Signature";
+
+/// إعدادات Webhook
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WebhookConfig {
+    /// رابط الاستقبال
+    pub url: String,
+    
+    /// سر توقيع الحمولة
+    #[serde(default)]
+    pub secret: Option<String>,
+}
+
+/// خلفية Webhook عامة
+pub struct WebhookNotifier {
+    url: reqwest::Url,
+    secret: Option<String>,
+}
+
+impl WebhookNotifier {
+    /// إنشاء الخلفية من الإعدادات
+    pub fn new(config: &WebhookConfig) -> Result<Self> {
+        let url = reqwest::Url::parse(&config.url)
+            .context(format!("رابط Webhook غير صالح: {}", config.url))?;
+        
+        Ok(Self { url, secret: config.secret.clone() })
+    }
+}
+
+impl Notifier for WebhookNotifier {
+    fn name(&self) -> &'static str {
+        "Webhook"
+    }
+    
+    fn request(&self, client: &reqwest::Client, event: &Event) -> Result<reqwest::RequestBuilder> {
+        let body = serde_json::to_vec(&payload(event))?;
+        
+        let mut request = client
+            .post(self.url.clone())
+            .header(reqwest::header::CONTENT_TYPE, "application/json");
+        if let Some(secret) = &self.secret {
+            request = request.header(SIGNATURE_HEADER, format!("sha256={}", sign(secret, &body)));
+        }
+        
+        Ok(request.body(body))
+    }
+}
+
+/// حمولة JSON للحدث
+fn payload(event: &Event) -> Value {
+    match event {
+        Event::CredentialFound { target, result } => json!({
+            "event": "credential_found",
+            "target": target,
+            "username": result.username,
+            "password": result.password,
+            "status_code": result.status_code,
+            "timestamp": result.timestamp.to_rfc3339(),
+        }),
+        Event::ScanCompleted { target, total_attempts, credentials, duration } => json!({
+            "event": "scan_completed",
+            "target": target,
+            "total_attempts": total_attempts,
+            "successful": credentials.len(),
+            "duration_secs": duration.as_secs_f64(),
+            "timestamp": chrono::Utc::now().to_rfc3339(),
+        }),
+    }
+}
+
+/// توقيع HMAC-SHA256 بالست عشري
+pub fn sign(secret: &str, body: &[u8]) -> String {
+    #[allow(clippy::expect_used)] // لا يفشل: HMAC يقبل مفاتيح بأي طول
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC يقبل مفاتيح بأي طول");
+    mac.update(body);
+    mac.finalize().into_bytes().iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_sign_matches_rfc4231_vector() {
+        // RFC 4231 حالة الاختبار 2
+        assert_eq!(
+            sign("Jefe", b"what do ya want for nothing?"),
+            "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+        );
+    }
+}
@@ -0,0 +1,120 @@
+//! سياسة المخرجات
+//! تنسيق مخرجات stdout (نص أو JSON للسكربتات) والوضع الهادئ ومستوى التفاصيل
+
+use std::sync::OnceLock;
+use std::time::Duration;
+use serde_json::{json, Value};
+
+use crate::scanner::ScanResult;
+
+/// تنسيق مخرجات stdout
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum OutputFormat {
+    /// نص ملون للمشغل
+    #[default]
+    Text,
+    /// أسطر JSON (NDJSON) بلا بانر أو ألوان أو نصوص وصفية
+    Json,
+}
+
+/// سياسة المخرجات العامة للعملية
+#[derive(Debug, Clone, Copy, Default)]
+pub struct OutputPolicy {
+    /// تنسيق stdout
+    pub format: OutputFormat,
+    
+    /// الوضع الهادئ (--quiet): لا بانر ولا رسائل ولا أشرطة تقدم
+    pub quiet: bool,
+    
+    /// عدد مرات -v (0 تحذيرات، 1 معلومات، 2 تصحيح، 3 تتبع)
+    pub verbosity: u8,
+    
+    /// تنسيق السجلات (--log-format)
+    pub log_format: crate::telemetry::LogFormat,
+}
+
+/// السياسة المعينة مرة واحدة عند بدء التشغيل
+static POLICY: OnceLock<OutputPolicy> = OnceLock::new();
+
+/// تعيين سياسة المخرجات (أول استدعاء فقط هو المعتمد)
+pub fn init(policy: OutputPolicy) {
+    if policy.format == OutputFormat::Json {
+        colored::control::set_override(false);
+    }
+    POLICY.set(policy).ok();
+}
+
+/// السياسة الحالية (الافتراضية إن لم تُعين)
+pub fn policy() -> OutputPolicy {
+    POLICY.get().copied().unwrap_or_default()
+}
+
+/// هل المخرجات بتنسيق JSON؟
+pub fn is_json() -> bool {
+    policy().format == OutputFormat::Json
+}
+
+/// هل تُكتم الرسائل الوصفية؟ (الوضع الهادئ أو مخرجات JSON)
+pub fn is_quiet() -> bool {
+    let policy = policy();
+    policy.quiet || policy.format == OutputFormat::Json
+}
+
+/// هل تُعرض أشرطة التقدم؟
+pub fn show_progress() -> bool {
+    !is_quiet()
+}
+
+/// طباعة كائن JSON كسطر واحد على stdout
+pub fn emit(value: &Value) {
+    println!("{}", value);
+}
+
+/// سطر JSON لبيانات اعتماد صالحة
+pub fn credential(result: &ScanResult) -> Value {
+    let mut value = serde_json::to_value(result).unwrap_or_default();
+    if let Value::Object(map) = &mut value {
+        map.insert("event".to_string(), Value::from("credential"));
+    }
+    value
+}
+
+/// كائن الملخص النهائي (الأعداد والتوقيت)
+pub fn summary(results: &[ScanResult], duration: Duration) -> Value {
+    let successful = results.iter().filter(|r| r.success).count();
+    let errors = results.iter().filter(|r| r.error.is_some()).count();
+    let seconds = duration.as_secs_f64();
+    
+    json!({
+        "event": "summary",
+        "total_attempts": results.len(),
+        "successful": successful,
+        "failed": results.len() - successful,
+        "errors": errors,
+        "duration_ms": duration.as_millis(),
+        "attempts_per_second": if seconds > 0.0 { results.len() as f64 / seconds } else { 0.0 },
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_summary_counts() {
+        let results = vec![
+            ScanResult { success: true, ..ScanResult::default() },
+            ScanResult { error: Some("timeout".to_string()), ..ScanResult::default() },
+            ScanResult::default(),
+        ];
+        
+        let summary = summary(&results, Duration::from_secs(2));
+        
+        assert_eq!(summary["event"], "summary");
+        assert_eq!(summary["successful"], 1);
+        assert_eq!(summary["failed"], 2);
+        assert_eq!(summary["errors"], 1);
+        assert_eq!(summary["attempts_per_second"], 1.5);
+        assert_eq!(credential(&results[0])["event"], "credential");
+    }
+}
@@ -1,16 +1,273 @@
 //! محلل المدخلات
 //! يحلل ملفات ومتغيرات الإدخال
 
-use std::fs;
-use std::path::Path;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use futures::future;
+use futures::stream::{self, BoxStream, StreamExt, TryStreamExt};
 use tokio::fs as tokio_fs;
+use async_compression::tokio::bufread::{BzDecoder, GzipDecoder, ZstdDecoder};
+use tokio::io::{AsyncBufReadExt, AsyncRead, AsyncWriteExt, BufReader, Lines};
+use tokio_stream::wrappers::LinesStream;
 use anyhow::{Result, Context};
 use glob::glob;
+use sha2::{Digest, Sha256};
 
-/// تحليل الإدخال (ملف أو نص)
+use crate::error::RedFoxError;
+use crate::modules::generator::RuleSet;
+use crate::utils::wordlists;
+
+/// بيانات وصفية للمستخدم مأخوذة من ملف CSV
+#[derive(Debug, Clone, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct UserMetadata {
+    /// الاسم المعروض
+    pub display_name: Option<String>,
+    
+    /// القسم
+    pub department: Option<String>,
+    
+    /// مستوى الصلاحيات
+    pub privilege_level: Option<String>,
+}
+
+impl UserMetadata {
+    /// التحقق مما إذا كان الحساب ذا صلاحيات عالية
+    pub fn is_privileged(&self) -> bool {
+        const PRIVILEGED_LEVELS: [&str; 8] = [
+            "admin", "administrator", "root", "superuser",
+            "domain admin", "privileged", "high", "critical",
+        ];
+        
+        self.privilege_level
+            .as_deref()
+            .map(|level| {
+                let level = level.trim().to_lowercase();
+                PRIVILEGED_LEVELS.iter().any(|p| level == *p || level.contains("admin"))
+            })
+            .unwrap_or(false)
+    }
+}
+
+/// تحليل إدخال المستخدمين مع البيانات الوصفية (ملف CSV أو إدخال عادي)
+pub async fn parse_user_input(
+    input: &str,
+) -> Result<(Vec<String>, HashMap<String, UserMetadata>)> {
+    if input.to_lowercase().ends_with(".csv") && Path::new(input).exists() {
+        return parse_users_csv(input).await;
+    }
+    
+    Ok((parse_input(input).await?, HashMap::new()))
+}
+
+/// تحليل ملف CSV للمستخدمين مع أعمدة إضافية
+///
+/// الأعمدة المدعومة: username, display_name, department, privilege.
+/// إذا لم يوجد صف عناوين تُقرأ الأعمدة بهذا الترتيب.
+pub async fn parse_users_csv(
+    filepath: &str,
+) -> Result<(Vec<String>, HashMap<String, UserMetadata>)> {
+    let content = tokio_fs::read_to_string(filepath)
+        .await
+        .context(format!("فشل في قراءة الملف: {}", filepath))?;
+    
+    parse_users_csv_content(&content)
+        .context(format!("فشل في تحليل ملف CSV: {}", filepath))
+}
+
+/// تحليل محتوى CSV للمستخدمين
+fn parse_users_csv_content(
+    content: &str,
+) -> Result<(Vec<String>, HashMap<String, UserMetadata>)> {
+    let mut reader = csv::ReaderBuilder::new()
+        .has_headers(false)
+        .flexible(true)
+        .trim(csv::Trim::All)
+        .comment(Some(b'#'))
+        .from_reader(content.as_bytes());
+    
+    let mut records = reader.records();
+    
+    // ترتيب الأعمدة الافتراضي: المستخدم، الاسم، القسم، الصلاحية
+    let mut columns = [Some(0), Some(1), Some(2), Some(3)];
+    let mut users = Vec::new();
+    let mut metadata = HashMap::new();
+    let mut pending = None;
+    
+    if let Some(first) = records.next() {
+        let first = first?;
+        let header: Vec<String> = first.iter().map(|h| h.to_lowercase()).collect();
+        let find = |names: &[&str]| header.iter().position(|h| names.contains(&h.as_str()));
+        
+        match find(&["username", "user", "login", "account"]) {
+            Some(user_col) => {
+                columns = [
+                    Some(user_col),
+                    find(&["display_name", "display name", "name", "full_name"]),
+                    find(&["department", "dept", "ou"]),
+                    find(&["privilege", "privilege_level", "role", "level"]),
+                ];
+            }
+            None => pending = Some(first),
+        }
+    }
+    
+    let field = |record: &csv::StringRecord, col: Option<usize>| {
+        col.and_then(|i| record.get(i))
+            .map(str::trim)
+            .filter(|v| !v.is_empty())
+            .map(|v| v.to_string())
+    };
+    
+    for record in pending.into_iter().map(Ok).chain(records) {
+        let record = record?;
+        
+        let Some(username) = field(&record, columns[0]) else {
+            continue;
+        };
+        
+        let user_metadata = UserMetadata {
+            display_name: field(&record, columns[1]),
+            department: field(&record, columns[2]),
+            privilege_level: field(&record, columns[3]),
+        };
+        
+        if !users.contains(&username) {
+            users.push(username.clone());
+        }
+        
+        if user_metadata != UserMetadata::default() {
+            metadata.insert(username, user_metadata);
+        }
+    }
+    
+    if users.is_empty() {
+        return Err(anyhow::anyhow!("ملف CSV لا يحتوي على مستخدمين"));
+    }
+    
+    Ok((users, metadata))
+}
+
+/// تحليل ملف أزواج (username:password) لهجمات الأزواج المحددة
+///
+/// يُقسم كل سطر عند أول نقطتين، لذا يمكن أن تحتوي كلمة المرور على `:`.
+pub async fn parse_combo_input(input: &str) -> Result<Vec<(String, String)>> {
+    let lines = parse_input(input).await?;
+    let mut combos = Vec::with_capacity(lines.len());
+    
+    for line in lines {
+        match parse_combo_line(&line) {
+            Some(combo) => combos.push(combo),
+            None => tracing::warn!("تجاهل سطر غير صالح في ملف الأزواج: {}", line),
+        }
+    }
+    
+    if combos.is_empty() {
+        return Err(anyhow::anyhow!("لا توجد أزواج صالحة في: {}", input));
+    }
+    
+    Ok(combos)
+}
+
+/// تحليل سطر زوج واحد
+fn parse_combo_line(line: &str) -> Option<(String, String)> {
+    let (username, password) = line.split_once(':')?;
+    let username = username.trim();
+    
+    if username.is_empty() {
+        return None;
+    }
+    
+    Some((username.to_string(), password.to_string()))
+}
+
+/// مجلد تخزين القوائم البعيدة المنزلة
+pub const REMOTE_CACHE_DIR: &str = "~/.redfox/cache";
+
+/// هل الإدخال رابط قائمة بعيدة
+pub fn is_remote(input: &str) -> bool {
+    input.starts_with("http://") || input.starts_with("https://")
+}
+
+/// مسار النسخة المخزنة لرابط (بادئة من تجزئة الرابط مع اسم الملف الأصلي لحفظ امتداد الضغط)
+fn cache_path(url: &str) -> PathBuf {
+    let digest = Sha256::digest(url.as_bytes());
+    let prefix: String = digest[..8].iter().map(|b| format!("{:02x}", b)).collect();
+    let name = url
+        .split(['?', '#'])
+        .next()
+        .and_then(|path| path.rsplit('/').next())
+        .filter(|name| !name.is_empty())
+        .unwrap_or("wordlist.txt");
+    
+    PathBuf::from(shellexpand::tilde(REMOTE_CACHE_DIR).as_ref()).join(format!("{}-{}", prefix, name))
+}
+
+/// تنزيل قائمة بعيدة متدفقًا إلى مجلد التخزين (تُعاد النسخة المنزلة سابقًا دون تنزيل)
+pub async fn fetch_remote(url: &str) -> Result<String> {
+    let path = cache_path(url);
+    if path.exists() {
+        tracing::info!("استخدام النسخة المخزنة من {}: {}", url, path.display());
+        return Ok(path.to_string_lossy().into_owned());
+    }
+    
+    if let Some(dir) = path.parent() {
+        tokio_fs::create_dir_all(dir)
+            .await
+            .context(format!("فشل في إنشاء مجلد التخزين: {}", dir.display()))?;
+    }
+    
+    tracing::info!("تنزيل قائمة الكلمات: {}", url);
+    let response = reqwest::get(url)
+        .await
+        .and_then(reqwest::Response::error_for_status)
+        .context(format!("فشل في تنزيل القائمة: {}", url))?;
+    
+    // التنزيل إلى ملف جزئي ثم إعادة التسمية حتى لا يبقى ملف ناقص في التخزين
+    let partial = PathBuf::from(format!("{}.part", path.display()));
+    let mut file = tokio_fs::File::create(&partial)
+        .await
+        .context(format!("فشل في إنشاء الملف: {}", partial.display()))?;
+    
+    let mut body = response.bytes_stream();
+    while let Some(chunk) = body.try_next().await.context(format!("انقطع تنزيل القائمة: {}", url))? {
+        file.write_all(&chunk).await?;
+    }
+    file.flush().await?;
+    
+    tokio_fs::rename(&partial, &path).await?;
+    Ok(path.to_string_lossy().into_owned())
+}
+
+/// الإدخال الذي يعني القراءة من stdin (`-P -` أو `-U -`)
+pub const STDIN_INPUT: &str = "-";
+
+/// هل الإدخال هو stdin
+pub fn is_stdin(input: &str) -> bool {
+    input == STDIN_INPUT
+}
+
+/// أسطر stdin للقراءة المتدفقة (خطوط أنابيب مثل `hashcat --stdout | redfox scan -P -`)
+fn stdin_lines() -> Lines<BufReader<WordlistReader>> {
+    BufReader::new(Box::new(tokio::io::stdin()) as WordlistReader).lines()
+}
+
+/// تحليل الإدخال (ملف أو رابط أو stdin أو نص)
 pub async fn parse_input(input: &str) -> Result<Vec<String>> {
-    // إذا كان الإدخال مسار ملف
-    if Path::new(input).exists() {
+    if is_stdin(input) {
+        let items: Vec<String> = WordSource::Stdin.stream().try_collect().await?;
+        if items.is_empty() {
+            return Err(anyhow::anyhow!("لم يُقرأ أي إدخال من stdin"));
+        }
+        return Ok(items);
+    }
+    
+    if is_remote(input) {
+        return parse_file(&fetch_remote(input).await?).await;
+    }
+    
+    // إذا كان الإدخال مسار ملف (أو اسم قائمة كلمات يُبحث عنه في المسارات الشائعة)
+    if Path::new(input).exists() || looks_like_wordlist(input) {
         parse_file(input).await
     } else if input.contains(',') {
         // إذا كان نصًا مفصولًا بفواصل
@@ -24,46 +281,259 @@ pub async fn parse_input(input: &str) -> Result<Vec<String>> {
     }
 }
 
-/// تحليل ملف
+/// هل يبدو الإدخال اسم ملف قائمة كلمات لا كلمة مرور؟ (rockyou.txt وليس admin)
+fn looks_like_wordlist(input: &str) -> bool {
+    const EXTENSIONS: [&str; 6] = [".txt", ".lst", ".dic", ".gz", ".zst", ".bz2"];
+    
+    let lower = input.to_lowercase();
+    !input.contains(',') && !input.contains('\n') && EXTENSIONS.iter().any(|ext| lower.ends_with(ext))
+}
+
+/// تحليل ملف (الملفات المضغوطة .gz و .zst و .bz2 تُفك أثناء القراءة)
 async fn parse_file(filepath: &str) -> Result<Vec<String>> {
-    // التحقق من وجود الملف
-    if !Path::new(filepath).exists() {
-        // البحث في المسارات الشائعة
-        let common_paths = [
-            filepath,
-            &format!("/usr/share/wordlists/{}", filepath),
-            &format!("/usr/share/seclists/{}", filepath),
-            &format!("/usr/share/redfox/wordlists/{}", filepath),
-            &format!("~/.redfox/wordlists/{}", filepath),
-        ];
+    let resolved = resolve_path(filepath)?;
+    parse_file_contents(&resolved).await
+}
+
+/// قارئ ملف قائمة كلمات (مفكوك الضغط إن لزم)
+pub type WordlistReader = Box<dyn AsyncRead + Send + Unpin>;
+
+/// صيغة ضغط الملف حسب امتداده
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Compression {
+    None,
+    Gzip,
+    Zstd,
+    Bzip2,
+}
+
+impl Compression {
+    /// استنتاج الصيغة من امتداد الملف
+    fn from_path(filepath: &str) -> Self {
+        let lower = filepath.to_lowercase();
         
-        for path in &common_paths {
-            let expanded = shellexpand::full(path)
-                .context("فشل في توسيع المسار")?;
-            
-            if Path::new(&*expanded).exists() {
-                return parse_file_contents(&expanded).await;
+        if lower.ends_with(".gz") {
+            Self::Gzip
+        } else if lower.ends_with(".zst") {
+            Self::Zstd
+        } else if lower.ends_with(".bz2") {
+            Self::Bzip2
+        } else {
+            Self::None
+        }
+    }
+    
+    /// تغليف الملف بفاك الضغط المناسب (بث دون فك الملف كاملًا)
+    fn reader(self, file: tokio_fs::File) -> WordlistReader {
+        let buffered = BufReader::new(file);
+        
+        match self {
+            Self::None => Box::new(buffered),
+            Self::Gzip => {
+                let mut decoder = GzipDecoder::new(buffered);
+                decoder.multiple_members(true);
+                Box::new(decoder)
             }
+            Self::Zstd => Box::new(ZstdDecoder::new(buffered)),
+            Self::Bzip2 => Box::new(BzDecoder::new(buffered)),
         }
+    }
+}
+
+/// البحث عن الملف في المسارات الشائعة لقوائم الكلمات
+pub fn resolve_path(filepath: &str) -> Result<String> {
+    // التحقق من وجود الملف
+    if Path::new(filepath).exists() {
+        return Ok(filepath.to_string());
+    }
+    
+    // البحث في المسارات الشائعة
+    let common_paths = std::iter::once(filepath.to_string())
+        .chain(wordlists::SEARCH_DIRS.iter().map(|dir| format!("{}/{}", dir, filepath)));
+    
+    for path in common_paths {
+        let expanded = shellexpand::full(&path)
+            .context("فشل في توسيع المسار")?;
         
-        return Err(anyhow::anyhow!("الملف غير موجود: {}", filepath));
+        if Path::new(&*expanded).exists() {
+            return Ok(expanded.to_string());
+        }
+        
+        // التوزيعات تشحن القوائم الكبيرة مضغوطة (rockyou.txt.gz)
+        let compressed = format!("{}.gz", expanded);
+        if Path::new(&compressed).exists() {
+            return Ok(compressed);
+        }
     }
     
-    parse_file_contents(filepath).await
+    Err(RedFoxError::WordlistNotFound(filepath.into()).into())
+}
+
+/// فتح ملف قائمة كلمات للقراءة المتدفقة سطرًا بسطر (مع فك ضغط .gz و .zst و .bz2 شفافًا)
+pub async fn open_lines(filepath: &str) -> Result<Lines<BufReader<WordlistReader>>> {
+    let resolved = resolve_path(filepath)?;
+    let file = tokio_fs::File::open(&resolved)
+        .await
+        .context(format!("فشل في فتح الملف: {}", resolved))?;
+    
+    Ok(BufReader::new(Compression::from_path(&resolved).reader(file)).lines())
+}
+
+/// تنظيف سطر من ملف قائمة (إرجاع None للأسطر الفارغة والتعليقات)
+pub fn normalize_line(line: &str) -> Option<&str> {
+    let line = line.trim();
+    
+    if line.is_empty() || line.starts_with('#') {
+        None
+    } else {
+        Some(line)
+    }
+}
+
+/// مصدر كلمات يُقرأ بذاكرة ثابتة: الملفات تُبث سطرًا بسطر عند كل مرور ولا تُحمل كاملة
+#[derive(Debug, Clone)]
+pub enum WordSource {
+    /// قائمة صغيرة في الذاكرة (قيمة، أو مفصولة بفواصل، أو أزواج محددة)
+    Memory(Arc<[String]>),
+    /// ملف قائمة كلمات (المسار المحلول وعدد إدخالاته الصالحة)
+    File {
+        /// المسار المحلول
+        path: String,
+        /// عدد الإدخالات الصالحة
+        len: usize,
+    },
+    /// stdin (يُقرأ مرة واحدة وعدده غير معروف مسبقًا)
+    Stdin,
+    /// مصدر أساس تتوسع كل كلمة منه بقواعد hashcat أثناء البث
+    Ruled {
+        /// المصدر الأساس
+        base: Box<WordSource>,
+        /// القواعد المطبقة على كل كلمة
+        rules: Arc<RuleSet>,
+    },
+}
+
+impl Default for WordSource {
+    fn default() -> Self {
+        Self::Memory(Arc::from(Vec::new()))
+    }
+}
+
+impl<S: Into<String>> FromIterator<S> for WordSource {
+    fn from_iter<I: IntoIterator<Item = S>>(words: I) -> Self {
+        Self::Memory(words.into_iter().map(Into::into).collect())
+    }
+}
+
+impl WordSource {
+    /// مصدر من إدخال المستخدم (الملفات تُعد فقط دون تحميلها)
+    pub async fn from_input(input: &str) -> Result<Self> {
+        let path = match Self::locate(input).await? {
+            Self::File { path, .. } => path,
+            source => return Ok(source),
+        };
+        
+        let len = Self::File { path: path.clone(), len: 0 }
+            .stream()
+            .try_fold(0, |count, _| future::ready(Ok(count + 1)))
+            .await?;
+        if len == 0 {
+            return Err(anyhow::anyhow!("الملف فارغ: {}", path));
+        }
+        
+        Ok(Self::File { path, len })
+    }
+    
+    /// تحديد نوع المصدر دون عد الملفات (العدد 0 حتى يُحسب)
+    pub(crate) async fn locate(input: &str) -> Result<Self> {
+        let path = if is_stdin(input) {
+            return Ok(Self::Stdin);
+        } else if is_remote(input) {
+            fetch_remote(input).await?
+        } else if Path::new(input).exists() || looks_like_wordlist(input) {
+            resolve_path(input)?
+        } else {
+            return Ok(Self::Memory(parse_input(input).await?.into()));
+        };
+        
+        Ok(Self::File { path, len: 0 })
+    }
+    
+    /// بث إدخال مباشرة بمرور واحد دون عد مسبق (ملف، رابط، stdin، أو قائمة نصية)
+    pub async fn stream_input(input: &str) -> Result<BoxStream<'static, Result<String>>> {
+        Ok(Self::locate(input).await?.stream())
+    }
+    
+    /// تطبيق قواعد hashcat على كل كلمة أثناء البث
+    pub fn with_rules(self, rules: Arc<RuleSet>) -> Self {
+        Self::Ruled { base: Box::new(self), rules }
+    }
+    
+    /// عدد الكلمات (0 لـ stdin لأنه غير معروف قبل القراءة، وحد أعلى مع القواعد لأن بعضها يرفض الكلمة)
+    pub fn len(&self) -> usize {
+        match self {
+            Self::Memory(words) => words.len(),
+            Self::File { len, .. } => *len,
+            Self::Stdin => 0,
+            Self::Ruled { base, rules } => base.len().saturating_mul(rules.len()),
+        }
+    }
+    
+    /// هل المصدر يُقرأ مرة واحدة فقط (لا يمكن إعادة بثه لكل مستخدم)
+    pub fn is_single_pass(&self) -> bool {
+        match self {
+            Self::Ruled { base, .. } => base.is_single_pass(),
+            source => matches!(source, Self::Stdin),
+        }
+    }
+    
+    /// هل المصدر فارغ
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+    
+    /// بث الكلمات بالترتيب (كل استدعاء يبدأ مرورًا جديدًا من أول المصدر، عدا stdin)
+    pub fn stream(&self) -> BoxStream<'static, Result<String>> {
+        match self {
+            Self::Memory(words) => {
+                let words = Arc::clone(words);
+                stream::iter((0..words.len()).map(move |i| Ok(words[i].clone()))).boxed()
+            }
+            Self::File { path, .. } => {
+                let path = path.clone();
+                stream::once(async move { open_lines(&path).await })
+                    .map_ok(|lines| LinesStream::new(lines).map_err(anyhow::Error::from))
+                    .try_flatten()
+                    .try_filter_map(|line| future::ready(Ok(normalize_line(&line).map(str::to_string))))
+                    .boxed()
+            }
+            Self::Stdin => LinesStream::new(stdin_lines())
+                .map_err(anyhow::Error::from)
+                .try_filter_map(|line| future::ready(Ok(normalize_line(&line).map(str::to_string))))
+                .boxed(),
+            Self::Ruled { base, rules } => {
+                let rules = Arc::clone(rules);
+                base.stream()
+                    .map_ok(move |word| stream::iter(rules.apply(&word).into_iter().map(Ok)))
+                    .try_flatten()
+                    .boxed()
+            }
+        }
+    }
 }
 
 /// تحليل محتويات الملف
 async fn parse_file_contents(filepath: &str) -> Result<Vec<String>> {
-    let content = tokio_fs::read_to_string(filepath)
-        .await
-        .context(format!("فشل في قراءة الملف: {}", filepath))?;
+    let mut lines = open_lines(filepath).await?;
+    let mut items = Vec::new();
     
-    let items: Vec<String> = content
-        .lines()
-        .map(|line| line.trim())
-        .filter(|line| !line.is_empty() && !line.starts_with('#'))
-        .map(|line| line.to_string())
-        .collect();
+    while let Some(line) = lines
+        .next_line()
+        .await
+        .context(format!("فشل في قراءة الملف: {}", filepath))?
+    {
+        items.extend(normalize_line(&line).map(str::to_string));
+    }
     
     if items.is_empty() {
         return Err(anyhow::anyhow!("الملف فارغ: {}", filepath));
@@ -102,7 +572,7 @@ pub async fn parse_glob_pattern(pattern: &str) -> Result<Vec<String>> {
                     files.push(path.to_string_lossy().to_string());
                 }
             }
-            Err(e) => log::warn!("خطأ في نمط جلوب: {}", e),
+            Err(e) => tracing::warn!("خطأ في نمط جلوب: {}", e),
         }
     }
     
@@ -131,23 +601,85 @@ pub async fn merge_sources(sources: &[String]) -> Result<Vec<String>> {
 
 /// تحليل الإدخال مع توسيع الأنماط
 pub async fn parse_input_with_expansion(input: &str) -> Result<Vec<String>> {
-    // التحقق من الأنماط الخاصة
-    if input.contains('*') || input.contains('?') || input.contains('[') {
+    // التحقق من الأنماط الخاصة (الروابط أولًا لأنها قد تحتوي ? في الاستعلام)
+    if is_remote(input) {
+        // رابط URL (يُنزل متدفقًا ويُخزن)
+        parse_file(&fetch_remote(input).await?).await
+    } else if input.contains('*') || input.contains('?') || input.contains('[') {
         // نمط جلوب
         parse_glob_pattern(input).await
     } else if input.starts_with("file://") {
         // مسار ملف
         let filepath = input.trim_start_matches("file://");
         parse_file(filepath).await
-    } else if input.starts_with("http://") || input.starts_with("https://") {
-        // رابط URL (غير مدعوم حالياً)
-        Err(anyhow::anyhow!("روابط URL غير مدعومة حالياً"))
     } else {
         // تحليل عادي
         parse_input(input).await
     }
 }
 
+/// تحليل كائن JSON مسطح إلى أزواج نصية (لـ --headers و --data)
+pub fn parse_json_pairs(input: &str) -> Result<Vec<(String, String)>> {
+    let value: serde_json::Value = serde_json::from_str(input)
+        .context(format!("JSON غير صالح: {}", input))?;
+    let object = value
+        .as_object()
+        .ok_or_else(|| anyhow::anyhow!("يجب أن يكون JSON كائنًا: {}", input))?;
+    
+    Ok(object
+        .iter()
+        .map(|(key, value)| {
+            let value = match value {
+                serde_json::Value::String(s) => s.clone(),
+                other => other.to_string(),
+            };
+            (key.clone(), value)
+        })
+        .collect())
+}
+
+/// تحميل الكوكيز من ملف (تنسيق Netscape أو name=value لكل سطر) أو من سلسلة مباشرة
+pub async fn parse_cookies(input: &str) -> Result<String> {
+    if !Path::new(input).exists() {
+        return Ok(input.trim().to_string());
+    }
+    
+    let content = tokio_fs::read_to_string(input)
+        .await
+        .context(format!("فشل في قراءة ملف الكوكيز: {}", input))?;
+    let cookies = parse_cookie_content(&content);
+    
+    if cookies.is_empty() {
+        return Err(anyhow::anyhow!("لا توجد كوكيز في الملف: {}", input));
+    }
+    
+    Ok(cookies)
+}
+
+/// تحويل محتوى ملف كوكيز إلى قيمة ترويسة Cookie
+fn parse_cookie_content(content: &str) -> String {
+    content
+        .lines()
+        .map(|line| line.trim().trim_start_matches("#HttpOnly_"))
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .flat_map(|line| {
+            let fields: Vec<&str> = line.split('\t').collect();
+            
+            // Netscape: domain, flag, path, secure, expiry, name, value
+            if fields.len() == 7 {
+                vec![format!("{}={}", fields[5], fields[6])]
+            } else {
+                line.split(';')
+                    .map(str::trim)
+                    .filter(|pair| pair.contains('='))
+                    .map(str::to_string)
+                    .collect()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
 /// تحويل المتجه إلى سلسلة مفصولة بفواصل
 pub fn vec_to_comma_separated(items: &[String]) -> String {
     items.join(",")
@@ -156,8 +688,23 @@ pub fn vec_to_comma_separated(items: &[String]) -> String {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::fs;
+    use std::io::Write;
     use tempfile::NamedTempFile;
     
+    #[test]
+    fn test_parse_cookie_content() {
+        let content = "# Netscape HTTP Cookie File\n\
+                       .target.com\tTRUE\t/\tFALSE\t0\tsession\tabc123\n\
+                       #HttpOnly_.target.com\tTRUE\t/\tTRUE\t0\ttoken\txyz\n\
+                       lang=en; theme=dark\n";
+        
+        assert_eq!(
+            parse_cookie_content(content),
+            "session=abc123; token=xyz; lang=en; theme=dark"
+        );
+    }
+    
     #[tokio::test]
     async fn test_parse_comma_separated() {
         let input = "admin, user, test,guest";
@@ -194,4 +741,57 @@ mod tests {
         
         assert_eq!(result, vec!["admin"]);
     }
+    
+    #[tokio::test]
+    async fn test_word_source_streams_file_lazily() {
+        let temp_file = NamedTempFile::with_suffix(".txt").unwrap();
+        fs::write(temp_file.path(), "123456\n# تعليق\n\npassword\n").unwrap();
+        
+        let source = WordSource::from_input(temp_file.path().to_str().unwrap()).await.unwrap();
+        assert!(matches!(source, WordSource::File { len: 2, .. }));
+        
+        // كل مرور يعيد القراءة من البداية
+        for _ in 0..2 {
+            let words: Vec<String> = source.stream().try_collect().await.unwrap();
+            assert_eq!(words, vec!["123456", "password"]);
+        }
+    }
+    
+    #[tokio::test]
+    async fn test_parse_gzip_wordlist() {
+        use async_compression::tokio::write::GzipEncoder;
+        use tokio::io::AsyncWriteExt;
+        
+        let mut encoder = GzipEncoder::new(Vec::new());
+        encoder.write_all("admin\n# تعليق\nroot\n".as_bytes()).await.unwrap();
+        encoder.shutdown().await.unwrap();
+        
+        let temp_file = NamedTempFile::with_suffix(".txt.gz").unwrap();
+        fs::write(temp_file.path(), encoder.into_inner()).unwrap();
+        
+        let result = parse_file(temp_file.path().to_str().unwrap()).await.unwrap();
+        
+        assert_eq!(result, vec!["admin", "root"]);
+    }
+    
+    #[test]
+    fn test_parse_combo_line() {
+        assert_eq!(
+            parse_combo_line("admin:pa:ss"),
+            Some(("admin".to_string(), "pa:ss".to_string()))
+        );
+        assert_eq!(parse_combo_line("no-separator"), None);
+        assert_eq!(parse_combo_line(":password"), None);
+    }
+    
+    #[test]
+    fn test_parse_users_csv_with_header() {
+        let content = "department,username,role\nIT,admin,Domain Admin\nHR,sara,user\n";
+        let (users, metadata) = parse_users_csv_content(content).unwrap();
+        
+        assert_eq!(users, vec!["admin", "sara"]);
+        assert_eq!(metadata["admin"].department.as_deref(), Some("IT"));
+        assert!(metadata["admin"].is_privileged());
+        assert!(!metadata["sara"].is_privileged());
+    }
 }
\ No newline at end of file
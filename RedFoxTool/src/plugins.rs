@@ -0,0 +1,324 @@
+//! إضافات WASM
+//! منطق مصادقة مخصص (بناء الطلب وكشف النجاح) من وحدات WebAssembly معزولة في ~/.redfox/plugins/
+//!
+//! الواجهة المطلوبة من الإضافة (بدون أي استيرادات، فلا وصول للملفات أو الشبكة):
+//!
+//! - `memory`: الذاكرة المصدرة
+//! - `redfox_alloc(len: i32) -> i32`: حجز مساحة لنقل النصوص إلى الإضافة
+//! - `redfox_build_request(user_ptr, user_len, pass_ptr, pass_len: i32) -> i64`: طلب HTTP خام
+//!   (بنفس تنسيق `--raw-request`) مُعاد كمؤشر وطول مدمجين `(ptr << 32) | len`
+//! - `redfox_check_response(status: i32, body_ptr: i32, body_len: i32) -> i32`:
+//!   1 نجاح، 0 فشل، -1 قفل الحساب
+//! - `redfox_name() -> i64` (اختياري): اسم الإضافة بنفس ترميز المؤشر والطول
+
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use anyhow::{Result, Context};
+use wasmtime::{Engine, Instance, Memory, Module, Store, TypedFunc};
+
+use crate::error::RedFoxError;
+use crate::http_client::HttpClient;
+use crate::modules::{AttemptOutcome, AuthModule};
+use crate::raw_request::RawRequest;
+
+/// مجلد الإضافات الافتراضي
+pub const DEFAULT_PLUGIN_DIR: &str = "~/.redfox/plugins";
+
+/// حد الوقود لكل استدعاء (يمنع الحلقات اللانهائية في الإضافة)
+const FUEL_PER_CALL: u64 = 50_000_000;
+
+/// أقصى حجم لجسم الاستجابة الممرر للإضافة
+const MAX_BODY_BYTES: usize = 1024 * 1024;
+
+/// حكم الإضافة على الاستجابة
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PluginVerdict {
+    /// بيانات اعتماد صالحة
+    Success,
+    /// بيانات اعتماد خاطئة
+    Failure,
+    /// الحساب مقفل
+    Lockout,
+}
+
+/// وحدة WASM مُترجمة (آمنة للمشاركة بين الخيوط، ونسخة معزولة جديدة لكل استدعاء)
+pub struct PluginRuntime {
+    name: String,
+    engine: Engine,
+    module: Module,
+}
+
+/// نسخة قيد التشغيل من الإضافة
+struct PluginInstance {
+    store: Store<()>,
+    instance: Instance,
+    memory: Memory,
+}
+
+impl PluginRuntime {
+    /// تحميل إضافة من ملف .wasm
+    pub fn load(path: &Path) -> Result<Self> {
+        let bytes = std::fs::read(path).context(format!("فشل في قراءة الإضافة: {}", path.display()))?;
+        let fallback = path
+            .file_stem()
+            .and_then(|stem| stem.to_str())
+            .unwrap_or("plugin")
+            .to_string();
+        
+        Self::from_bytes(&fallback, &bytes).context(format!("إضافة غير صالحة: {}", path.display()))
+    }
+    
+    /// تحميل إضافة من بايتات WASM (أو نص WAT)
+    pub fn from_bytes(fallback_name: &str, bytes: &[u8]) -> Result<Self> {
+        let mut config = wasmtime::Config::new();
+        config.consume_fuel(true);
+        let engine = Engine::new(&config)?;
+        let module = Module::new(&engine, bytes).context("فشل في ترجمة وحدة WASM")?;
+        
+        // لا استيرادات مسموحة: الإضافة حساب بحت داخل صندوقها
+        if let Some(import) = module.imports().next() {
+            return Err(anyhow::anyhow!(
+                "الإضافة تطلب استيرادًا غير مسموح: {}::{}",
+                import.module(),
+                import.name()
+            ));
+        }
+        
+        let mut runtime = Self { name: fallback_name.to_string(), engine, module };
+        
+        let mut plugin = runtime.instantiate()?;
+        if let Ok(name) = plugin.instance.get_typed_func::<(), i64>(&mut plugin.store, "redfox_name") {
+            let packed = name.call(&mut plugin.store, ())?;
+            runtime.name = plugin.read_string(packed)?;
+        }
+        
+        // التحقق من الصادرات الإلزامية مبكرًا بدل الفشل عند أول محاولة
+        plugin.build_request_fn()?;
+        plugin.check_response_fn()?;
+        
+        Ok(runtime)
+    }
+    
+    /// اسم الإضافة
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+    
+    /// إنشاء نسخة معزولة جديدة بحد وقود
+    fn instantiate(&self) -> Result<PluginInstance> {
+        let mut store = Store::new(&self.engine, ());
+        store.set_fuel(FUEL_PER_CALL)?;
+        
+        let instance = Instance::new(&mut store, &self.module, &[]).context("فشل في تشغيل الإضافة")?;
+        let memory = instance
+            .get_memory(&mut store, "memory")
+            .ok_or_else(|| anyhow::anyhow!("الإضافة لا تصدر memory"))?;
+        
+        Ok(PluginInstance { store, instance, memory })
+    }
+    
+    /// بناء طلب HTTP خام لمحاولة واحدة
+    pub fn build_request(&self, username: &str, password: &str) -> Result<String> {
+        let mut plugin = self.instantiate()?;
+        let build = plugin.build_request_fn()?;
+        
+        let (user_ptr, user_len) = plugin.write(username.as_bytes())?;
+        let (pass_ptr, pass_len) = plugin.write(password.as_bytes())?;
+        let packed = build
+            .call(&mut plugin.store, (user_ptr, user_len, pass_ptr, pass_len))
+            .context(format!("فشل redfox_build_request في الإضافة {}", self.name))?;
+        
+        plugin.read_string(packed)
+    }
+    
+    /// تصنيف الاستجابة
+    pub fn check_response(&self, status: u16, body: &[u8]) -> Result<PluginVerdict> {
+        let mut plugin = self.instantiate()?;
+        let check = plugin.check_response_fn()?;
+        
+        let body = &body[..body.len().min(MAX_BODY_BYTES)];
+        let (body_ptr, body_len) = plugin.write(body)?;
+        let verdict = check
+            .call(&mut plugin.store, (i32::from(status), body_ptr, body_len))
+            .context(format!("فشل redfox_check_response في الإضافة {}", self.name))?;
+        
+        Ok(match verdict {
+            1 => PluginVerdict::Success,
+            -1 => PluginVerdict::Lockout,
+            _ => PluginVerdict::Failure,
+        })
+    }
+}
+
+impl PluginInstance {
+    /// دالة بناء الطلب
+    fn build_request_fn(&mut self) -> Result<TypedFunc<(i32, i32, i32, i32), i64>> {
+        self.instance
+            .get_typed_func(&mut self.store, "redfox_build_request")
+            .context("الإضافة لا تصدر redfox_build_request(i32, i32, i32, i32) -> i64")
+    }
+    
+    /// دالة تصنيف الاستجابة
+    fn check_response_fn(&mut self) -> Result<TypedFunc<(i32, i32, i32), i32>> {
+        self.instance
+            .get_typed_func(&mut self.store, "redfox_check_response")
+            .context("الإضافة لا تصدر redfox_check_response(i32, i32, i32) -> i32")
+    }
+    
+    /// نسخ بايتات إلى ذاكرة الإضافة عبر redfox_alloc
+    fn write(&mut self, bytes: &[u8]) -> Result<(i32, i32)> {
+        let alloc: TypedFunc<i32, i32> = self
+            .instance
+            .get_typed_func(&mut self.store, "redfox_alloc")
+            .context("الإضافة لا تصدر redfox_alloc(i32) -> i32")?;
+        
+        let len = i32::try_from(bytes.len()).context("بيانات أكبر من ذاكرة الإضافة")?;
+        let ptr = alloc.call(&mut self.store, len)?;
+        self.memory
+            .write(&mut self.store, ptr as u32 as usize, bytes)
+            .context("redfox_alloc أعاد مؤشرًا خارج الذاكرة")?;
+        
+        Ok((ptr, len))
+    }
+    
+    /// قراءة نص UTF-8 من مؤشر وطول مدمجين
+    fn read_string(&mut self, packed: i64) -> Result<String> {
+        let (ptr, len) = unpack(packed);
+        let mut buffer = vec![0; len];
+        self.memory
+            .read(&self.store, ptr, &mut buffer)
+            .context("الإضافة أعادت نطاقًا خارج الذاكرة")?;
+        
+        String::from_utf8(buffer).context("الإضافة أعادت نصًا غير صالح (UTF-8)")
+    }
+}
+
+/// فك المؤشر والطول المدمجين `(ptr << 32) | len`
+fn unpack(packed: i64) -> (usize, usize) {
+    let packed = packed as u64;
+    ((packed >> 32) as usize, (packed & 0xFFFF_FFFF) as usize)
+}
+
+/// وحدة مصادقة تنفذ منطق الإضافة فوق عميل HTTP الماسح (البروكسي وحد المعدل والمهلة كما هي)
+pub struct WasmPlugin {
+    runtime: Arc<PluginRuntime>,
+    http: Arc<HttpClient>,
+}
+
+impl WasmPlugin {
+    /// ربط إضافة بعميل HTTP
+    pub fn new(runtime: PluginRuntime, http: Arc<HttpClient>) -> Self {
+        Self { runtime: Arc::new(runtime), http }
+    }
+    
+    /// اسم الإضافة
+    pub fn name(&self) -> &str {
+        self.runtime.name()
+    }
+}
+
+#[async_trait::async_trait]
+impl AuthModule for WasmPlugin {
+    fn endpoint(&self) -> String {
+        format!("{} ({})", self.http.base_url, self.runtime.name())
+    }
+    
+    async fn try_credential(&self, username: &str, password: &str) -> Result<AttemptOutcome> {
+        // استدعاءات WASM محدودة بالوقود لكنها متزامنة، فتُنفذ خارج خيوط المفاعل
+        let runtime = Arc::clone(&self.runtime);
+        let (user, pass) = (username.to_string(), password.to_string());
+        let raw = tokio::task::spawn_blocking(move || runtime.build_request(&user, &pass)).await??;
+        
+        let rendered = RawRequest::parse(&raw)
+            .context(format!("الإضافة {} أعادت طلبًا غير صالح", self.runtime.name()))?
+            .render(&self.http.base_url, username, password)?;
+        
+        let response = self.http.send_rendered(rendered).await?;
+        let status_code = response.status().as_u16();
+        let http_version = format!("{:?}", response.version());
+        let redirect = response
+            .headers()
+            .get(reqwest::header::LOCATION)
+            .and_then(|location| location.to_str().ok())
+            .map(str::to_string);
+        let body = response.bytes().await.context("فشل في قراءة الاستجابة")?;
+        
+        let runtime = Arc::clone(&self.runtime);
+        let verdict = tokio::task::spawn_blocking(move || runtime.check_response(status_code, &body)).await??;
+        
+        if verdict == PluginVerdict::Lockout {
+            return Err(RedFoxError::LockoutDetected(self.runtime.name().to_string()).into());
+        }
+        
+        Ok(AttemptOutcome {
+            success: verdict == PluginVerdict::Success,
+            status_code,
+            http_version: Some(http_version),
+            redirect,
+            ..Default::default()
+        })
+    }
+}
+
+/// الإضافات المتاحة في المجلد الافتراضي (ملفات .wasm مرتبة بالاسم)
+pub fn discover() -> Vec<PathBuf> {
+    let dir = PathBuf::from(shellexpand::tilde(DEFAULT_PLUGIN_DIR).as_ref());
+    let Ok(entries) = std::fs::read_dir(&dir) else {
+        return Vec::new();
+    };
+    
+    let mut plugins: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("wasm")))
+        .collect();
+    plugins.sort();
+    plugins
+}
+
+/// إيجاد إضافة بالمسار أو بالاسم في المجلد الافتراضي (مثال: --plugin radius)
+pub fn resolve(reference: &str) -> Result<PathBuf> {
+    let path = PathBuf::from(shellexpand::tilde(reference).as_ref());
+    if path.is_file() {
+        return Ok(path);
+    }
+    
+    discover()
+        .into_iter()
+        .find(|candidate| candidate.file_stem().is_some_and(|stem| stem == reference))
+        .ok_or_else(|| anyhow::anyhow!("الإضافة غير موجودة: {} (ابحث في {})", reference, DEFAULT_PLUGIN_DIR))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_plugin_builds_request_and_checks_status() {
+        // إضافة بسيطة: طلب ثابت، والنجاح عند إعادة التوجيه 302
+        let wat = r#"
+            (module
+              (memory (export "memory") 1)
+              (data (i32.const 0) "POST /login HTTP/1.1\0aHost: x\0a\0auser=^USER^&pass=^PASS^")
+              (data (i32.const 512) "demo")
+              (global $next (mut i32) (i32.const 1024))
+              (func (export "redfox_alloc") (param $len i32) (result i32)
+                (local $ptr i32)
+                (local.set $ptr (global.get $next))
+                (global.set $next (i32.add (global.get $next) (local.get $len)))
+                (local.get $ptr))
+              (func (export "redfox_name") (result i64)
+                (i64.or (i64.shl (i64.const 512) (i64.const 32)) (i64.const 4)))
+              (func (export "redfox_build_request") (param i32 i32 i32 i32) (result i64)
+                (i64.const 53))
+              (func (export "redfox_check_response") (param $status i32) (param i32 i32) (result i32)
+                (i32.eq (local.get $status) (i32.const 302))))
+        "#;
+        
+        let runtime = PluginRuntime::from_bytes("fallback", wat.as_bytes()).unwrap();
+        
+        assert_eq!(runtime.name(), "demo");
+        assert!(runtime.build_request("admin", "secret").unwrap().starts_with("POST /login"));
+        assert_eq!(runtime.check_response(302, b"").unwrap(), PluginVerdict::Success);
+        assert_eq!(runtime.check_response(200, b"").unwrap(), PluginVerdict::Failure);
+    }
+}
@@ -0,0 +1,162 @@
+//! ملف بيانات الاعتماد المكتشفة (potfile)
+//! سجل دائم لبيانات الاعتماد المؤكدة مفهرس حسب الهدف لتجنب إعادة تخمينها
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+use anyhow::{Result, Context};
+use tokio::fs as tokio_fs;
+use tokio::io::AsyncWriteExt;
+
+use crate::scanner::ScanResult;
+
+/// مسار ملف potfile الافتراضي
+pub const DEFAULT_POTFILE: &str = "~/.redfox/redfox.pot";
+
+/// سجل بيانات الاعتماد المؤكدة (سطر لكل هدف واسم مستخدم وكلمة مرور مفصولة بـ TAB)
+#[derive(Debug, Default)]
+pub struct Potfile {
+    path: PathBuf,
+    entries: HashMap<String, Vec<(String, String)>>,
+}
+
+impl Potfile {
+    /// فتح ملف potfile (الملف غير الموجود يعني سجلًا فارغًا)
+    pub async fn open(path: &str) -> Result<Self> {
+        let path = PathBuf::from(shellexpand::tilde(path).as_ref());
+        let mut potfile = Self { path, entries: HashMap::new() };
+        
+        if !potfile.path.exists() {
+            return Ok(potfile);
+        }
+        
+        let content = tokio_fs::read_to_string(&potfile.path)
+            .await
+            .context(format!("فشل في قراءة potfile: {}", potfile.path.display()))?;
+        
+        for (target, username, password) in content.lines().filter_map(parse_line) {
+            potfile.insert(&target, username, password);
+        }
+        
+        Ok(potfile)
+    }
+    
+    /// مسار الملف
+    pub fn path(&self) -> &std::path::Path {
+        &self.path
+    }
+    
+    /// بيانات الاعتماد المعروفة لهدف
+    pub fn known(&self, target: &str) -> &[(String, String)] {
+        self.entries.get(&target_key(target)).map_or(&[], Vec::as_slice)
+    }
+    
+    /// إلحاق النتائج الناجحة الجديدة بالملف وإرجاع عددها
+    pub async fn record(&mut self, target: &str, results: &[ScanResult]) -> Result<usize> {
+        let mut lines = String::new();
+        let mut added = 0;
+        
+        for result in results.iter().filter(|r| r.success) {
+            if self.insert(target, result.username.clone(), result.password.clone()) {
+                lines.push_str(&format!(
+                    "{}\t{}\t{}\n",
+                    escape(&target_key(target)),
+                    escape(&result.username),
+                    escape(&result.password)
+                ));
+                added += 1;
+            }
+        }
+        
+        if added == 0 {
+            return Ok(0);
+        }
+        
+        if let Some(parent) = self.path.parent() {
+            tokio_fs::create_dir_all(parent).await?;
+        }
+        
+        let mut file = tokio_fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .await
+            .context(format!("فشل في فتح potfile: {}", self.path.display()))?;
+        file.write_all(lines.as_bytes()).await?;
+        
+        Ok(added)
+    }
+    
+    /// إضافة إدخال إلى الذاكرة (false إن كان موجودًا)
+    fn insert(&mut self, target: &str, username: String, password: String) -> bool {
+        let known = self.entries.entry(target_key(target)).or_default();
+        let pair = (username, password);
+        
+        if known.contains(&pair) {
+            return false;
+        }
+        
+        known.push(pair);
+        true
+    }
+}
+
+/// مفتاح الهدف الموحد (بدون الشرطة المائلة الأخيرة وبأحرف صغيرة)
+fn target_key(target: &str) -> String {
+    target.trim().trim_end_matches('/').to_lowercase()
+}
+
+/// تحليل سطر target\tusername\tpassword
+fn parse_line(line: &str) -> Option<(String, String, String)> {
+    let mut fields = line.split('\t');
+    let (target, username, password) = (fields.next()?, fields.next()?, fields.next()?);
+    
+    if target.is_empty() || fields.next().is_some() {
+        return None;
+    }
+    
+    Some((unescape(target), unescape(username), unescape(password)))
+}
+
+/// تهريب TAB ونهاية السطر والشرطة المائلة العكسية
+fn escape(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('\t', "\\t").replace('\n', "\\n").replace('\r', "\\r")
+}
+
+/// عكس التهريب
+fn unescape(value: &str) -> String {
+    let mut output = String::with_capacity(value.len());
+    let mut chars = value.chars();
+    
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            output.push(c);
+            continue;
+        }
+        
+        match chars.next() {
+            Some('t') => output.push('\t'),
+            Some('n') => output.push('\n'),
+            Some('r') => output.push('\r'),
+            Some(other) => output.push(other),
+            None => output.push('\\'),
+        }
+    }
+    
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_line_roundtrip_with_special_chars() {
+        let line = format!("{}\t{}\t{}", escape("https://a.com/login"), escape("admin"), escape("p\ta\\ss"));
+        
+        assert_eq!(
+            parse_line(&line),
+            Some(("https://a.com/login".to_string(), "admin".to_string(), "p\ta\\ss".to_string()))
+        );
+        assert_eq!(target_key("HTTPS://A.com/login/"), "https://a.com/login");
+    }
+}
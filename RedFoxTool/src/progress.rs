@@ -2,11 +2,30 @@
 //! يوفر تتبعًا مرئيًا للتقدم
 
 use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::time::{Instant, Duration};
-use indicatif::{ProgressBar, ProgressStyle, MultiProgress, HumanDuration};
+use indicatif::{ProgressBar, ProgressDrawTarget, ProgressStyle, MultiProgress, HumanDuration};
 use tokio::sync::RwLock;
 use colored::Colorize;
 
+use crate::output;
+use crate::telemetry::Logger;
+
+/// شريط تقدم بالنمط الموحد (آمن للمشاركة بين الخيوط، ومخفي في الوضع الهادئ)
+pub fn new_bar(total_items: usize) -> ProgressBar {
+    let pb = ProgressBar::new(total_items as u64);
+    if !output::show_progress() {
+        pb.set_draw_target(ProgressDrawTarget::hidden());
+    }
+    pb.set_style(
+        ProgressStyle::default_bar()
+            .template("{spinner:.green} [{elapsed_precise}] [{bar:40.cyan/blue}] {pos}/{len} ({eta}) {msg}")
+            .unwrap_or_else(|_| ProgressStyle::default_bar())
+            .progress_chars("#>-")
+    );
+    pb
+}
+
 /// متعقب التقدم
 pub struct ProgressTracker {
     pb: Option<ProgressBar>,
@@ -21,14 +40,7 @@ impl ProgressTracker {
     /// إنشاء متعقب جديد
     pub fn new(total_items: usize) -> Self {
         let pb = if total_items > 100 {
-            let pb = ProgressBar::new(total_items as u64);
-            pb.set_style(
-                ProgressStyle::default_bar()
-                    .template("{spinner:.green} [{elapsed_precise}] [{bar:40.cyan/blue}] {pos}/{len} ({eta}) {msg}")
-                    .unwrap()
-                    .progress_chars("#>-")
-            );
-            Some(pb)
+            Some(new_bar(total_items))
         } else {
             None
         };
@@ -73,16 +85,20 @@ impl ProgressTracker {
             pb.finish_with_message("اكتمل!");
         }
         
+        if !output::show_progress() {
+            return;
+        }
+        
         let elapsed = self.start_time.elapsed();
         let speed = self.completed as f64 / elapsed.as_secs_f64();
         
-        println!(
+        Logger.print(&format!(
             "{}: {} عنصر في {} ({:.1} عنصر/ثانية)",
             "اكتمل".bright_green(),
             self.completed,
             HumanDuration(elapsed),
             speed
-        );
+        ));
     }
     
     /// الحصول على النسبة المئوية للتقدم
@@ -132,11 +148,15 @@ impl ProgressTracker {
     
     /// عرض حالة التقدم
     pub fn display_status(&self) {
+        if !output::show_progress() {
+            return;
+        }
+        
         let percentage = self.percentage();
         let elapsed = self.start_time.elapsed();
         let speed = self.average_speed();
         
-        println!(
+        Logger.print(&format!(
             "{}: {:.1}% | {} / {} | {:.1}/ثانية | {}",
             "التقدم".bright_cyan(),
             percentage,
@@ -144,10 +164,10 @@ impl ProgressTracker {
             self.total_items,
             speed,
             HumanDuration(elapsed)
-        );
+        ));
         
         if let Some(eta) = self.eta() {
-            println!("{}: {}", "الوقت المتبقي".bright_yellow(), HumanDuration(eta));
+            Logger.print(&format!("{}: {}", "الوقت المتبقي".bright_yellow(), HumanDuration(eta)));
         }
     }
 }
@@ -158,11 +178,21 @@ pub struct MultiProgressTracker {
     trackers: Vec<Arc<RwLock<ProgressTracker>>>,
 }
 
+impl Default for MultiProgressTracker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl MultiProgressTracker {
     /// إنشاء متعقب متعدد
     pub fn new() -> Self {
         Self {
-            multi: MultiProgress::new(),
+            multi: if output::show_progress() {
+                MultiProgress::new()
+            } else {
+                MultiProgress::with_draw_target(ProgressDrawTarget::hidden())
+            },
             trackers: Vec::new(),
         }
     }
@@ -173,7 +203,7 @@ impl MultiProgressTracker {
         pb.set_style(
             ProgressStyle::default_bar()
                 .template(&format!("{{spinner:.green}} {} [{{bar:40.cyan/blue}}] {{pos}}/{{len}} ({{eta}})", name))
-                .unwrap()
+                .unwrap_or_else(|_| ProgressStyle::default_bar())
                 .progress_chars("#>-")
         );
         
@@ -194,7 +224,7 @@ impl MultiProgressTracker {
     
     /// إنهاء جميع المهام
     pub fn finish_all(&self) {
-        self.multi.clear().unwrap();
+        let _ = self.multi.clear();
     }
 }
 
@@ -232,6 +262,10 @@ impl SimpleProgress {
     
     /// طباعة الحالة
     fn print_status(&self) {
+        if !output::show_progress() {
+            return;
+        }
+        
         let elapsed = self.start_time.elapsed();
         let percentage = (self.current as f64 / self.total as f64) * 100.0;
         let speed = self.current as f64 / elapsed.as_secs_f64();
@@ -252,7 +286,7 @@ impl SimpleProgress {
             }
         }
         
-        std::io::Write::flush(&mut std::io::stdout()).unwrap();
+        let _ = std::io::Write::flush(&mut std::io::stdout());
     }
     
     /// تقدير الوقت المتبقي
@@ -276,7 +310,141 @@ impl SimpleProgress {
     pub fn finish(&mut self) {
         self.current = self.total;
         self.print_status();
-        println!(); // سطر جديد بعد الانتهاء
+        if output::show_progress() {
+            Logger.print(""); // سطر جديد بعد الانتهاء
+        }
+    }
+}
+
+/// الفاصل الافتراضي بين لقطات التقدم المرسلة للمراقبين
+pub const DEFAULT_PROGRESS_INTERVAL: Duration = Duration::from_secs(1);
+
+/// لقطة تقدم دورية (للواجهات الرسومية ووضع الخادم بدل تحليل مخرجات الطرفية)
+#[derive(Debug, Clone, Copy, PartialEq, serde::Serialize)]
+pub struct ProgressSnapshot {
+    /// المحاولات المكتملة
+    pub completed: usize,
+    /// إجمالي المحاولات المتوقعة
+    pub total: usize,
+    /// المحاولات في الثانية منذ البداية
+    pub rps: f64,
+    /// الوقت المتبقي المقدر (None قبل أول نتيجة)
+    pub eta: Option<Duration>,
+}
+
+impl ProgressSnapshot {
+    /// حساب المعدل والوقت المتبقي من العدادات والوقت المنقضي
+    pub fn compute(completed: usize, total: usize, elapsed: Duration) -> Self {
+        let secs = elapsed.as_secs_f64();
+        let rps = if secs > 0.0 { completed as f64 / secs } else { 0.0 };
+        let remaining = total.saturating_sub(completed);
+        
+        let eta = if remaining == 0 {
+            Some(Duration::ZERO)
+        } else if rps > 0.0 {
+            Some(Duration::from_secs_f64(remaining as f64 / rps))
+        } else {
+            None
+        };
+        
+        Self { completed, total, rps, eta }
+    }
+    
+    /// نسبة الإنجاز المئوية
+    pub fn percentage(&self) -> f64 {
+        if self.total == 0 {
+            return 100.0;
+        }
+        (self.completed as f64 / self.total as f64) * 100.0
+    }
+}
+
+/// مراقب يتلقى لقطات التقدم الدورية من الماسح
+pub trait ProgressObserver: Send + Sync {
+    /// استقبال لقطة تقدم
+    fn on_progress(&self, snapshot: &ProgressSnapshot);
+}
+
+impl<F> ProgressObserver for F
+where
+    F: Fn(&ProgressSnapshot) + Send + Sync,
+{
+    fn on_progress(&self, snapshot: &ProgressSnapshot) {
+        self(snapshot)
+    }
+}
+
+/// إرسال لقطات دورية لكل مراقب بفاصله الخاص حتى الإنهاء (تتوقف المهام عند الإسقاط)
+pub(crate) struct ProgressReporter {
+    observers: Vec<Arc<dyn ProgressObserver>>,
+    tasks: Vec<tokio::task::JoinHandle<()>>,
+    completed: Arc<AtomicUsize>,
+    total: Arc<AtomicUsize>,
+    start: Instant,
+}
+
+impl ProgressReporter {
+    /// بدء مهمة إرسال لكل مراقب
+    pub(crate) fn start(
+        observers: &[(Arc<dyn ProgressObserver>, Duration)],
+        completed: Arc<AtomicUsize>,
+        total: Arc<AtomicUsize>,
+    ) -> Self {
+        let start = Instant::now();
+        
+        let tasks = observers
+            .iter()
+            .map(|(observer, interval)| {
+                let observer = observer.clone();
+                let (completed, total) = (completed.clone(), total.clone());
+                let interval = (*interval).max(Duration::from_millis(10));
+                
+                tokio::spawn(async move {
+                    let mut ticker = tokio::time::interval(interval);
+                    ticker.tick().await;
+                    loop {
+                        ticker.tick().await;
+                        observer.on_progress(&ProgressSnapshot::compute(
+                            completed.load(Ordering::Relaxed),
+                            total.load(Ordering::Relaxed),
+                            start.elapsed(),
+                        ));
+                    }
+                })
+            })
+            .collect();
+        
+        Self {
+            observers: observers.iter().map(|(observer, _)| observer.clone()).collect(),
+            tasks,
+            completed,
+            total,
+            start,
+        }
+    }
+    
+    /// إيقاف الإرسال الدوري وإرسال لقطة أخيرة
+    pub(crate) fn finish(self) {
+        for task in &self.tasks {
+            task.abort();
+        }
+        
+        let snapshot = ProgressSnapshot::compute(
+            self.completed.load(Ordering::Relaxed),
+            self.total.load(Ordering::Relaxed),
+            self.start.elapsed(),
+        );
+        for observer in &self.observers {
+            observer.on_progress(&snapshot);
+        }
+    }
+}
+
+impl Drop for ProgressReporter {
+    fn drop(&mut self) {
+        for task in &self.tasks {
+            task.abort();
+        }
     }
 }
 
@@ -305,11 +473,25 @@ mod tests {
     fn test_simple_progress() {
         let mut progress = SimpleProgress::new(500);
         
-        for i in 0..5 {
+        for _ in 0..5 {
             progress.update(100);
             thread::sleep(Duration::from_millis(100));
         }
         
         progress.finish();
     }
+    
+    #[test]
+    fn test_snapshot_rate_and_eta() {
+        let snapshot = ProgressSnapshot::compute(50, 150, Duration::from_secs(10));
+        assert_eq!(snapshot.rps, 5.0);
+        assert_eq!(snapshot.eta, Some(Duration::from_secs(20)));
+        
+        let idle = ProgressSnapshot::compute(0, 150, Duration::ZERO);
+        assert_eq!(idle.rps, 0.0);
+        assert_eq!(idle.eta, None);
+        
+        let done = ProgressSnapshot::compute(150, 150, Duration::from_secs(30));
+        assert_eq!(done.eta, Some(Duration::ZERO));
+    }
 }
\ No newline at end of file
@@ -0,0 +1,273 @@
+//! فاحص البروكسيات
+//! يتحقق من البروكسيات (الاتصال، زمن الاستجابة، عنوان الخروج، مستوى إخفاء الهوية)
+
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use anyhow::{Result, Context};
+use colored::Colorize;
+use reqwest::{Client, ClientBuilder, Proxy};
+use serde_json::Value;
+use tokio::sync::Semaphore;
+
+use crate::parser::parse_input;
+use crate::telemetry::Logger;
+use crate::validator::validate_proxy;
+
+/// نقطة الفحص الافتراضية (تعيد عنوان المصدر والترويسات المستلمة)
+pub const DEFAULT_CHECK_URL: &str = "https://httpbin.org/get";
+
+/// ترويسات تكشف وجود بروكسي
+const PROXY_HEADERS: [&str; 5] = ["Via", "X-Forwarded-For", "Forwarded", "X-Real-Ip", "Proxy-Connection"];
+
+/// مستوى إخفاء الهوية
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, serde::Serialize)]
+pub enum AnonymityLevel {
+    /// يكشف عنوان IP الحقيقي
+    Transparent,
+    /// يخفي العنوان لكن يكشف وجود بروكسي
+    Anonymous,
+    /// لا يكشف العنوان ولا وجود بروكسي
+    Elite,
+}
+
+impl std::str::FromStr for AnonymityLevel {
+    type Err = String;
+    
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "transparent" => Ok(AnonymityLevel::Transparent),
+            "anonymous" => Ok(AnonymityLevel::Anonymous),
+            "elite" => Ok(AnonymityLevel::Elite),
+            _ => Err(format!("مستوى إخفاء غير صالح: {}", s)),
+        }
+    }
+}
+
+impl std::fmt::Display for AnonymityLevel {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AnonymityLevel::Transparent => write!(f, "transparent"),
+            AnonymityLevel::Anonymous => write!(f, "anonymous"),
+            AnonymityLevel::Elite => write!(f, "elite"),
+        }
+    }
+}
+
+/// نتيجة فحص بروكسي واحد
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct ProxyCheckResult {
+    /// رابط البروكسي
+    pub proxy: String,
+    /// هل يعمل البروكسي؟
+    pub alive: bool,
+    /// زمن الاستجابة
+    pub latency: Duration,
+    /// عنوان IP الخارجي
+    pub exit_ip: Option<String>,
+    /// مستوى إخفاء الهوية
+    pub anonymity: Option<AnonymityLevel>,
+    /// رسالة الخطأ إذا فشل
+    pub error: Option<String>,
+}
+
+/// بناء عميل للفحص
+fn build_client(proxy: Option<&str>, timeout: Duration) -> Result<Client> {
+    let mut builder = ClientBuilder::new()
+        .timeout(timeout)
+        .connect_timeout(timeout)
+        .use_rustls_tls();
+    
+    if let Some(proxy_url) = proxy {
+        builder = builder.proxy(Proxy::all(proxy_url).context("فشل في إنشاء بروكسي")?);
+    }
+    
+    builder.build().context("فشل في بناء عميل HTTP")
+}
+
+/// استخراج عنوان المصدر من استجابة نقطة الفحص
+fn origin_of(body: &Value) -> Option<String> {
+    body.get("origin")
+        .or_else(|| body.get("ip"))
+        .and_then(Value::as_str)
+        .map(|origin| origin.to_string())
+}
+
+/// تحديد مستوى إخفاء الهوية
+fn classify(body: &Value, real_ip: Option<&str>) -> AnonymityLevel {
+    let origin = origin_of(body).unwrap_or_default();
+    
+    if real_ip.map(|ip| origin.contains(ip)).unwrap_or(false) {
+        return AnonymityLevel::Transparent;
+    }
+    
+    let headers = body.get("headers").and_then(Value::as_object);
+    let leaks = headers
+        .map(|h| {
+            h.keys().any(|key| PROXY_HEADERS.iter().any(|p| key.eq_ignore_ascii_case(p)))
+        })
+        .unwrap_or(false);
+    
+    if leaks {
+        AnonymityLevel::Anonymous
+    } else {
+        AnonymityLevel::Elite
+    }
+}
+
+/// فحص بروكسي واحد
+async fn check_proxy(
+    proxy: &str,
+    check_url: &str,
+    timeout: Duration,
+    real_ip: Option<&str>,
+) -> ProxyCheckResult {
+    let mut result = ProxyCheckResult {
+        proxy: proxy.to_string(),
+        alive: false,
+        latency: Duration::default(),
+        exit_ip: None,
+        anonymity: None,
+        error: None,
+    };
+    
+    let client = match build_client(Some(proxy), timeout) {
+        Ok(client) => client,
+        Err(e) => {
+            result.error = Some(e.to_string());
+            return result;
+        }
+    };
+    
+    let start = Instant::now();
+    let response = match client.get(check_url).send().await {
+        Ok(response) => response,
+        Err(e) => {
+            result.error = Some(e.to_string());
+            return result;
+        }
+    };
+    result.latency = start.elapsed();
+    
+    if !response.status().is_success() {
+        result.error = Some(format!("رمز حالة غير متوقع: {}", response.status()));
+        return result;
+    }
+    
+    match response.json::<Value>().await {
+        Ok(body) => {
+            result.alive = true;
+            result.exit_ip = origin_of(&body);
+            result.anonymity = Some(classify(&body, real_ip));
+        }
+        Err(e) => {
+            result.error = Some(format!("استجابة غير صالحة من نقطة الفحص: {}", e));
+        }
+    }
+    
+    result
+}
+
+/// فحص قائمة بروكسيات بالتوازي
+pub async fn check_all(
+    proxy_source: &str,
+    check_url: &str,
+    timeout_secs: u64,
+    concurrency: usize,
+) -> Result<Vec<ProxyCheckResult>> {
+    let timeout = Duration::from_secs(timeout_secs);
+    
+    let proxies: Vec<String> = parse_input(proxy_source)
+        .await
+        .context("فشل في تحليل قائمة البروكسيات")?
+        .into_iter()
+        .filter(|proxy| validate_proxy(proxy).is_valid)
+        .collect();
+    
+    if proxies.is_empty() {
+        return Err(anyhow::anyhow!("لا توجد بروكسيات صالحة في: {}", proxy_source));
+    }
+    
+    // الحصول على عنوان IP الحقيقي للمقارنة
+    let real_ip = match build_client(None, timeout)?.get(check_url).send().await {
+        Ok(response) => response.json::<Value>().await.ok().and_then(|b| origin_of(&b)),
+        Err(e) => {
+            tracing::warn!("تعذر تحديد عنوان IP الحقيقي: {}", e);
+            None
+        }
+    };
+    let real_ip = Arc::new(real_ip);
+    
+    let semaphore = Arc::new(Semaphore::new(concurrency.max(1)));
+    let mut handles = Vec::with_capacity(proxies.len());
+    
+    for proxy in proxies {
+        let semaphore = Arc::clone(&semaphore);
+        let real_ip = Arc::clone(&real_ip);
+        let check_url = check_url.to_string();
+        
+        handles.push(tokio::spawn(async move {
+            let _permit = semaphore.acquire().await;
+            check_proxy(&proxy, &check_url, timeout, real_ip.as_deref()).await
+        }));
+    }
+    
+    let mut results = Vec::with_capacity(handles.len());
+    for handle in handles {
+        results.push(handle.await?);
+    }
+    
+    // ترتيب: العاملة أولاً حسب زمن الاستجابة
+    results.sort_by_key(|r| (!r.alive, r.latency));
+    
+    Ok(results)
+}
+
+/// كتابة البروكسيات العاملة إلى ملف جاهز لـ --proxy-file
+pub async fn write_proxy_file(
+    results: &[ProxyCheckResult],
+    output: &str,
+    min_anonymity: Option<AnonymityLevel>,
+) -> Result<usize> {
+    let working: Vec<&str> = results
+        .iter()
+        .filter(|r| r.alive)
+        .filter(|r| min_anonymity.map_or(true, |min| r.anonymity.is_some_and(|a| a >= min)))
+        .map(|r| r.proxy.as_str())
+        .collect();
+    
+    let mut content = working.join("\n");
+    content.push('\n');
+    
+    tokio::fs::write(output, content)
+        .await
+        .context(format!("فشل في كتابة الملف: {}", output))?;
+    
+    Ok(working.len())
+}
+
+/// عرض نتائج الفحص
+pub fn display_results(results: &[ProxyCheckResult]) {
+    Logger.print(&format!("\n{}", "نتائج فحص البروكسيات:".bright_green().bold()));
+    Logger.print(&"-".repeat(80).bright_blue().to_string());
+    
+    for result in results {
+        if result.alive {
+            Logger.print(&format!(
+                "✓ {:<40} {:>8.0?} {:<16} {}",
+                result.proxy.bright_cyan(),
+                result.latency,
+                result.exit_ip.as_deref().unwrap_or("-"),
+                result.anonymity.map(|a| a.to_string()).unwrap_or_default().bright_yellow()
+            ));
+        } else {
+            Logger.print(&format!(
+                "✗ {:<40} {}",
+                result.proxy,
+                result.error.as_deref().unwrap_or("غير معروف").bright_red()
+            ));
+        }
+    }
+    
+    let alive = results.iter().filter(|r| r.alive).count();
+    Logger.print(&format!("\nالبروكسيات العاملة: {} / {}", alive.to_string().bright_green(), results.len()));
+}
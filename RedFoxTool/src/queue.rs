@@ -0,0 +1,196 @@
+//! طابور العمل الموزع (Redis)
+//! المنسق يدفع دفعات بيانات الاعتماد والعمال يسحبونها ويعيدون النتائج دون اتصال مباشر بينهما
+
+use std::collections::HashMap;
+use anyhow::{Context, Result};
+use futures::stream::{BoxStream, Stream, TryStreamExt};
+use redis::AsyncCommands;
+
+use crate::scanner::{RedFoxScanner, ScanResult};
+use crate::session::SessionConfig;
+use crate::telemetry::Logger;
+
+/// حجم الدفعة الافتراضي (أزواج لكل سحب)
+pub const DEFAULT_BATCH_SIZE: usize = 100;
+
+/// بادئة مفاتيح Redis
+const PREFIX: &str = "redfox";
+
+/// الطابور المشترك للدفعات (من كل المهام)
+const BATCHES_KEY: &str = "redfox:batches";
+
+/// مهلة انتظار النتائج قبل التحقق من اكتمال الدفعات (ثوانٍ)
+const POLL_TIMEOUT_SECS: f64 = 1.0;
+
+/// مدة بقاء مفاتيح المهمة في Redis (ثوانٍ)
+const JOB_TTL_SECS: i64 = 24 * 60 * 60;
+
+/// دفعة بيانات اعتماد في الطابور
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+struct Batch {
+    job: String,
+    pairs: Vec<(String, String)>,
+}
+
+/// مفتاح خاص بمهمة (config، results، done)
+fn job_key(job: &str, name: &str) -> String {
+    format!("{}:{}:{}", PREFIX, job, name)
+}
+
+/// تقسيم بث الأزواج إلى دفعات بالحجم المحدد (لا تُحمّل في الذاكرة إلا دفعة واحدة في كل مرة)
+pub(crate) fn batches(
+    pairs: BoxStream<'static, Result<(String, String)>>,
+    size: usize,
+) -> impl Stream<Item = Result<Vec<(String, String)>>> + Unpin {
+    pairs.try_chunks(size.max(1)).map_err(|e| e.1)
+}
+
+/// الاتصال بخادم Redis
+async fn connect(queue_url: &str) -> Result<redis::aio::MultiplexedConnection> {
+    redis::Client::open(queue_url)
+        .context(format!("رابط Redis غير صالح: {}", queue_url))?
+        .get_multiplexed_async_connection()
+        .await
+        .context(format!("فشل في الاتصال بـ Redis: {}", queue_url))
+}
+
+/// توزيع الأزواج على العمال عبر الطابور وجمع نتائجهم (المنسق)
+///
+/// الإيقاف عند النجاح يُطبق داخل كل دفعة فقط، لا بين العمال.
+pub async fn coordinate(
+    queue_url: &str,
+    config: &SessionConfig,
+    pairs: BoxStream<'static, Result<(String, String)>>,
+    batch_size: usize,
+) -> Result<Vec<ScanResult>> {
+    let logger = Logger;
+    let mut conn = connect(queue_url).await?;
+    
+    let job = format!("{:016x}", rand::random::<u64>());
+    let config_json = serde_json::to_string(config).context("فشل في ترميز إعدادات الفحص")?;
+    conn.set_ex::<_, _, ()>(job_key(&job, "config"), config_json, JOB_TTL_SECS as u64).await?;
+    
+    // الدفعات تُدفع فور توليدها فيبدأ العمال قبل اكتمال البث
+    let mut pending = batches(pairs, batch_size);
+    let mut total = 0;
+    while let Some(pairs) = pending.try_next().await? {
+        let batch = serde_json::to_string(&Batch { job: job.clone(), pairs })?;
+        conn.rpush::<_, _, ()>(BATCHES_KEY, batch).await?;
+        total += 1;
+    }
+    
+    logger.info(&format!("المهمة {}: {} دفعة في الطابور، بانتظار العمال", job, total));
+    
+    let results_key = job_key(&job, "results");
+    let done_key = job_key(&job, "done");
+    let mut results = Vec::new();
+    let mut reported = 0;
+    
+    loop {
+        let popped: Option<(String, String)> = conn.blpop(&results_key, POLL_TIMEOUT_SECS).await?;
+        if let Some((_, payload)) = popped {
+            results.push(serde_json::from_str(&payload).context("نتيجة عامل غير صالحة")?);
+            continue;
+        }
+        
+        let done: usize = conn.get::<_, Option<usize>>(&done_key).await?.unwrap_or(0);
+        if done != reported {
+            logger.info(&format!("المهمة {}: {}/{} دفعة مكتملة", job, done, total));
+            reported = done;
+        }
+        
+        // العامل يدفع النتائج قبل زيادة العداد، فما تبقى في القائمة هو آخر النتائج
+        if done >= total {
+            while let Some(payload) = conn.lpop::<_, Option<String>>(&results_key, None).await? {
+                results.push(serde_json::from_str(&payload).context("نتيجة عامل غير صالحة")?);
+            }
+            break;
+        }
+    }
+    
+    conn.del::<_, ()>(&[job_key(&job, "config"), done_key, results_key]).await?;
+    Ok(results)
+}
+
+/// سحب الدفعات وتنفيذها وإعادة نتائجها حتى الإيقاف (العامل)
+pub async fn work(queue_url: &str) -> Result<()> {
+    let logger = Logger;
+    let mut conn = connect(queue_url).await?;
+    let mut configs: HashMap<String, SessionConfig> = HashMap::new();
+    
+    logger.success(&format!("العامل متصل بالطابور: {}", queue_url));
+    
+    loop {
+        let popped: Option<(String, String)> = conn.blpop(BATCHES_KEY, 0.0).await?;
+        let Some((_, payload)) = popped else {
+            continue;
+        };
+        let batch: Batch = match serde_json::from_str(&payload) {
+            Ok(batch) => batch,
+            Err(e) => {
+                logger.warn(&format!("دفعة غير صالحة في الطابور: {}", e));
+                continue;
+            }
+        };
+        
+        if !configs.contains_key(&batch.job) {
+            let Some(json) = conn.get::<_, Option<String>>(job_key(&batch.job, "config")).await? else {
+                logger.warn(&format!("المهمة {} منتهية أو محذوفة، تخطي الدفعة", batch.job));
+                continue;
+            };
+            let config = serde_json::from_str(&json).context("إعدادات مهمة غير صالحة")?;
+            configs.insert(batch.job.clone(), config);
+        }
+        let config = &configs[&batch.job];
+        
+        logger.info(&format!("المهمة {}: تنفيذ دفعة من {} زوج", batch.job, batch.pairs.len()));
+        let results = run_batch(config, batch.pairs).await;
+        
+        let results_key = job_key(&batch.job, "results");
+        for result in &results {
+            conn.rpush::<_, _, ()>(&results_key, serde_json::to_string(result)?).await?;
+        }
+        let done_key = job_key(&batch.job, "done");
+        conn.incr::<_, _, ()>(&done_key, 1).await?;
+        conn.expire::<_, ()>(&results_key, JOB_TTL_SECS).await?;
+        conn.expire::<_, ()>(&done_key, JOB_TTL_SECS).await?;
+    }
+}
+
+/// تنفيذ دفعة واحدة (فشل الدفعة يُعاد كنتيجة خطأ لكل زوج حتى لا يضيع من التقرير)
+pub(crate) async fn run_batch(config: &SessionConfig, pairs: Vec<(String, String)>) -> Vec<ScanResult> {
+    let scanned = match RedFoxScanner::from_config_pairs(config, pairs.clone()).await {
+        Ok(scanner) => scanner.scan(false).await,
+        Err(e) => Err(e),
+    };
+    
+    scanned.unwrap_or_else(|e| {
+        Logger.warn(&format!("فشلت الدفعة: {:#}", e));
+        pairs
+            .into_iter()
+            .map(|(username, password)| ScanResult {
+                username,
+                password,
+                error: Some(format!("{:#}", e)),
+                timestamp: chrono::Utc::now(),
+                ..Default::default()
+            })
+            .collect()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::StreamExt;
+    
+    #[tokio::test]
+    async fn test_batches_keep_remainder() {
+        let pairs = (0..7).map(|i| Ok((format!("user{}", i), "pass".to_string())));
+        
+        let batches: Vec<_> = batches(futures::stream::iter(pairs).boxed(), 3).try_collect().await.unwrap();
+        
+        assert_eq!(batches.iter().map(Vec::len).collect::<Vec<_>>(), vec![3, 3, 1]);
+        assert_eq!(batches[2][0].0, "user6");
+    }
+}
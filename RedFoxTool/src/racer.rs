@@ -0,0 +1,194 @@
+//! سباق الاتصالات (Happy Eyeballs)
+//! يختار أسرع عنوان من عناوين الهدف المحلولة ويتحول تلقائيًا عند تعطل أحدها
+
+use std::net::SocketAddr;
+use std::sync::atomic::{AtomicU32, AtomicUsize, Ordering};
+use std::time::Duration;
+use anyhow::{Result, Context};
+use reqwest::{Client, ClientBuilder};
+use tokio::net::TcpStream;
+use tokio::task::JoinSet;
+use url::Url;
+
+/// الفاصل الزمني بين بدء محاولات الاتصال (RFC 8305)
+const ATTEMPT_DELAY: Duration = Duration::from_millis(250);
+
+/// عدد الإخفاقات المتتالية قبل اعتبار العنوان معطلاً
+const FAILURE_THRESHOLD: u32 = 3;
+
+/// عنوان محلول مع عميل مثبت عليه
+struct AddressRoute {
+    addr: SocketAddr,
+    client: Client,
+}
+
+/// متسابق العناوين
+pub struct AddressRacer {
+    routes: Vec<AddressRoute>,
+    preferred: AtomicUsize,
+    consecutive_failures: AtomicU32,
+    connect_timeout: Duration,
+    racing: tokio::sync::Mutex<()>,
+}
+
+impl AddressRacer {
+    /// حل عناوين الهدف وبناء عميل لكل عنوان
+    pub async fn new(
+        base_url: &str,
+        connect_timeout: Duration,
+        builder: impl Fn() -> ClientBuilder,
+    ) -> Result<Self> {
+        let url = Url::parse(base_url).context("رابط غير صالح")?;
+        let host = url
+            .host_str()
+            .ok_or_else(|| anyhow::anyhow!("رابط غير صالح: لا يوجد نطاق"))?
+            .to_string();
+        let port = url.port_or_known_default().unwrap_or(80);
+        
+        let resolved: Vec<SocketAddr> = tokio::net::lookup_host((host.as_str(), port))
+            .await
+            .context(format!("فشل في حل اسم المضيف: {}", host))?
+            .collect();
+        
+        if resolved.is_empty() {
+            return Err(anyhow::anyhow!("لم يتم العثور على عناوين للمضيف: {}", host));
+        }
+        
+        let routes = interleave_families(resolved)
+            .into_iter()
+            .map(|addr| {
+                Ok(AddressRoute {
+                    addr,
+                    client: builder()
+                        .resolve(&host, addr)
+                        .build()
+                        .context("فشل في بناء عميل HTTP")?,
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+        
+        let racer = Self {
+            routes,
+            preferred: AtomicUsize::new(0),
+            consecutive_failures: AtomicU32::new(0),
+            connect_timeout,
+            racing: tokio::sync::Mutex::new(()),
+        };
+        
+        racer.race(None).await;
+        Ok(racer)
+    }
+    
+    /// العناوين المحلولة
+    pub fn addresses(&self) -> Vec<SocketAddr> {
+        self.routes.iter().map(|r| r.addr).collect()
+    }
+    
+    /// العنوان المفضل حاليًا
+    pub fn current_address(&self) -> SocketAddr {
+        self.routes[self.preferred.load(Ordering::Relaxed)].addr
+    }
+    
+    /// العميل المثبت على العنوان المفضل حاليًا
+    pub fn current(&self) -> &Client {
+        &self.routes[self.preferred.load(Ordering::Relaxed)].client
+    }
+    
+    /// تسجيل طلب ناجح
+    pub fn record_success(&self) {
+        self.consecutive_failures.store(0, Ordering::Relaxed);
+    }
+    
+    /// تسجيل فشل في الاتصال، وإعادة السباق عند تجاوز الحد
+    pub async fn record_failure(&self) {
+        let failures = self.consecutive_failures.fetch_add(1, Ordering::Relaxed) + 1;
+        
+        if failures >= FAILURE_THRESHOLD && self.routes.len() > 1 {
+            let failing = self.preferred.load(Ordering::Relaxed);
+            tracing::warn!(
+                "العنوان {} لا يستجيب ({} إخفاقات متتالية)، إعادة السباق",
+                self.routes[failing].addr,
+                failures
+            );
+            self.race(Some(failing)).await;
+        }
+    }
+    
+    /// تشغيل سباق الاتصال واختيار أول عنوان يكمل المصافحة
+    async fn race(&self, exclude: Option<usize>) {
+        // منع عدة سباقات متزامنة من مهام مختلفة
+        let Ok(_guard) = self.racing.try_lock() else {
+            return;
+        };
+        
+        let mut attempts = JoinSet::new();
+        
+        for (stagger, (index, route)) in self
+            .routes
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| Some(*i) != exclude)
+            .enumerate()
+        {
+            let addr = route.addr;
+            let connect_timeout = self.connect_timeout;
+            
+            attempts.spawn(async move {
+                tokio::time::sleep(ATTEMPT_DELAY * stagger as u32).await;
+                tokio::time::timeout(connect_timeout, TcpStream::connect(addr))
+                    .await
+                    .ok()
+                    .and_then(|connected| connected.ok())
+                    .map(|_| index)
+            });
+        }
+        
+        while let Some(attempt) = attempts.join_next().await {
+            if let Ok(Some(index)) = attempt {
+                attempts.abort_all();
+                
+                if self.preferred.swap(index, Ordering::Relaxed) != index {
+                    tracing::info!("التحويل إلى العنوان: {}", self.routes[index].addr);
+                }
+                self.consecutive_failures.store(0, Ordering::Relaxed);
+                return;
+            }
+        }
+        
+        tracing::warn!("لم يستجب أي عنوان في سباق الاتصال، الإبقاء على العنوان الحالي");
+    }
+}
+
+/// ترتيب العناوين بالتناوب بين IPv6 و IPv4 (IPv6 أولاً)
+fn interleave_families(addrs: Vec<SocketAddr>) -> Vec<SocketAddr> {
+    let (mut v6, mut v4): (Vec<_>, Vec<_>) = addrs.into_iter().partition(SocketAddr::is_ipv6);
+    v6.reverse();
+    v4.reverse();
+    
+    let mut ordered = Vec::with_capacity(v6.len() + v4.len());
+    while !v6.is_empty() || !v4.is_empty() {
+        ordered.extend(v6.pop());
+        ordered.extend(v4.pop());
+    }
+    
+    ordered
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_interleave_families() {
+        let addrs: Vec<SocketAddr> = ["10.0.0.1:80", "10.0.0.2:80", "[::1]:80"]
+            .iter()
+            .map(|a| a.parse().unwrap())
+            .collect();
+        
+        let ordered = interleave_families(addrs);
+        
+        assert!(ordered[0].is_ipv6());
+        assert_eq!(ordered[1].to_string(), "10.0.0.1:80");
+        assert_eq!(ordered[2].to_string(), "10.0.0.2:80");
+    }
+}
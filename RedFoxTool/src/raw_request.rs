@@ -0,0 +1,182 @@
+//! قوالب الطلبات الخام
+//! إعادة إرسال طلب HTTP خام (محفوظ من Burp مثلًا) لكل محاولة مع استبدال ^USER^ و ^PASS^
+
+use std::path::Path;
+use anyhow::{Result, Context};
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue, CONTENT_LENGTH, CONTENT_TYPE};
+use reqwest::Method;
+use url::Url;
+
+use crate::login_body::{form_escape, json_escape, xml_escape, PASS_PLACEHOLDER, USER_PLACEHOLDER};
+
+/// طلب HTTP خام محلل
+#[derive(Debug, Clone)]
+pub struct RawRequest {
+    method: String,
+    target: String,
+    headers: Vec<(String, String)>,
+    body: String,
+}
+
+/// طلب جاهز للإرسال بعد استبدال العناصر النائبة
+#[derive(Debug)]
+pub struct RenderedRequest {
+    /// طريقة HTTP
+    pub method: Method,
+    /// الرابط الكامل
+    pub url: Url,
+    /// الترويسات كما وردت في الملف (عدا Content-Length)
+    pub headers: HeaderMap,
+    /// جسم الطلب
+    pub body: String,
+}
+
+impl RawRequest {
+    /// تحميل طلب خام من ملف
+    pub async fn load(path: &Path) -> Result<Self> {
+        let content = tokio::fs::read_to_string(path)
+            .await
+            .context(format!("فشل في قراءة الطلب الخام: {}", path.display()))?;
+        
+        Self::parse(&content).context(format!("طلب خام غير صالح: {}", path.display()))
+    }
+    
+    /// تحليل نص طلب HTTP/1.x (سطر الطلب، الترويسات، سطر فارغ، الجسم)
+    pub fn parse(text: &str) -> Result<Self> {
+        let text = text.trim_start();
+        let (head, body) = match text.find("\r\n\r\n") {
+            Some(i) => (&text[..i], &text[i + 4..]),
+            None => match text.find("\n\n") {
+                Some(i) => (&text[..i], &text[i + 2..]),
+                None => (text, ""),
+            },
+        };
+        
+        let mut lines = head.lines();
+        let request_line = lines.next().unwrap_or_default();
+        let mut parts = request_line.split_whitespace();
+        let (method, target) = match (parts.next(), parts.next()) {
+            (Some(method), Some(target)) => (method.to_string(), target.to_string()),
+            _ => return Err(anyhow::anyhow!("سطر الطلب غير صالح: {}", request_line)),
+        };
+        
+        Method::from_bytes(method.as_bytes())
+            .map_err(|_| anyhow::anyhow!("طريقة HTTP غير صالحة: {}", method))?;
+        
+        let headers = lines
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| {
+                line.split_once(':')
+                    .map(|(name, value)| (name.trim().to_string(), value.trim().to_string()))
+                    .ok_or_else(|| anyhow::anyhow!("ترويسة غير صالحة: {}", line))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        
+        let request = Self {
+            method,
+            target,
+            headers,
+            body: body.to_string(),
+        };
+        
+        let has_placeholder = |s: &str| s.contains(USER_PLACEHOLDER) || s.contains(PASS_PLACEHOLDER);
+        if !has_placeholder(&request.target)
+            && !has_placeholder(&request.body)
+            && !request.headers.iter().any(|(_, value)| has_placeholder(value))
+        {
+            tracing::warn!("الطلب الخام لا يحتوي {} أو {}", USER_PLACEHOLDER, PASS_PLACEHOLDER);
+        }
+        
+        Ok(request)
+    }
+    
+    /// ملخص الطلب (الطريقة والمسار)
+    pub fn summary(&self) -> String {
+        format!("{} {}", self.method, self.target)
+    }
+    
+    /// توليد الطلب لمحاولة واحدة
+    ///
+    /// يُستخدم مخطط الهدف ومضيفه من `base_url` مع مسار الطلب الخام، وتُهرب القيم
+    /// حسب موضعها: ترميز URL في المسار، وحسب نوع المحتوى في الجسم.
+    pub fn render(&self, base_url: &str, username: &str, password: &str) -> Result<RenderedRequest> {
+        let target = fill(&self.target, &form_escape(username), &form_escape(password));
+        let url = Url::parse(base_url)
+            .and_then(|base| base.join(&target))
+            .context(format!("مسار غير صالح: {}", target))?;
+        
+        let mut headers = HeaderMap::new();
+        for (name, value) in &self.headers {
+            let name = HeaderName::from_bytes(name.as_bytes())
+                .context(format!("اسم ترويسة غير صالح: {}", name))?;
+            if name == CONTENT_LENGTH {
+                continue;
+            }
+            
+            let value = fill(value, username, password);
+            headers.append(
+                name,
+                HeaderValue::from_str(&value).context("قيمة ترويسة غير صالحة بعد الاستبدال")?,
+            );
+        }
+        
+        let content_type = headers
+            .get(CONTENT_TYPE)
+            .and_then(|v| v.to_str().ok())
+            .unwrap_or_default()
+            .to_lowercase();
+        let body = if content_type.contains("json") {
+            fill(&self.body, &json_escape(username), &json_escape(password))
+        } else if content_type.contains("xml") {
+            fill(&self.body, &xml_escape(username), &xml_escape(password))
+        } else if content_type.contains("x-www-form-urlencoded") {
+            fill(&self.body, &form_escape(username), &form_escape(password))
+        } else {
+            fill(&self.body, username, password)
+        };
+        
+        Ok(RenderedRequest {
+            method: Method::from_bytes(self.method.as_bytes())?,
+            url,
+            headers,
+            body,
+        })
+    }
+}
+
+/// استبدال العناصر النائبة بالقيم
+fn fill(template: &str, username: &str, password: &str) -> String {
+    template
+        .replace(USER_PLACEHOLDER, username)
+        .replace(PASS_PLACEHOLDER, password)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_burp_request_is_replayed_with_escaping() {
+        let raw = RawRequest::parse(
+            "POST /api/login?lang=^USER^ HTTP/1.1\r\n\
+             Host: app.target.com\r\n\
+             Content-Type: application/json\r\n\
+             Content-Length: 42\r\n\
+             X-User: ^USER^\r\n\
+             \r\n\
+             {\"user\":\"^USER^\",\"pass\":\"^PASS^\"}",
+        )
+        .unwrap();
+        
+        let request = raw.render("https://10.0.0.5:8443/", "a b", r#"p"w"#).unwrap();
+        
+        assert_eq!(request.method, Method::POST);
+        assert_eq!(request.url.as_str(), "https://10.0.0.5:8443/api/login?lang=a+b");
+        assert_eq!(request.headers["host"], "app.target.com");
+        assert_eq!(request.headers["x-user"], "a b");
+        assert!(request.headers.get(CONTENT_LENGTH).is_none());
+        
+        let body: serde_json::Value = serde_json::from_str(&request.body).unwrap();
+        assert_eq!(body["pass"], r#"p"w"#);
+    }
+}
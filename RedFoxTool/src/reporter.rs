@@ -1,19 +1,44 @@
 //! نظام التقارير
 //! يولد تقارير بتنسيقات مختلفة
 
+use std::borrow::Cow;
+use std::collections::BTreeMap;
 use std::fs;
 use std::path::{Path, PathBuf};
 use std::time::Duration;
 use chrono::{Local, DateTime};
-use serde_json::json;
+use serde_json::{json, Value};
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
 use anyhow::{Result, Context};
 use tokio::fs as tokio_fs;
 
+use crate::parser::UserMetadata;
+use crate::results_store;
 use crate::scanner::ScanResult;
 
+/// إصدار الماسح المسجل في كل صف
+const SCANNER_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// متغير البيئة لمفتاح HMAC الذي يحتفظ به المشغل لتجزئة كلمات المرور المخفية (لا يُكتب في التقرير)
+pub const REDACTION_KEY_ENV: &str = "REDFOX_REDACTION_KEY";
+
+/// مفتاح الإخفاء من البيئة (None إن لم يُضبط)
+pub fn redaction_key() -> Option<String> {
+    std::env::var(REDACTION_KEY_ENV).ok().filter(|key| !key.is_empty())
+}
+
+/// إعداد إخفاء كلمات المرور
+struct Redaction {
+    /// مفتاح HMAC لدى المشغل (بدونه تُخفى كلمات المرور بلا تجزئة)
+    key: Option<Vec<u8>>,
+}
+
 /// مولد التقارير
 pub struct ReportGenerator {
     output_dir: PathBuf,
+    template: Option<PathBuf>,
+    redaction: Option<Redaction>,
 }
 
 impl ReportGenerator {
@@ -28,7 +53,88 @@ impl ReportGenerator {
         // إنشاء المجلد إذا لم يكن موجودًا
         std::fs::create_dir_all(&output_dir).ok();
         
-        Self { output_dir }
+        Self { output_dir, template: None, redaction: None }
+    }
+    
+    /// استخدام قالب Tera مخصص بدل التنسيقات المدمجة (report.html.tera)
+    pub fn with_template(mut self, template: impl Into<PathBuf>) -> Self {
+        self.template = Some(template.into());
+        self
+    }
+    
+    /// إخفاء كلمات المرور في كل التنسيقات (p*******d)، مع تجزئة HMAC-SHA256 في JSON إن مُرر مفتاح
+    ///
+    /// المفتاح يبقى لدى المشغل ولا يُكتب في التقرير، فحامل التقرير وحده لا يستطيع تجربة قاموس على التجزئات.
+    pub fn with_redaction(mut self, key: Option<&str>) -> Self {
+        self.redaction = Some(Redaction { key: key.map(|key| key.as_bytes().to_vec()) });
+        self
+    }
+    
+    /// كلمة المرور كما تظهر في التقرير
+    fn shown_password<'a>(&self, password: &'a str) -> Cow<'a, str> {
+        match self.redaction {
+            Some(_) => Cow::Owned(mask_password(password)),
+            None => Cow::Borrowed(password),
+        }
+    }
+    
+    /// إخفاء كلمات المرور في أقسام تقرير JSON مع إضافة تجزئة HMAC عند وجود مفتاح
+    fn redact_report(&self, report: &mut Value) {
+        let Some(redaction) = &self.redaction else {
+            return;
+        };
+        
+        for key in ["successful", "failed", "results"] {
+            let Some(entries) = report.get_mut(key).and_then(Value::as_array_mut) else {
+                continue;
+            };
+            for entry in entries {
+                let password = entry["password"].as_str().unwrap_or_default().to_string();
+                entry["password"] = json!(mask_password(&password));
+                if let Some(key) = &redaction.key {
+                    entry["password_hash"] = json!(keyed_hash(key, &password));
+                }
+            }
+        }
+        
+        report["metadata"]["redaction"] = match redaction.key {
+            Some(_) => json!({ "algorithm": "hmac-sha256(key, password)", "key": REDACTION_KEY_ENV }),
+            None => json!({ "algorithm": "mask" }),
+        };
+    }
+    
+    /// السياق المتاح للقوالب: metadata و successful و failed و departments و statistics و results (كل المحاولات)
+    pub fn template_context(&self, results: &[ScanResult]) -> Value {
+        let mut context = report_value(results);
+        
+        context["tool"] = json!({ "name": "RedFoxTool", "version": SCANNER_VERSION });
+        context["results"] = results
+            .iter()
+            .map(|r| {
+                json!({
+                    "username": r.username,
+                    "password": r.password,
+                    "success": r.success,
+                    "status_code": r.status_code,
+                    "response_time_ms": r.response_time.as_millis() as u64,
+                    "error": r.error,
+                    "timestamp": r.timestamp.to_rfc3339(),
+                    "metadata": r.metadata,
+                    "department": department_of(r),
+                    "captcha_detected": r.captcha_detected,
+                    "banner": r.banner,
+                    "http_version": r.http_version,
+                    "redirect": r.redirect,
+                    "target": r.target,
+                    "attack_mode": r.attack_mode,
+                    "attempt": r.attempt,
+                    "privileged_weak": is_privileged_weak(r)
+                })
+            })
+            .collect();
+        self.redact_report(&mut context);
+        
+        context
     }
     
     /// توليد تقرير
@@ -39,15 +145,27 @@ impl ReportGenerator {
         format: &str,
     ) -> Result<String> {
         let timestamp = Local::now().format("%Y%m%d_%H%M%S");
+        
+        // القالب المخصص يحدد الامتداد من اسمه (report.html.tera -> html)
+        if let Some(template) = &self.template {
+            let extension = template_extension(template).unwrap_or(format);
+            let filepath = self.output_dir.join(format!("{}_{}.{}", base_filename, timestamp, extension));
+            self.generate_template(results, template, &filepath).await?;
+            return Ok(filepath.to_string_lossy().to_string());
+        }
+        
         let filename = format!("{}_{}.{}", base_filename, timestamp, format);
         let filepath = self.output_dir.join(&filename);
         
         match format.to_lowercase().as_str() {
             "json" => self.generate_json(results, &filepath).await,
             "html" => self.generate_html(results, &filepath).await,
+            "pdf" => self.generate_pdf(results, &filepath).await,
             "csv" => self.generate_csv(results, &filepath).await,
             "txt" => self.generate_text(results, &filepath).await,
             "xml" => self.generate_xml(results, &filepath).await,
+            "ndjson" => results_store::save_ndjson(&self.masked(results), &filepath).await,
+            "sqlite" | "db" => results_store::save_sqlite(&self.masked(results), &filepath).await,
             _ => {
                 // الافتراضي: JSON
                 self.generate_json(results, &filepath).await
@@ -57,66 +175,41 @@ impl ReportGenerator {
         Ok(filepath.to_string_lossy().to_string())
     }
     
+    /// توليد تقرير من قالب Tera
+    async fn generate_template(&self, results: &[ScanResult], template: &Path, filepath: &Path) -> Result<()> {
+        let source = tokio_fs::read_to_string(template)
+            .await
+            .context(format!("فشل في قراءة القالب: {}", template.display()))?;
+        let context = tera::Context::from_value(self.template_context(results))
+            .context("فشل في بناء سياق القالب")?;
+        
+        // التهريب التلقائي لقوالب HTML/XML فقط
+        let autoescape = matches!(template_extension(template), Some("html" | "htm" | "xml"));
+        let rendered = tera::Tera::one_off(&source, &context, autoescape)
+            .context(format!("فشل في تصيير القالب: {}", template.display()))?;
+        
+        tokio_fs::write(filepath, rendered).await?;
+        Ok(())
+    }
+    
+    /// نسخة من النتائج بكلمات مرور مخفية (للتنسيقات الخام)
+    fn masked<'a>(&self, results: &'a [ScanResult]) -> Cow<'a, [ScanResult]> {
+        if self.redaction.is_none() {
+            return Cow::Borrowed(results);
+        }
+        
+        Cow::Owned(
+            results
+                .iter()
+                .map(|r| ScanResult { password: mask_password(&r.password), ..r.clone() })
+                .collect(),
+        )
+    }
+    
     /// توليد تقرير JSON
     async fn generate_json(&self, results: &[ScanResult], filepath: &Path) -> Result<()> {
-        let successful: Vec<_> = results.iter().filter(|r| r.success).collect();
-        let failed: Vec<_> = results.iter().filter(|r| !r.success).collect();
-        
-        let report = json!({
-            "metadata": {
-                "generated_at": chrono::Utc::now().to_rfc3339(),
-                "total_results": results.len(),
-                "successful_count": successful.len(),
-                "failed_count": failed.len(),
-                "success_rate": if results.is_empty() {
-                    0.0
-                } else {
-                    (successful.len() as f64 / results.len() as f64) * 100.0
-                }
-            },
-            "successful": successful.iter().map(|r| {
-                json!({
-                    "username": r.username,
-                    "password": r.password,
-                    "status_code": r.status_code,
-                    "response_time_ms": r.response_time.as_millis(),
-                    "timestamp": r.timestamp.to_rfc3339()
-                })
-            }).collect::<Vec<_>>(),
-            "failed": failed.iter().take(100).map(|r| { // Limit failed to 100
-                json!({
-                    "username": r.username,
-                    "password": r.password,
-                    "error": r.error,
-                    "timestamp": r.timestamp.to_rfc3339()
-                })
-            }).collect::<Vec<_>>(),
-            "statistics": {
-                "total_attempts": results.len(),
-                "unique_users": {
-                    let mut users: Vec<_> = results.iter().map(|r| &r.username).collect();
-                    users.sort();
-                    users.dedup();
-                    users.len()
-                },
-                "unique_passwords": {
-                    let mut passwords: Vec<_> = results.iter().map(|r| &r.password).collect();
-                    passwords.sort();
-                    passwords.dedup();
-                    passwords.len()
-                },
-                "average_response_time_ms": {
-                    if !results.is_empty() {
-                        let total: u128 = results.iter()
-                            .map(|r| r.response_time.as_millis())
-                            .sum();
-                        total / results.len() as u128
-                    } else {
-                        0
-                    }
-                }
-            }
-        });
+        let mut report = report_value(results);
+        self.redact_report(&mut report);
         
         let json_string = serde_json::to_string_pretty(&report)?;
         tokio_fs::write(filepath, json_string).await?;
@@ -127,7 +220,7 @@ impl ReportGenerator {
     /// توليد تقرير HTML
     async fn generate_html(&self, results: &[ScanResult], filepath: &Path) -> Result<()> {
         let successful: Vec<_> = results.iter().filter(|r| r.success).collect();
-        let failed: Vec<_> = results.iter().filter(|r| !r.success).take(50).collect(); // Limit failed
+        let failed: Vec<_> = results.iter().filter(|r| !r.success).collect();
         
         let success_rate = if results.is_empty() {
             0.0
@@ -303,6 +396,20 @@ impl ReportGenerator {
             background: #c3e6cb !important;
         }}
         
+        .privileged-weak {{
+            background: #f8d7da !important;
+            font-weight: bold;
+        }}
+        
+        .badge {{
+            display: inline-block;
+            padding: 2px 8px;
+            border-radius: 10px;
+            background: #dc3545;
+            color: white;
+            font-size: 0.8em;
+        }}
+        
         .footer {{
             background: #1a1a2e;
             color: white;
@@ -316,11 +423,80 @@ impl ReportGenerator {
             opacity: 0.8;
         }}
         
+        .charts {{
+            display: grid;
+            grid-template-columns: repeat(auto-fit, minmax(420px, 1fr));
+            gap: 20px;
+            padding: 0 30px 30px;
+            background: #f8f9fa;
+        }}
+        
+        .chart {{
+            background: white;
+            padding: 20px;
+            border-radius: 15px;
+            box-shadow: 0 5px 15px rgba(0,0,0,0.1);
+        }}
+        
+        .chart h3 {{
+            margin-bottom: 10px;
+            color: #1a1a2e;
+        }}
+        
+        .chart svg {{
+            width: 100%;
+            height: auto;
+        }}
+        
+        .table-tools {{
+            display: flex;
+            gap: 10px;
+            align-items: center;
+            margin-bottom: 10px;
+        }}
+        
+        .table-tools input {{
+            flex: 1;
+            padding: 8px 12px;
+            border: 1px solid #ccc;
+            border-radius: 8px;
+        }}
+        
+        .table-tools button {{
+            padding: 8px 14px;
+            border: none;
+            border-radius: 8px;
+            background: #667eea;
+            color: white;
+            cursor: pointer;
+        }}
+        
+        .table-tools button:disabled {{
+            background: #ccc;
+            cursor: default;
+        }}
+        
+        table.sortable th {{
+            cursor: pointer;
+            user-select: none;
+        }}
+        
+        table.sortable th[data-order="asc"]::after {{ content: " ▲"; }}
+        table.sortable th[data-order="desc"]::after {{ content: " ▼"; }}
+        
         @media (max-width: 768px) {{
             .header h1 {{ font-size: 2em; }}
             .stats {{ grid-template-columns: 1fr; }}
             table {{ display: block; overflow-x: auto; }}
         }}
+        
+        @media print {{
+            * {{ -webkit-print-color-adjust: exact; print-color-adjust: exact; }}
+            body {{ padding: 0; background: white; }}
+            .container {{ box-shadow: none; border-radius: 0; }}
+            tr {{ page-break-inside: avoid; }}
+            .table-tools {{ display: none; }}
+        }}
     </style>
 </head>
 <body>
@@ -356,21 +532,37 @@ impl ReportGenerator {
             </div>
         </div>
         
+        <div class="charts">
+            <div class="chart">
+                <h3>⏱️ توزيع زمن الاستجابة (مللي ثانية)</h3>
+                {}
+            </div>
+            <div class="chart">
+                <h3>📈 المحاولات عبر الزمن</h3>
+                {}
+            </div>
+        </div>
+        
         <div class="results">
+            {}
+            
             <h2 class="section-title">📊 النتائج الناجحة</h2>
             {}
             
-            <h2 class="section-title">⚠️ المحاولات الفاشلة (عرض 50)</h2>
+            {}
+            
+            <h2 class="section-title">⚠️ المحاولات الفاشلة ({})</h2>
             {}
         </div>
         
         <div class="footer">
             <div class="timestamp">
                 تم إنشاء التقرير في: {} |
-                بواسطة RedFoxTool v1.0
+                بواسطة RedFoxTool v{}
             </div>
         </div>
     </div>
+    <script>{}</script>
 </body>
 </html>
 "#,
@@ -390,9 +582,16 @@ impl ReportGenerator {
                 passwords.dedup();
                 passwords.len()
             },
+            svg_bar_chart(&response_time_histogram(results), "#17a2b8"),
+            svg_bar_chart(&attempts_over_time(results), "#667eea"),
+            self.generate_target_section(results),
             self.generate_successful_table(successful),
+            self.generate_department_section(results),
+            failed.len(),
             self.generate_failed_table(failed),
-            Local::now().format("%Y-%m-%d %H:%M:%S")
+            Local::now().format("%Y-%m-%d %H:%M:%S"),
+            SCANNER_VERSION,
+            HTML_SCRIPT
         );
         
         tokio_fs::write(filepath, html).await?;
@@ -405,27 +604,45 @@ impl ReportGenerator {
             return "<p style='text-align: center; padding: 20px; color: #666;'>لا توجد نتائج ناجحة</p>".to_string();
         }
         
-        let mut table = String::from("<table>\n");
+        let mut table = String::from(
+            "<div class='table-tools'><input type='search' data-filter='successful-table' placeholder='تصفية...'></div>\n",
+        );
+        table.push_str("<table id='successful-table' class='sortable'>\n");
         table.push_str("<tr>\n");
         table.push_str("    <th>#</th>\n");
         table.push_str("    <th>اسم المستخدم</th>\n");
         table.push_str("    <th>كلمة المرور</th>\n");
+        table.push_str("    <th>القسم</th>\n");
         table.push_str("    <th>رمز الحالة</th>\n");
         table.push_str("    <th>وقت الاستجابة</th>\n");
         table.push_str("    <th>الوقت</th>\n");
         table.push_str("</tr>\n");
         
         for (i, result) in results.iter().enumerate() {
-            let row_class = if i % 2 == 0 { "success-row" } else { "" };
+            let row_class = if is_privileged_weak(result) {
+                "privileged-weak"
+            } else if i % 2 == 0 {
+                "success-row"
+            } else {
+                ""
+            };
             table.push_str(&format!(
                 "<tr class='{}'>\n",
                 row_class
             ));
             table.push_str(&format!("    <td>{}</td>\n", i + 1));
-            table.push_str(&format!("    <td><strong>{}</strong></td>\n", result.username));
-            table.push_str(&format!("    <td><code>{}</code></td>\n", result.password));
+            table.push_str(&format!("    <td><strong>{}</strong></td>\n", escape_xml(&result.username)));
+            table.push_str(&format!("    <td><code>{}</code>{}</td>\n",
+                escape_xml(&self.shown_password(&result.password)),
+                if is_privileged_weak(result) {
+                    " <span class='badge'>حساب مميز بكلمة مرور ضعيفة</span>"
+                } else {
+                    ""
+                }));
+            table.push_str(&format!("    <td>{}</td>\n", escape_xml(department_of(result))));
             table.push_str(&format!("    <td>{}</td>\n", result.status_code));
-            table.push_str(&format!("    <td>{:.2?}</td>\n", result.response_time));
+            table.push_str(&format!("    <td data-sort='{}'>{:.2?}</td>\n",
+                result.response_time.as_millis(), result.response_time));
             table.push_str(&format!("    <td>{}</td>\n", 
                 result.timestamp.with_timezone(&Local).format("%H:%M:%S")));
             table.push_str("</tr>\n");
@@ -435,30 +652,140 @@ impl ReportGenerator {
         table
     }
     
-    /// إنشاء جدول المحاولات الفاشلة
-    fn generate_failed_table(&self, results: Vec<&ScanResult>) -> String {
-        if results.is_empty() {
-            return "<p style='text-align: center; padding: 20px; color: #666;'>لا توجد محاولات فاشلة</p>".to_string();
+    /// إنشاء الملخص التنفيذي وبيانات الاعتماد لكل هدف (عند تعدد الأهداف فقط)
+    fn generate_target_section(&self, results: &[ScanResult]) -> String {
+        let targets = group_by_target(results);
+        if targets.len() < 2 {
+            return String::new();
         }
         
-        let mut table = String::from("<table>\n");
-        table.push_str("<tr>\n");
-        table.push_str("    <th>اسم المستخدم</th>\n");
-        table.push_str("    <th>كلمة المرور</th>\n");
-        table.push_str("    <th>الخطأ</th>\n");
-        table.push_str("</tr>\n");
+        let mut section = String::from("<h2 class=\"section-title\">🎯 الملخص التنفيذي حسب الهدف</h2>\n");
+        section.push_str("<table class='sortable'>\n");
+        section.push_str("<tr>\n");
+        section.push_str("    <th>الهدف</th>\n");
+        section.push_str("    <th>المحاولات</th>\n");
+        section.push_str("    <th>المستخدمون</th>\n");
+        section.push_str("    <th>الحسابات المخترقة</th>\n");
+        section.push_str("    <th>معدل النجاح</th>\n");
+        section.push_str("    <th>متوسط الاستجابة</th>\n");
+        section.push_str("</tr>\n");
+        
+        for (target, summary) in &targets {
+            section.push_str(&format!("<tr class='{}'>\n", if summary.compromised.is_empty() { "" } else { "success-row" }));
+            section.push_str(&format!("    <td><strong>{}</strong></td>\n", escape_xml(target)));
+            section.push_str(&format!("    <td>{}</td>\n", summary.attempts));
+            section.push_str(&format!("    <td>{}</td>\n", summary.users));
+            section.push_str(&format!("    <td>{}</td>\n", summary.compromised.len()));
+            section.push_str(&format!("    <td data-sort='{:.4}'>{:.1}%</td>\n", summary.success_rate(), summary.success_rate()));
+            section.push_str(&format!("    <td data-sort='{}'>{} ms</td>\n", summary.average_response_ms(), summary.average_response_ms()));
+            section.push_str("</tr>\n");
+        }
+        section.push_str("</table>\n");
+        
+        for (target, summary) in targets.iter().filter(|(_, s)| !s.compromised.is_empty()) {
+            section.push_str(&format!("<h3 style='margin: 15px 0;'>{}</h3>\n", escape_xml(target)));
+            section.push_str("<table>\n");
+            section.push_str("<tr><th>اسم المستخدم</th><th>كلمة المرور</th><th>رمز الحالة</th></tr>\n");
+            for result in &summary.compromised {
+                section.push_str(&format!(
+                    "<tr><td><strong>{}</strong></td><td><code>{}</code></td><td>{}</td></tr>\n",
+                    escape_xml(&result.username),
+                    escape_xml(&self.shown_password(&result.password)),
+                    result.status_code
+                ));
+            }
+            section.push_str("</table>\n");
+        }
         
-        for result in results {
-            table.push_str("<tr>\n");
-            table.push_str(&format!("    <td>{}</td>\n", result.username));
-            table.push_str(&format!("    <td>{}</td>\n", result.password));
-            table.push_str(&format!("    <td>{}</td>\n", 
-                result.error.as_deref().unwrap_or("غير معروف")));
-            table.push_str("</tr>\n");
+        section
+    }
+    
+    /// إنشاء قسم النتائج حسب القسم الإداري
+    fn generate_department_section(&self, results: &[ScanResult]) -> String {
+        if results.iter().all(|r| r.metadata.is_none()) {
+            return String::new();
         }
         
-        table.push_str("</table>");
-        table
+        let mut section = String::from("<h2 class=\"section-title\">🏢 النتائج حسب القسم</h2>\n");
+        section.push_str("<table>\n");
+        section.push_str("<tr>\n");
+        section.push_str("    <th>القسم</th>\n");
+        section.push_str("    <th>المستخدمون</th>\n");
+        section.push_str("    <th>المحاولات</th>\n");
+        section.push_str("    <th>الحسابات المخترقة</th>\n");
+        section.push_str("    <th>حسابات مميزة ضعيفة</th>\n");
+        section.push_str("</tr>\n");
+        
+        for (department, summary) in group_by_department(results) {
+            let privileged_weak: Vec<_> = summary.compromised
+                .iter()
+                .filter(|r| is_privileged_weak(r))
+                .map(|r| escape_xml(&r.username))
+                .collect();
+            
+            section.push_str("<tr>\n");
+            section.push_str(&format!("    <td><strong>{}</strong></td>\n", escape_xml(&department)));
+            section.push_str(&format!("    <td>{}</td>\n", summary.users));
+            section.push_str(&format!("    <td>{}</td>\n", summary.attempts));
+            section.push_str(&format!("    <td>{}</td>\n", summary.compromised.len()));
+            section.push_str(&format!("    <td>{}</td>\n", privileged_weak.join(", ")));
+            section.push_str("</tr>\n");
+        }
+        
+        section.push_str("</table>");
+        section
+    }
+    
+    /// إنشاء جدول المحاولات الفاشلة (كل المحاولات كبيانات JSON مضمنة تعرض بصفحات في المتصفح)
+    fn generate_failed_table(&self, results: Vec<&ScanResult>) -> String {
+        if results.is_empty() {
+            return "<p style='text-align: center; padding: 20px; color: #666;'>لا توجد محاولات فاشلة</p>".to_string();
+        }
+        
+        let rows: Vec<_> = results
+            .iter()
+            .map(|r| {
+                json!([
+                    r.username,
+                    self.shown_password(&r.password),
+                    r.status_code,
+                    r.error.as_deref().unwrap_or("غير معروف"),
+                    r.timestamp.with_timezone(&Local).format("%H:%M:%S").to_string()
+                ])
+            })
+            .collect();
+        // منع إغلاق وسم script من داخل البيانات
+        let data = Value::from(rows).to_string().replace("</", "<\\/");
+        
+        format!(
+            "<div class='table-tools'>
+    <input type='search' id='failed-filter' placeholder='تصفية...'>
+    <button id='failed-prev'>السابق</button>
+    <span id='failed-page'></span>
+    <button id='failed-next'>التالي</button>
+</div>
+<table id='failed-table' class='sortable'>
+<tr>
+    <th>اسم المستخدم</th>
+    <th>كلمة المرور</th>
+    <th>رمز الحالة</th>
+    <th>الخطأ</th>
+    <th>الوقت</th>
+</tr>
+</table>
+<script type='application/json' id='failed-data'>{}</script>",
+            data
+        )
+    }
+    
+    /// توليد تقرير PDF بطباعة تقرير HTML (بطاقات الإحصائيات وجدول النتائج) عبر متصفح دون واجهة
+    async fn generate_pdf(&self, results: &[ScanResult], filepath: &Path) -> Result<()> {
+        let html_path = filepath.with_extension("pdf.html");
+        self.generate_html(results, &html_path).await?;
+        
+        let rendered = render_pdf(&html_path, filepath).await;
+        tokio_fs::remove_file(&html_path).await.ok();
+        rendered
     }
     
     /// توليد تقرير CSV
@@ -466,26 +793,46 @@ impl ReportGenerator {
         let mut csv_writer = csv::Writer::from_path(filepath)?;
         
         // كتابة العناوين
-        csv_writer.write_record(&[
+        csv_writer.write_record([
             "Username",
             "Password",
             "Success",
             "Status Code",
             "Response Time (ms)",
             "Error",
-            "Timestamp"
+            "Timestamp",
+            "Display Name",
+            "Department",
+            "Privilege Level",
+            "Privileged Weak",
+            "Target",
+            "Attack Mode",
+            "Attempt",
+            "HTTP Version",
+            "Redirect",
+            "Scanner Version"
         ])?;
         
         // كتابة البيانات
         for result in results {
-            csv_writer.write_record(&[
+            csv_writer.write_record([
                 &result.username,
-                &result.password,
+                &*self.shown_password(&result.password),
                 &result.success.to_string(),
                 &result.status_code.to_string(),
                 &result.response_time.as_millis().to_string(),
                 result.error.as_deref().unwrap_or(""),
-                &result.timestamp.to_rfc3339()
+                &result.timestamp.to_rfc3339(),
+                metadata_field(result, |m| m.display_name.as_deref()),
+                metadata_field(result, |m| m.department.as_deref()),
+                metadata_field(result, |m| m.privilege_level.as_deref()),
+                &is_privileged_weak(result).to_string(),
+                result.target.as_deref().unwrap_or(""),
+                result.attack_mode.as_deref().unwrap_or(""),
+                &result.attempt.map(|n| n.to_string()).unwrap_or_default(),
+                result.http_version.as_deref().unwrap_or(""),
+                result.redirect.as_deref().unwrap_or(""),
+                SCANNER_VERSION
             ])?;
         }
         
@@ -519,15 +866,62 @@ impl ReportGenerator {
             text.push_str(&format!("{}\n", "-".repeat(70)));
             
             for (i, result) in successful.iter().enumerate() {
-                text.push_str(&format!("{:3}. {:20} {:30} [{}] {:.2?}\n",
+                text.push_str(&format!("{:3}. {:20} {:30} [{}] {:.2?}{}\n",
                     i + 1,
                     result.username,
-                    result.password,
+                    self.shown_password(&result.password),
                     result.status_code,
-                    result.response_time
+                    result.response_time,
+                    if is_privileged_weak(result) { "  ⚠ حساب مميز بكلمة مرور ضعيفة" } else { "" }
                 ));
             }
-            text.push_str("\n");
+            text.push('\n');
+        }
+        
+        // الملخص التنفيذي حسب الهدف
+        let targets = group_by_target(results);
+        if targets.len() > 1 {
+            text.push_str(&format!("{}\n", "-".repeat(70)));
+            text.push_str("الملخص حسب الهدف:\n");
+            text.push_str(&format!("{}\n", "-".repeat(70)));
+            
+            for (target, summary) in &targets {
+                text.push_str(&format!("{}\n    المحاولات: {:6} المخترقة: {:4} معدل النجاح: {:.1}% متوسط الاستجابة: {} مللي ثانية\n",
+                    target,
+                    summary.attempts,
+                    summary.compromised.len(),
+                    summary.success_rate(),
+                    summary.average_response_ms()
+                ));
+                
+                for result in &summary.compromised {
+                    text.push_str(&format!("    - {} : {}\n", result.username, self.shown_password(&result.password)));
+                }
+            }
+            text.push('\n');
+        }
+        
+        // النتائج حسب القسم
+        if results.iter().any(|r| r.metadata.is_some()) {
+            text.push_str(&format!("{}\n", "-".repeat(70)));
+            text.push_str("النتائج حسب القسم:\n");
+            text.push_str(&format!("{}\n", "-".repeat(70)));
+            
+            for (department, summary) in group_by_department(results) {
+                text.push_str(&format!("{:25} المستخدمون: {:5} المخترقة: {}\n",
+                    department,
+                    summary.users,
+                    summary.compromised.len()
+                ));
+                
+                for result in &summary.compromised {
+                    text.push_str(&format!("    - {}{}\n",
+                        result.username,
+                        if is_privileged_weak(result) { " (مميز، كلمة مرور ضعيفة)" } else { "" }
+                    ));
+                }
+            }
+            text.push('\n');
         }
         
         // إحصائيات
@@ -573,62 +967,793 @@ impl ReportGenerator {
         Ok(())
     }
     
-    /// توليد تقرير XML
+    /// توليد تقرير XML (تسلسل quick-xml مطابق لمخطط redfox-report.xsd المكتوب بجانبه)
     async fn generate_xml(&self, results: &[ScanResult], filepath: &Path) -> Result<()> {
         let successful: Vec<_> = results.iter().filter(|r| r.success).collect();
         let failed: Vec<_> = results.iter().filter(|r| !r.success).collect();
         
-        let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
-        xml.push_str("<!DOCTYPE redfox-report SYSTEM \"https://redfox.security/dtd/report.dtd\">\n");
-        xml.push_str("<redfox-report>\n");
-        
-        // المعلومات الوصفية
-        xml.push_str("  <metadata>\n");
-        xml.push_str(&format!("    <generated-at>{}</generated-at>\n", chrono::Utc::now().to_rfc3339()));
-        xml.push_str(&format!("    <tool>RedFoxTool</tool>\n"));
-        xml.push_str(&format!("    <version>1.0.0</version>\n"));
-        xml.push_str(&format!("    <total-attempts>{}</total-attempts>\n", results.len()));
-        xml.push_str(&format!("    <successful>{}</successful>\n", successful.len()));
-        xml.push_str(&format!("    <failed>{}</failed>\n", failed.len()));
-        xml.push_str(&format!("    <success-rate>{:.2}</success-rate>\n",
-            if results.is_empty() { 0.0 } else { (successful.len() as f64 / results.len() as f64) * 100.0 }));
-        xml.push_str("  </metadata>\n");
+        let report = XmlReport {
+            xmlns_xsi: "http://www.w3.org/2001/XMLSchema-instance",
+            schema_location: XML_SCHEMA_FILE,
+            metadata: XmlMetadata {
+                generated_at: chrono::Utc::now().to_rfc3339(),
+                tool: "RedFoxTool",
+                version: SCANNER_VERSION,
+                total_attempts: results.len(),
+                successful: successful.len(),
+                failed: failed.len(),
+                success_rate: format!(
+                    "{:.2}",
+                    if results.is_empty() { 0.0 } else { (successful.len() as f64 / results.len() as f64) * 100.0 }
+                ),
+            },
+            statistics: XmlStatistics::from_results(results),
+            successful_results: XmlSuccessful {
+                count: successful.len(),
+                credentials: successful.iter().map(|r| self.xml_result(r)).collect(),
+            },
+            failed_results: XmlFailed {
+                count: failed.len(),
+                // مقتطعة مثل تقرير JSON
+                attempts: failed.iter().take(100).map(|r| self.xml_result(r)).collect(),
+            },
+        };
         
-        // النتائج الناجحة
-        if !successful.is_empty() {
-            xml.push_str("  <successful-results>\n");
-            for result in successful {
-                xml.push_str("    <credential>\n");
-                xml.push_str(&format!("      <username>{}</username>\n", escape_xml(&result.username)));
-                xml.push_str(&format!("      <password>{}</password>\n", escape_xml(&result.password)));
-                xml.push_str(&format!("      <status-code>{}</status-code>\n", result.status_code));
-                xml.push_str(&format!("      <response-time-ms>{}</response-time-ms>\n", result.response_time.as_millis()));
-                xml.push_str(&format!("      <timestamp>{}</timestamp>\n", result.timestamp.to_rfc3339()));
-                xml.push_str("    </credential>\n");
-            }
-            xml.push_str("  </successful-results>\n");
+        let body = quick_xml::se::to_string(&report).context("فشل في ترميز تقرير XML")?;
+        let xml = format!("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n{}", body);
+        tokio_fs::write(filepath, xml).await?;
+        
+        // نشر المخطط بجانب التقرير للتحقق (xmllint --schema redfox-report.xsd)
+        if let Some(dir) = filepath.parent() {
+            tokio_fs::write(dir.join(XML_SCHEMA_FILE), XML_SCHEMA).await?;
         }
         
-        // النتائج الفاشلة (محدودة)
-        if !failed.is_empty() {
-            xml.push_str("  <failed-results>\n");
-            for result in failed.iter().take(100) {
-                xml.push_str("    <attempt>\n");
-                xml.push_str(&format!("      <username>{}</username>\n", escape_xml(&result.username)));
-                xml.push_str(&format!("      <password>{}</password>\n", escape_xml(&result.password)));
-                xml.push_str(&format!("      <error>{}</error>\n", 
-                    escape_xml(result.error.as_deref().unwrap_or("unknown"))));
-                xml.push_str(&format!("      <timestamp>{}</timestamp>\n", result.timestamp.to_rfc3339()));
-                xml.push_str("    </attempt>\n");
+        Ok(())
+    }
+    
+    /// نتيجة واحدة في تقرير XML
+    fn xml_result(&self, result: &ScanResult) -> XmlResult {
+        let metadata = result.metadata.as_ref();
+        
+        XmlResult {
+            username: xml_text(&result.username),
+            password: xml_text(&self.shown_password(&result.password)),
+            department: metadata.and_then(|m| m.department.clone()),
+            privilege_level: metadata.and_then(|m| m.privilege_level.clone()),
+            privileged_weak: is_privileged_weak(result),
+            status_code: result.status_code,
+            response_time_ms: result.response_time.as_millis() as u64,
+            error: result.error.as_deref().map(xml_text),
+            captcha_detected: result.captcha_detected,
+            timestamp: result.timestamp.to_rfc3339(),
+            target: result.target.clone(),
+            attack_mode: result.attack_mode.clone(),
+            attempt_index: result.attempt,
+            http_version: result.http_version.clone(),
+            redirect: result.redirect.clone(),
+            banner: result.banner.as_deref().map(xml_text),
+        }
+    }
+}
+
+/// اسم ملف مخطط XML المنشور بجانب التقارير
+pub const XML_SCHEMA_FILE: &str = "redfox-report.xsd";
+
+/// مخطط XSD لتقارير XML
+pub const XML_SCHEMA: &str = include_str!("../schemas/redfox-report.xsd");
+
+/// جذر تقرير XML
+#[derive(serde::Serialize)]
+#[serde(rename = "redfox-report")]
+struct XmlReport {
+    #[serde(rename = "@xmlns:xsi")]
+    xmlns_xsi: &'static str,
+    #[serde(rename = "@xsi:noNamespaceSchemaLocation")]
+    schema_location: &'static str,
+    metadata: XmlMetadata,
+    statistics: XmlStatistics,
+    #[serde(rename = "successful-results")]
+    successful_results: XmlSuccessful,
+    #[serde(rename = "failed-results")]
+    failed_results: XmlFailed,
+}
+
+/// المعلومات الوصفية في تقرير XML
+#[derive(serde::Serialize)]
+#[serde(rename_all = "kebab-case")]
+struct XmlMetadata {
+    generated_at: String,
+    tool: &'static str,
+    version: &'static str,
+    total_attempts: usize,
+    successful: usize,
+    failed: usize,
+    success_rate: String,
+}
+
+/// الإحصائيات الكاملة في تقرير XML
+#[derive(serde::Serialize)]
+#[serde(rename_all = "kebab-case")]
+struct XmlStatistics {
+    unique_users: usize,
+    unique_passwords: usize,
+    average_response_time_ms: u64,
+    captcha_detected: usize,
+    privileged_weak: usize,
+    targets: XmlTargets,
+    departments: XmlDepartments,
+}
+
+impl XmlStatistics {
+    /// حساب الإحصائيات من النتائج
+    fn from_results(results: &[ScanResult]) -> Self {
+        let unique = |field: fn(&ScanResult) -> &str| {
+            results.iter().map(field).collect::<std::collections::HashSet<_>>().len()
+        };
+        let total_ms: u128 = results.iter().map(|r| r.response_time.as_millis()).sum();
+        
+        Self {
+            unique_users: unique(|r| r.username.as_str()),
+            unique_passwords: unique(|r| r.password.as_str()),
+            average_response_time_ms: if results.is_empty() { 0 } else { (total_ms / results.len() as u128) as u64 },
+            captcha_detected: results.iter().filter(|r| r.captcha_detected).count(),
+            privileged_weak: results.iter().filter(|r| is_privileged_weak(r)).count(),
+            targets: XmlTargets {
+                targets: results
+                    .iter()
+                    .filter_map(|r| r.target.clone())
+                    .collect::<std::collections::BTreeSet<_>>()
+                    .into_iter()
+                    .collect(),
+            },
+            departments: XmlDepartments {
+                departments: group_by_department(results)
+                    .into_iter()
+                    .map(|(name, summary)| XmlDepartment {
+                        name,
+                        users: summary.users,
+                        attempts: summary.attempts,
+                        compromised: summary.compromised.len(),
+                    })
+                    .collect(),
+            },
+        }
+    }
+}
+
+/// قائمة الأهداف
+#[derive(serde::Serialize)]
+struct XmlTargets {
+    #[serde(rename = "target")]
+    targets: Vec<String>,
+}
+
+/// قائمة الأقسام
+#[derive(serde::Serialize)]
+struct XmlDepartments {
+    #[serde(rename = "department")]
+    departments: Vec<XmlDepartment>,
+}
+
+/// ملخص قسم
+#[derive(serde::Serialize)]
+struct XmlDepartment {
+    #[serde(rename = "@name")]
+    name: String,
+    #[serde(rename = "@users")]
+    users: usize,
+    #[serde(rename = "@attempts")]
+    attempts: usize,
+    #[serde(rename = "@compromised")]
+    compromised: usize,
+}
+
+/// بيانات الاعتماد الصالحة
+#[derive(serde::Serialize)]
+struct XmlSuccessful {
+    #[serde(rename = "@count")]
+    count: usize,
+    #[serde(rename = "credential")]
+    credentials: Vec<XmlResult>,
+}
+
+/// المحاولات الفاشلة (العدد الكلي مع أول 100 محاولة)
+#[derive(serde::Serialize)]
+struct XmlFailed {
+    #[serde(rename = "@count")]
+    count: usize,
+    #[serde(rename = "attempt")]
+    attempts: Vec<XmlResult>,
+}
+
+/// نتيجة في تقرير XML
+#[derive(serde::Serialize)]
+#[serde(rename_all = "kebab-case")]
+struct XmlResult {
+    username: String,
+    password: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    department: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    privilege_level: Option<String>,
+    privileged_weak: bool,
+    status_code: u16,
+    response_time_ms: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+    captcha_detected: bool,
+    timestamp: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    target: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    attack_mode: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    attempt_index: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    http_version: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    redirect: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    banner: Option<String>,
+}
+
+/// إدخال نتيجة في تقرير JSON (قابل للتحميل مجددًا بواسطة results_store)
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct ReportEntry {
+    /// اسم المستخدم
+    pub username: String,
+    
+    /// كلمة المرور
+    pub password: String,
+    
+    /// رمز الحالة
+    #[serde(default)]
+    pub status_code: u16,
+    
+    /// وقت الاستجابة (مللي ثانية)
+    #[serde(default)]
+    pub response_time_ms: u64,
+    
+    /// رسالة الخطأ
+    #[serde(default)]
+    pub error: Option<String>,
+    
+    /// وقت المحاولة
+    #[serde(default = "chrono::Utc::now")]
+    pub timestamp: DateTime<chrono::Utc>,
+    
+    /// البيانات الوصفية للمستخدم
+    #[serde(default)]
+    pub metadata: Option<UserMetadata>,
+    
+    /// هل احتوت الاستجابة على CAPTCHA؟
+    #[serde(default)]
+    pub captcha_detected: bool,
+    
+    /// رسالة ترحيب الخادم
+    #[serde(default)]
+    pub banner: Option<String>,
+    
+    /// إصدار HTTP
+    #[serde(default)]
+    pub http_version: Option<String>,
+    
+    /// وجهة إعادة التوجيه
+    #[serde(default)]
+    pub redirect: Option<String>,
+    
+    /// الهدف
+    #[serde(default)]
+    pub target: Option<String>,
+    
+    /// وضع الهجوم
+    #[serde(default)]
+    pub attack_mode: Option<String>,
+    
+    /// ترتيب المحاولة
+    #[serde(default)]
+    pub attempt: Option<u64>,
+    
+    /// إصدار الماسح الذي أنتج النتيجة
+    #[serde(default)]
+    pub scanner_version: Option<String>,
+    
+    /// حساب مميز بكلمة مرور ضعيفة
+    #[serde(default)]
+    pub privileged_weak: bool,
+}
+
+impl From<&ScanResult> for ReportEntry {
+    fn from(result: &ScanResult) -> Self {
+        Self {
+            username: result.username.clone(),
+            password: result.password.clone(),
+            status_code: result.status_code,
+            response_time_ms: result.response_time.as_millis() as u64,
+            error: result.error.clone(),
+            timestamp: result.timestamp,
+            metadata: result.metadata.clone(),
+            captcha_detected: result.captcha_detected,
+            banner: result.banner.clone(),
+            http_version: result.http_version.clone(),
+            redirect: result.redirect.clone(),
+            target: result.target.clone(),
+            attack_mode: result.attack_mode.clone(),
+            attempt: result.attempt,
+            scanner_version: Some(SCANNER_VERSION.to_string()),
+            privileged_weak: is_privileged_weak(result),
+        }
+    }
+}
+
+impl ReportEntry {
+    /// تحويل الإدخال إلى نتيجة (النجاح يُحدد من القسم الذي ورد فيه)
+    pub fn into_result(self, success: bool) -> ScanResult {
+        ScanResult {
+            username: self.username,
+            password: self.password,
+            success,
+            status_code: self.status_code,
+            response_time: Duration::from_millis(self.response_time_ms),
+            error: self.error,
+            timestamp: self.timestamp,
+            metadata: self.metadata,
+            captcha_detected: self.captcha_detected,
+            banner: self.banner,
+            http_version: self.http_version,
+            redirect: self.redirect,
+            target: self.target,
+            attack_mode: self.attack_mode,
+            attempt: self.attempt,
+        }
+    }
+}
+
+/// محتوى تقرير JSON (البيانات الوصفية والنتائج الناجحة والأقسام والإحصائيات)
+fn report_value(results: &[ScanResult]) -> Value {
+    let successful: Vec<_> = results.iter().filter(|r| r.success).collect();
+    let failed: Vec<_> = results.iter().filter(|r| !r.success).collect();
+    let unique_users = results.iter().map(|r| &r.username).collect::<std::collections::HashSet<_>>().len();
+    let unique_passwords = results.iter().map(|r| &r.password).collect::<std::collections::HashSet<_>>().len();
+    let average_response_time_ms = if results.is_empty() {
+        0
+    } else {
+        results.iter().map(|r| r.response_time.as_millis()).sum::<u128>() / results.len() as u128
+    };
+    
+    json!({
+        "metadata": {
+            "generated_at": chrono::Utc::now().to_rfc3339(),
+            "scanner_version": SCANNER_VERSION,
+            "targets": results
+                .iter()
+                .filter_map(|r| r.target.as_deref())
+                .collect::<std::collections::BTreeSet<_>>(),
+            "total_results": results.len(),
+            "successful_count": successful.len(),
+            "failed_count": failed.len(),
+            "captcha_count": results.iter().filter(|r| r.captcha_detected).count(),
+            "success_rate": if results.is_empty() {
+                0.0
+            } else {
+                (successful.len() as f64 / results.len() as f64) * 100.0
             }
-            xml.push_str("  </failed-results>\n");
+        },
+        "successful": successful.iter().map(|r| ReportEntry::from(*r)).collect::<Vec<_>>(),
+        "by_target": group_by_target(results).iter().map(|(target, summary)| {
+            (target.clone(), json!({
+                "attempts": summary.attempts,
+                "users": summary.users,
+                "successful_count": summary.compromised.len(),
+                "success_rate": summary.success_rate(),
+                "average_response_time_ms": summary.average_response_ms(),
+                "compromised": summary.compromised.iter().map(|r| &r.username).collect::<Vec<_>>()
+            }))
+        }).collect::<serde_json::Map<_, _>>(),
+        "departments": group_by_department(results).iter().map(|(department, summary)| {
+            (department.clone(), json!({
+                "attempts": summary.attempts,
+                "users": summary.users,
+                "compromised": summary.compromised.iter().map(|r| &r.username).collect::<Vec<_>>(),
+                "privileged_weak": summary.compromised.iter()
+                    .filter(|r| is_privileged_weak(r))
+                    .map(|r| &r.username)
+                    .collect::<Vec<_>>()
+            }))
+        }).collect::<serde_json::Map<_, _>>(),
+        "failed": failed.iter().take(100).map(|r| ReportEntry::from(*r)).collect::<Vec<_>>(), // Limit failed to 100
+        "statistics": {
+            "total_attempts": results.len(),
+            "unique_users": unique_users,
+            "unique_passwords": unique_passwords,
+            "average_response_time_ms": average_response_time_ms
+        }
+    })
+}
+
+/// امتداد الملف الناتج من اسم القالب (report.html.tera -> html)
+fn template_extension(template: &Path) -> Option<&str> {
+    let name = template.file_name()?.to_str()?;
+    let name = name.strip_suffix(".tera").unwrap_or(name);
+    
+    name.rsplit_once('.').map(|(_, extension)| extension)
+}
+
+/// ملخص النتائج لهدف واحد
+struct TargetSummary<'a> {
+    attempts: usize,
+    users: usize,
+    total_response_ms: u128,
+    compromised: Vec<&'a ScanResult>,
+}
+
+impl TargetSummary<'_> {
+    /// معدل النجاح (%)
+    fn success_rate(&self) -> f64 {
+        if self.attempts == 0 {
+            0.0
+        } else {
+            self.compromised.len() as f64 / self.attempts as f64 * 100.0
+        }
+    }
+    
+    /// متوسط زمن الاستجابة (مللي ثانية)
+    fn average_response_ms(&self) -> u128 {
+        self.total_response_ms / self.attempts.max(1) as u128
+    }
+}
+
+/// تجميع النتائج حسب الهدف (النتائج بلا هدف تُجمع تحت "غير محدد")
+fn group_by_target(results: &[ScanResult]) -> BTreeMap<String, TargetSummary<'_>> {
+    let mut groups: BTreeMap<String, (TargetSummary<'_>, std::collections::HashSet<&str>)> = BTreeMap::new();
+    
+    for result in results {
+        let (summary, users) = groups
+            .entry(result.target.clone().unwrap_or_else(|| "غير محدد".to_string()))
+            .or_insert_with(|| {
+                let summary = TargetSummary { attempts: 0, users: 0, total_response_ms: 0, compromised: Vec::new() };
+                (summary, std::collections::HashSet::new())
+            });
+        
+        summary.attempts += 1;
+        summary.total_response_ms += result.response_time.as_millis();
+        users.insert(&result.username);
+        if result.success {
+            summary.compromised.push(result);
+        }
+    }
+    
+    groups
+        .into_iter()
+        .map(|(target, (summary, users))| (target, TargetSummary { users: users.len(), ..summary }))
+        .collect()
+}
+
+/// ملخص النتائج لقسم واحد
+struct DepartmentSummary<'a> {
+    attempts: usize,
+    users: usize,
+    compromised: Vec<&'a ScanResult>,
+}
+
+/// أدوات تحويل HTML إلى PDF بترتيب التفضيل
+const PDF_RENDERERS: &[&str] = &["chromium", "chromium-browser", "google-chrome", "wkhtmltopdf"];
+
+/// طباعة ملف HTML إلى PDF بأول أداة مثبتة
+async fn render_pdf(html_path: &Path, pdf_path: &Path) -> Result<()> {
+    let html_path = fs::canonicalize(html_path)?;
+    
+    // ملف قديم من تشغيل سابق لا يُحسب نجاحًا إن فشل المولد
+    if pdf_path.exists() {
+        tokio_fs::remove_file(pdf_path)
+            .await
+            .context(format!("فشل في حذف ملف PDF السابق: {}", pdf_path.display()))?;
+    }
+    
+    for renderer in PDF_RENDERERS {
+        let mut command = tokio::process::Command::new(renderer);
+        if *renderer == "wkhtmltopdf" {
+            command
+                .args(["--quiet", "--enable-local-file-access"])
+                .arg(&html_path)
+                .arg(pdf_path);
+        } else {
+            command
+                .args(["--headless", "--disable-gpu", "--no-pdf-header-footer"])
+                .arg(format!("--print-to-pdf={}", pdf_path.display()))
+                .arg(format!("file://{}", html_path.display()));
         }
         
-        xml.push_str("</redfox-report>");
+        match command.output().await {
+            Ok(output) if output.status.success() && pdf_path.exists() => return Ok(()),
+            Ok(output) => tracing::warn!(
+                "فشل {} في توليد PDF: {}",
+                renderer,
+                String::from_utf8_lossy(&output.stderr).trim()
+            ),
+            // الأداة غير مثبتة
+            Err(_) => continue,
+        }
+    }
+    
+    Err(anyhow::anyhow!("توليد PDF يتطلب تثبيت chromium أو google-chrome أو wkhtmltopdf"))
+}
+
+/// تجميع النتائج حسب القسم
+fn group_by_department(results: &[ScanResult]) -> BTreeMap<String, DepartmentSummary<'_>> {
+    let mut groups: BTreeMap<String, (usize, Vec<&str>, Vec<&ScanResult>)> = BTreeMap::new();
+    
+    for result in results {
+        let entry = groups.entry(department_of(result).to_string()).or_default();
+        entry.0 += 1;
         
-        tokio_fs::write(filepath, xml).await?;
-        Ok(())
+        if !entry.1.contains(&result.username.as_str()) {
+            entry.1.push(&result.username);
+        }
+        
+        if result.success {
+            entry.2.push(result);
+        }
     }
+    
+    groups
+        .into_iter()
+        .map(|(department, (attempts, users, compromised))| {
+            (department, DepartmentSummary { attempts, users: users.len(), compromised })
+        })
+        .collect()
+}
+
+/// الحصول على قسم المستخدم
+fn department_of(result: &ScanResult) -> &str {
+    result
+        .metadata
+        .as_ref()
+        .and_then(|m| m.department.as_deref())
+        .unwrap_or("غير محدد")
+}
+
+/// قراءة حقل من البيانات الوصفية
+fn metadata_field<'a>(
+    result: &'a ScanResult,
+    field: impl Fn(&'a crate::parser::UserMetadata) -> Option<&'a str>,
+) -> &'a str {
+    result.metadata.as_ref().and_then(field).unwrap_or("")
+}
+
+/// التحقق مما إذا كانت النتيجة حسابًا مميزًا بكلمة مرور ضعيفة
+fn is_privileged_weak(result: &ScanResult) -> bool {
+    result.success
+        && result.metadata.as_ref().map(|m| m.is_privileged()).unwrap_or(false)
+        && is_weak_password(&result.password)
+}
+
+/// التحقق مما إذا كانت كلمة المرور ضعيفة
+fn is_weak_password(password: &str) -> bool {
+    let classes = [
+        password.chars().any(|c| c.is_lowercase()),
+        password.chars().any(|c| c.is_uppercase()),
+        password.chars().any(|c| c.is_ascii_digit()),
+        password.chars().any(|c| !c.is_alphanumeric()),
+    ]
+    .iter()
+    .filter(|&&present| present)
+    .count();
+    
+    password.chars().count() < 10 || classes < 3
+}
+
+/// عدد أعمدة مخططات تقرير HTML
+const CHART_BUCKETS: usize = 12;
+
+/// سكربت تقرير HTML: فرز الجداول وتصفيتها وتقسيم المحاولات الفاشلة إلى صفحات (مضمن دون CDN)
+const HTML_SCRIPT: &str = r#"
+(function () {
+    const PAGE_SIZE = 50;
+    
+    function cellValue(row, col) {
+        const cell = row.cells[col];
+        return cell.dataset.sort !== undefined ? cell.dataset.sort : cell.textContent.trim();
+    }
+    
+    function sortTable(table, col, th) {
+        const order = th.dataset.order === 'asc' ? 'desc' : 'asc';
+        table.querySelectorAll('th').forEach(h => delete h.dataset.order);
+        th.dataset.order = order;
+        
+        if (table.id === 'failed-table') {
+            failed.sort(col, order);
+            return;
+        }
+        
+        const rows = Array.from(table.rows).slice(1);
+        rows.sort((a, b) => {
+            const x = cellValue(a, col), y = cellValue(b, col);
+            const cmp = (isNaN(x) || isNaN(y)) ? x.localeCompare(y) : x - y;
+            return order === 'asc' ? cmp : -cmp;
+        });
+        rows.forEach(row => row.parentNode.appendChild(row));
+    }
+    
+    document.querySelectorAll('table.sortable').forEach(table => {
+        Array.from(table.rows[0].cells).forEach((th, col) => {
+            th.addEventListener('click', () => sortTable(table, col, th));
+        });
+    });
+    
+    document.querySelectorAll('input[data-filter]').forEach(input => {
+        const table = document.getElementById(input.dataset.filter);
+        input.addEventListener('input', () => {
+            const term = input.value.toLowerCase();
+            Array.from(table.rows).slice(1).forEach(row => {
+                row.style.display = row.textContent.toLowerCase().includes(term) ? '' : 'none';
+            });
+        });
+    });
+    
+    const data = document.getElementById('failed-data');
+    const failed = {
+        all: data ? JSON.parse(data.textContent) : [],
+        visible: [],
+        page: 0,
+        filter(term) {
+            term = term.toLowerCase();
+            this.visible = this.all.filter(row => row.join(' ').toLowerCase().includes(term));
+            this.page = 0;
+            this.render();
+        },
+        sort(col, order) {
+            const cmp = (a, b) => typeof a[col] === 'number' ? a[col] - b[col] : String(a[col]).localeCompare(String(b[col]));
+            this.visible.sort((a, b) => order === 'asc' ? cmp(a, b) : -cmp(a, b));
+            this.render();
+        },
+        render() {
+            const table = document.getElementById('failed-table');
+            const pages = Math.max(1, Math.ceil(this.visible.length / PAGE_SIZE));
+            this.page = Math.min(this.page, pages - 1);
+            
+            Array.from(table.rows).slice(1).forEach(row => row.remove());
+            this.visible.slice(this.page * PAGE_SIZE, (this.page + 1) * PAGE_SIZE).forEach(values => {
+                const row = table.insertRow();
+                values.forEach(value => { row.insertCell().textContent = value; });
+            });
+            
+            document.getElementById('failed-page').textContent = (this.page + 1) + ' / ' + pages;
+            document.getElementById('failed-prev').disabled = this.page === 0;
+            document.getElementById('failed-next').disabled = this.page >= pages - 1;
+        },
+    };
+    
+    if (data) {
+        document.getElementById('failed-filter').addEventListener('input', e => failed.filter(e.target.value));
+        document.getElementById('failed-prev').addEventListener('click', () => { failed.page--; failed.render(); });
+        document.getElementById('failed-next').addEventListener('click', () => { failed.page++; failed.render(); });
+        failed.filter('');
+    }
+})();
+"#;
+
+/// توزيع أزمنة الاستجابة على فئات متساوية العرض (مللي ثانية)
+fn response_time_histogram(results: &[ScanResult]) -> Vec<(String, usize)> {
+    let times: Vec<u128> = results.iter().map(|r| r.response_time.as_millis()).collect();
+    let Some(&max) = times.iter().max() else {
+        return Vec::new();
+    };
+    
+    let width = (max / CHART_BUCKETS as u128 + 1).max(1);
+    let mut counts = vec![0usize; CHART_BUCKETS];
+    for time in times {
+        counts[((time / width) as usize).min(CHART_BUCKETS - 1)] += 1;
+    }
+    
+    counts
+        .into_iter()
+        .enumerate()
+        .map(|(i, count)| (format!("{}-{}", i as u128 * width, (i as u128 + 1) * width), count))
+        .collect()
+}
+
+/// عدد المحاولات في فترات زمنية متساوية بين أول وآخر محاولة
+fn attempts_over_time(results: &[ScanResult]) -> Vec<(String, usize)> {
+    let (Some(first), Some(last)) = (
+        results.iter().map(|r| r.timestamp).min(),
+        results.iter().map(|r| r.timestamp).max(),
+    ) else {
+        return Vec::new();
+    };
+    
+    let span = (last - first).num_milliseconds().max(1);
+    let width = span / CHART_BUCKETS as i64 + 1;
+    let mut counts = vec![0usize; CHART_BUCKETS];
+    for result in results {
+        let offset = (result.timestamp - first).num_milliseconds();
+        counts[((offset / width) as usize).min(CHART_BUCKETS - 1)] += 1;
+    }
+    
+    counts
+        .into_iter()
+        .enumerate()
+        .map(|(i, count)| {
+            let start = first + chrono::Duration::milliseconds(i as i64 * width);
+            (start.with_timezone(&Local).format("%H:%M:%S").to_string(), count)
+        })
+        .collect()
+}
+
+/// مخطط أعمدة SVG مضمن
+fn svg_bar_chart(bars: &[(String, usize)], color: &str) -> String {
+    if bars.is_empty() {
+        return "<p style='color: #666;'>لا توجد بيانات</p>".to_string();
+    }
+    
+    const WIDTH: usize = 480;
+    const HEIGHT: usize = 200;
+    const LABEL_HEIGHT: usize = 40;
+    
+    let max = bars.iter().map(|(_, count)| *count).max().unwrap_or(0).max(1);
+    let slot = WIDTH / bars.len();
+    
+    let mut svg = format!(
+        "<svg viewBox='0 0 {} {}' xmlns='http://www.w3.org/2000/svg' direction='ltr'>\n",
+        WIDTH,
+        HEIGHT + LABEL_HEIGHT
+    );
+    for (i, (label, count)) in bars.iter().enumerate() {
+        let height = count * HEIGHT / max;
+        let x = i * slot;
+        svg.push_str(&format!(
+            "  <rect x='{}' y='{}' width='{}' height='{}' fill='{}'><title>{}: {}</title></rect>\n",
+            x + 2,
+            HEIGHT - height,
+            slot.saturating_sub(4),
+            height,
+            color,
+            escape_xml(label),
+            count
+        ));
+        svg.push_str(&format!(
+            "  <text x='{}' y='{}' font-size='9' text-anchor='end' transform='rotate(-45 {} {})'>{}</text>\n",
+            x + slot / 2,
+            HEIGHT + 12,
+            x + slot / 2,
+            HEIGHT + 12,
+            escape_xml(label)
+        ));
+    }
+    svg.push_str("</svg>");
+    
+    svg
+}
+
+/// إخفاء كلمة المرور مع إبقاء الحرفين الأول والأخير (p*******d)
+pub(crate) fn mask_password(password: &str) -> String {
+    let chars: Vec<char> = password.chars().collect();
+    
+    match chars.len() {
+        0 => String::new(),
+        len @ 1..=2 => "*".repeat(len),
+        len => format!("{}{}{}", chars[0], "*".repeat(len - 2), chars[len - 1]),
+    }
+}
+
+/// تجزئة HMAC-SHA256 بمفتاح المشغل بالست عشري
+fn keyed_hash(key: &[u8], password: &str) -> String {
+    #[allow(clippy::expect_used)] // لا يفشل: HMAC يقبل مفاتيح بأي طول
+    let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("HMAC يقبل مفاتيح بأي طول");
+    mac.update(password.as_bytes());
+    hex_string(&mac.finalize().into_bytes())
+}
+
+/// تحويل بايتات إلى نص ست عشري
+fn hex_string(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// حذف الأحرف غير المسموحة في XML 1.0 (أحرف التحكم) قبل التسلسل
+fn xml_text(text: &str) -> String {
+    text.chars()
+        .filter(|&c| matches!(c, '\t' | '\n' | '\r') || (c >= ' ' && c != '\u{FFFE}' && c != '\u{FFFF}'))
+        .collect()
 }
 
 /// تهريب أحرف XML
@@ -644,4 +1769,57 @@ impl Default for ReportGenerator {
     fn default() -> Self {
         Self::new()
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_mask_password_keeps_first_and_last() {
+        assert_eq!(mask_password("password"), "p******d");
+        assert_eq!(mask_password("كلمةسر"), "ك****ر");
+        assert_eq!(mask_password("ab"), "**");
+        assert_ne!(keyed_hash(b"a", "secret"), keyed_hash(b"b", "secret"));
+        assert_eq!(keyed_hash(b"a", "secret"), keyed_hash(b"a", "secret"));
+    }
+    
+    #[test]
+    fn test_response_time_histogram_covers_all_results() {
+        let results: Vec<ScanResult> = [5u64, 10, 120, 600]
+            .iter()
+            .map(|&ms| ScanResult { response_time: Duration::from_millis(ms), ..ScanResult::default() })
+            .collect();
+        
+        let histogram = response_time_histogram(&results);
+        
+        assert_eq!(histogram.len(), CHART_BUCKETS);
+        assert_eq!(histogram.iter().map(|(_, count)| count).sum::<usize>(), 4);
+        assert_eq!(histogram.last().unwrap().1, 1);
+    }
+    
+    #[test]
+    fn test_group_by_target_counts_per_target() {
+        let result = |target: &str, username: &str, success| ScanResult {
+            target: Some(target.to_string()),
+            username: username.to_string(),
+            success,
+            response_time: Duration::from_millis(100),
+            ..ScanResult::default()
+        };
+        let results = vec![
+            result("https://a.example", "admin", true),
+            result("https://a.example", "admin", false),
+            result("https://a.example", "root", false),
+            result("https://b.example", "admin", false),
+        ];
+        
+        let targets = group_by_target(&results);
+        
+        assert_eq!(targets.len(), 2);
+        assert_eq!(targets["https://a.example"].attempts, 3);
+        assert_eq!(targets["https://a.example"].users, 2);
+        assert_eq!(targets["https://a.example"].compromised.len(), 1);
+        assert!(targets["https://b.example"].compromised.is_empty());
+    }
+}
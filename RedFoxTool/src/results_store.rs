@@ -0,0 +1,301 @@
+//! تخزين النتائج الخام
+//! حفظ وتحميل نتائج الفحص (JSON, NDJSON, SQLite) لإعادة توليد التقارير
+
+use std::collections::HashMap;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use anyhow::{Result, Context};
+use parking_lot::Mutex;
+use serde_json::Value;
+use tokio::fs as tokio_fs;
+
+use crate::reporter::ReportEntry;
+use crate::scanner::ScanResult;
+
+/// حفظ النتائج بتنسيق NDJSON (نتيجة كاملة في كل سطر)
+pub async fn save_ndjson(results: &[ScanResult], filepath: &Path) -> Result<()> {
+    let mut content = String::new();
+    
+    for result in results {
+        content.push_str(&serde_json::to_string(result)?);
+        content.push('\n');
+    }
+    
+    tokio_fs::write(filepath, content).await?;
+    Ok(())
+}
+
+/// بث النتائج بتنسيق JSONL فور إنتاجها (إلحاق وتفريغ لكل سطر حتى لا يضيع شيء عند الانهيار)
+pub struct ResultStream {
+    path: PathBuf,
+    file: Mutex<std::fs::File>,
+}
+
+impl ResultStream {
+    /// فتح الملف للإلحاق (يُنشأ إن لم يوجد)
+    pub fn open(path: &Path) -> Result<Self> {
+        let file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .context(format!("فشل في فتح ملف البث: {}", path.display()))?;
+        
+        Ok(Self { path: path.to_path_buf(), file: Mutex::new(file) })
+    }
+    
+    /// مسار الملف
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+    
+    /// إلحاق نتيجة كسطر JSON
+    pub fn write(&self, result: &ScanResult) {
+        let line = match serde_json::to_string(result) {
+            Ok(json) => json + "\n",
+            Err(e) => {
+                tracing::warn!("فشل في ترميز النتيجة: {}", e);
+                return;
+            }
+        };
+        
+        let mut file = self.file.lock();
+        if let Err(e) = file.write_all(line.as_bytes()).and_then(|_| file.flush()) {
+            tracing::warn!("فشل في الكتابة إلى {}: {}", self.path.display(), e);
+        }
+    }
+}
+
+/// حفظ النتائج في قاعدة بيانات SQLite
+pub async fn save_sqlite(results: &[ScanResult], filepath: &Path) -> Result<()> {
+    let results = results.to_vec();
+    let filepath = filepath.to_path_buf();
+    
+    tokio::task::spawn_blocking(move || -> Result<()> {
+        let mut conn = rusqlite::Connection::open(&filepath)
+            .context(format!("فشل في فتح قاعدة البيانات: {}", filepath.display()))?;
+        
+        conn.execute_batch(
+            "CREATE TABLE IF NOT EXISTS results (
+                username TEXT NOT NULL,
+                password TEXT NOT NULL,
+                success INTEGER NOT NULL,
+                status_code INTEGER NOT NULL,
+                response_time_ms INTEGER NOT NULL,
+                error TEXT,
+                timestamp TEXT NOT NULL,
+                metadata TEXT,
+                captcha_detected INTEGER NOT NULL DEFAULT 0,
+                banner TEXT,
+                http_version TEXT
+            );",
+        )?;
+        
+        let tx = conn.transaction()?;
+        {
+            let mut stmt = tx.prepare(
+                "INSERT INTO results
+                 (username, password, success, status_code, response_time_ms, error, timestamp, metadata, captcha_detected, banner, http_version)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)",
+            )?;
+            
+            for result in &results {
+                stmt.execute(rusqlite::params![
+                    result.username,
+                    result.password,
+                    result.success,
+                    result.status_code,
+                    result.response_time.as_millis() as i64,
+                    result.error,
+                    result.timestamp.to_rfc3339(),
+                    result.metadata.as_ref().map(serde_json::to_string).transpose()?,
+                    result.captcha_detected,
+                    result.banner,
+                    result.http_version,
+                ])?;
+            }
+        }
+        tx.commit()?;
+        
+        Ok(())
+    })
+    .await?
+}
+
+/// تحميل نتائج محفوظة (يُحدد التنسيق من امتداد الملف)
+pub async fn load_results(filepath: &str) -> Result<Vec<ScanResult>> {
+    let extension = Path::new(filepath)
+        .extension()
+        .and_then(|e| e.to_str())
+        .unwrap_or("")
+        .to_lowercase();
+    
+    let results = match extension.as_str() {
+        "ndjson" | "jsonl" => load_ndjson(filepath).await,
+        "db" | "sqlite" | "sqlite3" => load_sqlite(filepath).await,
+        "json" => load_json(filepath).await,
+        _ => Err(anyhow::anyhow!("تنسيق نتائج غير مدعوم: {}", filepath)),
+    }?;
+    
+    if results.is_empty() {
+        return Err(anyhow::anyhow!("لا توجد نتائج في الملف: {}", filepath));
+    }
+    
+    Ok(results)
+}
+
+/// تحميل ملف NDJSON
+async fn load_ndjson(filepath: &str) -> Result<Vec<ScanResult>> {
+    let content = tokio_fs::read_to_string(filepath)
+        .await
+        .context(format!("فشل في قراءة الملف: {}", filepath))?;
+    
+    content
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(i, line)| {
+            serde_json::from_str(line).context(format!("سطر غير صالح ({}): {}", i + 1, filepath))
+        })
+        .collect()
+}
+
+/// تحميل ملف JSON (مصفوفة نتائج خام أو تقرير JSON سابق)
+async fn load_json(filepath: &str) -> Result<Vec<ScanResult>> {
+    let content = tokio_fs::read_to_string(filepath)
+        .await
+        .context(format!("فشل في قراءة الملف: {}", filepath))?;
+    
+    let value: Value = serde_json::from_str(&content)
+        .context(format!("ملف JSON غير صالح: {}", filepath))?;
+    
+    // مصفوفة نتائج خام
+    if value.is_array() {
+        return serde_json::from_value(value).context("فشل في تحليل النتائج");
+    }
+    
+    // تقرير JSON مولد بواسطة ReportGenerator
+    let mut results = Vec::new();
+    
+    for (key, success) in [("successful", true), ("failed", false)] {
+        if let Some(entries) = value.get(key).and_then(Value::as_array) {
+            for entry in entries {
+                let entry: ReportEntry = serde_json::from_value(entry.clone())
+                    .context(format!("إدخال تقرير غير صالح: {}", filepath))?;
+                results.push(entry.into_result(success));
+            }
+        }
+    }
+    
+    let total = value
+        .pointer("/metadata/total_results")
+        .and_then(Value::as_u64)
+        .unwrap_or(0) as usize;
+    
+    if total > results.len() {
+        tracing::warn!(
+            "التقرير يحتوي على {} نتيجة فقط من أصل {} (المحاولات الفاشلة مقتطعة)",
+            results.len(),
+            total
+        );
+    }
+    
+    Ok(results)
+}
+
+/// دمج نتائج من عدة مصادر مع إزالة التكرار (المحاولة الناجحة تحل محل الفاشلة لنفس الهدف وبيانات الاعتماد)
+pub fn merge_results(sources: impl IntoIterator<Item = Vec<ScanResult>>) -> Vec<ScanResult> {
+    let mut merged: Vec<ScanResult> = Vec::new();
+    let mut index: HashMap<(Option<String>, String, String), usize> = HashMap::new();
+    
+    for result in sources.into_iter().flatten() {
+        let key = (result.target.clone(), result.username.clone(), result.password.clone());
+        match index.get(&key) {
+            Some(&i) if result.success && !merged[i].success => merged[i] = result,
+            Some(_) => {}
+            None => {
+                index.insert(key, merged.len());
+                merged.push(result);
+            }
+        }
+    }
+    
+    merged
+}
+
+/// تحميل قاعدة بيانات SQLite
+async fn load_sqlite(filepath: &str) -> Result<Vec<ScanResult>> {
+    let filepath = filepath.to_string();
+    
+    tokio::task::spawn_blocking(move || -> Result<Vec<ScanResult>> {
+        let conn = rusqlite::Connection::open_with_flags(
+            &filepath,
+            rusqlite::OpenFlags::SQLITE_OPEN_READ_ONLY,
+        )
+        .context(format!("فشل في فتح قاعدة البيانات: {}", filepath))?;
+        
+        let mut stmt = conn.prepare(
+            "SELECT username, password, success, status_code, response_time_ms, error, timestamp, metadata,
+                    captcha_detected, banner, http_version
+             FROM results",
+        )?;
+        
+        let rows = stmt.query_map([], |row| {
+            let timestamp: String = row.get(6)?;
+            let metadata: Option<String> = row.get(7)?;
+            
+            Ok(ScanResult {
+                username: row.get(0)?,
+                password: row.get(1)?,
+                success: row.get(2)?,
+                status_code: row.get(3)?,
+                response_time: Duration::from_millis(row.get::<_, i64>(4)?.max(0) as u64),
+                error: row.get(5)?,
+                timestamp: chrono::DateTime::parse_from_rfc3339(&timestamp)
+                    .map(|t| t.with_timezone(&chrono::Utc))
+                    .unwrap_or_else(|_| chrono::Utc::now()),
+                metadata: metadata.and_then(|m| serde_json::from_str(&m).ok()),
+                captcha_detected: row.get::<_, Option<bool>>(8)?.unwrap_or(false),
+                banner: row.get(9)?,
+                http_version: row.get(10)?,
+                ..Default::default()
+            })
+        })?;
+        
+        rows.collect::<rusqlite::Result<Vec<_>>>().context("فشل في قراءة النتائج")
+    })
+    .await?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_merge_prefers_successful_duplicate() {
+        let result = |username: &str, success| ScanResult {
+            username: username.to_string(),
+            password: "secret".to_string(),
+            success,
+            status_code: if success { 302 } else { 200 },
+            response_time: Duration::from_millis(10),
+            error: None,
+            timestamp: chrono::Utc::now(),
+            metadata: None,
+            captcha_detected: false,
+            banner: None,
+            http_version: None,
+            ..Default::default()
+        };
+        
+        let merged = merge_results(vec![
+            vec![result("admin", false), result("guest", false)],
+            vec![result("admin", true), result("guest", false)],
+        ]);
+        
+        assert_eq!(merged.len(), 2);
+        assert!(merged[0].success);
+        assert_eq!(merged[0].username, "admin");
+        assert!(!merged[1].success);
+    }
+}
@@ -1,20 +1,46 @@
 //! الماسح الرئيسي لـ RedFoxTool
 //! يدير عملية الفحص الكاملة
 
+use std::collections::{HashMap, HashSet};
+use std::future::Future;
+use std::path::Path;
 use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 use std::time::{Instant, Duration};
-use tokio::sync::Semaphore;
+use tokio::sync::{Notify, Semaphore};
+use tokio::task::JoinSet;
+use tokio_util::sync::CancellationToken;
+use reqwest::Method;
 use anyhow::{Result, Context};
+use futures::future;
+use futures::stream::{self, BoxStream, StreamExt, TryStreamExt};
 use indicatif::{ProgressBar, ProgressStyle};
+use tracing::Instrument;
 
-use crate::bruteforcer::{Bruteforcer, AttackMode};
-use crate::http_client::HttpClient;
-use crate::parser::parse_input;
-use crate::progress::ProgressTracker;
-use crate::utils::logger::Logger;
+use crate::bruteforcer::AttackMode;
+use crate::csrf::CsrfExtractor;
+use crate::error::{self, RedFoxError};
+use crate::events::{EventHook, ScanEvent};
+use crate::http_client::{AuthType, HttpClient, OAuthOptions, TransportOptions};
+use crate::login_body::{BodyFormat, FormFields, LoginBody};
+use crate::matcher::{SuccessCriteria, SuccessMatcher};
+use crate::modules::presets::{Preset, PresetProbe};
+use crate::modules::defaults::QUICK_DEFAULTS;
+use crate::modules::generator::RuleSet;
+use crate::plugins::{self, PluginRuntime, WasmPlugin};
+use crate::modules::{wordpress, AuthModule, LoginTarget, Protocol, ServiceModule, ServiceOptions, ANONYMOUS_USER};
+use crate::parser::{is_stdin, parse_combo_input, parse_input, parse_json_pairs, parse_user_input, UserMetadata, WordSource};
+use crate::progress::{ProgressObserver, ProgressReporter};
+use crate::raw_request::RawRequest;
+use crate::results_store::ResultStream;
+use crate::session::{SessionConfig, SessionRecorder};
+use crate::throttle::{random_delay, HostLimiter};
+use crate::telemetry::Logger;
+use crate::notifiers::Notifications;
+use crate::validator::{detect_waf, validate_proxy, WafDetection};
 
 /// نتيجة فحص واحدة
-#[derive(Debug, Clone, serde::Serialize)]
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
 pub struct ScanResult {
     /// اسم المستخدم
     pub username: String,
@@ -36,21 +62,537 @@ pub struct ScanResult {
     
     /// الطابع الزمني
     pub timestamp: chrono::DateTime<chrono::Utc>,
+    
+    /// البيانات الوصفية للمستخدم (من ملف CSV)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<UserMetadata>,
+    
+    /// هل احتوت الاستجابة على اختبار CAPTCHA؟
+    #[serde(default)]
+    pub captcha_detected: bool,
+    
+    /// رسالة ترحيب الخادم (لوحدات البروتوكولات مثل FTP)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub banner: Option<String>,
+    
+    /// إصدار HTTP المتفاوض عليه (HTTP/1.1, HTTP/2.0)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub http_version: Option<String>,
+    
+    /// وجهة إعادة التوجيه (ترويسة Location)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub redirect: Option<String>,
+    
+    /// الهدف الذي جُربت عليه المحاولة
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub target: Option<String>,
+    
+    /// وضع الهجوم
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub attack_mode: Option<String>,
+    
+    /// ترتيب المحاولة في نتائج الفحص (يبدأ من 1)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub attempt: Option<u64>,
 }
 
-/// الماسح الرئيسي
-pub struct RedFoxScanner {
+/// عدد الخيوط الافتراضي
+pub const DEFAULT_THREADS: usize = 20;
+
+/// مهلة الطلب الافتراضية (ثوانٍ)
+pub const DEFAULT_TIMEOUT_SECS: u64 = 30;
+
+/// عدد محاولات المعايرة الافتراضي
+pub const DEFAULT_CALIBRATION_SAMPLES: usize = 3;
+
+/// أدنى تأخير افتراضي بين الطلبات في الوضع الخفي (مللي ثانية)
+pub const DEFAULT_MIN_DELAY_MS: u64 = 100;
+
+/// أقصى تأخير افتراضي بين الطلبات في الوضع الخفي (مللي ثانية)
+pub const DEFAULT_MAX_DELAY_MS: u64 = 500;
+
+/// خيارات التحكم في سلوك الفحص (تُحمل أيضًا من TOML/JSON؛ المدد بصيغة "30s" أو "250ms")
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct ScanOptions {
+    /// عدد الطلبات المتوازية
+    pub threads: usize,
+    
+    /// مهلة كل طلب
+    #[serde(with = "humantime_serde")]
+    pub timeout: Duration,
+    
+    /// وضع الهجوم
+    pub mode: AttackMode,
+    
+    /// حد المعدل العام (طلبات/ثانية)
+    pub rate_limit: Option<u32>,
+    
+    /// إيقاف الفحص بالكامل عند أول بيانات اعتماد صالحة
+    pub stop_on_success_global: bool,
+    
+    /// إيقاف المحاولات المتبقية للمستخدم عند إيجاد كلمة مروره
+    pub stop_per_user: bool,
+    
+    /// عدد المحاولات الخاطئة المتعمدة لمعايرة استجابة الفشل (0 للتعطيل)
+    pub calibration_samples: usize,
+    
+    /// قواعد كشف النجاح المخصصة
+    pub success_criteria: SuccessCriteria,
+    
+    /// إيقاف الفحص عند اكتشاف CAPTCHA
+    pub halt_on_captcha: bool,
+    
+    /// أدنى تأخير عشوائي بين الطلبات
+    #[serde(with = "humantime_serde")]
+    pub min_delay: Duration,
+    
+    /// أقصى تأخير عشوائي بين الطلبات
+    #[serde(with = "humantime_serde")]
+    pub max_delay: Duration,
+    
+    /// تطبيق التأخير العشوائي في الوضع العادي أيضًا
+    pub jitter_normal: bool,
+    
+    /// اختبار أشهر بيانات الاعتماد الافتراضية قبل القائمة الرئيسية
+    pub quick_defaults: bool,
+}
+
+impl Default for ScanOptions {
+    fn default() -> Self {
+        Self {
+            threads: DEFAULT_THREADS,
+            timeout: Duration::from_secs(DEFAULT_TIMEOUT_SECS),
+            mode: AttackMode::Normal,
+            rate_limit: None,
+            stop_on_success_global: false,
+            stop_per_user: false,
+            calibration_samples: DEFAULT_CALIBRATION_SAMPLES,
+            success_criteria: SuccessCriteria::default(),
+            halt_on_captcha: false,
+            min_delay: Duration::from_millis(DEFAULT_MIN_DELAY_MS),
+            max_delay: Duration::from_millis(DEFAULT_MAX_DELAY_MS),
+            jitter_normal: false,
+            quick_defaults: false,
+        }
+    }
+}
+
+/// إشارة الإيقاف المشتركة بين مهام الفحص
+struct StopSignal {
+    options: ScanOptions,
+    cancel: CancellationToken,
+    global: AtomicBool,
+    captcha: AtomicBool,
+    users: parking_lot::Mutex<HashSet<String>>,
+    notify: Notify,
+}
+
+impl StopSignal {
+    /// إنشاء إشارة جديدة
+    fn new(options: ScanOptions, cancel: CancellationToken) -> Self {
+        Self {
+            options,
+            cancel,
+            global: AtomicBool::new(false),
+            captcha: AtomicBool::new(false),
+            users: parking_lot::Mutex::new(HashSet::new()),
+            notify: Notify::new(),
+        }
+    }
+    
+    /// هل توقف الفحص بالكامل؟ (بما في ذلك الإلغاء من المضيف)
+    fn is_global_stopped(&self) -> bool {
+        self.global.load(Ordering::Relaxed) || self.is_cancelled()
+    }
+    
+    /// هل ألغى المضيف الفحص؟ (المحاولات الجارية تكتمل ولا تبدأ محاولات جديدة)
+    fn is_cancelled(&self) -> bool {
+        self.cancel.is_cancelled()
+    }
+    
+    /// هل توقفت المحاولات لهذا المستخدم؟
+    fn is_stopped(&self, username: &str) -> bool {
+        self.is_global_stopped() || self.users.lock().contains(username)
+    }
+    
+    /// هل تم اكتشاف CAPTCHA؟
+    fn captcha_seen(&self) -> bool {
+        self.captcha.load(Ordering::Relaxed)
+    }
+    
+    /// تحديث الإشارة بنتيجة محاولة
+    fn observe(&self, result: &ScanResult) {
+        if result.captcha_detected {
+            let first = !self.captcha.swap(true, Ordering::Relaxed);
+            if first {
+                tracing::warn!(username = %result.username, "تم اكتشاف CAPTCHA في استجابة تسجيل الدخول");
+            }
+            
+            if self.options.halt_on_captcha {
+                self.global.store(true, Ordering::Relaxed);
+                self.notify.notify_waiters();
+            }
+        }
+        
+        if !result.success {
+            return;
+        }
+        
+        if self.options.stop_on_success_global {
+            self.global.store(true, Ordering::Relaxed);
+        }
+        if self.options.stop_per_user {
+            self.users.lock().insert(result.username.clone());
+        }
+        if self.options.stop_on_success_global || self.options.stop_per_user {
+            self.notify.notify_waiters();
+        }
+    }
+    
+    /// انتظار إيقاف هذا المستخدم
+    async fn stopped(&self, username: &str) {
+        loop {
+            let notified = self.notify.notified();
+            if self.is_stopped(username) {
+                return;
+            }
+            notified.await;
+        }
+    }
+    
+    /// تنفيذ محاولة مع إلغائها إذا توقف المستخدم قبلها أو أثناءها
+    async fn guard<F: Future>(&self, username: &str, attempt: F) -> Option<F::Output> {
+        if self.is_stopped(username) {
+            return None;
+        }
+        
+        tokio::select! {
+            output = attempt => Some(output),
+            () = self.stopped(username) => None,
+        }
+    }
+}
+
+/// مصدر النتائج الذي يُرفق بكل نتيجة
+#[derive(Default)]
+struct ResultOrigin {
+    target: String,
+    attack_mode: String,
+}
+
+impl ResultOrigin {
+    /// إرفاق الهدف ووضع الهجوم بالنتيجة إن لم يحددا
+    fn stamp(&self, result: &mut ScanResult) {
+        result.target.get_or_insert_with(|| self.target.clone());
+        result.attack_mode.get_or_insert_with(|| self.attack_mode.clone());
+    }
+}
+
+/// مستقبلو النتائج فور إنتاجها (الجلسة، ملف البث، الإشعارات، قناة الخادم، خطافات المضيف)
+#[derive(Clone, Default)]
+struct ResultSinks {
+    origin: Arc<ResultOrigin>,
+    session: Option<Arc<SessionRecorder>>,
+    stream: Option<Arc<ResultStream>>,
+    notifications: Option<Arc<Notifications>>,
+    live: Option<tokio::sync::mpsc::UnboundedSender<ScanResult>>,
+    hooks: Vec<Arc<dyn EventHook>>,
+    observers: Vec<(Arc<dyn ProgressObserver>, Duration)>,
+    completed: Arc<AtomicUsize>,
+    total: Arc<AtomicUsize>,
+}
+
+impl ResultSinks {
+    /// تمرير نتيجة إلى كل المستقبلين
+    fn record(&self, result: &ScanResult) {
+        let mut result = result.clone();
+        self.origin.stamp(&mut result);
+        let result = &result;
+        
+        tracing::debug!(
+            username = %result.username,
+            status = result.status_code,
+            latency_ms = result.response_time.as_millis() as u64,
+            success = result.success,
+            error = result.error.as_deref(),
+            "نتيجة محاولة"
+        );
+        
+        if let Some(session) = &self.session {
+            session.record(result);
+        }
+        if let Some(stream) = &self.stream {
+            stream.write(result);
+        }
+        if let Some(notifications) = self.notifications.as_ref().filter(|_| result.success) {
+            notifications.credential_found(result);
+        }
+        if let Some(live) = &self.live {
+            let _ = live.send(result.clone());
+        }
+        
+        let completed = self.completed.fetch_add(1, Ordering::Relaxed) + 1;
+        if !self.hooks.is_empty() {
+            self.emit(&ScanEvent::from_result(result));
+            self.emit(&ScanEvent::Progress { completed, total: self.total.load(Ordering::Relaxed) });
+        }
+    }
+    
+    /// تمرير حدث إلى خطافات المضيف
+    fn emit(&self, event: &ScanEvent<'_>) {
+        for hook in &self.hooks {
+            hook.on_event(event);
+        }
+    }
+    
+    /// انتظار مقصود مع إبلاغ الخطافات
+    async fn throttle(&self, delay: Duration, reason: &'static str) {
+        if delay.is_zero() {
+            return;
+        }
+        self.emit(&ScanEvent::Throttle { delay, reason });
+        tokio::time::sleep(delay).await;
+    }
+}
+
+/// الماسح الرئيسي (عام على وحدة المصادقة؛ الافتراضي LoginTarget للبروتوكولات المدمجة)
+pub struct RedFoxScanner<M = LoginTarget> {
     http_client: Arc<HttpClient>,
+    service: Option<Arc<ServiceModule>>,
+    module: Option<Arc<M>>,
     users: Vec<String>,
-    passwords: Vec<String>,
-    max_workers: usize,
-    attack_mode: AttackMode,
-    rate_limit: Option<u32>,
+    user_metadata: HashMap<String, UserMetadata>,
+    passwords: WordSource,
+    combos: Option<Vec<(String, String)>>,
+    options: ScanOptions,
+    host: Option<Arc<HostLimiter>>,
+    sinks: ResultSinks,
+    known_users: HashSet<String>,
+    cancel: CancellationToken,
     logger: Logger,
 }
 
+/// باني الماسح بواجهة سلسة وأنواع صريحة بدل المعاملات الموضعية
+///
+/// ```ignore
+/// let scanner = ScannerBuilder::new()
+///     .target("https://example.com/login")
+///     .users(["admin", "root"])
+///     .passwords(["123456", "admin"])
+///     .mode(AttackMode::Stealth)
+///     .rate_limit(10)
+///     .build()
+///     .await?;
+/// ```
+#[derive(Debug, Clone)]
+pub struct ScannerBuilder {
+    target: Option<String>,
+    users: Vec<String>,
+    user_metadata: HashMap<String, UserMetadata>,
+    passwords: WordSource,
+    combos: Option<Vec<(String, String)>>,
+    options: ScanOptions,
+}
+
+impl Default for ScannerBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ScannerBuilder {
+    /// باني جديد بالقيم الافتراضية لسطر الأوامر
+    pub fn new() -> Self {
+        Self {
+            target: None,
+            users: Vec::new(),
+            user_metadata: HashMap::new(),
+            passwords: WordSource::default(),
+            combos: None,
+            options: ScanOptions::default(),
+        }
+    }
+    
+    /// باني من معاملات المنشئات القديمة (الوضع كنص)
+    fn legacy(url: &str, threads: usize, timeout: u64, mode: &str, rate_limit: Option<u32>) -> Self {
+        let mut builder = Self::new()
+            .target(url)
+            .threads(threads)
+            .timeout(Duration::from_secs(timeout))
+            .mode(AttackMode::from_name(mode));
+        builder.options.rate_limit = rate_limit;
+        builder
+    }
+    
+    /// رابط الهدف
+    pub fn target(mut self, url: impl Into<String>) -> Self {
+        self.target = Some(url.into());
+        self
+    }
+    
+    /// قائمة المستخدمين
+    pub fn users<I, S>(mut self, users: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.users = users.into_iter().map(Into::into).collect();
+        self
+    }
+    
+    /// قائمة كلمات المرور
+    pub fn passwords<I, S>(mut self, passwords: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.passwords = passwords.into_iter().collect();
+        self
+    }
+    
+    /// أزواج (username, password) محددة بدل الجداء الكامل
+    pub fn combos(mut self, combos: Vec<(String, String)>) -> Self {
+        let mut seen_users = HashSet::new();
+        let mut seen_passwords = HashSet::new();
+        self.users = combos
+            .iter()
+            .filter(|(u, _)| seen_users.insert(u.as_str()))
+            .map(|(u, _)| u.clone())
+            .collect();
+        self.passwords = combos
+            .iter()
+            .filter(|(_, p)| seen_passwords.insert(p.as_str()))
+            .map(|(_, p)| p.clone())
+            .collect();
+        
+        Logger.info(&format!(
+            "تم تحميل {} زوج ({} مستخدم، {} كلمة مرور)",
+            combos.len(),
+            self.users.len(),
+            self.passwords.len()
+        ));
+        
+        self.combos = Some(combos);
+        self
+    }
+    
+    /// تحميل المستخدمين من اسم أو ملف أو CSV مع بيانات وصفية
+    pub async fn load_users(mut self, input: &str) -> error::Result<Self> {
+        let logger = Logger;
+        
+        logger.info("تحليل قائمة المستخدمين...");
+        let (users, user_metadata) = parse_user_input(input)
+            .await
+            .context("فشل في تحليل المستخدمين")?;
+        
+        logger.info(&format!("تم تحميل {} مستخدم", users.len()));
+        if !user_metadata.is_empty() {
+            logger.info(&format!("تم تحميل بيانات وصفية لـ {} مستخدم", user_metadata.len()));
+        }
+        
+        self.users = users;
+        self.user_metadata = user_metadata;
+        Ok(self)
+    }
+    
+    /// تحميل كلمات المرور من قيمة أو ملف أو قائمة مثبتة (الملفات تُبث أثناء الفحص ولا تُحمل كاملة)
+    pub async fn load_passwords(mut self, input: &str) -> error::Result<Self> {
+        let passwords = WordSource::from_input(input)
+            .await
+            .context("فشل في تحليل كلمات المرور")?;
+        
+        Logger.info(&format!("تم تحميل {} كلمة مرور", passwords.len()));
+        self.passwords = passwords;
+        Ok(self)
+    }
+    
+    /// تحميل ملف أزواج (username:password)
+    pub async fn load_combos(self, input: &str) -> error::Result<Self> {
+        Logger.info("تحليل ملف الأزواج...");
+        let combos = parse_combo_input(input)
+            .await
+            .context("فشل في تحليل ملف الأزواج")?;
+        
+        Ok(self.combos(combos))
+    }
+    
+    /// عدد خيوط المعالجة
+    pub fn threads(mut self, threads: usize) -> Self {
+        self.options.threads = threads.max(1);
+        self
+    }
+    
+    /// مهلة الطلب
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.options.timeout = timeout;
+        self
+    }
+    
+    /// وضع الهجوم
+    pub fn mode(mut self, mode: AttackMode) -> Self {
+        self.options.mode = mode;
+        self
+    }
+    
+    /// حد المعدل العام (طلبات/ثانية)
+    pub fn rate_limit(mut self, requests_per_second: u32) -> Self {
+        self.options.rate_limit = Some(requests_per_second);
+        self
+    }
+    
+    /// خيارات الفحص كاملة (تحل محل ما عُين بـ threads و timeout و mode و rate_limit)
+    pub fn options(mut self, options: ScanOptions) -> Self {
+        self.options = options;
+        self
+    }
+    
+    /// بناء الماسح
+    pub async fn build(self) -> error::Result<RedFoxScanner> {
+        let logger = Logger;
+        let url = self
+            .target
+            .ok_or_else(|| anyhow::anyhow!("الهدف غير محدد (.target())"))?;
+        
+        logger.info(&format!("تهيئة الماسح للهدف: {}", url));
+        logger.info(&format!("وضع الهجوم: {}", self.options.mode.name()));
+        logger.info(&format!("الخيوط: {}", self.options.threads));
+        
+        // إنشاء عميل HTTP (المهلة وحد المعدل تُطبق مع بقية الخيارات في set_options)
+        let http_client = HttpClient::new(&url, self.options.timeout.as_secs().max(1), None)
+            .await
+            .context("فشل في إنشاء عميل HTTP")?;
+        
+        let mut scanner = RedFoxScanner {
+            http_client: Arc::new(http_client),
+            service: None,
+            module: None,
+            users: self.users,
+            user_metadata: self.user_metadata,
+            passwords: self.passwords,
+            combos: self.combos,
+            options: ScanOptions::default(),
+            host: None,
+            sinks: ResultSinks {
+                origin: Arc::new(ResultOrigin {
+                    target: url,
+                    attack_mode: self.options.mode.name().to_string(),
+                }),
+                ..ResultSinks::default()
+            },
+            known_users: HashSet::new(),
+            cancel: CancellationToken::new(),
+            logger,
+        };
+        
+        scanner.set_options(self.options)?;
+        
+        Ok(scanner)
+    }
+}
+
 impl RedFoxScanner {
-    /// إنشاء ماسح جديد
+    /// إنشاء ماسح جديد (غلاف فوق ScannerBuilder للتوافق)
     pub async fn new(
         url: &str,
         user_input: &str,
@@ -59,80 +601,803 @@ impl RedFoxScanner {
         timeout: u64,
         mode: &str,
         rate_limit: Option<u32>,
-    ) -> Result<Self> {
-        let logger = Logger::new(true);
+    ) -> error::Result<Self> {
+        ScannerBuilder::legacy(url, max_workers, timeout, mode, rate_limit)
+            .load_users(user_input)
+            .await?
+            .load_passwords(password_file)
+            .await?
+            .build()
+            .await
+    }
+    
+    /// إنشاء ماسح من ملف أزواج (username:password) لاختبار الأزواج المحددة فقط
+    pub async fn from_combo_file(
+        url: &str,
+        combo_file: &str,
+        max_workers: usize,
+        timeout: u64,
+        mode: &str,
+        rate_limit: Option<u32>,
+    ) -> error::Result<Self> {
+        ScannerBuilder::legacy(url, max_workers, timeout, mode, rate_limit)
+            .load_combos(combo_file)
+            .await?
+            .build()
+            .await
+    }
+    
+    /// إنشاء ماسح من أزواج (username, password) محددة مسبقًا
+    pub async fn from_pairs(
+        url: &str,
+        combos: Vec<(String, String)>,
+        max_workers: usize,
+        timeout: u64,
+        mode: &str,
+        rate_limit: Option<u32>,
+    ) -> error::Result<Self> {
+        ScannerBuilder::legacy(url, max_workers, timeout, mode, rate_limit)
+            .combos(combos)
+            .build()
+            .await
+    }
+    
+    /// بناء ماسح كامل الإعداد من إعدادات الجلسة (سطر الأوامر، الوضع التفاعلي، الخادم)
+    pub async fn from_config(config: &SessionConfig) -> error::Result<Self> {
+        let logger = Logger;
+        let url = config.login_url()?;
+        
+        if config.user.as_deref().is_some_and(is_stdin) && config.password_file.as_deref().is_some_and(is_stdin) {
+            return Err(RedFoxError::InvalidConfig("لا يمكن قراءة المستخدمين وكلمات المرور معًا من stdin".to_string()));
+        }
+        
+        // مفاتيح API لا تحتاج مستخدمًا، فيُستخدم اسم ثابت عند غياب --user
+        let user = config
+            .user
+            .clone()
+            .or_else(|| config.secret_header.as_ref().map(|_| "api".to_string()));
+        
+        // إنشاء الماسح
+        let mut scanner = match (&config.combo_file, &user, &config.password_file) {
+            (Some(combo_file), _, _) => {
+                logger.info(&format!("ملف الأزواج: {}", combo_file));
+                
+                Self::from_combo_file(
+                    &url,
+                    combo_file,
+                    config.threads,
+                    config.timeout,
+                    &config.mode,
+                    config.rate_limit,
+                )
+                .await
+            }
+            (None, Some(user), Some(password_file)) => {
+                logger.info(&format!("المستخدمون: {}", user));
+                
+                Self::new(
+                    &url,
+                    user,
+                    password_file,
+                    config.threads,
+                    config.timeout,
+                    &config.mode,
+                    config.rate_limit,
+                )
+                .await
+            }
+            _ => Err(RedFoxError::InvalidConfig("يجب تحديد --user و --password-file أو --combo-file".to_string())),
+        }
+        .context("فشل في تهيئة الماسح")?;
+        
+        if let Some(rules) = &config.rules {
+            scanner.apply_rules(Path::new(rules)).await?;
+        }
+        
+        Ok(scanner.configure(config).await?)
+    }
+    
+    /// بناء ماسح لأزواج محددة بإعدادات الجلسة (دفعات العمال في الوضع الموزع)
+    pub async fn from_config_pairs(config: &SessionConfig, pairs: Vec<(String, String)>) -> error::Result<Self> {
+        let url = config.login_url()?;
+        let scanner = Self::from_pairs(&url, pairs, config.threads, config.timeout, &config.mode, config.rate_limit)
+            .await
+            .context("فشل في تهيئة الماسح")?;
+        
+        Ok(scanner.configure(config).await?)
+    }
+    
+    /// تطبيق بقية إعدادات الجلسة (النقل، البروتوكول، المصادقة، خيارات الفحص)
+    async fn configure(mut self, config: &SessionConfig) -> Result<Self> {
+        let scanner = &mut self;
+        let preset = config.preset.as_deref().map(crate::modules::presets::find).transpose()?;
+        
+        // إعدادات النقل أولًا لأن عملاء البروكسي والسباق تُبنى بها
+        let mut transport = TransportOptions {
+            http_version: config.http_version,
+            insecure: config.insecure,
+            min_tls: config.tls_min,
+            max_tls: config.tls_max,
+            resolve: config
+                .resolve
+                .iter()
+                .map(|entry| (entry.host.clone(), entry.addr))
+                .collect(),
+            ..TransportOptions::default()
+        };
+        if let Some(ca_cert) = &config.ca_cert {
+            transport.load_ca_bundle(ca_cert)?;
+        }
+        scanner.set_transport(transport)?;
+        
+        if config.host_header.is_some() || config.sni.is_some() {
+            scanner.set_virtual_host(config.host_header.as_deref(), config.sni.as_deref())?;
+        }
+        
+        // تعيين البروكسي أو مجموعة البروكسيات إذا وجدت
+        if let Some(proxy_source) = &config.proxy_file {
+            scanner.set_proxy_pool(proxy_source, config.rate_limit_per_proxy).await?;
+        } else if let Some(proxy_url) = &config.proxy {
+            if config.rate_limit_per_proxy.is_some() {
+                scanner.set_proxy_pool(proxy_url, config.rate_limit_per_proxy).await?;
+            } else {
+                scanner.set_proxy(proxy_url).await?;
+            }
+        } else if config.race_connections {
+            scanner.enable_connection_racing().await?;
+        }
+        
+        if config.protocol != Protocol::Http {
+            scanner.set_protocol(config.protocol, ServiceOptions {
+                timeout: Duration::from_secs(config.timeout),
+                tls: config.service_tls,
+                ldap_user_template: config.ldap_user_template.clone(),
+                udp_timeout: Duration::from_millis(config.udp_timeout),
+                udp_retries: config.udp_retries,
+                snmp_version: config.snmp_version,
+                snmp_auth: config.snmp_auth,
+            })?;
+        }
+        
+        if let Some(template) = &config.secret_header {
+            scanner.set_secret_header(template)?;
+        } else if config.auth_type == AuthType::Header {
+            return Err(anyhow::anyhow!("نوع المصادقة header يتطلب --secret-header"));
+        } else if config.auth_type == AuthType::OAuth {
+            scanner.set_oauth(OAuthOptions {
+                client_id: config.client_id.clone(),
+                client_secret: config.client_secret.clone(),
+                scope: config.scope.clone(),
+            });
+        } else if config.auth_type != AuthType::Form {
+            scanner.set_auth_type(config.auth_type);
+        }
+        
+        if let Some(preset) = preset {
+            scanner.apply_preset(preset, config.timeout).await?;
+        }
+        
+        if config.body_format != BodyFormat::Form || config.body_template.is_some() {
+            scanner.set_login_body(config.body_format, config.body_template.clone())?;
+        }
+        
+        if let Some(field) = &config.csrf_field {
+            scanner.set_csrf(field, config.csrf_selector.as_deref())?;
+        }
+        
+        if config.username_field != "username" || config.password_field != "password" || config.data.is_some() {
+            scanner.set_form_fields(&config.username_field, &config.password_field, config.data.as_deref())?;
+        }
+        
+        if let Some(headers) = &config.headers {
+            scanner.set_headers(headers)?;
+        }
+        
+        // كوكيز المستخدم تحل محل كوكيز الإعداد الجاهز
+        if let Some(cookies) = &config.cookies {
+            let cookies = crate::parser::parse_cookies(cookies)
+                .await
+                .context("فشل في تحميل الكوكيز")?;
+            scanner.set_cookies(&cookies);
+        }
+        
+        if !config.method.eq_ignore_ascii_case("POST") || config.query.is_some() {
+            scanner.set_login_method(&config.method, config.query.clone())?;
+        }
+        
+        if let Some(path) = &config.raw_request {
+            scanner.set_raw_request(path).await?;
+        }
+        
+        // قواعد المستخدم تتقدم على معايير الإعداد الجاهز
+        let mut options = config.scan_options();
+        if let Some(preset) = preset.filter(|_| config.success_criteria.is_empty()) {
+            options.success_criteria = preset.success_criteria();
+        }
+        scanner.set_options(options)?;
+        
+        // كشف WAF وتطبيق ملف التهرب (بعد الخيارات لأنه يخفض عدد الخيوط)
+        if config.evasion {
+            scanner.apply_evasion(config.timeout).await?;
+        }
+        
+        // الإضافة تُربط بعد اكتمال إعدادات العميل لأنها تستخدم نسخته النهائية
+        if let Some(reference) = &config.plugin {
+            scanner.set_plugin(reference)?;
+        }
+        
+        Ok(self)
+    }
+    
+    /// تحميل إضافة WASM (بالاسم من ~/.redfox/plugins أو بالمسار) كوحدة المصادقة
+    pub fn set_plugin(&mut self, reference: &str) -> Result<()> {
+        let path = plugins::resolve(reference)?;
+        let runtime = PluginRuntime::load(&path)?;
+        self.logger.info(&format!("الإضافة: {} ({})", runtime.name(), path.display()));
+        
+        let plugin = WasmPlugin::new(runtime, Arc::clone(&self.http_client));
+        self.module = Some(Arc::new(LoginTarget::Module(Arc::new(plugin))));
+        Ok(())
+    }
+}
+
+impl<M: AuthModule + 'static> RedFoxScanner<M> {
+    /// استبدال وحدة المصادقة بوحدة خارجية (المستخدمون والإعدادات والمخرجات تبقى كما هي)
+    pub fn with_module<N: AuthModule + 'static>(self, module: N) -> RedFoxScanner<N> {
+        self.logger.info(&format!("وحدة مصادقة خارجية: {}", module.endpoint()));
+        
+        RedFoxScanner {
+            http_client: self.http_client,
+            service: self.service,
+            module: Some(Arc::new(module)),
+            users: self.users,
+            user_metadata: self.user_metadata,
+            passwords: self.passwords,
+            combos: self.combos,
+            options: self.options,
+            host: self.host,
+            sinks: self.sinks,
+            known_users: self.known_users,
+            cancel: self.cancel,
+            logger: self.logger,
+        }
+    }
+    
+    /// وحدة المصادقة الخارجية (None عند استخدام HTTP أو وحدة بروتوكول مدمجة)
+    pub fn module(&self) -> Option<&M> {
+        self.module.as_deref()
+    }
+    
+    /// تعيين بروكسي
+    pub async fn set_proxy(&mut self, proxy_url: &str) -> error::Result<()> {
+        self.logger.info(&format!("تعيين بروكسي: {}", proxy_url));
+        
+        let mut client = (*self.http_client).clone();
+        client.set_proxy(proxy_url).map_err(|e| RedFoxError::ProxyFailure {
+            proxy: proxy_url.to_string(),
+            reason: format!("{:#}", e),
+        })?;
+        
+        self.http_client = Arc::new(client);
+        Ok(())
+    }
+    
+    /// تعيين إعدادات النقل (إصدار HTTP...)
+    pub fn set_transport(&mut self, transport: TransportOptions) -> Result<()> {
+        self.logger.info(&format!("إصدار HTTP: {}", transport.http_version));
+        if transport.insecure {
+            self.logger.warn("التحقق من شهادات TLS معطل (--insecure)");
+        }
+        for (host, addr) in &transport.resolve {
+            self.logger.info(&format!("تجاوز DNS: {} -> {}", host, addr));
+        }
+        
+        let mut client = (*self.http_client).clone();
+        client.set_transport(transport).context("فشل في تطبيق إعدادات النقل")?;
+        self.http_client = Arc::new(client);
+        
+        Ok(())
+    }
+    
+    /// استهداف مضيف افتراضي (ترويسة Host و/أو SNI) مع الاتصال بالعنوان الأصلي
+    pub fn set_virtual_host(&mut self, host_header: Option<&str>, sni: Option<&str>) -> Result<()> {
+        if let Some(sni) = sni {
+            self.logger.info(&format!("SNI: {}", sni));
+        }
+        if let Some(host) = host_header {
+            self.logger.info(&format!("ترويسة Host: {}", host));
+        }
+        
+        let mut client = (*self.http_client).clone();
+        client.set_virtual_host(host_header, sni)?;
+        self.http_client = Arc::new(client);
+        
+        Ok(())
+    }
+    
+    /// تفعيل سباق الاتصالات بين عناوين الهدف
+    pub async fn enable_connection_racing(&mut self) -> Result<()> {
+        self.logger.info("تفعيل سباق الاتصالات (Happy Eyeballs)");
+        
+        let mut client = (*self.http_client).clone();
+        client.enable_connection_racing().await?;
+        self.http_client = Arc::new(client);
+        
+        Ok(())
+    }
+    
+    /// تعيين مجموعة بروكسيات من ملف مع حد معدل لكل بروكسي
+    pub async fn set_proxy_pool(
+        &mut self,
+        proxy_source: &str,
+        per_proxy_rate_limit: Option<u32>,
+    ) -> error::Result<()> {
+        let proxies: Vec<String> = parse_input(proxy_source)
+            .await
+            .context("فشل في تحليل قائمة البروكسيات")?
+            .into_iter()
+            .filter(|proxy| {
+                let validation = validate_proxy(proxy);
+                if !validation.is_valid {
+                    self.logger.warn(&format!("تجاهل بروكسي غير صالح: {}", proxy));
+                }
+                validation.is_valid
+            })
+            .collect();
+        
+        self.logger.info(&format!(
+            "تعيين مجموعة بروكسيات: {} بروكسي ({})",
+            proxies.len(),
+            per_proxy_rate_limit
+                .map(|rps| format!("{} طلب/ثانية لكل بروكسي", rps))
+                .unwrap_or_else(|| "بدون حد".to_string())
+        ));
+        
+        let mut client = (*self.http_client).clone();
+        client.set_proxy_pool(&proxies, per_proxy_rate_limit).map_err(|e| RedFoxError::ProxyFailure {
+            proxy: proxy_source.to_string(),
+            reason: format!("{:#}", e),
+        })?;
+        self.http_client = Arc::new(client);
+        
+        Ok(())
+    }
+    
+    /// خيارات الفحص الحالية
+    pub fn options(&self) -> &ScanOptions {
+        &self.options
+    }
+    
+    /// تعيين خيارات الفحص كاملة (بما فيها التوازي والمهلة والوضع وحد المعدل)
+    pub fn set_options(&mut self, options: ScanOptions) -> Result<()> {
+        if options.min_delay > options.max_delay {
+            return Err(anyhow::anyhow!(
+                "أدنى تأخير ({:?}) أكبر من أقصى تأخير ({:?})",
+                options.min_delay,
+                options.max_delay
+            ));
+        }
+        
+        if options.stop_on_success_global {
+            self.logger.info("سيتوقف الفحص عند أول بيانات اعتماد صالحة");
+        } else if options.stop_per_user {
+            self.logger.info("ستتوقف محاولات كل مستخدم عند إيجاد كلمة مروره");
+        }
+        
+        // تجميع قواعد النجاح
+        let matcher = SuccessMatcher::compile(&options.success_criteria)
+            .context("فشل في تجميع قواعد النجاح")?;
+        if matcher.has_rules() {
+            self.logger.info("استخدام قواعد كشف النجاح المخصصة");
+        }
+        
+        // حد المعدل العام لجميع أوضاع الهجوم
+        if let Some(rps) = options.rate_limit {
+            self.logger.info(&format!("حد المعدل: {} طلب/ثانية", rps));
+        }
+        
+        let mut client = (*self.http_client).clone();
+        client.set_success_matcher(matcher);
+        client.set_request_timeout(options.timeout);
+        client.set_rate_limit(options.rate_limit);
+        if let Some(limiter) = self.host.as_ref().and_then(|host| host.rate_limiter()) {
+            client.share_rate_limiter(limiter);
+        }
+        self.http_client = Arc::new(client);
+        
+        self.sinks.origin = Arc::new(ResultOrigin {
+            target: self.sinks.origin.target.clone(),
+            attack_mode: options.mode.name().to_string(),
+        });
+        self.options = ScanOptions {
+            threads: options.threads.max(1),
+            ..options
+        };
+        Ok(())
+    }
+    
+    /// تعيين نوع المصادقة (نموذج أو HTTP Basic)
+    pub fn set_auth_type(&mut self, auth_type: AuthType) {
+        self.logger.info(&format!("نوع المصادقة: {}", auth_type));
+        
+        let mut client = (*self.http_client).clone();
+        client.set_auth_type(auth_type);
+        self.http_client = Arc::new(client);
+    }
+    
+    /// تعيين أسماء حقول النموذج وبيانات POST الإضافية (كائن JSON)
+    pub fn set_form_fields(
+        &mut self,
+        username_field: &str,
+        password_field: &str,
+        data: Option<&str>,
+    ) -> Result<()> {
+        let extra = match data {
+            Some(data) => parse_json_pairs(data).context("بيانات --data غير صالحة")?,
+            None => Vec::new(),
+        };
+        
+        self.logger.info(&format!(
+            "حقول النموذج: {} / {} (+{} حقل إضافي)",
+            username_field,
+            password_field,
+            extra.len()
+        ));
+        
+        let mut client = (*self.http_client).clone();
+        client.set_form_fields(FormFields {
+            username: username_field.to_string(),
+            password: password_field.to_string(),
+            extra,
+        });
+        self.http_client = Arc::new(client);
+        
+        Ok(())
+    }
+    
+    /// إضافة ترويسات HTTP مخصصة (كائن JSON)
+    pub fn set_headers(&mut self, headers: &str) -> Result<()> {
+        let headers = parse_json_pairs(headers).context("ترويسات --headers غير صالحة")?;
+        self.logger.info(&format!("ترويسات مخصصة: {}", headers.len()));
+        
+        let mut client = (*self.http_client).clone();
+        client.set_extra_headers(&headers)?;
+        self.http_client = Arc::new(client);
+        
+        Ok(())
+    }
+    
+    /// تخمين سر في ترويسة (مفتاح API، رمز) بدل جسم الطلب
+    pub fn set_secret_header(&mut self, template: &str) -> Result<()> {
+        self.logger.info(&format!("ترويسة السر: {}", template));
+        
+        let mut client = (*self.http_client).clone();
+        client.set_secret_header(template)?;
+        self.http_client = Arc::new(client);
+        
+        Ok(())
+    }
+    
+    /// تخمين منحة كلمة المرور OAuth2 على نقطة الرموز
+    pub fn set_oauth(&mut self, options: OAuthOptions) {
+        self.logger.info(&format!(
+            "OAuth2 password grant (client_id: {})",
+            options.client_id.as_deref().unwrap_or("-")
+        ));
+        
+        let mut client = (*self.http_client).clone();
+        client.set_oauth(options);
+        self.http_client = Arc::new(client);
+    }
+    
+    /// تعيين الكوكيز المرسلة مع كل طلب
+    pub fn set_cookies(&mut self, cookies: &str) {
+        self.logger.info("تم تعيين الكوكيز");
+        
+        let mut client = (*self.http_client).clone();
+        client.set_cookies(cookies);
+        self.http_client = Arc::new(client);
+    }
+    
+    /// تعيين طريقة طلب تسجيل الدخول (GET, PUT, PATCH...) وقالب سلسلة الاستعلام
+    pub fn set_login_method(&mut self, method: &str, query: Option<String>) -> Result<()> {
+        let method = Method::from_bytes(method.to_uppercase().as_bytes())
+            .map_err(|_| anyhow::anyhow!("طريقة HTTP غير صالحة: {}", method))?;
+        
+        if (method == Method::GET || method == Method::HEAD) && query.is_none() {
+            self.logger.warn(&format!(
+                "الطريقة {} لا ترسل جسمًا، استخدم --query لتمرير بيانات الاعتماد",
+                method
+            ));
+        }
+        self.logger.info(&format!("طريقة الطلب: {}", method));
+        
+        let mut client = (*self.http_client).clone();
+        client.set_login_method(method, query);
+        self.http_client = Arc::new(client);
+        
+        Ok(())
+    }
+    
+    /// تحميل طلب خام (من Burp مثلًا) وإعادة إرساله لكل محاولة
+    pub async fn set_raw_request(&mut self, path: &Path) -> Result<()> {
+        let request = RawRequest::load(path).await?;
+        self.logger.info(&format!("طلب خام: {}", request.summary()));
+        
+        let mut client = (*self.http_client).clone();
+        client.set_raw_request(request);
+        self.http_client = Arc::new(client);
+        
+        Ok(())
+    }
+    
+    /// تعيين قالب جسم طلب تسجيل الدخول
+    pub fn set_login_body(&mut self, format: BodyFormat, template: Option<String>) -> Result<()> {
+        let login_body = LoginBody::new(format, template).context("قالب جسم الطلب غير صالح")?;
+        self.logger.info(&format!("تنسيق جسم الطلب: {}", format.content_type()));
+        
+        let mut client = (*self.http_client).clone();
+        client.set_login_body(login_body);
+        self.http_client = Arc::new(client);
+        
+        Ok(())
+    }
+    
+    /// تفعيل استخراج رمز CSRF من صفحة تسجيل الدخول قبل كل محاولة
+    pub fn set_csrf(&mut self, field: &str, selector: Option<&str>) -> Result<()> {
+        let extractor = CsrfExtractor::new(field, selector).context("إعدادات CSRF غير صالحة")?;
+        self.logger.info(&format!("استخراج رمز CSRF للحقل: {}", field));
+        
+        let mut client = (*self.http_client).clone();
+        client.set_csrf_extractor(extractor);
+        self.http_client = Arc::new(client);
+        
+        Ok(())
+    }
+    
+    /// تعيين بروتوكول الهدف (HTTP افتراضيًا، أو وحدة بروتوكول مثل SSH و FTP)
+    pub fn set_protocol(&mut self, protocol: Protocol, options: ServiceOptions) -> Result<()> {
+        self.service = ServiceModule::for_protocol(protocol, &self.http_client.base_url, &options)
+            .context(format!("فشل في تهيئة وحدة {}", protocol))?
+            .map(Arc::new);
+        
+        if let Some(service) = &self.service {
+            self.logger.info(&format!("البروتوكول: {} ({})", protocol, service.endpoint()));
+        }
+        
+        Ok(())
+    }
+    
+    /// الهدف الذي توزع عليه المحاولات حسب البروتوكول
+    fn login_target(&self) -> LoginTarget {
+        if let Some(module) = &self.module {
+            return LoginTarget::Module(Arc::clone(module) as Arc<dyn AuthModule>);
+        }
+        
+        match &self.service {
+            Some(service) => LoginTarget::Service(Arc::clone(service)),
+            None => LoginTarget::Http(Arc::clone(&self.http_client)),
+        }
+    }
+    
+    /// تطبيق إعداد جاهز (نموذج الدخول، CSRF، رسائل القفل، فحوصات خاصة)
+    pub async fn apply_preset(&mut self, preset: &'static Preset, timeout_secs: u64) -> Result<()> {
+        self.logger.info(&format!("الإعداد الجاهز: {}", preset.description));
         
-        logger.info(&format!("تهيئة الماسح للهدف: {}", url));
-        logger.info(&format!("وضع الهجوم: {}", mode));
-        logger.info(&format!("الخيوط: {}", max_workers));
+        self.set_login_body(BodyFormat::Form, Some(preset.body_template.to_string()))?;
+        if let Some((field, selector)) = preset.csrf {
+            self.set_csrf(field, selector)?;
+        }
         
-        // إنشاء عميل HTTP
-        let http_client = Arc::new(
-            HttpClient::new(url, timeout, None)
-                .await
-                .context("فشل في إنشاء عميل HTTP")?
-        );
+        let mut client = (*self.http_client).clone();
+        client.set_lockout_markers(preset.lockout_markers);
+        if let Some(cookies) = preset.cookies {
+            client.set_cookies(cookies);
+        }
+        self.http_client = Arc::new(client);
         
-        // تحليل المدخلات
-        logger.info("تحليل قوائم المستخدمين وكلمات المرور...");
-        let users = parse_input(user_input)
-            .await
-            .context("فشل في تحليل المستخدمين")?;
+        match preset.probe {
+            Some(PresetProbe::XmlRpcMulticall) => {
+                match wordpress::xmlrpc_multicall_available(&self.http_client.base_url, timeout_secs).await {
+                    Ok(true) => self.logger.warn(
+                        "XML-RPC system.multicall متاح: يمكن تجربة مئات كلمات المرور في طلب واحد (تضخيم)",
+                    ),
+                    Ok(false) => self.logger.info("XML-RPC system.multicall غير متاح"),
+                    Err(e) => self.logger.warn(&format!("فشل فحص XML-RPC: {}", e)),
+                }
+            }
+            None => {}
+        }
+        
+        Ok(())
+    }
+    
+    /// كشف WAF أمام الهدف وتطبيق ملف التهرب المناسب عند وجوده
+    pub async fn apply_evasion(&mut self, timeout_secs: u64) -> Result<Option<WafDetection>> {
+        self.logger.info("كشف جدار حماية تطبيقات الويب (WAF)...");
         
-        let passwords = parse_input(password_file)
+        let detection = detect_waf(&self.http_client.base_url, timeout_secs)
             .await
-            .context("فشل في تحليل كلمات المرور")?;
+            .context("فشل في كشف WAF")?;
         
-        logger.info(&format!("تم تحميل {} مستخدم", users.len()));
-        logger.info(&format!("تم تحميل {} كلمة مرور", passwords.len()));
-        
-        // تحويل وضع الهجوم
-        let attack_mode = match mode.to_lowercase().as_str() {
-            "fast" => AttackMode::Fast,
-            "stealth" => AttackMode::Stealth,
-            "aggressive" => AttackMode::Aggressive,
-            _ => AttackMode::Normal,
+        let Some(detection) = detection else {
+            self.logger.info("لم يتم اكتشاف WAF");
+            return Ok(None);
         };
         
-        Ok(Self {
-            http_client,
-            users,
-            passwords,
-            max_workers,
-            attack_mode,
-            rate_limit,
-            logger,
-        })
+        let profile = detection.evasion_profile();
+        self.logger.warn(&format!(
+            "تم اكتشاف WAF: {} ({})",
+            detection.vendor,
+            detection.evidence.join("، ")
+        ));
+        self.logger.info(&format!(
+            "ملف التهرب: {} خيوط، تأخير {:?}-{:?}، تدوير وكيل المستخدم",
+            profile.max_workers.min(self.options.threads),
+            profile.min_delay,
+            profile.max_delay
+        ));
+        
+        self.options.threads = self.options.threads.min(profile.max_workers);
+        
+        let mut client = (*self.http_client).clone();
+        client.set_evasion(profile);
+        self.http_client = Arc::new(client);
+        
+        Ok(Some(detection))
     }
     
-    /// تعيين بروكسي
-    pub async fn set_proxy(&mut self, proxy_url: &str) -> Result<()> {
-        self.logger.info(&format!("تعيين بروكسي: {}", proxy_url));
+    /// كتابة كل نتيجة فور إنتاجها في ملف JSONL
+    pub fn attach_stream(&mut self, stream: Arc<ResultStream>) {
+        self.logger.info(&format!("بث النتائج إلى: {}", stream.path().display()));
+        self.sinks.stream = Some(stream);
+    }
+    
+    /// إرسال إشعار بكل بيانات اعتماد صالحة فور اكتشافها
+    pub fn attach_notifications(&mut self, notifications: Arc<Notifications>) {
+        self.sinks.notifications = Some(notifications);
+    }
+    
+    /// ربط رمز إلغاء يوقف إطلاق المحاولات الجديدة وينهي scan() بعد اكتمال الجارية
+    pub fn attach_cancellation(&mut self, token: CancellationToken) {
+        self.cancel = token;
+    }
+    
+    /// رمز الإلغاء الحالي (لإلغاء الفحص من مهمة أخرى)
+    pub fn cancellation_token(&self) -> CancellationToken {
+        self.cancel.clone()
+    }
+    
+    /// تسجيل خطاف لأحداث الفحص (نجاح، فشل، خطأ، تباطؤ، تقدم)
+    pub fn on_event(&mut self, hook: impl EventHook + 'static) {
+        self.sinks.hooks.push(Arc::new(hook));
+    }
+    
+    /// تسجيل مراقب يتلقى لقطة تقدم كل `interval` أثناء scan() ولقطة أخيرة عند انتهائه
+    pub fn observe_progress(&mut self, observer: impl ProgressObserver + 'static, interval: Duration) {
+        self.sinks.observers.push((Arc::new(observer), interval));
+    }
+    
+    /// ربط الماسح بحدود مضيفه المشتركة (المعدل والتزامن) عند فحص عدة أهداف في آن واحد
+    pub fn attach_host_limiter(&mut self, host: Arc<HostLimiter>) {
+        self.logger.info(&format!("حدود مشتركة للمضيف: {}", host.authority()));
         
-        let new_client = Arc::new(
-            HttpClient::new(&self.http_client.base_url, 30, Some(proxy_url))
-                .await
-                .context("فشل في إنشاء عميل HTTP مع بروكسي")?
-        );
+        if let Some(limiter) = host.rate_limiter() {
+            let mut client = (*self.http_client).clone();
+            client.share_rate_limiter(limiter);
+            self.http_client = Arc::new(client);
+        }
+        self.host = Some(host);
+    }
+    
+    /// فتحات التزامن (المشتركة مع فحوصات المضيف نفسه إن رُبط بحدوده)
+    fn slots(&self) -> Arc<Semaphore> {
+        match &self.host {
+            Some(host) => host.slots(),
+            None => Arc::new(Semaphore::new(self.options.threads)),
+        }
+    }
+    
+    /// إرسال كل نتيجة فور إنتاجها إلى قناة (مهام الخادم وبث SSE)
+    pub fn attach_live(&mut self, sender: tokio::sync::mpsc::UnboundedSender<ScanResult>) {
+        self.sinks.live = Some(sender);
+    }
+    
+    /// إجمالي المحاولات المخططة (بعد تخطي المعروف مسبقًا، و0 عند قراءة كلمات المرور من stdin)
+    pub fn total_attempts(&self) -> usize {
+        let planned = match &self.combos {
+            Some(combos) => combos.iter().filter(|(u, _)| !self.known_users.contains(u)).count(),
+            None => self.users.iter().filter(|u| !self.known_users.contains(*u)).count() * self.passwords.len(),
+        };
+        
+        match &self.sinks.session {
+            Some(session) => planned.saturating_sub(session.with_state(|state| state.completed_attempts())),
+            None => planned,
+        }
+    }
+    
+    /// تطبيق ملف قواعد hashcat على كلمات المرور أثناء البث (كل كلمة تتوسع بكل القواعد)
+    pub async fn apply_rules(&mut self, path: &Path) -> Result<()> {
+        let rules = RuleSet::load(path).await?;
+        self.logger.info(&format!("تطبيق {} قاعدة على كلمات المرور: {}", rules.len(), path.display()));
         
-        self.http_client = new_client;
+        self.passwords = std::mem::take(&mut self.passwords).with_rules(Arc::new(rules));
         Ok(())
     }
     
+    /// تخطي المستخدمين ذوي بيانات الاعتماد المعروفة مسبقًا (من potfile)
+    pub fn skip_known(&mut self, known: &[(String, String)]) {
+        self.known_users.extend(known.iter().map(|(username, _)| username.clone()));
+    }
+    
+    /// ربط الماسح بجلسة قابلة للاستئناف
+    pub fn attach_session(&mut self, recorder: Arc<SessionRecorder>) {
+        let total_attempts = self.total_attempts();
+        
+        recorder.with_state(|state| {
+            if state.completed_attempts() == 0 {
+                state.user_count = self.users.len();
+                state.password_count = self.passwords.len();
+                state.total_attempts = total_attempts;
+            } else {
+                if state.user_count != self.users.len() || state.password_count != self.passwords.len() {
+                    self.logger.warn(&format!(
+                        "تغيرت القوائم منذ بدء الجلسة ({}/{} -> {}/{})",
+                        state.user_count,
+                        state.password_count,
+                        self.users.len(),
+                        self.passwords.len()
+                    ));
+                }
+                
+                self.logger.info(&format!(
+                    "استئناف الجلسة: {}/{} محاولة مكتملة، {} بيانات اعتماد مكتشفة",
+                    state.completed_attempts(),
+                    state.total_attempts,
+                    state.found.len()
+                ));
+            }
+        });
+        
+        self.logger.info(&format!("حفظ الجلسة في: {}", recorder.path().display()));
+        self.sinks.session = Some(recorder);
+    }
+    
     /// تنفيذ الفحص
-    pub async fn scan(&self, verbose: bool) -> Result<Vec<ScanResult>> {
+    #[tracing::instrument(
+        name = "scan",
+        skip_all,
+        fields(target = %self.sinks.origin.target, mode = %self.sinks.origin.attack_mode)
+    )]
+    pub async fn scan(&self, verbose: bool) -> error::Result<Vec<ScanResult>> {
+        Ok(self.run_scan(verbose).await?)
+    }
+    
+    /// جسم الفحص (أخطاء anyhow الداخلية تُصنف في scan)
+    async fn run_scan(&self, verbose: bool) -> Result<Vec<ScanResult>> {
         let start_time = Instant::now();
-        let total_attempts = self.users.len() * self.passwords.len();
+        let total_attempts = self.total_attempts();
+        self.sinks.total.store(total_attempts, Ordering::Relaxed);
+        self.sinks.completed.store(0, Ordering::Relaxed);
+        let reporter = ProgressReporter::start(
+            &self.sinks.observers,
+            self.sinks.completed.clone(),
+            self.sinks.total.clone(),
+        );
         
         self.logger.info(&format!("بدء الفحص: {} محاولة", total_attempts));
         
-        // إنشاء شريط التقدم
-        let progress = if verbose {
-            let pb = ProgressBar::new(total_attempts as u64);
+        // إنشاء شريط التقدم (مؤشر دوار عند القراءة من stdin لأن الإجمالي غير معروف)
+        let progress = if verbose && crate::output::show_progress() {
+            let pb = if self.passwords.is_single_pass() {
+                ProgressBar::new_spinner()
+            } else {
+                ProgressBar::new(total_attempts as u64)
+            };
             pb.set_style(
                 ProgressStyle::default_bar()
                     .template("{spinner:.green} [{elapsed_precise}] [{bar:40.cyan/blue}] {pos}/{len} ({eta}) {msg}")
-                    .unwrap()
+                    .unwrap_or_else(|_| ProgressStyle::default_bar())
                     .progress_chars("#>-")
             );
             Some(pb)
@@ -140,35 +1405,140 @@ impl RedFoxScanner {
             None
         };
         
-        // إنشاء متعقب التقدم
-        let progress_tracker = ProgressTracker::new(total_attempts);
-        
         // إنشاء مقسم الطلبات
-        let semaphore = Arc::new(Semaphore::new(self.max_workers));
+        let semaphore = self.slots();
+        
+        // معايرة استجابة الفشل قبل بدء الفحص
+        if self.options.calibration_samples > 0 && self.service.is_none() && self.module.is_none() {
+            match self.http_client.calibrate(self.options.calibration_samples).await {
+                Ok(baseline) => {
+                    self.logger.info(&format!("خط أساس الفشل: {}", baseline.summary()));
+                }
+                Err(e) => {
+                    self.logger.warn(&format!(
+                        "فشلت المعايرة، سيتم التصنيف بالكلمات المفتاحية: {}",
+                        e
+                    ));
+                }
+            }
+        }
+        
+        // فحص الوصول بدون مصادقة قبل التخمين (نتيجة حرجة مستقلة)
+        let mut anonymous = None;
+        let target = self.login_target();
+        match target.precheck().await {
+            Ok(Some(outcome)) => {
+                self.logger.error(&format!(
+                    "ثغرة حرجة: {} يسمح بالوصول بدون مصادقة",
+                    target.endpoint()
+                ));
+                anonymous = Some(ScanResult {
+                    username: ANONYMOUS_USER.to_string(),
+                    password: String::new(),
+                    success: true,
+                    status_code: outcome.status_code,
+                    response_time: Duration::default(),
+                    error: None,
+                    timestamp: chrono::Utc::now(),
+                    metadata: None,
+                    captcha_detected: false,
+                    banner: outcome.banner,
+                    http_version: outcome.http_version,
+                    redirect: outcome.redirect,
+                    ..Default::default()
+                });
+            }
+            Ok(None) => {}
+            Err(e) => {
+                self.logger.warn(&format!("فشل فحص الوصول بدون مصادقة: {}", e));
+            }
+        }
+        
+        // إشارة الإيقاف (مع مراعاة ما اكتُشف في الجلسة السابقة)
+        let stop = Arc::new(StopSignal::new(self.options.clone(), self.cancel.clone()));
+        if let Some(session) = &self.sinks.session {
+            for found in session.found() {
+                stop.observe(&found);
+            }
+        }
         
         // تجميع النتائج
-        let mut results = Vec::with_capacity(total_attempts);
+        let mut results = Vec::new();
+        
+        // مسح سريع للبيانات الافتراضية قبل القائمة الرئيسية
+        let quick = if self.options.quick_defaults {
+            self.sweep_quick_defaults(&stop).await
+        } else {
+            Vec::new()
+        };
         
         // تنفيذ الفحص حسب وضع الهجوم
-        match self.attack_mode {
+        match self.options.mode {
+            _ if stop.is_global_stopped() => {}
             AttackMode::Fast => {
-                results = self.scan_fast(&semaphore, progress.as_ref()).await?;
+                results = self.scan_fast(&semaphore, &stop, progress.as_ref()).await?;
             }
             AttackMode::Normal => {
-                results = self.scan_normal(&semaphore, progress.as_ref()).await?;
+                results = self.scan_normal(&semaphore, &stop, progress.as_ref()).await?;
             }
             AttackMode::Stealth => {
-                results = self.scan_stealth(&semaphore, progress.as_ref()).await?;
+                results = self.scan_stealth(&semaphore, &stop, progress.as_ref()).await?;
             }
             AttackMode::Aggressive => {
-                results = self.scan_aggressive(&semaphore, progress.as_ref()).await?;
+                results = self.scan_aggressive(&semaphore, &stop, progress.as_ref()).await?;
+            }
+        }
+        
+        results.extend(quick);
+        results.extend(anonymous);
+        
+        if stop.captcha_seen() {
+            let count = results.iter().filter(|r| r.captcha_detected).count();
+            if self.options.halt_on_captcha {
+                self.logger.error(&format!("تم إيقاف الفحص بسبب اكتشاف CAPTCHA ({} استجابة)", count));
+            } else {
+                self.logger.warn(&format!(
+                    "تم اكتشاف CAPTCHA في {} استجابة، قد لا تنجح المحاولات (استخدم --halt-on-captcha)",
+                    count
+                ));
+            }
+        } else if stop.is_cancelled() {
+            self.logger.warn(&format!("تم إلغاء الفحص بعد {} محاولة", results.len()));
+        } else if stop.is_global_stopped() {
+            self.logger.success("تم إيقاف الفحص بعد إيجاد بيانات اعتماد صالحة");
+        }
+        
+        // دمج بيانات الاعتماد المكتشفة في الجلسة السابقة وحفظ الحالة النهائية
+        if let Some(session) = &self.sinks.session {
+            let previous: Vec<ScanResult> = session
+                .found()
+                .into_iter()
+                .filter(|found| !results.iter().any(|r| r.username == found.username && r.password == found.password))
+                .collect();
+            results.extend(previous);
+            
+            // الفحص الملغى يبقى قابلًا للاستئناف
+            if stop.is_cancelled() {
+                session.checkpoint().context("فشل في حفظ الجلسة")?;
+            } else {
+                session.finish().context("فشل في حفظ الجلسة")?;
             }
         }
         
+        // إرفاق البيانات الوصفية للمستخدمين
+        self.attach_metadata(&mut results);
+        
+        // إرفاق الهدف ووضع الهجوم وترتيب كل محاولة
+        for (i, result) in results.iter_mut().enumerate() {
+            self.sinks.origin.stamp(result);
+            result.attempt = Some(i as u64 + 1);
+        }
+        
         // إكمال شريط التقدم
-        if let Some(pb) = progress {
+        if let Some(pb) = &progress {
             pb.finish_with_message("اكتمل!");
         }
+        reporter.finish();
         
         let duration = start_time.elapsed();
         let rps = total_attempts as f64 / duration.as_secs_f64();
@@ -181,10 +1551,87 @@ impl RedFoxScanner {
         Ok(results)
     }
     
+    /// اختبار قائمة البيانات الافتراضية المدمجة مع الإبلاغ الفوري عن أي نجاح
+    async fn sweep_quick_defaults(&self, stop: &Arc<StopSignal>) -> Vec<ScanResult> {
+        self.logger.info(&format!(
+            "مسح سريع لـ {} زوج افتراضي قبل القائمة الرئيسية...",
+            QUICK_DEFAULTS.len()
+        ));
+        
+        let target = self.login_target();
+        let semaphore = self.slots();
+        let mut attempts = JoinSet::new();
+        
+        for (username, password) in QUICK_DEFAULTS {
+            let target = target.clone();
+            let semaphore = Arc::clone(&semaphore);
+            let stop = Arc::clone(stop);
+            
+            attempts.spawn(async move {
+                let _permit = semaphore.acquire().await.ok()?;
+                let start = Instant::now();
+                let outcome = stop.guard(username, target.attempt(username, password)).await?;
+                
+                Some(match outcome {
+                    Ok(outcome) => ScanResult {
+                        username: (*username).to_string(),
+                        password: (*password).to_string(),
+                        success: outcome.success,
+                        status_code: outcome.status_code,
+                        response_time: start.elapsed(),
+                        error: None,
+                        timestamp: chrono::Utc::now(),
+                        metadata: None,
+                        captcha_detected: outcome.captcha.is_some(),
+                        banner: outcome.banner,
+                        http_version: outcome.http_version,
+                        redirect: outcome.redirect,
+                        ..Default::default()
+                    },
+                    Err(e) => ScanResult {
+                        username: (*username).to_string(),
+                        password: (*password).to_string(),
+                        success: false,
+                        status_code: 0,
+                        response_time: start.elapsed(),
+                        error: Some(e.to_string()),
+                        timestamp: chrono::Utc::now(),
+                        metadata: None,
+                        captcha_detected: false,
+                        banner: None,
+                        http_version: None,
+                        ..Default::default()
+                    },
+                })
+            }.in_current_span());
+        }
+        
+        let mut results = Vec::with_capacity(QUICK_DEFAULTS.len());
+        while let Some(joined) = attempts.join_next().await {
+            let Ok(Some(result)) = joined else {
+                continue;
+            };
+            
+            if result.success {
+                self.logger.success(&format!(
+                    "بيانات اعتماد افتراضية صالحة: {}:{}",
+                    result.username, result.password
+                ));
+            }
+            
+            stop.observe(&result);
+            self.sinks.record(&result);
+            results.push(result);
+        }
+        
+        results
+    }
+    
     /// فحص سريع (أقصى سرعة)
     async fn scan_fast(
         &self,
         semaphore: &Arc<Semaphore>,
+        stop: &Arc<StopSignal>,
         progress: Option<&ProgressBar>,
     ) -> Result<Vec<ScanResult>> {
         self.logger.info("بدء الفحص السريع...");
@@ -192,72 +1639,101 @@ impl RedFoxScanner {
         let mut handles = Vec::new();
         let results = Arc::new(tokio::sync::Mutex::new(Vec::new()));
         
-        // تقسيم العمل إلى قطع
-        let chunk_size = (self.users.len() / self.max_workers).max(1);
+        // عمال بعدد الخيوط يسحبون الأزواج من بث مشترك (بلا تجسيد للقائمة)
+        let pairs = Arc::new(tokio::sync::Mutex::new(self.pair_stream()));
         
-        for chunk in self.users.chunks(chunk_size) {
-            let chunk_users = chunk.to_vec();
-            let chunk_passwords = self.passwords.clone();
-            let client = Arc::clone(&self.http_client);
+        for _ in 0..self.options.threads {
+            let pairs = Arc::clone(&pairs);
+            let client = self.login_target();
             let results_ref = Arc::clone(&results);
             let semaphore = Arc::clone(semaphore);
+            let stop = Arc::clone(stop);
+            let sinks = self.sinks.clone();
+            let progress = progress.cloned();
             
             let handle = tokio::spawn(async move {
                 let mut chunk_results = Vec::new();
                 
-                for username in chunk_users {
-                    for password in &chunk_passwords {
-                        let _permit = semaphore.acquire().await.unwrap();
-                        
-                        let start = Instant::now();
-                        let result = match client.test_login(&username, password).await {
-                            Ok(response) => {
-                                let success = response.status().is_success();
-                                let status_code = response.status().as_u16();
-                                let response_time = start.elapsed();
-                                
-                                ScanResult {
-                                    username: username.clone(),
-                                    password: password.clone(),
-                                    success,
-                                    status_code,
-                                    response_time,
-                                    error: None,
-                                    timestamp: chrono::Utc::now(),
-                                }
+                loop {
+                    let next = pairs.lock().await.next().await;
+                    let Some(pair) = next else {
+                        break;
+                    };
+                    let (username, password) = &pair?;
+                    let Ok(_permit) = semaphore.acquire().await else {
+                        break;
+                    };
+                    
+                    let start = Instant::now();
+                    let Some(outcome) = stop.guard(username, client.attempt(username, password)).await else {
+                        if stop.is_global_stopped() {
+                            break;
+                        }
+                        if let Some(pb) = &progress {
+                            pb.inc(1);
+                        }
+                        continue;
+                    };
+                    
+                    let result = match outcome {
+                        Ok(outcome) => {
+                            let response_time = start.elapsed();
+                            
+                            ScanResult {
+                                username: username.clone(),
+                                password: password.clone(),
+                                success: outcome.success,
+                                status_code: outcome.status_code,
+                                response_time,
+                                error: None,
+                                timestamp: chrono::Utc::now(),
+                                metadata: None,
+                                captcha_detected: outcome.captcha.is_some(),
+                                banner: outcome.banner,
+                                http_version: outcome.http_version,
+                                redirect: outcome.redirect,
+                                ..Default::default()
                             }
-                            Err(e) => {
-                                ScanResult {
-                                    username: username.clone(),
-                                    password: password.clone(),
-                                    success: false,
-                                    status_code: 0,
-                                    response_time: start.elapsed(),
-                                    error: Some(e.to_string()),
-                                    timestamp: chrono::Utc::now(),
-                                }
+                        }
+                        Err(e) => {
+                            ScanResult {
+                                username: username.clone(),
+                                password: password.clone(),
+                                success: false,
+                                status_code: 0,
+                                response_time: start.elapsed(),
+                                error: Some(e.to_string()),
+                                timestamp: chrono::Utc::now(),
+                                metadata: None,
+                                captcha_detected: false,
+                                banner: None,
+                                http_version: None,
+                                ..Default::default()
                             }
-                        };
-                        
-                        chunk_results.push(result);
-                        
-                        // تحديث التقدم
-                        if let Some(pb) = progress {
-                            pb.inc(1);
                         }
+                    };
+                    
+                    stop.observe(&result);
+                    sinks.record(&result);
+                    chunk_results.push(result);
+                    
+                    // تحديث التقدم
+                    if let Some(pb) = &progress {
+                        pb.inc(1);
                     }
                 }
                 
                 let mut results_lock = results_ref.lock().await;
                 results_lock.extend(chunk_results);
-            });
+                Ok::<_, anyhow::Error>(())
+            }.in_current_span());
             
             handles.push(handle);
         }
         
         // انتظار اكتمال جميع المهام
         for handle in handles {
-            handle.await?;
+            handle.await??;
         }
         
         let final_results = results.lock().await.clone();
@@ -268,57 +1744,80 @@ impl RedFoxScanner {
     async fn scan_normal(
         &self,
         semaphore: &Arc<Semaphore>,
+        stop: &Arc<StopSignal>,
         progress: Option<&ProgressBar>,
     ) -> Result<Vec<ScanResult>> {
         self.logger.info("بدء الفحص العادي...");
         
-        let mut results = Vec::new();
-        
         // استخدام قناة للإنتاج والاستهلاك
         let (tx, mut rx) = tokio::sync::mpsc::channel(1000);
         
         // إنتاج المهام
         let producer = tokio::spawn({
-            let users = self.users.clone();
-            let passwords = self.passwords.clone();
-            let client = Arc::clone(&self.http_client);
+            let mut pairs = self.pair_stream();
+            let semaphore = Arc::clone(semaphore);
+            let client = self.login_target();
+            let stop = Arc::clone(stop);
+            let jitter = self.options.jitter_normal.then_some((self.options.min_delay, self.options.max_delay));
+            let sinks = self.sinks.clone();
             let tx = tx.clone();
             
             async move {
-                for username in users {
-                    for password in &passwords {
-                        let client = Arc::clone(&client);
-                        let tx = tx.clone();
-                        let username_clone = username.clone();
-                        let password_clone = password.clone();
-                        
-                        tokio::spawn(async move {
-                            let result = client.test_login(&username_clone, &password_clone).await;
-                            let _ = tx.send((username_clone, password_clone, result)).await;
-                        });
+                while let Some(pair) = pairs.next().await {
+                    let (username, password) = pair?;
+                    if stop.is_global_stopped() {
+                        break;
+                    }
+                    
+                    // تأخير عشوائي بين إطلاق المحاولات
+                    if let Some((min, max)) = jitter {
+                        sinks.throttle(random_delay(min, max), "jitter").await;
                     }
+                    
+                    // المحاولات الجارية محدودة بالخيوط حتى لا تتراكم المهام مع القوائم الضخمة
+                    let permit = Arc::clone(&semaphore).acquire_owned().await?;
+                    let client = client.clone();
+                    let stop = Arc::clone(&stop);
+                    let tx = tx.clone();
+                    
+                    tokio::spawn(async move {
+                        let _permit = permit;
+                        // تصنيف الاستجابة داخل المهمة لتجنب تسلسل قراءة المحتوى
+                        let start = Instant::now();
+                        let attempt = client.attempt(&username, &password);
+                        if let Some(result) = stop.guard(&username, attempt).await {
+                            let _ = tx.send((username, password, result, start.elapsed())).await;
+                        }
+                    }.in_current_span());
                 }
+                Ok::<_, anyhow::Error>(())
             }
-        });
+        }.in_current_span());
         
         // استهلاك النتائج
+        let sinks = self.sinks.clone();
+        let stop = Arc::clone(stop);
+        let progress = progress.cloned();
         let consumer = tokio::spawn(async move {
             let mut local_results = Vec::new();
             
-            while let Some((username, password, result)) = rx.recv().await {
+            while let Some((username, password, result, response_time)) = rx.recv().await {
                 let scan_result = match result {
-                    Ok(response) => {
-                        let success = response.status().is_success();
-                        let status_code = response.status().as_u16();
-                        
+                    Ok(outcome) => {
                         ScanResult {
                             username,
                             password,
-                            success,
-                            status_code,
-                            response_time: Duration::default(),
+                            success: outcome.success,
+                            status_code: outcome.status_code,
+                            response_time,
                             error: None,
                             timestamp: chrono::Utc::now(),
+                            metadata: None,
+                            captcha_detected: outcome.captcha.is_some(),
+                            banner: outcome.banner,
+                            http_version: outcome.http_version,
+                            redirect: outcome.redirect,
+                            ..Default::default()
                         }
                     }
                     Err(e) => {
@@ -327,30 +1826,37 @@ impl RedFoxScanner {
                             password,
                             success: false,
                             status_code: 0,
-                            response_time: Duration::default(),
+                            response_time,
                             error: Some(e.to_string()),
                             timestamp: chrono::Utc::now(),
+                            metadata: None,
+                            captcha_detected: false,
+                            banner: None,
+                            http_version: None,
+                            ..Default::default()
                         }
                     }
                 };
                 
+                stop.observe(&scan_result);
+                sinks.record(&scan_result);
                 local_results.push(scan_result);
                 
                 // تحديث التقدم
-                if let Some(pb) = progress {
+                if let Some(pb) = &progress {
                     pb.inc(1);
                 }
             }
             
             local_results
-        });
+        }.in_current_span());
         
         // انتظار المنتج
-        producer.await?;
+        producer.await??;
         drop(tx); // إغلاق القناة
         
         // الحصول على النتائج من المستهلك
-        results = consumer.await?;
+        let results = consumer.await?;
         
         Ok(results)
     }
@@ -359,56 +1865,80 @@ impl RedFoxScanner {
     async fn scan_stealth(
         &self,
         _semaphore: &Arc<Semaphore>,
+        stop: &Arc<StopSignal>,
         progress: Option<&ProgressBar>,
     ) -> Result<Vec<ScanResult>> {
         self.logger.info("بدء الفحص الخفي...");
         
         let mut results = Vec::new();
-        let delay = Duration::from_millis(100); // تأخير 100ms بين الطلبات
+        let target = self.login_target();
+        let mut pairs = self.pair_stream();
         
-        for username in &self.users {
-            for password in &self.passwords {
-                let start = Instant::now();
-                
-                let result = match self.http_client.test_login(username, password).await {
-                    Ok(response) => {
-                        let success = response.status().is_success();
-                        let status_code = response.status().as_u16();
-                        let response_time = start.elapsed();
-                        
-                        ScanResult {
-                            username: username.clone(),
-                            password: password.clone(),
-                            success,
-                            status_code,
-                            response_time,
-                            error: None,
-                            timestamp: chrono::Utc::now(),
-                        }
+        while let Some(pair) = pairs.next().await {
+            let (username, password) = &pair?;
+            let start = Instant::now();
+            
+            let Some(outcome) = stop.guard(username, target.attempt(username, password)).await else {
+                if stop.is_global_stopped() {
+                    break;
+                }
+                if let Some(pb) = &progress {
+                    pb.inc(1);
+                }
+                continue;
+            };
+            
+            let result = match outcome {
+                Ok(outcome) => {
+                    let response_time = start.elapsed();
+                    
+                    ScanResult {
+                        username: username.clone(),
+                        password: password.clone(),
+                        success: outcome.success,
+                        status_code: outcome.status_code,
+                        response_time,
+                        error: None,
+                        timestamp: chrono::Utc::now(),
+                        metadata: None,
+                        captcha_detected: outcome.captcha.is_some(),
+                        banner: outcome.banner,
+                        http_version: outcome.http_version,
+                        redirect: outcome.redirect,
+                        ..Default::default()
                     }
-                    Err(e) => {
-                        ScanResult {
-                            username: username.clone(),
-                            password: password.clone(),
-                            success: false,
-                            status_code: 0,
-                            response_time: start.elapsed(),
-                            error: Some(e.to_string()),
-                            timestamp: chrono::Utc::now(),
-                        }
+                }
+                Err(e) => {
+                    ScanResult {
+                        username: username.clone(),
+                        password: password.clone(),
+                        success: false,
+                        status_code: 0,
+                        response_time: start.elapsed(),
+                        error: Some(e.to_string()),
+                        timestamp: chrono::Utc::now(),
+                        metadata: None,
+                        captcha_detected: false,
+                        banner: None,
+                        http_version: None,
+                        ..Default::default()
                     }
-                };
-                
-                results.push(result);
-                
-                // تحديث التقدم
-                if let Some(pb) = progress {
-                    pb.inc(1);
                 }
-                
-                // تأخير لتجنب الاكتشاف
-                tokio::time::sleep(delay).await;
+            };
+            
+            stop.observe(&result);
+            self.sinks.record(&result);
+            results.push(result);
+            
+            // تحديث التقدم
+            if let Some(pb) = &progress {
+                pb.inc(1);
             }
+            
+            // تأخير عشوائي لتجنب الاكتشاف
+            self.sinks
+                .throttle(random_delay(self.options.min_delay, self.options.max_delay), "stealth_delay")
+                .await;
         }
         
         Ok(results)
@@ -418,6 +1948,7 @@ impl RedFoxScanner {
     async fn scan_aggressive(
         &self,
         semaphore: &Arc<Semaphore>,
+        stop: &Arc<StopSignal>,
         progress: Option<&ProgressBar>,
     ) -> Result<Vec<ScanResult>> {
         self.logger.info("بدء الفحص العدواني...");
@@ -425,120 +1956,83 @@ impl RedFoxScanner {
         let mut results = Vec::new();
         let retry_count = 3;
         
-        // استخدام Rayon للمعالجة المتوازية المكثفة
-        #[cfg(feature = "rayon")]
-        {
-            use rayon::prelude::*;
-            
-            let all_combinations: Vec<(String, String)> = self.users
-                .par_iter()
-                .flat_map(|user| {
-                    self.passwords.par_iter().map(|pass| {
-                        (user.clone(), pass.clone())
-                    })
-                })
-                .collect();
-            
-            let chunked_results: Vec<Vec<ScanResult>> = all_combinations
-                .par_chunks(1000)
-                .map(|chunk| {
-                    let mut chunk_results = Vec::new();
-                    
-                    for (username, password) in chunk {
-                        for attempt in 0..retry_count {
-                            match self.http_client.test_login(username, password) {
-                                Ok(response) => {
-                                    let result = ScanResult {
-                                        username: username.clone(),
-                                        password: password.clone(),
-                                        success: response.status().is_success(),
-                                        status_code: response.status().as_u16(),
-                                        response_time: Duration::default(),
-                                        error: None,
-                                        timestamp: chrono::Utc::now(),
-                                    };
-                                    chunk_results.push(result);
-                                    break;
-                                }
-                                Err(_) if attempt < retry_count - 1 => {
-                                    // إعادة المحاولة بعد تأخير قصير
-                                    std::thread::sleep(Duration::from_millis(50));
-                                }
-                                Err(e) => {
-                                    chunk_results.push(ScanResult {
-                                        username: username.clone(),
-                                        password: password.clone(),
-                                        success: false,
-                                        status_code: 0,
-                                        response_time: Duration::default(),
-                                        error: Some(e.to_string()),
-                                        timestamp: chrono::Utc::now(),
-                                    });
-                                }
-                            }
-                        }
-                    }
-                    
-                    chunk_results
-                })
-                .collect();
-            
-            for chunk in chunked_results {
-                results.extend(chunk);
-            }
-        }
+        let target = &self.login_target();
+        let sinks = &self.sinks;
         
-        #[cfg(not(feature = "rayon"))]
-        {
-            // نسخة بديلة بدون Rayon
-            for username in &self.users {
-                for password in &self.passwords {
-                    let _permit = semaphore.acquire().await?;
-                    
-                    let start = Instant::now();
-                    let mut last_error = None;
-                    
-                    for attempt in 0..retry_count {
-                        match self.http_client.test_login(username, password).await {
-                            Ok(response) => {
-                                let result = ScanResult {
-                                    username: username.clone(),
-                                    password: password.clone(),
-                                    success: response.status().is_success(),
-                                    status_code: response.status().as_u16(),
-                                    response_time: start.elapsed(),
-                                    error: None,
-                                    timestamp: chrono::Utc::now(),
-                                };
-                                results.push(result);
-                                break;
-                            }
-                            Err(e) => {
-                                last_error = Some(e);
-                                if attempt < retry_count - 1 {
-                                    tokio::time::sleep(Duration::from_millis(100)).await;
-                                }
+        // حتى `threads` زوج قيد الاختبار في آن واحد، ولكل زوج إعاداته الخاصة
+        let mut attempts = self
+            .pair_stream()
+            .map(|pair| async move {
+                let (username, password) = pair?;
+                if stop.is_global_stopped() || stop.is_stopped(&username) {
+                    return Ok(None);
+                }
+                
+                let _permit = semaphore.acquire().await?;
+                
+                let start = Instant::now();
+                let mut last_error = None;
+                
+                for attempt in 0..retry_count {
+                    match target.attempt(&username, &password).await {
+                        Ok(outcome) => {
+                            return Ok(Some(ScanResult {
+                                username,
+                                password,
+                                success: outcome.success,
+                                status_code: outcome.status_code,
+                                response_time: start.elapsed(),
+                                error: None,
+                                timestamp: chrono::Utc::now(),
+                                metadata: None,
+                                captcha_detected: outcome.captcha.is_some(),
+                                banner: outcome.banner,
+                                http_version: outcome.http_version,
+                                redirect: outcome.redirect,
+                                ..Default::default()
+                            }));
+                        }
+                        Err(e) => {
+                            last_error = Some(e.to_string());
+                            if attempt < retry_count - 1 {
+                                sinks.throttle(Duration::from_millis(100), "retry_backoff").await;
                             }
                         }
                     }
-                    
-                    if let Some(e) = last_error {
-                        results.push(ScanResult {
-                            username: username.clone(),
-                            password: password.clone(),
-                            success: false,
-                            status_code: 0,
-                            response_time: start.elapsed(),
-                            error: Some(e.to_string()),
-                            timestamp: chrono::Utc::now(),
-                        });
-                    }
-                    
-                    // تحديث التقدم
-                    if let Some(pb) = progress {
-                        pb.inc(1);
-                    }
                 }
+                
+                Ok::<_, anyhow::Error>(Some(ScanResult {
+                    username,
+                    password,
+                    success: false,
+                    status_code: 0,
+                    response_time: start.elapsed(),
+                    error: last_error,
+                    timestamp: chrono::Utc::now(),
+                    metadata: None,
+                    captcha_detected: false,
+                    banner: None,
+                    http_version: None,
+                    ..Default::default()
+                }))
+            })
+            .buffer_unordered(self.options.threads.max(1));
+        
+        while let Some(attempt) = attempts.next().await {
+            match attempt? {
+                Some(result) => {
+                    // خطأ الشبكة بعد استنفاد الإعادات يُسجل كغيره لتُعاد المحاولة عند الاستئناف
+                    stop.observe(&result);
+                    self.sinks.record(&result);
+                    results.push(result);
+                }
+                None if stop.is_global_stopped() => break,
+                None => {}
+            }
+            
+            // تحديث التقدم
+            if let Some(pb) = &progress {
+                pb.inc(1);
             }
         }
         
@@ -553,23 +2047,30 @@ impl RedFoxScanner {
         self.logger.info(&format!("فحص {} كلمة مرور محددة", passwords.len()));
         
         let mut results = Vec::new();
-        let semaphore = Arc::new(tokio::sync::Semaphore::new(self.max_workers));
+        let semaphore = self.slots();
+        let target = self.login_target();
         
         for username in &self.users {
             for password in passwords {
                 let _permit = semaphore.acquire().await?;
                 
                 let start = Instant::now();
-                match self.http_client.test_login(username, password).await {
-                    Ok(response) => {
+                match target.attempt(username, password).await {
+                    Ok(outcome) => {
                         results.push(ScanResult {
                             username: username.clone(),
                             password: (*password).to_string(),
-                            success: response.status().is_success(),
-                            status_code: response.status().as_u16(),
+                            success: outcome.success,
+                            status_code: outcome.status_code,
                             response_time: start.elapsed(),
                             error: None,
                             timestamp: chrono::Utc::now(),
+                            metadata: None,
+                            captcha_detected: outcome.captcha.is_some(),
+                            banner: outcome.banner,
+                            http_version: outcome.http_version,
+                            redirect: outcome.redirect,
+                            ..Default::default()
                         });
                     }
                     Err(e) => {
@@ -581,24 +2082,170 @@ impl RedFoxScanner {
                             response_time: start.elapsed(),
                             error: Some(e.to_string()),
                             timestamp: chrono::Utc::now(),
+                            metadata: None,
+                            captcha_detected: false,
+                            banner: None,
+                            http_version: None,
+                            ..Default::default()
                         });
                     }
                 }
             }
         }
         
+        self.attach_metadata(&mut results);
+        
         Ok(results)
     }
     
+    /// بث أزواج الاعتماد المطلوب اختبارها (الأزواج المحددة أو الجداء الكامل مولدًا عند الطلب)
+    ///
+    /// كلمات المرور تُقرأ من مصدرها مرة لكل مستخدم بدل تحميل الجداء في الذاكرة،
+    /// وفي الجلسة المستأنفة تُعاد أزواج أخطاء الشبكة أولًا ثم يُستأنف كل مستخدم من إزاحته.
+    pub fn pair_stream(&self) -> BoxStream<'static, Result<(String, String)>> {
+        let pairs = match &self.combos {
+            Some(combos) => {
+                let combos: Vec<_> = combos
+                    .iter()
+                    .filter(|(u, _)| !self.known_users.contains(u))
+                    .cloned()
+                    .map(Ok)
+                    .collect();
+                stream::iter(combos).boxed()
+            }
+            None => {
+                let users: Vec<String> = self
+                    .users
+                    .iter()
+                    .filter(|u| !self.known_users.contains(*u))
+                    .cloned()
+                    .collect();
+                let passwords = self.passwords.clone();
+                
+                if passwords.is_single_pass() {
+                    // stdin يُقرأ مرة واحدة: كل كلمة مرور تُجرب على كل المستخدمين
+                    passwords
+                        .stream()
+                        .map_ok(move |p| {
+                            let pairs: Vec<_> = users.iter().map(|u| Ok((u.clone(), p.clone()))).collect();
+                            stream::iter(pairs)
+                        })
+                        .try_flatten()
+                        .boxed()
+                } else {
+                    stream::iter(users)
+                        .flat_map(move |user| passwords.stream().map_ok(move |p| (user.clone(), p)))
+                        .boxed()
+                }
+            }
+        };
+        
+        match self.sinks.session.clone() {
+            Some(session) => {
+                let retry: Vec<_> = session
+                    .retry_pairs()
+                    .into_iter()
+                    .filter(|(u, _)| !self.known_users.contains(u))
+                    .map(Ok)
+                    .collect();
+                stream::iter(retry)
+                    .chain(pairs.try_filter(move |(u, p)| future::ready(session.dispatch(u, p))))
+                    .boxed()
+            }
+            None => pairs,
+        }
+    }
+    
+    /// إرفاق البيانات الوصفية بالنتائج
+    fn attach_metadata(&self, results: &mut [ScanResult]) {
+        if self.user_metadata.is_empty() {
+            return;
+        }
+        
+        for result in results.iter_mut() {
+            result.metadata = self.user_metadata.get(&result.username).cloned();
+        }
+    }
+    
     /// الحصول على إحصائيات الفحص
     pub fn get_stats(&self) -> serde_json::Value {
         serde_json::json!({
             "total_users": self.users.len(),
             "total_passwords": self.passwords.len(),
-            "total_attempts": self.users.len() * self.passwords.len(),
-            "max_workers": self.max_workers,
-            "attack_mode": format!("{:?}", self.attack_mode),
-            "rate_limit": self.rate_limit,
+            "total_attempts": self.total_attempts(),
+            "combo_mode": self.combos.is_some(),
+            "max_workers": self.options.threads,
+            "attack_mode": self.options.mode.name(),
+            "rate_limit": self.options.rate_limit,
         })
     }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    /// ماسح لهدف لا يقبل الاتصال (كل محاولة تنتهي بخطأ شبكة)
+    async fn unreachable_scanner(mode: AttackMode) -> RedFoxScanner {
+        ScannerBuilder::new()
+            .target("http://127.0.0.1:1/login")
+            .users(["admin"])
+            .passwords(["secret"])
+            .threads(1)
+            .timeout(Duration::from_secs(1))
+            .mode(mode)
+            .build()
+            .await
+            .unwrap()
+    }
+    
+    #[tokio::test]
+    async fn test_aggressive_unreachable_target_emits_error_event() {
+        let mut scanner = unreachable_scanner(AttackMode::Aggressive).await;
+        
+        let errors = Arc::new(AtomicUsize::new(0));
+        scanner.on_event({
+            let errors = Arc::clone(&errors);
+            move |event: &ScanEvent<'_>| {
+                if matches!(event, ScanEvent::Error(_)) {
+                    errors.fetch_add(1, Ordering::Relaxed);
+                }
+            }
+        });
+        
+        let results = scanner.scan(false).await.unwrap();
+        
+        // المحاولة الفاشلة بعد استنفاد الإعادات تصل إلى الخطافات كغيرها من النتائج
+        assert_eq!(results.len(), 1);
+        assert!(results[0].error.is_some());
+        assert_eq!(errors.load(Ordering::Relaxed), 1);
+    }
+    
+    #[tokio::test]
+    async fn test_aggressive_unreachable_target_reaches_output_stream() {
+        let file = tempfile::NamedTempFile::new().unwrap();
+        let mut scanner = unreachable_scanner(AttackMode::Aggressive).await;
+        scanner.attach_stream(Arc::new(ResultStream::open(file.path()).unwrap()));
+        
+        scanner.scan(false).await.unwrap();
+        
+        let lines = std::fs::read_to_string(file.path()).unwrap();
+        let streamed: Vec<ScanResult> = lines
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(streamed.len(), 1);
+        assert_eq!(streamed[0].username, "admin");
+        assert!(streamed[0].error.is_some());
+    }
+    
+    #[tokio::test]
+    async fn test_normal_mode_measures_response_time() {
+        let scanner = unreachable_scanner(AttackMode::Normal).await;
+        
+        let results = scanner.scan(false).await.unwrap();
+        
+        assert_eq!(results.len(), 1);
+        assert!(results[0].response_time > Duration::ZERO);
+    }
 }
\ No newline at end of file
@@ -0,0 +1,508 @@
+//! وضع الخادم (REST)
+//! واجهة HTTP لإرسال مهام الفحص ومتابعة تقدمها وبث نتائجها (SSE) وتنزيل تقاريرها
+
+use std::collections::BTreeMap;
+use std::convert::Infallible;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use anyhow::{Context, Result};
+use axum::extract::{Path, Query, Request, State};
+use axum::http::{header, StatusCode};
+use axum::middleware::{self, Next};
+use axum::response::sse::{Event, KeepAlive, Sse};
+use axum::response::{IntoResponse, Response};
+use axum::routing::get;
+use axum::{Json, Router};
+use chrono::{DateTime, Utc};
+use futures::stream::{self, Stream, StreamExt};
+use parking_lot::RwLock;
+use serde_json::{json, Map, Value};
+use tokio::sync::{broadcast, mpsc};
+use tokio_stream::wrappers::BroadcastStream;
+use tokio_util::sync::CancellationToken;
+
+use crate::agent::{AgentPool, TlsFiles};
+use crate::progress::{ProgressSnapshot, DEFAULT_PROGRESS_INTERVAL};
+use crate::reporter::{self, ReportGenerator};
+use crate::scanner::{RedFoxScanner, ScanResult};
+use crate::session::SessionConfig;
+use crate::shell::ShellState;
+use crate::telemetry::Logger;
+
+/// عنوان الاستماع الافتراضي (محلي فقط)
+pub const DEFAULT_LISTEN: &str = "127.0.0.1:8700";
+
+/// سعة قناة أحداث المهمة لكل مشترك SSE
+const EVENT_CAPACITY: usize = 1024;
+
+/// حالة مهمة فحص
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum JobState {
+    /// بانتظار التشغيل
+    Queued,
+    /// قيد الفحص
+    Running,
+    /// اكتمل الفحص
+    Completed,
+    /// فشل الفحص
+    Failed,
+    /// ألغي بطلب من العميل
+    Cancelled,
+}
+
+/// حدث مهمة يُبث لمشتركي SSE
+#[derive(Debug, Clone)]
+enum JobEvent {
+    /// نتيجة محاولة
+    Result(Box<ScanResult>),
+    /// انتهاء المهمة
+    Finished,
+}
+
+/// مهمة فحص مرسلة إلى الخادم
+pub struct Job {
+    id: u64,
+    url: String,
+    state: RwLock<JobState>,
+    error: RwLock<Option<String>>,
+    total: AtomicUsize,
+    snapshot: Arc<RwLock<Option<ProgressSnapshot>>>,
+    results: RwLock<Vec<ScanResult>>,
+    events: broadcast::Sender<JobEvent>,
+    cancel: CancellationToken,
+    created_at: DateTime<Utc>,
+    finished_at: RwLock<Option<DateTime<Utc>>>,
+}
+
+impl Job {
+    /// مهمة جديدة بانتظار التشغيل
+    fn new(id: u64, url: String) -> Self {
+        let (events, _) = broadcast::channel(EVENT_CAPACITY);
+        
+        Self {
+            id,
+            url,
+            state: RwLock::new(JobState::Queued),
+            error: RwLock::new(None),
+            total: AtomicUsize::new(0),
+            snapshot: Arc::new(RwLock::new(None)),
+            results: RwLock::new(Vec::new()),
+            events,
+            cancel: CancellationToken::new(),
+            created_at: Utc::now(),
+            finished_at: RwLock::new(None),
+        }
+    }
+    
+    /// ملخص المهمة وتقدمها
+    fn summary(&self) -> Value {
+        let results = self.results.read();
+        let total = self.total.load(Ordering::Relaxed);
+        let progress = if total == 0 {
+            0.0
+        } else {
+            (results.len() as f64 / total as f64 * 100.0).min(100.0)
+        };
+        let snapshot = *self.snapshot.read();
+        
+        json!({
+            "id": self.id,
+            "url": self.url,
+            "state": *self.state.read(),
+            "error": *self.error.read(),
+            "total_attempts": total,
+            "completed": results.len(),
+            "found": results.iter().filter(|r| r.success).count(),
+            "progress": progress,
+            "rps": snapshot.map(|s| s.rps),
+            "eta_secs": snapshot.and_then(|s| s.eta).map(|eta| eta.as_secs()),
+            "created_at": self.created_at,
+            "finished_at": *self.finished_at.read(),
+        })
+    }
+    
+    /// إضافة نتيجة وبثها (تحت قفل النتائج حتى لا يفوت المشترك الجديد أي حدث)
+    fn push(&self, result: ScanResult) {
+        let mut results = self.results.write();
+        let _ = self.events.send(JobEvent::Result(Box::new(result.clone())));
+        results.push(result);
+    }
+    
+    /// تشغيل المهمة حتى النهاية وتسجيل حالتها
+    async fn run(self: Arc<Self>, config: SessionConfig, agents: Option<Arc<AgentPool>>) {
+        *self.state.write() = JobState::Running;
+        
+        let outcome = self.execute(config, agents).await;
+        
+        // النتائج النهائية هي المرجع (تشمل فحص الوصول بدون مصادقة والبيانات الافتراضية)
+        let results = self.results.write();
+        match outcome {
+            Ok(()) if self.cancel.is_cancelled() => *self.state.write() = JobState::Cancelled,
+            Ok(()) => *self.state.write() = JobState::Completed,
+            Err(e) => {
+                Logger.warn(&format!("فشلت المهمة {}: {:#}", self.id, e));
+                *self.error.write() = Some(format!("{:#}", e));
+                *self.state.write() = JobState::Failed;
+            }
+        }
+        *self.finished_at.write() = Some(Utc::now());
+        let _ = self.events.send(JobEvent::Finished);
+        drop(results);
+    }
+    
+    /// بناء الماسح وتشغيله (أو توزيعه على الوكلاء المتصلين) مع جمع النتائج فور إنتاجها
+    async fn execute(&self, config: SessionConfig, agents: Option<Arc<AgentPool>>) -> Result<()> {
+        let mut scanner = RedFoxScanner::from_config(&config).await?;
+        self.total.store(scanner.total_attempts(), Ordering::Relaxed);
+        
+        if let Some(agents) = agents.filter(|pool| !pool.is_empty()) {
+            let pairs = scanner.pair_stream();
+            let dispatch = agents.dispatch(&config, pairs, config.batch_size, |result| self.push(result.clone()));
+            
+            // عند الإلغاء تبقى النتائج التي وصلت من الوكلاء حتى لحظته
+            tokio::select! {
+                results = dispatch => *self.results.write() = results?,
+                () = self.cancel.cancelled() => {}
+            }
+            return Ok(());
+        }
+        
+        let (sender, mut receiver) = mpsc::unbounded_channel();
+        scanner.attach_live(sender);
+        scanner.attach_cancellation(self.cancel.clone());
+        
+        let snapshot = self.snapshot.clone();
+        scanner.observe_progress(
+            move |progress: &ProgressSnapshot| *snapshot.write() = Some(*progress),
+            DEFAULT_PROGRESS_INTERVAL,
+        );
+        
+        let scan = scanner.scan(false);
+        tokio::pin!(scan);
+        
+        let results = loop {
+            tokio::select! {
+                Some(result) = receiver.recv() => self.push(result),
+                results = &mut scan => break results.context("فشل في تنفيذ الفحص")?,
+            }
+        };
+        
+        while let Ok(result) = receiver.try_recv() {
+            self.push(result);
+        }
+        
+        *self.results.write() = results;
+        Ok(())
+    }
+}
+
+/// حالة الخادم المشتركة
+struct Server {
+    jobs: RwLock<BTreeMap<u64, Arc<Job>>>,
+    next_id: AtomicU64,
+    token: Option<String>,
+    agents: Option<Arc<AgentPool>>,
+}
+
+impl Server {
+    /// البحث عن مهمة
+    fn job(&self, id: u64) -> Result<Arc<Job>, ApiError> {
+        self.jobs
+            .read()
+            .get(&id)
+            .cloned()
+            .ok_or_else(|| ApiError(StatusCode::NOT_FOUND, format!("المهمة غير موجودة: {}", id)))
+    }
+}
+
+/// خطأ واجهة برمجية يُعاد كـ JSON
+struct ApiError(StatusCode, String);
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        (self.0, Json(json!({ "error": self.1 }))).into_response()
+    }
+}
+
+/// معاملات تنزيل التقرير
+#[derive(Debug, serde::Deserialize)]
+struct ReportQuery {
+    #[serde(default = "default_report_format")]
+    format: String,
+    #[serde(default)]
+    redact: bool,
+}
+
+/// تنسيق التقرير الافتراضي
+fn default_report_format() -> String {
+    "json".to_string()
+}
+
+/// تشغيل الخادم حتى الإيقاف (Ctrl+C)، مع مستمع للوكلاء عبر mTLS إن طُلب
+pub async fn serve(listen: SocketAddr, token: Option<String>, agents: Option<(SocketAddr, TlsFiles)>) -> Result<()> {
+    let logger = Logger;
+    
+    if token.is_none() && !listen.ip().is_loopback() {
+        logger.warn("الخادم يستمع على عنوان غير محلي بدون --token");
+    }
+    
+    let pool = match agents {
+        Some((address, tls)) => {
+            let pool = Arc::new(AgentPool::default());
+            let acceptor = tls.acceptor()?;
+            tokio::spawn({
+                let pool = Arc::clone(&pool);
+                async move {
+                    if let Err(e) = pool.listen(address, acceptor).await {
+                        Logger.error(&format!("توقف مستمع الوكلاء: {:#}", e));
+                    }
+                }
+            });
+            Some(pool)
+        }
+        None => None,
+    };
+    
+    let state = Arc::new(Server {
+        jobs: RwLock::new(BTreeMap::new()),
+        next_id: AtomicU64::new(1),
+        token,
+        agents: pool,
+    });
+    
+    let listener = tokio::net::TcpListener::bind(listen)
+        .await
+        .context(format!("فشل في الاستماع على: {}", listen))?;
+    logger.success(&format!("الخادم يعمل على: http://{}", listen));
+    
+    axum::serve(listener, router(state))
+        .with_graceful_shutdown(async {
+            tokio::signal::ctrl_c().await.ok();
+        })
+        .await
+        .context("توقف الخادم بخطأ")
+}
+
+/// مسارات الواجهة
+fn router(state: Arc<Server>) -> Router {
+    Router::new()
+        .route("/scans", get(list_jobs).post(submit_job))
+        .route("/scans/:id", get(job_status).delete(cancel_job))
+        .route("/scans/:id/results", get(job_results))
+        .route("/scans/:id/events", get(job_events))
+        .route("/scans/:id/report", get(job_report))
+        .route("/agents", get(list_agents))
+        .route_layer(middleware::from_fn_with_state(Arc::clone(&state), authorize))
+        .route("/health", get(|| async { Json(json!({ "status": "ok", "version": env!("CARGO_PKG_VERSION") })) }))
+        .with_state(state)
+}
+
+/// التحقق من رمز Bearer إن كان مطلوبًا
+async fn authorize(State(state): State<Arc<Server>>, request: Request, next: Next) -> Response {
+    if let Some(token) = &state.token {
+        let provided = request
+            .headers()
+            .get(header::AUTHORIZATION)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.strip_prefix("Bearer "));
+        
+        if provided != Some(token.as_str()) {
+            return ApiError(StatusCode::UNAUTHORIZED, "رمز وصول غير صالح".to_string()).into_response();
+        }
+    }
+    
+    next.run(request).await
+}
+
+/// قائمة المهام
+async fn list_jobs(State(state): State<Arc<Server>>) -> Json<Value> {
+    let jobs: Vec<Value> = state.jobs.read().values().map(|job| job.summary()).collect();
+    Json(Value::Array(jobs))
+}
+
+/// إرسال مهمة فحص (كائن خيارات بنفس أسماء خيارات الوضع التفاعلي)
+async fn submit_job(
+    State(state): State<Arc<Server>>,
+    Json(options): Json<Map<String, Value>>,
+) -> Result<(StatusCode, Json<Value>), ApiError> {
+    let config = ShellState::new()
+        .with_options(options)
+        .config()
+        .map_err(|e| ApiError(StatusCode::BAD_REQUEST, format!("{:#}", e)))?;
+    let url = config
+        .login_url()
+        .map_err(|e| ApiError(StatusCode::BAD_REQUEST, format!("{:#}", e)))?;
+    
+    let id = state.next_id.fetch_add(1, Ordering::Relaxed);
+    let job = Arc::new(Job::new(id, url));
+    state.jobs.write().insert(id, Arc::clone(&job));
+    
+    Logger.info(&format!("مهمة جديدة {}: {}", id, job.url));
+    tokio::spawn(Arc::clone(&job).run(config, state.agents.clone()));
+    
+    Ok((StatusCode::ACCEPTED, Json(job.summary())))
+}
+
+/// الوكلاء المتصلون وسعاتهم
+async fn list_agents(State(state): State<Arc<Server>>) -> Json<Value> {
+    let agents = state.agents.as_ref().map(|pool| pool.agents()).unwrap_or_default();
+    
+    Json(Value::Array(
+        agents
+            .iter()
+            .map(|agent| {
+                json!({
+                    "name": agent.name,
+                    "capacity": agent.capacity,
+                    "address": agent.address.to_string(),
+                    "connected_at": agent.connected_at,
+                })
+            })
+            .collect(),
+    ))
+}
+
+/// حالة مهمة وتقدمها
+async fn job_status(State(state): State<Arc<Server>>, Path(id): Path<u64>) -> Result<Json<Value>, ApiError> {
+    Ok(Json(state.job(id)?.summary()))
+}
+
+/// إلغاء مهمة (المحاولات الجارية تكتمل ثم تنتهي المهمة بنتائجها الجزئية)
+async fn cancel_job(State(state): State<Arc<Server>>, Path(id): Path<u64>) -> Result<(StatusCode, Json<Value>), ApiError> {
+    let job = state.job(id)?;
+    if matches!(*job.state.read(), JobState::Completed | JobState::Failed | JobState::Cancelled) {
+        return Err(ApiError(StatusCode::CONFLICT, format!("المهمة {} منتهية", id)));
+    }
+    
+    job.cancel.cancel();
+    Logger.info(&format!("طلب إلغاء المهمة {}", id));
+    Ok((StatusCode::ACCEPTED, Json(job.summary())))
+}
+
+/// كل نتائج المهمة حتى الآن
+async fn job_results(State(state): State<Arc<Server>>, Path(id): Path<u64>) -> Result<Json<Vec<ScanResult>>, ApiError> {
+    Ok(Json(state.job(id)?.results.read().clone()))
+}
+
+/// بث النتائج (SSE): النتائج السابقة أولًا ثم الجديدة حتى انتهاء المهمة
+async fn job_events(
+    State(state): State<Arc<Server>>,
+    Path(id): Path<u64>,
+) -> Result<Sse<impl Stream<Item = Result<Event, Infallible>>>, ApiError> {
+    let job = state.job(id)?;
+    
+    let (history, finished, receiver) = {
+        let results = job.results.read();
+        let finished = matches!(*job.state.read(), JobState::Completed | JobState::Failed | JobState::Cancelled);
+        (results.clone(), finished, job.events.subscribe())
+    };
+    
+    let replay = stream::iter(history.into_iter().map(|result| JobEvent::Result(Box::new(result))));
+    let live = if finished {
+        stream::iter(vec![JobEvent::Finished]).boxed()
+    } else {
+        BroadcastStream::new(receiver).filter_map(|event| async move { event.ok() }).boxed()
+    };
+    
+    let events = replay
+        .chain(live)
+        .scan(false, |done, event| {
+            let item = (!*done).then(|| {
+                *done = matches!(event, JobEvent::Finished);
+                event
+            });
+            async move { item }
+        })
+        .map(move |event| {
+            let event = match event {
+                JobEvent::Result(result) => Event::default()
+                    .event("result")
+                    .json_data(&result)
+                    .unwrap_or_else(|_| Event::default().event("result")),
+                JobEvent::Finished => Event::default()
+                    .event("finished")
+                    .json_data(job.summary())
+                    .unwrap_or_else(|_| Event::default().event("finished")),
+            };
+            Ok(event)
+        });
+    
+    Ok(Sse::new(events).keep_alive(KeepAlive::default()))
+}
+
+/// تنزيل تقرير المهمة بالتنسيق المطلوب
+async fn job_report(
+    State(state): State<Arc<Server>>,
+    Path(id): Path<u64>,
+    Query(query): Query<ReportQuery>,
+) -> Result<Response, ApiError> {
+    let job = state.job(id)?;
+    let results = job.results.read().clone();
+    if results.is_empty() {
+        return Err(ApiError(StatusCode::CONFLICT, "لا توجد نتائج بعد".to_string()));
+    }
+    
+    let mut generator = ReportGenerator::new();
+    if query.redact {
+        generator = generator.with_redaction(reporter::redaction_key().as_deref());
+    }
+    
+    let internal = |e: anyhow::Error| ApiError(StatusCode::INTERNAL_SERVER_ERROR, format!("{:#}", e));
+    let path = generator
+        .generate(&results, &format!("job_{}", id), &query.format)
+        .await
+        .map_err(internal)?;
+    let body = tokio::fs::read(&path)
+        .await
+        .map_err(|e| internal(e.into()))?;
+    
+    let filename = std::path::Path::new(&path)
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default();
+    
+    Ok((
+        [
+            (header::CONTENT_TYPE, content_type(&query.format).to_string()),
+            (header::CONTENT_DISPOSITION, format!("attachment; filename=\"{}\"", filename)),
+        ],
+        body,
+    )
+        .into_response())
+}
+
+/// نوع المحتوى لتنسيق التقرير
+fn content_type(format: &str) -> &'static str {
+    match format.to_lowercase().as_str() {
+        "json" => "application/json",
+        "html" => "text/html; charset=utf-8",
+        "csv" => "text/csv; charset=utf-8",
+        "txt" => "text/plain; charset=utf-8",
+        "xml" => "application/xml",
+        "pdf" => "application/pdf",
+        "ndjson" => "application/x-ndjson",
+        _ => "application/octet-stream",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_job_progress_summary() {
+        let job = Job::new(7, "http://example.com/login".to_string());
+        job.total.store(4, Ordering::Relaxed);
+        job.push(ScanResult { username: "admin".to_string(), success: true, ..Default::default() });
+        
+        let summary = job.summary();
+        
+        assert_eq!(summary["id"], 7);
+        assert_eq!(summary["state"], "queued");
+        assert_eq!(summary["completed"], 1);
+        assert_eq!(summary["found"], 1);
+        assert_eq!(summary["progress"], 25.0);
+    }
+}
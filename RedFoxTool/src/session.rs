@@ -0,0 +1,878 @@
+//! جلسات الفحص القابلة للاستئناف
+//! حفظ حالة الماسح دوريًا في ملف `.redfox-session` لاستئناف الفحوصات المنقطعة
+
+use std::collections::{BTreeSet, HashMap, VecDeque};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+use anyhow::{Result, Context};
+use parking_lot::Mutex;
+
+use crate::http_client::{AuthType, HttpVersion, ResolveOverride, TlsVersion};
+use crate::login_body::BodyFormat;
+use crate::matcher::SuccessCriteria;
+use crate::notifiers::NotifiersConfig;
+use crate::modules::snmp::{SnmpAuthProtocol, SnmpVersion};
+use crate::modules::{Protocol, TlsMode};
+use crate::bruteforcer::AttackMode;
+use crate::reporter::mask_password;
+use crate::scanner::{ScanOptions, ScanResult};
+
+/// مسار ملف الجلسة الافتراضي
+pub const DEFAULT_SESSION_FILE: &str = ".redfox-session";
+
+/// فهرس مسارات الجلسات المسجلة (مسار مطلق في كل سطر) لأمر `sessions`
+pub const SESSION_INDEX: &str = "~/.redfox/sessions.idx";
+
+/// الفاصل الافتراضي بين نقاط الحفظ
+pub const DEFAULT_CHECKPOINT_INTERVAL: Duration = Duration::from_secs(30);
+
+/// إصدار تنسيق ملف الجلسة (2: إزاحات لكل مستخدم بدل بصمات كل المحاولات)
+const SESSION_VERSION: u32 = 2;
+
+/// إعدادات الفحص المحفوظة لإعادة بناء الماسح عند الاستئناف
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct SessionConfig {
+    /// رابط الهدف
+    pub url: String,
+    
+    /// بروتوكول الهدف
+    #[serde(default)]
+    pub protocol: Protocol,
+    
+    /// وضع TLS لوحدات البروتوكولات
+    #[serde(default)]
+    pub service_tls: TlsMode,
+    
+    /// قالب اسم الربط في LDAP
+    #[serde(default)]
+    pub ldap_user_template: Option<String>,
+    
+    /// إصدار SNMP
+    #[serde(default)]
+    pub snmp_version: SnmpVersion,
+    
+    /// بروتوكول مصادقة SNMPv3
+    #[serde(default)]
+    pub snmp_auth: SnmpAuthProtocol,
+    
+    /// مهلة رد UDP بالمللي ثانية
+    #[serde(default = "default_udp_timeout")]
+    pub udp_timeout: u64,
+    
+    /// عدد مرات إعادة إرسال UDP
+    #[serde(default = "default_udp_retries")]
+    pub udp_retries: u32,
+    
+    /// الإعداد الجاهز
+    #[serde(default)]
+    pub preset: Option<String>,
+    
+    /// اسم المستخدم أو ملف المستخدمين
+    pub user: Option<String>,
+    
+    /// ملف كلمات المرور
+    pub password_file: Option<String>,
+    
+    /// ملف قواعد hashcat يُطبق على كلمات المرور أثناء البث
+    #[serde(default)]
+    pub rules: Option<String>,
+    
+    /// ملف الأزواج
+    pub combo_file: Option<String>,
+    
+    /// عدد الخيوط
+    pub threads: usize,
+    
+    /// مهلة الطلب بالثواني
+    pub timeout: u64,
+    
+    /// وضع الهجوم
+    pub mode: String,
+    
+    /// حد المعدل (طلبات/ثانية)
+    pub rate_limit: Option<u32>,
+    
+    /// ملف المخرجات
+    pub output: Option<String>,
+    
+    /// ملف بث النتائج (JSONL)
+    #[serde(default)]
+    pub output_stream: Option<PathBuf>,
+    
+    /// طابور Redis للتوزيع على العمال
+    #[serde(default)]
+    pub queue: Option<String>,
+    
+    /// حجم الدفعة في الطابور الموزع
+    #[serde(default = "default_batch_size")]
+    pub batch_size: usize,
+    
+    /// إضافة WASM (اسم أو مسار)
+    #[serde(default)]
+    pub plugin: Option<String>,
+    
+    /// خلفيات الإشعار (Webhook، Slack، Discord، البريد)
+    #[serde(default)]
+    pub notifiers: NotifiersConfig,
+    
+    /// تعطيل potfile
+    #[serde(default)]
+    pub no_pot: bool,
+    
+    /// عدم الحفظ في الخزنة المشفرة
+    #[serde(default)]
+    pub no_vault: bool,
+    
+    /// كلمات المرور المكتشفة بنص واضح في التقارير و potfile والجلسة
+    #[serde(default)]
+    pub plaintext: bool,
+    
+    /// تنسيق المخرجات
+    pub format: Option<String>,
+    
+    /// قالب Tera مخصص للتقرير
+    #[serde(default)]
+    pub template: Option<PathBuf>,
+    
+    /// إخفاء كلمات المرور في التقرير
+    #[serde(default)]
+    pub redact: bool,
+    
+    /// خادم البروكسي
+    pub proxy: Option<String>,
+    
+    /// ملف قائمة البروكسيات
+    pub proxy_file: Option<String>,
+    
+    /// حد المعدل لكل بروكسي
+    pub rate_limit_per_proxy: Option<u32>,
+    
+    /// سباق الاتصالات
+    pub race_connections: bool,
+    
+    /// إيقاف الفحص عند أول نجاح
+    #[serde(default)]
+    pub stop_on_success: bool,
+    
+    /// إيقاف محاولات المستخدم عند إيجاد كلمة مروره
+    #[serde(default)]
+    pub stop_per_user: bool,
+    
+    /// عدد محاولات المعايرة
+    #[serde(default = "default_calibration_samples")]
+    pub calibration_samples: usize,
+    
+    /// قواعد كشف النجاح
+    #[serde(default)]
+    pub success_criteria: SuccessCriteria,
+    
+    /// إيقاف الفحص عند اكتشاف CAPTCHA
+    #[serde(default)]
+    pub halt_on_captcha: bool,
+    
+    /// مسح البيانات الافتراضية قبل القائمة الرئيسية
+    #[serde(default)]
+    pub quick_defaults: bool,
+    
+    /// كشف WAF وتطبيق ملف التهرب
+    #[serde(default)]
+    pub evasion: bool,
+    
+    /// أدنى تأخير عشوائي (مللي ثانية)
+    #[serde(default = "default_min_delay")]
+    pub min_delay: u64,
+    
+    /// أقصى تأخير عشوائي (مللي ثانية)
+    #[serde(default = "default_max_delay")]
+    pub max_delay: u64,
+    
+    /// التأخير العشوائي في الوضع العادي
+    #[serde(default)]
+    pub jitter: bool,
+    
+    /// إصدار HTTP
+    #[serde(default)]
+    pub http_version: HttpVersion,
+    
+    /// تخطي التحقق من شهادات TLS
+    #[serde(default)]
+    pub insecure: bool,
+    
+    /// أدنى إصدار TLS
+    #[serde(default)]
+    pub tls_min: Option<TlsVersion>,
+    
+    /// أقصى إصدار TLS
+    #[serde(default)]
+    pub tls_max: Option<TlsVersion>,
+    
+    /// حزمة شهادات جذر إضافية
+    #[serde(default)]
+    pub ca_cert: Option<PathBuf>,
+    
+    /// تجاوز ترويسة Host
+    #[serde(default)]
+    pub host_header: Option<String>,
+    
+    /// تجاوزات DNS
+    #[serde(default)]
+    pub resolve: Vec<ResolveOverride>,
+    
+    /// اسم المضيف في SNI
+    #[serde(default)]
+    pub sni: Option<String>,
+    
+    /// نوع المصادقة
+    #[serde(default)]
+    pub auth_type: AuthType,
+    
+    /// قالب ترويسة السر
+    #[serde(default)]
+    pub secret_header: Option<String>,
+    
+    /// معرف عميل OAuth2
+    #[serde(default)]
+    pub client_id: Option<String>,
+    
+    /// سر عميل OAuth2
+    #[serde(default)]
+    pub client_secret: Option<String>,
+    
+    /// نطاقات OAuth2
+    #[serde(default)]
+    pub scope: Option<String>,
+    
+    /// تنسيق جسم الطلب
+    #[serde(default)]
+    pub body_format: BodyFormat,
+    
+    /// طريقة طلب تسجيل الدخول
+    #[serde(default = "default_method")]
+    pub method: String,
+    
+    /// قالب سلسلة الاستعلام
+    #[serde(default)]
+    pub query: Option<String>,
+    
+    /// ملف الطلب الخام
+    #[serde(default)]
+    pub raw_request: Option<PathBuf>,
+    
+    /// قالب جسم الطلب
+    #[serde(default)]
+    pub body_template: Option<String>,
+    
+    /// حقل اسم المستخدم في النموذج
+    #[serde(default = "default_username_field")]
+    pub username_field: String,
+    
+    /// حقل كلمة المرور في النموذج
+    #[serde(default = "default_password_field")]
+    pub password_field: String,
+    
+    /// الكوكيز (ملف أو سلسلة)
+    #[serde(default)]
+    pub cookies: Option<String>,
+    
+    /// ترويسات مخصصة (JSON)
+    #[serde(default)]
+    pub headers: Option<String>,
+    
+    /// بيانات POST إضافية (JSON)
+    #[serde(default)]
+    pub data: Option<String>,
+    
+    /// حقل رمز CSRF
+    #[serde(default)]
+    pub csrf_field: Option<String>,
+    
+    /// محدد رمز CSRF
+    #[serde(default)]
+    pub csrf_selector: Option<String>,
+}
+
+/// القيمة الافتراضية لعدد محاولات المعايرة في الجلسات القديمة
+fn default_calibration_samples() -> usize {
+    crate::scanner::DEFAULT_CALIBRATION_SAMPLES
+}
+
+/// القيمة الافتراضية لأدنى تأخير في الجلسات القديمة
+fn default_min_delay() -> u64 {
+    crate::scanner::DEFAULT_MIN_DELAY_MS
+}
+
+/// القيمة الافتراضية لأقصى تأخير في الجلسات القديمة
+fn default_max_delay() -> u64 {
+    crate::scanner::DEFAULT_MAX_DELAY_MS
+}
+
+/// القيمة الافتراضية لمهلة UDP في الجلسات القديمة
+fn default_udp_timeout() -> u64 {
+    crate::modules::DEFAULT_UDP_TIMEOUT_MS
+}
+
+/// القيمة الافتراضية لإعادة إرسال UDP في الجلسات القديمة
+fn default_udp_retries() -> u32 {
+    crate::modules::DEFAULT_UDP_RETRIES
+}
+
+/// القيمة الافتراضية لحقل اسم المستخدم في الجلسات القديمة
+fn default_username_field() -> String {
+    "username".to_string()
+}
+
+impl SessionConfig {
+    /// رابط صفحة الدخول (الإعداد الجاهز يكمل المسار عند تمرير جذر الموقع)
+    pub fn login_url(&self) -> Result<String> {
+        let preset = self.preset.as_deref().map(crate::modules::presets::find).transpose()?;
+        Ok(preset.map_or_else(|| self.url.clone(), |p| p.login_url(&self.url)))
+    }
+    
+    /// هل تُخفى كلمات المرور في التقرير؟ (--redact، أو افتراضيًا ما لم يُطلب النص الواضح)
+    pub fn redacts(&self) -> bool {
+        self.redact || !self.plaintext
+    }
+    
+    /// خيارات الماسح المقابلة لهذه الإعدادات
+    pub fn scan_options(&self) -> ScanOptions {
+        ScanOptions {
+            threads: self.threads,
+            timeout: Duration::from_secs(self.timeout),
+            mode: AttackMode::from_name(&self.mode),
+            rate_limit: self.rate_limit,
+            stop_on_success_global: self.stop_on_success,
+            stop_per_user: self.stop_per_user,
+            calibration_samples: self.calibration_samples,
+            success_criteria: self.success_criteria.clone(),
+            halt_on_captcha: self.halt_on_captcha,
+            min_delay: Duration::from_millis(self.min_delay),
+            max_delay: Duration::from_millis(self.max_delay),
+            jitter_normal: self.jitter,
+            quick_defaults: self.quick_defaults,
+        }
+    }
+}
+
+/// حجم الدفعة الافتراضي في الجلسات القديمة
+fn default_batch_size() -> usize {
+    crate::queue::DEFAULT_BATCH_SIZE
+}
+
+/// القيمة الافتراضية لحقل كلمة المرور في الجلسات القديمة
+fn default_password_field() -> String {
+    "password".to_string()
+}
+
+/// القيمة الافتراضية لطريقة الطلب في الجلسات القديمة
+fn default_method() -> String {
+    "POST".to_string()
+}
+
+/// حالة جلسة فحص محفوظة
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct ScanSession {
+    /// إصدار التنسيق
+    pub version: u32,
+    
+    /// إعدادات الفحص
+    pub config: SessionConfig,
+    
+    /// وقت إنشاء الجلسة
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    
+    /// وقت آخر حفظ
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+    
+    /// عدد المستخدمين في القائمة عند بدء الجلسة
+    pub user_count: usize,
+    
+    /// عدد كلمات المرور في القائمة عند بدء الجلسة
+    pub password_count: usize,
+    
+    /// إجمالي المحاولات المخططة
+    pub total_attempts: usize,
+    
+    /// إزاحة كل مستخدم في قائمة كلمات المرور: كل ما قبلها مكتمل
+    #[serde(default)]
+    pub offsets: HashMap<String, u64>,
+    
+    /// أزواج فشلت بخطأ شبكة قبل الإزاحة، تُعاد أولًا عند الاستئناف
+    #[serde(default)]
+    pub retry: Vec<(String, String)>,
+    
+    /// بيانات الاعتماد الصالحة المكتشفة
+    pub found: Vec<ScanResult>,
+    
+    /// هل اكتمل الفحص؟
+    pub finished: bool,
+}
+
+impl ScanSession {
+    /// إنشاء جلسة جديدة
+    pub fn new(config: SessionConfig) -> Self {
+        let now = chrono::Utc::now();
+        
+        Self {
+            version: SESSION_VERSION,
+            config,
+            created_at: now,
+            updated_at: now,
+            user_count: 0,
+            password_count: 0,
+            total_attempts: 0,
+            offsets: HashMap::new(),
+            retry: Vec::new(),
+            found: Vec::new(),
+            finished: false,
+        }
+    }
+    
+    /// تحميل جلسة من ملف
+    pub async fn load(path: &Path) -> Result<Self> {
+        let content = tokio::fs::read_to_string(path)
+            .await
+            .context(format!("فشل في قراءة ملف الجلسة: {}", path.display()))?;
+        
+        let session: Self = serde_json::from_str(&content)
+            .context(format!("ملف جلسة غير صالح: {}", path.display()))?;
+        
+        if session.version != SESSION_VERSION {
+            return Err(anyhow::anyhow!(
+                "إصدار ملف الجلسة غير مدعوم: {} (المتوقع {})",
+                session.version,
+                SESSION_VERSION
+            ));
+        }
+        
+        Ok(session)
+    }
+    
+    /// تسلسل الجلسة مع تحديث وقت آخر حفظ
+    fn snapshot(&mut self) -> Result<String> {
+        self.updated_at = chrono::Utc::now();
+        Ok(serde_json::to_string(self)?)
+    }
+    
+    /// عدد المحاولات المكتملة
+    pub fn completed_attempts(&self) -> usize {
+        self.offsets.values().map(|&offset| offset as usize).sum()
+    }
+    
+    /// نسبة التقدم (%)
+    pub fn progress(&self) -> f64 {
+        if self.finished {
+            100.0
+        } else if self.total_attempts == 0 {
+            0.0
+        } else {
+            (self.completed_attempts() as f64 / self.total_attempts as f64 * 100.0).min(100.0)
+        }
+    }
+}
+
+/// مسار فهرس الجلسات
+fn index_path() -> PathBuf {
+    PathBuf::from(shellexpand::tilde(SESSION_INDEX).as_ref())
+}
+
+/// الجلسات المسجلة في الفهرس بترتيب تسجيلها
+pub fn registered() -> Vec<PathBuf> {
+    std::fs::read_to_string(index_path())
+        .map(|content| {
+            content
+                .lines()
+                .filter(|line| !line.trim().is_empty())
+                .map(PathBuf::from)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// تسجيل مسار جلسة في الفهرس (مرة واحدة لكل مسار)
+pub fn register(path: &Path) -> Result<()> {
+    let path = absolute(path)?;
+    let mut sessions = registered();
+    if sessions.contains(&path) {
+        return Ok(());
+    }
+    sessions.push(path);
+    
+    write_index(&sessions)
+}
+
+/// حذف ملف جلسة وإزالته من الفهرس
+pub fn delete(path: &Path) -> Result<()> {
+    if path.exists() {
+        std::fs::remove_file(path)
+            .context(format!("فشل في حذف ملف الجلسة: {}", path.display()))?;
+    }
+    
+    let path = absolute(path)?;
+    let sessions: Vec<PathBuf> = registered().into_iter().filter(|p| *p != path).collect();
+    write_index(&sessions)
+}
+
+/// المسار المطلق (نسبةً إلى مجلد العمل الحالي)
+fn absolute(path: &Path) -> Result<PathBuf> {
+    if path.is_absolute() {
+        Ok(path.to_path_buf())
+    } else {
+        Ok(std::env::current_dir()?.join(path))
+    }
+}
+
+/// كتابة الفهرس
+fn write_index(sessions: &[PathBuf]) -> Result<()> {
+    let index = index_path();
+    if let Some(parent) = index.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    
+    let content: String = sessions.iter().map(|p| format!("{}\n", p.display())).collect();
+    std::fs::write(&index, content)
+        .context(format!("فشل في كتابة فهرس الجلسات: {}", index.display()))
+}
+
+/// تحديد جلسة برقمها في `sessions list` (يبدأ من 1) أو بمسارها
+pub fn resolve(reference: &str) -> PathBuf {
+    reference
+        .parse::<usize>()
+        .ok()
+        .and_then(|n| n.checked_sub(1))
+        .and_then(|i| registered().into_iter().nth(i))
+        .unwrap_or_else(|| PathBuf::from(reference))
+}
+
+/// كتابة ملف الجلسة بشكل ذري (كتابة ملف مؤقت ثم إعادة تسميته)
+fn write_atomic(path: &Path, content: &str) -> Result<()> {
+    let tmp_path = path.with_extension("tmp");
+    
+    std::fs::write(&tmp_path, content)
+        .context(format!("فشل في كتابة ملف الجلسة: {}", tmp_path.display()))?;
+    std::fs::rename(&tmp_path, path)
+        .context(format!("فشل في حفظ ملف الجلسة: {}", path.display()))?;
+    
+    Ok(())
+}
+
+/// كتابة لقطة ما لم تُكتب لقطة أحدث منها (الحفظ الدوري يجري في الخلفية وقد يتأخر)
+fn write_snapshot(path: &Path, written: &Mutex<u64>, seq: u64, content: &str) -> Result<()> {
+    let mut written = written.lock();
+    if *written > seq {
+        return Ok(());
+    }
+    
+    write_atomic(path, content)?;
+    *written = seq;
+    Ok(())
+}
+
+/// موضع مستخدم في بث كلمات المرور أثناء الفحص (المحاولات الجارية في الذاكرة فقط، والإزاحة تُحفظ)
+#[derive(Debug, Default)]
+struct Cursor {
+    /// كل الأزواج قبل هذا الفهرس مكتملة
+    offset: u64,
+    
+    /// فهرس الزوج التالي في البث
+    next: u64,
+    
+    /// فهارس الأزواج الجارية لكل كلمة مرور (بترتيب سحبها)
+    pending: HashMap<String, VecDeque<u64>>,
+    
+    /// أزواج اكتملت بعد الإزاحة خارج الترتيب
+    done: BTreeSet<u64>,
+    
+    /// توقف التتبع بعد إيجاد كلمة مرور المستخدم
+    closed: bool,
+}
+
+impl Cursor {
+    /// موضع يبدأ من إزاحة محفوظة
+    fn new(offset: u64) -> Self {
+        Self { offset, ..Default::default() }
+    }
+    
+    /// تسجيل زوج مسحوب من البث، وfalse إن كان قبل الإزاحة (اكتمل في جلسة سابقة)
+    fn dispatch(&mut self, password: &str) -> bool {
+        let index = self.next;
+        self.next += 1;
+        if index < self.offset {
+            return false;
+        }
+        
+        if !self.closed {
+            self.pending.entry(password.to_string()).or_default().push_back(index);
+        }
+        true
+    }
+    
+    /// تعليم زوج جارٍ كمكتمل وتقديم الإزاحة، وfalse إن لم يكن جاريًا
+    fn complete(&mut self, password: &str) -> bool {
+        let Some(indices) = self.pending.get_mut(password) else {
+            return false;
+        };
+        let Some(index) = indices.pop_front() else {
+            return false;
+        };
+        if indices.is_empty() {
+            self.pending.remove(password);
+        }
+        
+        self.done.insert(index);
+        while self.done.remove(&self.offset) {
+            self.offset += 1;
+        }
+        true
+    }
+    
+    /// إيقاف التتبع بعد إيجاد كلمة المرور (الأزواج المتبقية قد لا تُختبر مع الإيقاف لكل مستخدم)
+    fn close(&mut self) {
+        self.closed = true;
+        self.pending.clear();
+        self.done.clear();
+    }
+}
+
+/// مسجل الجلسة المشترك بين مهام الفحص
+pub struct SessionRecorder {
+    path: PathBuf,
+    state: Mutex<ScanSession>,
+    cursors: Mutex<HashMap<String, Cursor>>,
+    last_save: Mutex<Instant>,
+    interval: Duration,
+    snapshots: AtomicU64,
+    written: Arc<Mutex<u64>>,
+}
+
+impl SessionRecorder {
+    /// إنشاء مسجل جديد
+    pub fn new(path: impl Into<PathBuf>, session: ScanSession, interval: Duration) -> Self {
+        Self {
+            path: path.into(),
+            state: Mutex::new(session),
+            cursors: Mutex::new(HashMap::new()),
+            last_save: Mutex::new(Instant::now()),
+            interval,
+            snapshots: AtomicU64::new(0),
+            written: Arc::new(Mutex::new(0)),
+        }
+    }
+    
+    /// مسار ملف الجلسة
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+    
+    /// تنفيذ دالة على حالة الجلسة
+    pub fn with_state<T>(&self, f: impl FnOnce(&mut ScanSession) -> T) -> T {
+        f(&mut self.state.lock())
+    }
+    
+    /// تسجيل زوج مسحوب من البث بترتيبه، وfalse إن كان مكتملًا في جلسة سابقة
+    pub fn dispatch(&self, username: &str, password: &str) -> bool {
+        let mut cursors = self.cursors.lock();
+        let cursor = cursors.entry(username.to_string()).or_insert_with(|| {
+            Cursor::new(self.state.lock().offsets.get(username).copied().unwrap_or(0))
+        });
+        
+        cursor.dispatch(password)
+    }
+    
+    /// الأزواج التي فشلت بخطأ شبكة وتُعاد قبل بقية البث
+    pub fn retry_pairs(&self) -> Vec<(String, String)> {
+        self.state.lock().retry.clone()
+    }
+    
+    /// تسجيل نتيجة محاولة وحفظ نقطة تحقق في الخلفية عند انقضاء الفاصل
+    pub fn record(&self, result: &ScanResult) {
+        {
+            let mut cursors = self.cursors.lock();
+            let mut state = self.state.lock();
+            let pair = (result.username.clone(), result.password.clone());
+            
+            let completed = cursors
+                .get_mut(&result.username)
+                .and_then(|cursor| cursor.complete(&result.password).then_some(cursor));
+            match completed {
+                Some(cursor) => {
+                    state.offsets.insert(result.username.clone(), cursor.offset);
+                    // أخطاء الشبكة لا توقف تقدم الإزاحة بل تُعاد أولًا عند الاستئناف
+                    if result.error.is_some() && !state.retry.contains(&pair) {
+                        state.retry.push(pair);
+                    }
+                    if result.success {
+                        cursor.close();
+                    }
+                }
+                _ => {
+                    // زوج من قائمة الإعادة يخرج منها عند اكتمال اختباره
+                    if result.error.is_none() {
+                        state.retry.retain(|retry| *retry != pair);
+                    }
+                }
+            }
+            
+            if result.success {
+                // كلمة المرور الكاملة في الخزنة، والجلسة تحتفظ بها مخفية ما لم يُطلب النص الواضح
+                let mut found = result.clone();
+                if !state.config.plaintext {
+                    found.password = mask_password(&found.password);
+                }
+                state.found.push(found);
+            }
+        }
+        
+        let due = {
+            let mut last_save = self.last_save.lock();
+            let due = last_save.elapsed() >= self.interval;
+            if due {
+                *last_save = Instant::now();
+            }
+            due
+        };
+        if due {
+            self.save_in_background();
+        }
+    }
+    
+    /// لقطة مرقمة من الحالة الحالية
+    fn snapshot(&self) -> Result<(u64, String)> {
+        let mut state = self.state.lock();
+        let seq = self.snapshots.fetch_add(1, Ordering::Relaxed) + 1;
+        Ok((seq, state.snapshot()?))
+    }
+    
+    /// حفظ نقطة تحقق دون حجز خيط المهمة الحالية
+    fn save_in_background(&self) {
+        let (seq, content) = match self.snapshot() {
+            Ok(snapshot) => snapshot,
+            Err(e) => {
+                tracing::warn!(path = %self.path.display(), "فشل في حفظ نقطة التحقق: {}", e);
+                return;
+            }
+        };
+        
+        let path = self.path.clone();
+        let written = Arc::clone(&self.written);
+        let save = move || {
+            if let Err(e) = write_snapshot(&path, &written, seq, &content) {
+                tracing::warn!(path = %path.display(), "فشل في حفظ نقطة التحقق: {}", e);
+            }
+        };
+        
+        match tokio::runtime::Handle::try_current() {
+            Ok(handle) => {
+                handle.spawn_blocking(save);
+            }
+            Err(_) => save(),
+        }
+    }
+    
+    /// حفظ الحالة الحالية فورًا
+    pub fn checkpoint(&self) -> Result<()> {
+        *self.last_save.lock() = Instant::now();
+        let (seq, content) = self.snapshot()?;
+        write_snapshot(&self.path, &self.written, seq, &content)
+    }
+    
+    /// تعليم الجلسة كمكتملة وحفظها
+    pub fn finish(&self) -> Result<()> {
+        self.state.lock().finished = true;
+        self.checkpoint()
+    }
+    
+    /// بيانات الاعتماد المكتشفة في الجلسة
+    pub fn found(&self) -> Vec<ScanResult> {
+        self.state.lock().found.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_cursor_advances_over_contiguous_completions() {
+        let mut cursor = Cursor::new(1);
+        
+        // الزوج الأول اكتمل في جلسة سابقة
+        assert!(!cursor.dispatch("123456"));
+        for password in ["password", "admin", "password"] {
+            assert!(cursor.dispatch(password));
+        }
+        
+        // الاكتمال خارج الترتيب لا يتجاوز زوجًا جاريًا
+        assert!(cursor.complete("admin"));
+        assert_eq!(cursor.offset, 1);
+        assert!(cursor.complete("password"));
+        assert_eq!(cursor.offset, 3);
+        assert!(cursor.complete("password"));
+        assert_eq!(cursor.offset, 4);
+        assert!(!cursor.complete("password"));
+    }
+    
+    /// جلسة لمستخدم واحد وأربع كلمات مرور
+    fn session() -> ScanSession {
+        serde_json::from_value(serde_json::json!({
+            "version": SESSION_VERSION,
+            "config": {
+                "url": "https://target.com/login",
+                "user": "admin",
+                "password_file": "pass.txt",
+                "combo_file": null,
+                "threads": 10,
+                "timeout": 30,
+                "mode": "normal",
+                "rate_limit": null,
+                "output": null,
+                "format": null,
+                "proxy": null,
+                "proxy_file": null,
+                "rate_limit_per_proxy": null,
+                "race_connections": false
+            },
+            "created_at": "2024-01-01T00:00:00Z",
+            "updated_at": "2024-01-01T00:00:00Z",
+            "user_count": 1,
+            "password_count": 4,
+            "total_attempts": 4,
+            "offsets": {},
+            "found": [],
+            "finished": false
+        }))
+        .unwrap()
+    }
+    
+    #[test]
+    fn test_progress_percentage() {
+        let mut session = session();
+        
+        session.offsets.insert("admin".to_string(), 1);
+        assert!((session.progress() - 25.0).abs() < f64::EPSILON);
+        
+        session.finished = true;
+        assert!((session.progress() - 100.0).abs() < f64::EPSILON);
+    }
+    
+    #[test]
+    fn test_found_passwords_masked_without_plaintext() {
+        let found = ScanResult {
+            username: "admin".to_string(),
+            password: "hunter2".to_string(),
+            success: true,
+            ..Default::default()
+        };
+        
+        let recorder = SessionRecorder::new("unused.json", session(), Duration::from_secs(3600));
+        recorder.record(&found);
+        assert_eq!(recorder.found()[0].password, "h*****2");
+        
+        let mut plaintext = session();
+        plaintext.config.plaintext = true;
+        let recorder = SessionRecorder::new("unused.json", plaintext, Duration::from_secs(3600));
+        recorder.record(&found);
+        assert_eq!(recorder.found()[0].password, "hunter2");
+    }
+}
@@ -0,0 +1,284 @@
+//! الوضع التفاعلي
+//! حالة وأوامر `redfox shell` (set/unset/show/run) على غرار msfconsole
+
+use anyhow::{Result, Context};
+use serde_json::{Map, Value};
+
+use crate::scanner::ScanResult;
+use crate::session::SessionConfig;
+
+/// نص المساعدة
+pub const HELP: &str = "\
+الأوامر:
+  set <OPTION> <VALUE>     تعيين خيار (target, users, passwords, threads, mode, proxy...)
+  unset <OPTION>           إزالة خيار
+  show options             عرض الخيارات الحالية
+  show results             عرض نتائج آخر فحص
+  run                      تنفيذ الفحص بالخيارات الحالية
+  save <FILE> [FORMAT]     حفظ نتائج آخر فحص في تقرير
+  help                     عرض هذه المساعدة
+  exit                     الخروج
+
+الخيارات المتداخلة بالنقطة: set success_criteria.status_codes [302]";
+
+/// أسماء مختصرة لخيارات SessionConfig
+const ALIASES: &[(&str, &str)] = &[
+    ("target", "url"),
+    ("users", "user"),
+    ("username", "user"),
+    ("passwords", "password_file"),
+    ("wordlist", "password_file"),
+    ("combo", "combo_file"),
+    ("success_match", "success_criteria.body_match"),
+    ("failure_match", "success_criteria.body_not_match"),
+    ("success_status", "success_criteria.status_codes"),
+    ("success_redirect", "success_criteria.redirect_match"),
+];
+
+/// أمر مُحلل من سطر الإدخال
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ShellCommand {
+    /// تعيين خيار
+    Set {
+        /// اسم الخيار
+        key: String,
+        /// القيمة
+        value: String,
+    },
+    /// إزالة خيار
+    Unset(String),
+    /// عرض الخيارات
+    ShowOptions,
+    /// عرض النتائج
+    ShowResults,
+    /// تنفيذ الفحص
+    Run,
+    /// حفظ النتائج
+    Save {
+        /// ملف التقرير
+        output: String,
+        /// التنسيق
+        format: Option<String>,
+    },
+    /// المساعدة
+    Help,
+    /// الخروج
+    Exit,
+}
+
+impl ShellCommand {
+    /// تحليل سطر إدخال (None للسطر الفارغ أو التعليق)
+    pub fn parse(line: &str) -> Result<Option<Self>> {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            return Ok(None);
+        }
+        
+        let (command, rest) = line.split_once(char::is_whitespace).unwrap_or((line, ""));
+        let rest = rest.trim();
+        
+        let command = match (command.to_lowercase().as_str(), rest) {
+            ("set", rest) => {
+                let (key, value) = rest
+                    .split_once(char::is_whitespace)
+                    .ok_or_else(|| anyhow::anyhow!("الاستخدام: set <OPTION> <VALUE>"))?;
+                Self::Set { key: key.to_string(), value: value.trim().to_string() }
+            }
+            ("unset", key) if !key.is_empty() => Self::Unset(key.to_string()),
+            ("show", "options") | ("options", "") => Self::ShowOptions,
+            ("show", "results") | ("results", "") => Self::ShowResults,
+            ("run" | "exploit", "") => Self::Run,
+            ("save", rest) if !rest.is_empty() => {
+                let mut parts = rest.split_whitespace();
+                Self::Save {
+                    output: parts.next().unwrap_or_default().to_string(),
+                    format: parts.next().map(str::to_string),
+                }
+            }
+            ("help" | "?", _) => Self::Help,
+            ("exit" | "quit", "") => Self::Exit,
+            _ => return Err(anyhow::anyhow!("أمر غير معروف: {} (اكتب help)", line)),
+        };
+        
+        Ok(Some(command))
+    }
+}
+
+/// حالة الجلسة التفاعلية (تبقى بين الأوامر)
+pub struct ShellState {
+    options: Map<String, Value>,
+    
+    /// نتائج آخر فحص
+    pub results: Vec<ScanResult>,
+}
+
+impl Default for ShellState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ShellState {
+    /// حالة جديدة بالقيم الافتراضية لسطر الأوامر
+    pub fn new() -> Self {
+        let mut options = Map::new();
+        options.insert("threads".to_string(), Value::from(crate::scanner::DEFAULT_THREADS));
+        options.insert("timeout".to_string(), Value::from(crate::scanner::DEFAULT_TIMEOUT_SECS));
+        options.insert("mode".to_string(), Value::from("normal"));
+        options.insert("race_connections".to_string(), Value::Bool(false));
+        
+        Self { options, results: Vec::new() }
+    }
+    
+    /// دمج خيارات جاهزة (كائن JSON من طلب الخادم) فوق القيم الحالية
+    pub fn with_options(mut self, overrides: Map<String, Value>) -> Self {
+        self.options.extend(overrides);
+        self
+    }
+    
+    /// تعيين خيار مع التحقق من اسمه ونوع قيمته
+    pub fn set(&mut self, key: &str, value: &str) -> Result<()> {
+        let key = resolve_alias(key);
+        
+        // القيمة كـ JSON (أرقام، قيم منطقية، مصفوفات) ثم كنص إن لم يقبلها الخيار
+        let candidates = serde_json::from_str(value)
+            .ok()
+            .into_iter()
+            .chain(std::iter::once(Value::from(value)));
+        
+        for candidate in candidates {
+            let mut options = self.options.clone();
+            insert_path(&mut options, key, candidate);
+            if validate(&options, key).is_ok() {
+                self.options = options;
+                return Ok(());
+            }
+        }
+        
+        let mut options = self.options.clone();
+        insert_path(&mut options, key, Value::from(value));
+        validate(&options, key)
+    }
+    
+    /// إزالة خيار
+    pub fn unset(&mut self, key: &str) -> Result<()> {
+        let key = resolve_alias(key);
+        let mut parts: Vec<&str> = key.split('.').collect();
+        let last = parts.pop().unwrap_or_default();
+        
+        let mut map = &mut self.options;
+        for part in parts {
+            map = map
+                .get_mut(part)
+                .and_then(Value::as_object_mut)
+                .ok_or_else(|| anyhow::anyhow!("خيار غير معين: {}", key))?;
+        }
+        
+        map.remove(last)
+            .map(|_| ())
+            .ok_or_else(|| anyhow::anyhow!("خيار غير معين: {}", key))
+    }
+    
+    /// الخيارات المعينة بأسماء مسطحة (a.b) ومرتبة
+    pub fn options(&self) -> Vec<(String, String)> {
+        let mut flat = Vec::new();
+        flatten("", &self.options, &mut flat);
+        flat.sort();
+        flat
+    }
+    
+    /// إعدادات الفحص من الخيارات الحالية
+    pub fn config(&self) -> Result<SessionConfig> {
+        if !self.options.contains_key("url") {
+            return Err(anyhow::anyhow!("الهدف غير محدد (set target <URL>)"));
+        }
+        
+        serde_json::from_value(Value::Object(self.options.clone())).context("خيارات غير صالحة")
+    }
+}
+
+/// تحويل الاسم المختصر إلى اسم الخيار الفعلي
+fn resolve_alias(key: &str) -> &str {
+    ALIASES
+        .iter()
+        .find(|(alias, _)| *alias == key)
+        .map_or(key, |(_, name)| name)
+}
+
+/// إدراج قيمة في مسار منقط داخل الخريطة
+fn insert_path(options: &mut Map<String, Value>, key: &str, value: Value) {
+    match key.split_once('.') {
+        Some((head, tail)) => {
+            let entry = options
+                .entry(head.to_string())
+                .or_insert_with(|| Value::Object(Map::new()));
+            if !entry.is_object() {
+                *entry = Value::Object(Map::new());
+            }
+            if let Value::Object(inner) = entry {
+                insert_path(inner, tail, value);
+            }
+        }
+        None => {
+            options.insert(key.to_string(), value);
+        }
+    }
+}
+
+/// التحقق من أن الخيار معروف وقيمته بالنوع الصحيح (بتحويل ذهابًا وإيابًا عبر SessionConfig)
+fn validate(options: &Map<String, Value>, key: &str) -> Result<()> {
+    let mut probe = options.clone();
+    probe.entry("url").or_insert_with(|| Value::from("http://probe"));
+    
+    let config: SessionConfig = serde_json::from_value(Value::Object(probe))
+        .context(format!("قيمة غير صالحة للخيار: {}", key))?;
+    let known = serde_json::to_value(config)?;
+    
+    known
+        .pointer(&format!("/{}", key.replace('.', "/")))
+        .filter(|value| !value.is_null())
+        .map(|_| ())
+        .ok_or_else(|| anyhow::anyhow!("خيار غير معروف: {}", key))
+}
+
+/// تسطيح الخريطة المتداخلة إلى أزواج (a.b, value)
+fn flatten(prefix: &str, options: &Map<String, Value>, out: &mut Vec<(String, String)>) {
+    for (key, value) in options {
+        let name = if prefix.is_empty() { key.clone() } else { format!("{}.{}", prefix, key) };
+        match value {
+            Value::Object(inner) => flatten(&name, inner, out),
+            Value::String(text) => out.push((name, text.clone())),
+            other => out.push((name, other.to_string())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_set_options_build_config() {
+        let mut state = ShellState::new();
+        assert!(state.config().is_err());
+        
+        state.set("target", "https://target.com/login").unwrap();
+        state.set("users", "admin").unwrap();
+        state.set("wordlist", "pass.txt").unwrap();
+        state.set("threads", "5").unwrap();
+        state.set("success_status", "[302]").unwrap();
+        
+        assert!(state.set("threads", "many").is_err());
+        assert!(state.set("no_such_option", "1").is_err());
+        
+        let config = state.config().unwrap();
+        assert_eq!(config.url, "https://target.com/login");
+        assert_eq!(config.user.as_deref(), Some("admin"));
+        assert_eq!(config.threads, 5);
+        assert_eq!(config.success_criteria.status_codes, vec![302]);
+        
+        state.unset("users").unwrap();
+        assert!(state.config().unwrap().user.is_none());
+        assert_eq!(ShellCommand::parse("show options").unwrap(), Some(ShellCommand::ShowOptions));
+    }
+}
@@ -0,0 +1,269 @@
+//! التتبع والسجلات
+//! مشترك tracing الموحد: مستويات -v، نطاقات لكل هدف ومحاولة، وطبقات قابلة للإضافة (TUI، الخادم)
+
+use std::fmt;
+use std::io::Write as _;
+use anyhow::{Context, Result};
+use colored::Colorize;
+use tracing::field::{Field, Visit};
+use tracing::{Event, Level, Subscriber};
+use tracing_subscriber::fmt::format::Writer;
+use tracing_subscriber::fmt::writer::MakeWriterExt;
+use tracing_subscriber::fmt::{FmtContext, FormatEvent, FormatFields};
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::registry::LookupSpan;
+use tracing_subscriber::util::SubscriberInitExt;
+use tracing_subscriber::filter::Targets;
+use tracing_subscriber::{EnvFilter, Layer, Registry};
+
+use crate::output::OutputPolicy;
+
+/// تنسيق السجلات
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum LogFormat {
+    /// نص ملون بوسوم المستوى
+    #[default]
+    Text,
+    /// كائن JSON لكل حدث (الوقت، المستوى، الوحدة، الرسالة، الحقول، النطاقات)
+    Json,
+}
+
+/// طبقة مشترك إضافية (واجهة TUI، بث أحداث الخادم...)
+pub type BoxedLayer = Box<dyn Layer<Registry> + Send + Sync>;
+
+/// اسم الخدمة في التتبعات المصدرة
+const SERVICE_NAME: &str = "redfox";
+
+/// قيمة الحقل kind التي تعرض الحدث كرسالة نجاح
+const SUCCESS_KIND: &str = "success";
+
+/// مرشح المستويات لسياسة المخرجات (RUST_LOG يتقدم عليه)
+///
+/// 0 تحذيرات، -v معلومات، -vv تصحيح لوحدات الأداة، -vvv تتبع للجميع بما فيها المكتبات الخارجية
+pub fn directives(policy: &OutputPolicy) -> String {
+    // سجلات JSON تذهب إلى stderr فلا تختلط بمخرجات stdout بتنسيق JSON
+    let stdout_json = policy.format == crate::output::OutputFormat::Json && policy.log_format == LogFormat::Text;
+    if policy.quiet || stdout_json {
+        return "error".to_string();
+    }
+    
+    let crate_name = env!("CARGO_CRATE_NAME");
+    match policy.verbosity {
+        0 => "warn".to_string(),
+        1 => format!("warn,{}=info", crate_name),
+        2 => format!("warn,{}=debug", crate_name),
+        _ => "trace".to_string(),
+    }
+}
+
+/// تثبيت المشترك العام (طبقة الطرفية أو JSON + الطبقات الإضافية، مع تحويل سجلات log إلى tracing)
+pub fn init(policy: &OutputPolicy, layers: Vec<BoxedLayer>) {
+    let filter = EnvFilter::try_from_default_env()
+        .unwrap_or_else(|_| EnvFilter::new(directives(policy)));
+    
+    let console = match policy.log_format {
+        LogFormat::Text => tracing_subscriber::fmt::layer()
+            .event_format(ConsoleFormat)
+            .with_writer(std::io::stderr.with_max_level(Level::ERROR).or_else(std::io::stdout))
+            .with_filter(filter)
+            .boxed(),
+        LogFormat::Json => tracing_subscriber::fmt::layer()
+            .json()
+            .with_current_span(true)
+            .with_span_list(true)
+            .with_writer(std::io::stderr)
+            .with_filter(filter)
+            .boxed(),
+    };
+    
+    let mut all = vec![console];
+    all.extend(layers);
+    
+    // لا يفشل التثبيت إلا بوجود مشترك عام سابق، فيصل التحذير إليه
+    if let Err(e) = tracing_subscriber::registry().with(all).try_init() {
+        tracing::warn!("فشل في تهيئة السجلات: {}", e);
+    }
+}
+
+/// طبقة تصدير النطاقات عبر OTLP (نطاق لكل فحص ومحاولة، ترسل على دفعات)
+///
+/// تتطلب وقت تشغيل Tokio؛ مستوى النطاقات مستقل عن -v حتى تصل نطاقات المحاولات دائمًا
+pub fn otlp_layer(endpoint: &str) -> Result<BoxedLayer> {
+    use opentelemetry::trace::TracerProvider as _;
+    use opentelemetry::KeyValue;
+    use opentelemetry_otlp::WithExportConfig;
+    
+    let provider = opentelemetry_otlp::new_pipeline()
+        .tracing()
+        .with_exporter(
+            opentelemetry_otlp::new_exporter()
+                .tonic()
+                .with_endpoint(endpoint),
+        )
+        .with_trace_config(
+            opentelemetry_sdk::trace::Config::default().with_resource(opentelemetry_sdk::Resource::new(vec![
+                KeyValue::new("service.name", SERVICE_NAME),
+                KeyValue::new("service.version", env!("CARGO_PKG_VERSION")),
+            ])),
+        )
+        .install_batch(opentelemetry_sdk::runtime::Tokio)
+        .context(format!("فشل في تهيئة مصدر OTLP: {}", endpoint))?;
+    let tracer = provider.tracer(SERVICE_NAME);
+    // المزود العام حتى يفرغ shutdown الدفعة المتبقية
+    opentelemetry::global::set_tracer_provider(provider);
+    
+    let filter = Targets::new().with_target(env!("CARGO_CRATE_NAME"), Level::DEBUG);
+    
+    Ok(tracing_opentelemetry::layer()
+        .with_tracer(tracer)
+        .with_filter(filter)
+        .boxed())
+}
+
+/// تفريغ النطاقات المتبقية في الدفعة قبل الخروج
+pub fn shutdown() {
+    opentelemetry::global::shutdown_tracer_provider();
+}
+
+/// وسم المستوى الملون
+fn tag(level: Level) -> colored::ColoredString {
+    match level {
+        Level::ERROR => "[-]".bright_red(),
+        Level::WARN => "[!]".bright_yellow(),
+        Level::INFO => "[*]".bright_blue(),
+        Level::DEBUG => "[d]".bright_magenta(),
+        Level::TRACE => "[t]".bright_black(),
+    }
+}
+
+/// حقول الحدث: الرسالة والنوع وبقية الحقول المهيكلة
+#[derive(Default)]
+struct EventFields {
+    message: String,
+    kind: Option<String>,
+    extra: Vec<(&'static str, String)>,
+}
+
+impl Visit for EventFields {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        match field.name() {
+            "message" => self.message = value.to_string(),
+            "kind" => self.kind = Some(value.to_string()),
+            name => self.extra.push((name, value.to_string())),
+        }
+    }
+    
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        match field.name() {
+            "message" => self.message = format!("{:?}", value),
+            "kind" => self.kind = Some(format!("{:?}", value)),
+            name => self.extra.push((name, format!("{:?}", value))),
+        }
+    }
+}
+
+/// تنسيق الطرفية: وسم المستوى والرسالة، والحقول ونطاقات السياق في مستويي التصحيح والتتبع
+struct ConsoleFormat;
+
+impl<S, N> FormatEvent<S, N> for ConsoleFormat
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+    N: for<'a> FormatFields<'a> + 'static,
+{
+    fn format_event(&self, ctx: &FmtContext<'_, S, N>, mut writer: Writer<'_>, event: &Event<'_>) -> fmt::Result {
+        let mut fields = EventFields::default();
+        event.record(&mut fields);
+        let level = *event.metadata().level();
+        
+        if fields.kind.as_deref() == Some(SUCCESS_KIND) {
+            write!(writer, "{} {}", "[+]".bright_green(), fields.message.green())?;
+        } else {
+            let message = match level {
+                Level::ERROR => fields.message.red(),
+                Level::WARN => fields.message.yellow(),
+                Level::INFO => fields.message.normal(),
+                _ => fields.message.bright_black(),
+            };
+            write!(writer, "{} {}", tag(level), message)?;
+        }
+        
+        if level >= Level::DEBUG {
+            for (name, value) in &fields.extra {
+                write!(writer, " {}={}", name.bright_black(), value)?;
+            }
+            if let Some(scope) = ctx.event_scope() {
+                let spans: Vec<_> = scope.from_root().map(|span| span.name()).collect();
+                if !spans.is_empty() {
+                    write!(writer, " {}", format!("[{}]", spans.join(" > ")).bright_black())?;
+                }
+            }
+        }
+        
+        writeln!(writer)
+    }
+}
+
+/// واجهة رسائل المشغل فوق tracing (تبقي أسلوب logger.info(...) في main والماسح)
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Logger;
+
+impl Logger {
+    /// رسالة معلومات (-v)
+    pub fn info(&self, message: &str) {
+        tracing::info!("{}", message);
+    }
+    
+    /// رسالة تصحيح (-vv)
+    pub fn debug(&self, message: &str) {
+        tracing::debug!("{}", message);
+    }
+    
+    /// رسالة تتبع (-vvv)
+    pub fn trace(&self, message: &str) {
+        tracing::trace!("{}", message);
+    }
+    
+    /// رسالة نجاح (بمستوى التحذير حتى تظهر افتراضيًا)
+    pub fn success(&self, message: &str) {
+        tracing::warn!(kind = SUCCESS_KIND, "{}", message);
+    }
+    
+    /// رسالة تحذير
+    pub fn warn(&self, message: &str) {
+        tracing::warn!("{}", message);
+    }
+    
+    /// رسالة خطأ (على stderr)
+    pub fn error(&self, message: &str) {
+        tracing::error!("{}", message);
+    }
+    
+    /// مخرجات الأمر نفسها (جداول، قوائم، ملخصات) على stdout بغض النظر عن مستوى السجلات
+    ///
+    /// أخطاء الكتابة تُهمل حتى لا ينهار الأمر عند إغلاق الأنبوب (`| head`)
+    pub fn print(&self, output: &str) {
+        let _ = writeln!(std::io::stdout().lock(), "{}", output);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::output::OutputFormat;
+    
+    #[test]
+    fn test_verbosity_directives() {
+        let policy = |verbosity, quiet| OutputPolicy { quiet, verbosity, ..Default::default() };
+        let crate_name = env!("CARGO_CRATE_NAME");
+        
+        assert_eq!(directives(&policy(0, false)), "warn");
+        assert_eq!(directives(&policy(1, false)), format!("warn,{}=info", crate_name));
+        assert_eq!(directives(&policy(2, false)), format!("warn,{}=debug", crate_name));
+        assert_eq!(directives(&policy(5, false)), "trace");
+        assert_eq!(directives(&policy(3, true)), "error");
+        
+        let json = OutputPolicy { format: OutputFormat::Json, verbosity: 1, ..Default::default() };
+        assert_eq!(directives(&json), "error");
+        assert_eq!(directives(&OutputPolicy { log_format: LogFormat::Json, ..json }), format!("warn,{}=info", crate_name));
+    }
+}
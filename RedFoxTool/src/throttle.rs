@@ -0,0 +1,223 @@
+//! التحكم في معدل الطلبات
+//! يوفر محدد معدل بخوارزمية دلو الرموز (Token Bucket) وسجل حدود لكل مضيف
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use parking_lot::Mutex;
+use rand::Rng;
+use tokio::sync::Semaphore;
+
+/// حالة دلو الرموز
+struct BucketState {
+    tokens: f64,
+    last_refill: Instant,
+}
+
+/// محدد معدل بخوارزمية دلو الرموز
+pub struct RateLimiter {
+    rate: f64,
+    capacity: f64,
+    state: Mutex<BucketState>,
+}
+
+impl RateLimiter {
+    /// إنشاء محدد معدل جديد (طلبات/ثانية)
+    pub fn new(requests_per_second: u32) -> Self {
+        let rate = f64::from(requests_per_second.max(1));
+        
+        Self {
+            rate,
+            capacity: rate,
+            state: Mutex::new(BucketState {
+                tokens: rate,
+                last_refill: Instant::now(),
+            }),
+        }
+    }
+    
+    /// المعدل المسموح (طلبات/ثانية)
+    pub fn rate(&self) -> u32 {
+        self.rate as u32
+    }
+    
+    /// محاولة أخذ رمز دون انتظار
+    pub fn try_acquire(&self) -> bool {
+        self.take_or_wait().is_none()
+    }
+    
+    /// انتظار حتى يتوفر رمز ثم أخذه
+    pub async fn acquire(&self) {
+        while let Some(wait) = self.take_or_wait() {
+            tokio::time::sleep(wait).await;
+        }
+    }
+    
+    /// أخذ رمز إن توفر، وإلا إرجاع مدة الانتظار اللازمة
+    fn take_or_wait(&self) -> Option<Duration> {
+        let mut state = self.state.lock();
+        
+        // إعادة ملء الدلو حسب الوقت المنقضي
+        let now = Instant::now();
+        let elapsed = now.duration_since(state.last_refill).as_secs_f64();
+        state.tokens = (state.tokens + elapsed * self.rate).min(self.capacity);
+        state.last_refill = now;
+        
+        if state.tokens >= 1.0 {
+            state.tokens -= 1.0;
+            None
+        } else {
+            Some(Duration::from_secs_f64((1.0 - state.tokens) / self.rate))
+        }
+    }
+}
+
+/// تأخير عشوائي موزع بانتظام بين حدين (لتجنب أنماط التوقيت القابلة للكشف)
+pub fn random_delay(min: Duration, max: Duration) -> Duration {
+    if max <= min {
+        return min;
+    }
+    
+    rand::thread_rng().gen_range(min..=max)
+}
+
+/// سلطة الهدف (host:port بأحرف صغيرة) مع المنفذ الافتراضي للمخطط إن لم يُحدد
+pub fn authority(target: &str) -> String {
+    if let Some(url) = target.contains("://").then(|| url::Url::parse(target).ok()).flatten() {
+        if let Some(host) = url.host_str() {
+            return match url.port_or_known_default() {
+                Some(port) => format!("{}:{}", host, port),
+                None => host.to_string(),
+            }
+            .to_lowercase();
+        }
+    }
+    
+    // host:port بدون مخطط (وحدات البروتوكولات)
+    target
+        .split_once("://")
+        .map_or(target, |(_, rest)| rest)
+        .split(['/', '?', '#'])
+        .next()
+        .unwrap_or_default()
+        .to_lowercase()
+}
+
+/// حدود مضيف واحد يتقاسمها كل فحص موجه إليه (معدل الطلبات والتزامن)
+pub struct HostLimiter {
+    authority: String,
+    rate: Option<Arc<RateLimiter>>,
+    slots: Arc<Semaphore>,
+}
+
+impl HostLimiter {
+    /// سلطة المضيف
+    pub fn authority(&self) -> &str {
+        &self.authority
+    }
+    
+    /// محدد المعدل المشترك (إن وُجد حد)
+    pub fn rate_limiter(&self) -> Option<Arc<RateLimiter>> {
+        self.rate.clone()
+    }
+    
+    /// فتحات التزامن المشتركة
+    pub fn slots(&self) -> Arc<Semaphore> {
+        Arc::clone(&self.slots)
+    }
+}
+
+/// سجل حدود لكل مضيف مفهرس بالسلطة (حتى لا تتجاوز الأهداف المتعددة على نفس المضيف الحد مجتمعة)
+pub struct HostLimiters {
+    rate_limit: Option<u32>,
+    concurrency: usize,
+    hosts: Mutex<HashMap<String, Arc<HostLimiter>>>,
+}
+
+impl HostLimiters {
+    /// سجل جديد بحد المعدل والتزامن المطبق على كل مضيف
+    pub fn new(rate_limit: Option<u32>, concurrency: usize) -> Self {
+        Self {
+            rate_limit,
+            concurrency: concurrency.max(1),
+            hosts: Mutex::new(HashMap::new()),
+        }
+    }
+    
+    /// حدود مضيف الهدف (تُنشأ عند أول طلب لها)
+    pub fn get(&self, target: &str) -> Arc<HostLimiter> {
+        let authority = authority(target);
+        
+        let mut hosts = self.hosts.lock();
+        let limiter = hosts.entry(authority.clone()).or_insert_with(|| {
+            Arc::new(HostLimiter {
+                authority,
+                rate: self.rate_limit.map(|rps| Arc::new(RateLimiter::new(rps))),
+                slots: Arc::new(Semaphore::new(self.concurrency)),
+            })
+        });
+        
+        Arc::clone(limiter)
+    }
+    
+    /// عدد المضيفين المسجلين
+    pub fn len(&self) -> usize {
+        self.hosts.lock().len()
+    }
+    
+    /// هل السجل فارغ
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_burst_is_capped_by_rate() {
+        let limiter = RateLimiter::new(5);
+        
+        let granted = (0..10).filter(|_| limiter.try_acquire()).count();
+        
+        assert_eq!(granted, 5);
+    }
+    
+    #[tokio::test]
+    async fn test_acquire_waits_for_refill() {
+        let limiter = RateLimiter::new(10);
+        while limiter.try_acquire() {}
+        
+        let start = Instant::now();
+        limiter.acquire().await;
+        
+        assert!(start.elapsed() >= Duration::from_millis(50));
+    }
+    
+    #[test]
+    fn test_random_delay_within_bounds() {
+        let (min, max) = (Duration::from_millis(10), Duration::from_millis(20));
+        
+        for _ in 0..100 {
+            let delay = random_delay(min, max);
+            assert!(delay >= min && delay <= max);
+        }
+        assert_eq!(random_delay(max, min), max);
+    }
+    
+    #[test]
+    fn test_host_limiters_shared_per_authority() {
+        let limits = HostLimiters::new(Some(10), 4);
+        
+        let admin = limits.get("https://Site.com/admin/login");
+        let wp = limits.get("https://site.com:443/wp-login.php");
+        let ssh = limits.get("site.com:22");
+        
+        assert!(Arc::ptr_eq(&admin, &wp));
+        assert_eq!(admin.authority(), "site.com:443");
+        assert_eq!(ssh.authority(), "site.com:22");
+        assert_eq!(limits.len(), 2);
+        assert_eq!(admin.slots().available_permits(), 4);
+    }
+}
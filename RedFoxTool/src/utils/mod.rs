@@ -0,0 +1,6 @@
+//! أدوات مساعدة عامة
+//! فحص بيئة التشغيل، اكتشاف قوائم الكلمات المثبتة، والتحقق من التحديثات
+
+pub mod system;
+pub mod updater;
+pub mod wordlists;
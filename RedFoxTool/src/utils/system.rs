@@ -0,0 +1,45 @@
+//! فحص بيئة التشغيل
+//! صلاحيات المستخدم وحدود النظام التي تؤثر على الفحص عالي التزامن
+
+/// أقل حد مقترح لعدد الملفات المفتوحة (كل اتصال متزامن يستهلك واصفًا)
+pub const RECOMMENDED_OPEN_FILES: u64 = 4096;
+
+/// هل تعمل العملية بصلاحيات root
+#[cfg(unix)]
+pub fn is_root() -> bool {
+    // SAFETY: geteuid لا تفشل ولا تلمس الذاكرة
+    unsafe { libc::geteuid() == 0 }
+}
+
+/// هل تعمل العملية بصلاحيات root
+#[cfg(not(unix))]
+pub fn is_root() -> bool {
+    false
+}
+
+/// الحد الحالي لعدد الملفات المفتوحة (None إن تعذرت قراءته)
+#[cfg(unix)]
+pub fn open_files_limit() -> Option<u64> {
+    let mut limit = libc::rlimit { rlim_cur: 0, rlim_max: 0 };
+    // SAFETY: المؤشر يشير إلى بنية محلية صالحة طوال الاستدعاء
+    let status = unsafe { libc::getrlimit(libc::RLIMIT_NOFILE, &mut limit) };
+    (status == 0).then_some(limit.rlim_cur)
+}
+
+/// الحد الحالي لعدد الملفات المفتوحة (None إن تعذرت قراءته)
+#[cfg(not(unix))]
+pub fn open_files_limit() -> Option<u64> {
+    None
+}
+
+/// التحقق من المتطلبات والتحذير مما قد يبطئ الفحص أو يفشله
+pub fn check_requirements() {
+    if let Some(limit) = open_files_limit().filter(|limit| *limit < RECOMMENDED_OPEN_FILES) {
+        tracing::warn!(
+            limit,
+            "حد الملفات المفتوحة منخفض ({}): ارفعه بـ ulimit -n {} قبل الفحص بخيوط كثيرة",
+            limit,
+            RECOMMENDED_OPEN_FILES
+        );
+    }
+}
@@ -0,0 +1,62 @@
+//! التحقق من التحديثات
+//! مقارنة الإصدار الحالي بآخر إصدار منشور على GitHub (التحديث نفسه عبر scripts/update.sh)
+
+use std::time::Duration;
+use anyhow::{Context, Result};
+
+/// واجهة آخر إصدار منشور
+const LATEST_RELEASE_URL: &str = "https://api.github.com/repos/redfox-security/redfox-tool/releases/latest";
+
+/// مهلة طلب التحقق
+const CHECK_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// آخر إصدار منشور إن كان أحدث من الإصدار الحالي
+pub async fn check_for_updates() -> Result<Option<String>> {
+    let client = reqwest::Client::builder()
+        .timeout(CHECK_TIMEOUT)
+        .user_agent(concat!("RedFoxTool/", env!("CARGO_PKG_VERSION")))
+        .build()?;
+    
+    let release: serde_json::Value = client
+        .get(LATEST_RELEASE_URL)
+        .send()
+        .await
+        .context("فشل في الاتصال بخادم الإصدارات")?
+        .error_for_status()?
+        .json()
+        .await
+        .context("رد غير صالح من خادم الإصدارات")?;
+    
+    let latest = release["tag_name"]
+        .as_str()
+        .context("رد خادم الإصدارات بدون tag_name")?
+        .trim_start_matches('v');
+    
+    Ok(is_newer(latest, env!("CARGO_PKG_VERSION")).then(|| latest.to_string()))
+}
+
+/// مقارنة إصدارين بصيغة x.y.z (الأجزاء غير الرقمية تعامل كصفر)
+fn is_newer(candidate: &str, current: &str) -> bool {
+    let parts = |version: &str| -> Vec<u64> {
+        version
+            .split(['.', '-', '+'])
+            .take(3)
+            .map(|part| part.parse().unwrap_or(0))
+            .collect()
+    };
+    
+    parts(candidate) > parts(current)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_is_newer_compares_numerically() {
+        assert!(is_newer("1.10.0", "1.9.3"));
+        assert!(is_newer("2.0.0", "1.0.0"));
+        assert!(!is_newer("1.0.0", "1.0.0"));
+        assert!(!is_newer("0.9.9", "1.0.0"));
+    }
+}
@@ -0,0 +1,40 @@
+//! قوائم الكلمات المثبتة
+//! المجلدات الشائعة التي يبحث فيها resolve_path، وسردها للأمر list-wordlists وإكمال الطرفية
+
+use std::collections::BTreeSet;
+use std::path::PathBuf;
+
+/// المجلدات الشائعة لقوائم الكلمات بترتيب البحث
+pub const SEARCH_DIRS: &[&str] = &[
+    "/usr/share/wordlists",
+    "/usr/share/seclists",
+    "/usr/share/redfox/wordlists",
+    "~/.redfox/wordlists",
+];
+
+/// امتدادات الملفات المعروضة كقوائم كلمات (بما فيها المضغوطة)
+const EXTENSIONS: &[&str] = &["txt", "lst", "dic", "gz", "zst", "bz2"];
+
+/// أسماء قوائم الكلمات المتاحة في المجلدات الشائعة (مرتبة وبدون تكرار، تُمرر كما هي إلى -P)
+pub fn list_available() -> Vec<String> {
+    let mut names = BTreeSet::new();
+    
+    for dir in SEARCH_DIRS {
+        let dir = PathBuf::from(shellexpand::tilde(dir).as_ref());
+        let Ok(entries) = std::fs::read_dir(&dir) else { continue };
+        
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let listed = path.is_file()
+                && path
+                    .extension()
+                    .and_then(|e| e.to_str())
+                    .is_some_and(|e| EXTENSIONS.iter().any(|known| e.eq_ignore_ascii_case(known)));
+            if let (true, Some(name)) = (listed, path.file_name().and_then(|n| n.to_str())) {
+                names.insert(name.to_string());
+            }
+        }
+    }
+    
+    names.into_iter().collect()
+}
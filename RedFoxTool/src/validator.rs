@@ -3,18 +3,31 @@
 
 use std::net::IpAddr;
 use std::str::FromStr;
+use std::time::Duration;
+use colored::Colorize;
 use url::Url;
-use regex::Regex;
-use anyhow::{Result, Context};
+use anyhow::{Context, Result};
+
+use crate::http_client::EvasionProfile;
+use crate::telemetry::Logger;
 
 /// نتيجة التحقق
 #[derive(Debug, Clone)]
 pub struct ValidationResult {
+    /// لا أخطاء (التحذيرات لا تبطل النتيجة)
     pub is_valid: bool,
+    /// الأخطاء المانعة
     pub errors: Vec<String>,
+    /// التحذيرات
     pub warnings: Vec<String>,
 }
 
+impl Default for ValidationResult {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl ValidationResult {
     /// إنشاء نتيجة جديدة
     pub fn new() -> Self {
@@ -44,21 +57,21 @@ impl ValidationResult {
     /// عرض النتيجة
     pub fn display(&self) {
         if !self.errors.is_empty() {
-            println!("{}", "أخطاء:".bright_red());
+            Logger.print(&"أخطاء:".bright_red().to_string());
             for error in &self.errors {
-                println!("  • {}", error);
+                Logger.print(&format!("  • {}", error));
             }
         }
         
         if !self.warnings.is_empty() {
-            println!("{}", "تحذيرات:".bright_yellow());
+            Logger.print(&"تحذيرات:".bright_yellow().to_string());
             for warning in &self.warnings {
-                println!("  • {}", warning);
+                Logger.print(&format!("  • {}", warning));
             }
         }
         
         if self.is_valid {
-            println!("{}", "التحقق ناجح!".bright_green());
+            Logger.print(&"التحقق ناجح!".bright_green().to_string());
         }
     }
 }
@@ -83,7 +96,7 @@ pub async fn validate_url(url: &str) -> Result<ValidationResult> {
             
             // التحقق من المنفذ إذا كان موجودًا
             if let Some(port) = parsed_url.port() {
-                if port == 0 || port > 65535 {
+                if port == 0 {
                     result.add_error(format!("رقم المنفذ غير صالح: {}", port));
                 }
             }
@@ -152,7 +165,7 @@ pub fn validate_ip(ip_str: &str) -> ValidationResult {
                 result.add_warning("عنوان IP هو loopback (127.0.0.1)".to_string());
             }
             
-            if ip.is_private() {
+            if matches!(ip, IpAddr::V4(v4) if v4.is_private()) {
                 result.add_warning("عنوان IP خاص (private)".to_string());
             }
             
@@ -182,11 +195,10 @@ pub fn validate_proxy(proxy_url: &str) -> ValidationResult {
     
     let mut matched = false;
     for (prefix, default_port) in patterns {
-        if proxy_url.starts_with(prefix) {
+        if let Some(rest) = proxy_url.strip_prefix(prefix) {
             matched = true;
             
-            // استخراج الجزء بعد البروتوكول
-            let rest = &proxy_url[prefix.len()..];
+            // الجزء بعد البروتوكول
             
             // التحقق من وجود المنفذ
             if !rest.contains(':') {
@@ -196,7 +208,7 @@ pub fn validate_proxy(proxy_url: &str) -> ValidationResult {
                 let parts: Vec<&str> = rest.split(':').collect();
                 if parts.len() == 2 {
                     if let Ok(port) = parts[1].parse::<u16>() {
-                        if port == 0 || port > 65535 {
+                        if port == 0 {
                             result.add_error(format!("رقم المنفذ غير صالح: {}", port));
                         }
                     } else {
@@ -363,4 +375,142 @@ pub async fn validate_target(url: &str, threads: usize, timeout: u64) -> Result<
     }
     
     Ok(result)
-}
\ No newline at end of file
+}
+
+/// مزود جدار حماية تطبيقات الويب (WAF)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WafVendor {
+    /// Cloudflare
+    Cloudflare,
+    /// Akamai
+    Akamai,
+    /// ModSecurity
+    ModSecurity,
+}
+
+impl std::fmt::Display for WafVendor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            WafVendor::Cloudflare => "Cloudflare",
+            WafVendor::Akamai => "Akamai",
+            WafVendor::ModSecurity => "ModSecurity",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// نتيجة كشف WAF
+#[derive(Debug, Clone)]
+pub struct WafDetection {
+    /// المزود المكتشف
+    pub vendor: WafVendor,
+    
+    /// الأدلة التي أدت إلى الكشف
+    pub evidence: Vec<String>,
+}
+
+impl WafDetection {
+    /// ملف التهرب المناسب للمزود المكتشف
+    pub fn evasion_profile(&self) -> EvasionProfile {
+        match self.vendor {
+            WafVendor::Cloudflare | WafVendor::Akamai => EvasionProfile {
+                max_workers: 2,
+                min_delay: Duration::from_millis(1000),
+                max_delay: Duration::from_millis(3000),
+                rotate_user_agents: true,
+            },
+            WafVendor::ModSecurity => EvasionProfile {
+                max_workers: 5,
+                min_delay: Duration::from_millis(200),
+                max_delay: Duration::from_millis(1000),
+                rotate_user_agents: true,
+            },
+        }
+    }
+}
+
+/// حمولة استفزازية لإثارة قواعد WAF دون التأثير على الهدف
+const WAF_PROBE_QUERY: &str = "redfox=%3Cscript%3Ealert(1)%3C%2Fscript%3E%27%20OR%20%271%27%3D%271";
+
+/// كشف WAF أمام الهدف بطلب عادي وطلب استفزازي
+pub async fn detect_waf(url: &str, timeout_secs: u64) -> Result<Option<WafDetection>> {
+    let client = reqwest::Client::builder()
+        .timeout(Duration::from_secs(timeout_secs))
+        .build()
+        .context("فشل في إنشاء عميل HTTP")?;
+    
+    let separator = if url.contains('?') { '&' } else { '?' };
+    let probe_url = format!("{}{}{}", url, separator, WAF_PROBE_QUERY);
+    
+    for target in [url, probe_url.as_str()] {
+        let response = match client.get(target).send().await {
+            Ok(response) => response,
+            Err(e) => {
+                tracing::warn!("فشل طلب كشف WAF: {}", e);
+                continue;
+            }
+        };
+        
+        let status = response.status().as_u16();
+        let headers = response.headers().clone();
+        let body = response.text().await.unwrap_or_default();
+        
+        if let Some(detection) = fingerprint_waf(status, &headers, &body) {
+            return Ok(Some(detection));
+        }
+    }
+    
+    Ok(None)
+}
+
+/// مطابقة توقيعات WAF المعروفة في استجابة
+pub fn fingerprint_waf(status: u16, headers: &reqwest::header::HeaderMap, body: &str) -> Option<WafDetection> {
+    let header = |name: &str| {
+        headers
+            .get_all(name)
+            .iter()
+            .filter_map(|v| v.to_str().ok())
+            .map(str::to_lowercase)
+            .collect::<Vec<_>>()
+            .join(";")
+    };
+    let server = header("server");
+    let cookies = header("set-cookie");
+    let body_lower = body.to_lowercase();
+    
+    let signatures: [(WafVendor, Vec<(bool, &str)>); 3] = [
+        (WafVendor::Cloudflare, vec![
+            (server.contains("cloudflare"), "Server: cloudflare"),
+            (headers.contains_key("cf-ray"), "ترويسة CF-Ray"),
+            (cookies.contains("__cf_bm") || cookies.contains("__cfduid"), "كوكيز Cloudflare"),
+            (body_lower.contains("attention required! | cloudflare"), "صفحة حظر Cloudflare"),
+        ]),
+        (WafVendor::Akamai, vec![
+            (server.contains("akamaighost"), "Server: AkamaiGHost"),
+            (headers.keys().any(|k| k.as_str().starts_with("x-akamai")), "ترويسات X-Akamai"),
+            (headers.contains_key("akamai-grn"), "ترويسة Akamai-GRN"),
+            (cookies.contains("ak_bmsc") || cookies.contains("bm_sz"), "كوكيز Akamai Bot Manager"),
+            (body_lower.contains("reference #") && body_lower.contains("access denied"), "صفحة حظر Akamai"),
+        ]),
+        (WafVendor::ModSecurity, vec![
+            (server.contains("mod_security") || server.contains("modsecurity"), "Server: ModSecurity"),
+            (server.contains("noyb"), "Server: NOYB"),
+            (body_lower.contains("mod_security") || body_lower.contains("modsecurity"), "رسالة ModSecurity"),
+            (status == 406 || status == 501, "رفض الحمولة الاستفزازية (406/501)"),
+        ]),
+    ];
+    
+    signatures
+        .into_iter()
+        .map(|(vendor, checks)| WafDetection {
+            vendor,
+            evidence: checks
+                .into_iter()
+                .filter(|(matched, _)| *matched)
+                .map(|(_, evidence)| evidence.to_string())
+                .collect(),
+        })
+        // رمز الحالة وحده لا يكفي لنسب الحظر إلى ModSecurity
+        .filter(|d| !d.evidence.is_empty() && (d.evidence.len() != 1 || !d.evidence[0].contains("406/501")))
+        .max_by_key(|d| d.evidence.len())
+}
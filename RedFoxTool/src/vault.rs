@@ -0,0 +1,223 @@
+//! خزنة بيانات الاعتماد المشفرة
+//! حفظ بيانات الاعتماد المكتشفة محليًا بتشفير ChaCha20-Poly1305 ومفتاح مشتق من عبارة مرور (Argon2id)
+
+use std::io::IsTerminal;
+use std::path::{Path, PathBuf};
+use anyhow::{Result, Context};
+use argon2::Argon2;
+use chacha20poly1305::aead::{Aead, KeyInit};
+use chacha20poly1305::{ChaCha20Poly1305, Key, Nonce};
+use rand::RngCore;
+use tokio::fs as tokio_fs;
+
+use crate::scanner::ScanResult;
+
+/// مسار الخزنة الافتراضي
+pub const DEFAULT_VAULT: &str = "~/.redfox/vault.bin";
+
+/// متغير البيئة الحامل لعبارة مرور الخزنة (للتشغيل غير التفاعلي)
+pub const PASSPHRASE_ENV: &str = "REDFOX_VAULT_PASSPHRASE";
+
+/// توقيع ملف الخزنة وإصدار تنسيقه
+const MAGIC: &[u8; 8] = b"RFVAULT1";
+
+/// طول الملح
+const SALT_LEN: usize = 16;
+
+/// طول رقم الاستخدام الواحد
+const NONCE_LEN: usize = 12;
+
+/// بيانات اعتماد محفوظة في الخزنة
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct VaultEntry {
+    /// الهدف
+    pub target: String,
+    
+    /// اسم المستخدم
+    pub username: String,
+    
+    /// كلمة المرور
+    pub password: String,
+    
+    /// وقت الاكتشاف
+    pub found_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// خزنة مفتوحة في الذاكرة
+pub struct Vault {
+    path: PathBuf,
+    passphrase: String,
+    entries: Vec<VaultEntry>,
+}
+
+impl Vault {
+    /// فتح الخزنة وفك تشفيرها (الملف غير الموجود يعني خزنة فارغة)
+    pub async fn open(path: &str, passphrase: String) -> Result<Self> {
+        let path = PathBuf::from(shellexpand::tilde(path).as_ref());
+        
+        let entries = if path.exists() {
+            let data = tokio_fs::read(&path)
+                .await
+                .context(format!("فشل في قراءة الخزنة: {}", path.display()))?;
+            let plaintext = decrypt(&data, &passphrase)?;
+            serde_json::from_slice(&plaintext).context("محتوى الخزنة تالف")?
+        } else {
+            Vec::new()
+        };
+        
+        Ok(Self { path, passphrase, entries })
+    }
+    
+    /// مسار الخزنة
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+    
+    /// الإدخالات المحفوظة
+    pub fn entries(&self) -> &[VaultEntry] {
+        &self.entries
+    }
+    
+    /// إضافة النتائج الناجحة الجديدة وإرجاع عددها
+    pub fn add_results(&mut self, target: &str, results: &[ScanResult]) -> usize {
+        let before = self.entries.len();
+        
+        for result in results.iter().filter(|r| r.success) {
+            let exists = self.entries.iter().any(|e| {
+                e.target == target && e.username == result.username && e.password == result.password
+            });
+            
+            if !exists {
+                self.entries.push(VaultEntry {
+                    target: target.to_string(),
+                    username: result.username.clone(),
+                    password: result.password.clone(),
+                    found_at: result.timestamp,
+                });
+            }
+        }
+        
+        self.entries.len() - before
+    }
+    
+    /// تشفير الخزنة وكتابتها (بصلاحيات المالك فقط على Unix)
+    pub async fn save(&self) -> Result<()> {
+        if let Some(parent) = self.path.parent() {
+            tokio_fs::create_dir_all(parent).await?;
+        }
+        
+        let plaintext = serde_json::to_vec(&self.entries)?;
+        let data = encrypt(&plaintext, &self.passphrase)?;
+        
+        // الكتابة في ملف مؤقت ثم الاستبدال لتجنب خزنة نصف مكتوبة
+        let temp = self.path.with_extension("tmp");
+        tokio_fs::write(&temp, data)
+            .await
+            .context(format!("فشل في كتابة الخزنة: {}", temp.display()))?;
+        
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            tokio_fs::set_permissions(&temp, std::fs::Permissions::from_mode(0o600)).await?;
+        }
+        
+        tokio_fs::rename(&temp, &self.path).await?;
+        Ok(())
+    }
+}
+
+/// هل توجد خزنة في المسار
+pub fn exists(path: &str) -> bool {
+    Path::new(shellexpand::tilde(path).as_ref()).exists()
+}
+
+/// تصدير الإدخالات كنص صريح (CSV أو JSON حسب الامتداد)
+pub async fn export(entries: &[VaultEntry], filepath: &Path) -> Result<()> {
+    let is_csv = filepath
+        .extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|e| e.eq_ignore_ascii_case("csv"));
+    
+    if is_csv {
+        let mut csv_writer = csv::Writer::from_path(filepath)?;
+        for entry in entries {
+            csv_writer.serialize(entry)?;
+        }
+        csv_writer.flush()?;
+    } else {
+        tokio_fs::write(filepath, serde_json::to_string_pretty(entries)?).await?;
+    }
+    
+    Ok(())
+}
+
+/// عبارة المرور من متغير البيئة أو من الطرفية (None إن تعذر الحصول عليها)
+pub fn passphrase(confirm: bool) -> Result<Option<String>> {
+    if let Ok(passphrase) = std::env::var(PASSPHRASE_ENV) {
+        return Ok(Some(passphrase));
+    }
+    
+    if !std::io::stdin().is_terminal() {
+        return Ok(None);
+    }
+    
+    let passphrase = rpassword::prompt_password("عبارة مرور الخزنة: ")?;
+    if confirm && rpassword::prompt_password("تأكيد عبارة المرور: ")? != passphrase {
+        return Err(anyhow::anyhow!("عبارتا المرور غير متطابقتين"));
+    }
+    
+    Ok(Some(passphrase))
+}
+
+/// اشتقاق مفتاح 256 بت من عبارة المرور
+fn derive_key(passphrase: &str, salt: &[u8]) -> Result<Key> {
+    let mut key = Key::default();
+    Argon2::default()
+        .hash_password_into(passphrase.as_bytes(), salt, &mut key)
+        .map_err(|e| anyhow::anyhow!("فشل في اشتقاق المفتاح: {}", e))?;
+    Ok(key)
+}
+
+/// التشفير: MAGIC | salt | nonce | ciphertext
+fn encrypt(plaintext: &[u8], passphrase: &str) -> Result<Vec<u8>> {
+    let mut salt = [0u8; SALT_LEN];
+    let mut nonce = [0u8; NONCE_LEN];
+    rand::thread_rng().fill_bytes(&mut salt);
+    rand::thread_rng().fill_bytes(&mut nonce);
+    
+    let cipher = ChaCha20Poly1305::new(&derive_key(passphrase, &salt)?);
+    let ciphertext = cipher
+        .encrypt(Nonce::from_slice(&nonce), plaintext)
+        .map_err(|_| anyhow::anyhow!("فشل في تشفير الخزنة"))?;
+    
+    Ok([MAGIC.as_slice(), &salt, &nonce, &ciphertext].concat())
+}
+
+/// فك التشفير والتحقق من سلامة البيانات
+fn decrypt(data: &[u8], passphrase: &str) -> Result<Vec<u8>> {
+    let header = MAGIC.len() + SALT_LEN + NONCE_LEN;
+    if data.len() < header || &data[..MAGIC.len()] != MAGIC {
+        return Err(anyhow::anyhow!("ملف الخزنة غير صالح"));
+    }
+    
+    let (salt, rest) = data[MAGIC.len()..].split_at(SALT_LEN);
+    let (nonce, ciphertext) = rest.split_at(NONCE_LEN);
+    
+    ChaCha20Poly1305::new(&derive_key(passphrase, salt)?)
+        .decrypt(Nonce::from_slice(nonce), ciphertext)
+        .map_err(|_| anyhow::anyhow!("عبارة مرور خاطئة أو خزنة تالفة"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_encrypt_roundtrip_and_wrong_passphrase() {
+        let data = encrypt(b"admin:secret", "correct horse").unwrap();
+        
+        assert_eq!(decrypt(&data, "correct horse").unwrap(), b"admin:secret");
+        assert!(decrypt(&data, "wrong").is_err());
+        assert!(!data.windows(6).any(|w| w == b"secret"));
+    }
+}
@@ -0,0 +1,731 @@
+//! أدوات قوائم الكلمات
+//! دمج وإزالة تكرار وفرز وتصفية وإحصاء قوائم كبيرة بشكل متدفق
+
+use std::cmp::Reverse;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{BTreeMap, BinaryHeap, HashSet};
+use std::f64::consts::LN_2;
+use std::hash::{Hash, Hasher};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::time::Duration;
+use anyhow::{Result, Context};
+use chrono::{DateTime, Utc};
+use colored::Colorize;
+use futures::TryStreamExt;
+use indicatif::HumanDuration;
+use sha2::{Digest, Sha256};
+use tokio::fs as tokio_fs;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader, BufWriter};
+
+use crate::parser::{normalize_line, open_lines, WordSource};
+use crate::telemetry::Logger;
+
+/// عدد الأسطر في كل قطعة أثناء الفرز الخارجي
+const SORT_CHUNK_LINES: usize = 1_000_000;
+
+/// نسبة الإيجابيات الكاذبة الافتراضية لإزالة التكرار بمرشح بلوم
+pub const DEFAULT_BLOOM_FP_RATE: f64 = 0.001;
+
+/// سعة مرشح بلوم المفترضة لكل مصدر لا يُعرف طوله مسبقًا (stdin)
+pub const DEFAULT_BLOOM_STREAM_CAPACITY: usize = 10_000_000;
+
+/// طريقة إزالة التكرار عند الدمج
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DedupeMode {
+    /// بدون إزالة تكرار
+    None,
+    /// دقيقة (مجموعة تجزئات في الذاكرة)
+    Exact,
+    /// احتمالية بمرشح بلوم بذاكرة ثابتة (قد يُسقط إدخالًا فريدًا بالنسبة المحددة)
+    Bloom {
+        /// نسبة الإيجابيات الكاذبة المقبولة
+        false_positive_rate: f64,
+    },
+}
+
+/// مرشح بلوم لإزالة التكرار دون حفظ الإدخالات (لا يُبقي مكررًا أبدًا، وقد يُسقط فريدًا نادرًا)
+pub struct BloomFilter {
+    bits: Vec<u64>,
+    bit_count: u64,
+    hashes: u32,
+}
+
+impl BloomFilter {
+    /// مرشح بحجم مثالي لعدد الإدخالات المتوقع ونسبة الإيجابيات الكاذبة
+    pub fn new(expected: usize, false_positive_rate: f64) -> Self {
+        let n = expected.max(1) as f64;
+        let p = false_positive_rate.clamp(1e-9, 0.5);
+        let bit_count = (-(n * p.ln()) / (LN_2 * LN_2)).ceil().max(64.0) as u64;
+        let hashes = ((bit_count as f64 / n) * LN_2).round().clamp(1.0, 32.0) as u32;
+        
+        Self {
+            bits: vec![0; ((bit_count + 63) / 64) as usize],
+            bit_count,
+            hashes,
+        }
+    }
+    
+    /// إضافة إدخال (true إن لم يُرَ من قبل)
+    pub fn insert(&mut self, item: &str) -> bool {
+        let first = line_hash(item);
+        let second = {
+            let mut hasher = DefaultHasher::new();
+            (item, first).hash(&mut hasher);
+            hasher.finish() | 1
+        };
+        
+        let mut inserted = false;
+        for i in 0..u64::from(self.hashes) {
+            let bit = first.wrapping_add(i.wrapping_mul(second)) % self.bit_count;
+            let (word, mask) = ((bit / 64) as usize, 1u64 << (bit % 64));
+            
+            if self.bits[word] & mask == 0 {
+                self.bits[word] |= mask;
+                inserted = true;
+            }
+        }
+        
+        inserted
+    }
+    
+    /// حجم المرشح في الذاكرة (بايت)
+    pub fn memory_bytes(&self) -> usize {
+        self.bits.len() * std::mem::size_of::<u64>()
+    }
+}
+
+/// مرشح التكرار حسب الطريقة المختارة
+enum Seen {
+    None,
+    Exact(HashSet<u64>),
+    Bloom(BloomFilter),
+}
+
+impl Seen {
+    /// تجهيز المرشح (بلوم يُحجّم بعدد إدخالات المصادر، وبسعة افتراضية لما يُقرأ مرة واحدة)
+    async fn new(mode: DedupeMode, inputs: &[String]) -> Result<Self> {
+        Ok(match mode {
+            DedupeMode::None => Self::None,
+            DedupeMode::Exact => Self::Exact(HashSet::new()),
+            DedupeMode::Bloom { false_positive_rate } => {
+                let mut expected = 0usize;
+                for input in inputs {
+                    let source = WordSource::from_input(input).await?;
+                    if source.is_single_pass() {
+                        tracing::warn!(
+                            "لا يُعرف طول {} مسبقًا، يُفترض {} إدخال لمرشح بلوم (ما يزيد عنها يرفع الإسقاط الخاطئ)",
+                            input,
+                            DEFAULT_BLOOM_STREAM_CAPACITY
+                        );
+                        expected = expected.saturating_add(DEFAULT_BLOOM_STREAM_CAPACITY);
+                    } else {
+                        expected = expected.saturating_add(source.len());
+                    }
+                }
+                
+                let filter = BloomFilter::new(expected, false_positive_rate);
+                tracing::info!(
+                    "مرشح بلوم: {} إدخال متوقع، {} كيلوبايت",
+                    expected,
+                    filter.memory_bytes() / 1024
+                );
+                Self::Bloom(filter)
+            }
+        })
+    }
+    
+    /// هل الإدخال جديد (ويُسجل)
+    fn insert(&mut self, entry: &str) -> bool {
+        match self {
+            Self::None => true,
+            Self::Exact(seen) => seen.insert(line_hash(entry)),
+            Self::Bloom(filter) => filter.insert(entry),
+        }
+    }
+}
+
+/// سياسة كلمات المرور للتصفية
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PasswordPolicy {
+    /// الحد الأدنى للطول
+    pub min_length: Option<usize>,
+    /// الحد الأقصى للطول
+    pub max_length: Option<usize>,
+    /// يتطلب حرفًا صغيرًا
+    pub require_lower: bool,
+    /// يتطلب حرفًا كبيرًا
+    pub require_upper: bool,
+    /// يتطلب رقمًا
+    pub require_digit: bool,
+    /// يتطلب رمزًا خاصًا
+    pub require_special: bool,
+}
+
+impl PasswordPolicy {
+    /// التحقق من مطابقة كلمة المرور للسياسة
+    pub fn matches(&self, password: &str) -> bool {
+        let length = password.chars().count();
+        
+        self.min_length.map_or(true, |min| length >= min)
+            && self.max_length.map_or(true, |max| length <= max)
+            && (!self.require_lower || password.chars().any(char::is_lowercase))
+            && (!self.require_upper || password.chars().any(char::is_uppercase))
+            && (!self.require_digit || password.chars().any(|c| c.is_ascii_digit()))
+            && (!self.require_special || password.chars().any(|c| !c.is_alphanumeric()))
+    }
+}
+
+impl FromStr for PasswordPolicy {
+    type Err = String;
+    
+    /// صيغة السياسة: `min=8,max=64,lower,upper,digit,special`
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut policy = Self::default();
+        
+        for rule in s.split(',').map(str::trim).filter(|r| !r.is_empty()) {
+            let parse_len = |value: &str| {
+                value.parse::<usize>().map_err(|_| format!("طول غير صالح في السياسة: {}", rule))
+            };
+            
+            match rule.split_once('=') {
+                Some(("min", value)) => policy.min_length = Some(parse_len(value)?),
+                Some(("max", value)) => policy.max_length = Some(parse_len(value)?),
+                None if rule == "lower" => policy.require_lower = true,
+                None if rule == "upper" => policy.require_upper = true,
+                None if rule == "digit" => policy.require_digit = true,
+                None if rule == "special" => policy.require_special = true,
+                _ => return Err(format!("قاعدة سياسة غير معروفة: {}", rule)),
+            }
+        }
+        
+        Ok(policy)
+    }
+}
+
+/// تغطية مجموعات الأحرف (عدد الإدخالات التي تحتوي كل مجموعة)
+#[derive(Debug, Clone, Default, serde::Serialize)]
+pub struct CharsetCoverage {
+    /// أحرف صغيرة
+    pub lowercase: usize,
+    /// أحرف كبيرة
+    pub uppercase: usize,
+    /// أرقام
+    pub digits: usize,
+    /// رموز خاصة
+    pub special: usize,
+    /// أحرف غير ASCII
+    pub non_ascii: usize,
+}
+
+/// إحصائيات قائمة كلمات
+#[derive(Debug, Clone, Default, serde::Serialize)]
+pub struct WordlistStats {
+    /// إجمالي الإدخالات
+    pub total: usize,
+    /// الإدخالات الفريدة
+    pub unique: usize,
+    /// عدد التكرارات
+    pub duplicates: usize,
+    /// أقصر إدخال
+    pub min_length: usize,
+    /// أطول إدخال
+    pub max_length: usize,
+    /// متوسط الطول
+    pub average_length: f64,
+    /// توزيع الأطوال
+    pub length_distribution: BTreeMap<usize, usize>,
+    /// تغطية مجموعات الأحرف
+    pub charset: CharsetCoverage,
+}
+
+impl WordlistStats {
+    /// المدة المقدرة لتجربة الإدخالات الفريدة على عدد من المستخدمين بمعدل ثابت
+    pub fn estimated_duration(&self, users: usize, rps: u32) -> Duration {
+        let attempts = self.unique as f64 * users.max(1) as f64;
+        Duration::from_secs_f64(attempts / f64::from(rps.max(1)))
+    }
+    
+    /// عرض الإحصائيات مع تقدير مدة الهجوم
+    pub fn display(&self, users: usize, rps: u32) {
+        Logger.print(&format!("\n{}", "إحصائيات قائمة الكلمات:".bright_magenta().bold()));
+        Logger.print(&"=".repeat(60).bright_blue().to_string());
+        Logger.print(&format!("إجمالي الإدخالات:      {}", self.total));
+        Logger.print(&format!("الإدخالات الفريدة:     {}", self.unique.to_string().bright_green()));
+        Logger.print(&format!("التكرارات:             {}", self.duplicates.to_string().bright_red()));
+        Logger.print(&format!("الطول (أدنى/أقصى):     {} / {}", self.min_length, self.max_length));
+        Logger.print(&format!("متوسط الطول:           {:.2}", self.average_length));
+        
+        Logger.print(&format!("\n{}", "تغطية الأحرف:".bright_cyan()));
+        let percent = |count: usize| {
+            if self.total == 0 { 0.0 } else { count as f64 / self.total as f64 * 100.0 }
+        };
+        Logger.print(&format!("  أحرف صغيرة:  {:6.2}%", percent(self.charset.lowercase)));
+        Logger.print(&format!("  أحرف كبيرة:  {:6.2}%", percent(self.charset.uppercase)));
+        Logger.print(&format!("  أرقام:       {:6.2}%", percent(self.charset.digits)));
+        Logger.print(&format!("  رموز خاصة:   {:6.2}%", percent(self.charset.special)));
+        Logger.print(&format!("  غير ASCII:   {:6.2}%", percent(self.charset.non_ascii)));
+        
+        Logger.print(&format!("\n{}", "توزيع الأطوال:".bright_cyan()));
+        let max_count = self.length_distribution.values().copied().max().unwrap_or(1);
+        for (length, count) in &self.length_distribution {
+            let bar = "#".repeat((count * 40 / max_count).max(1));
+            Logger.print(&format!("  {:3}: {:>10} {}", length, count, bar.bright_yellow()));
+        }
+        
+        Logger.print(&format!("\n{}", "تقدير مدة الهجوم:".bright_cyan()));
+        Logger.print(&format!(
+            "  {} محاولة ({} مستخدم) بمعدل {} طلب/ثانية: {}",
+            self.unique * users.max(1),
+            users.max(1),
+            rps.max(1),
+            HumanDuration(self.estimated_duration(users, rps)).to_string().bright_yellow()
+        ));
+    }
+}
+
+/// تجزئة سطر لاستخدامها في إزالة التكرار
+fn line_hash(line: &str) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    line.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// إنشاء كاتب مخزن مؤقتًا لملف الإخراج
+async fn create_writer(output: &str) -> Result<BufWriter<tokio_fs::File>> {
+    let file = tokio_fs::File::create(output)
+        .await
+        .context(format!("فشل في إنشاء الملف: {}", output))?;
+    
+    Ok(BufWriter::new(file))
+}
+
+/// دمج عدة قوائم في ملف واحد مع إزالة تكرار اختيارية (دقيقة أو بمرشح بلوم)
+pub async fn merge(inputs: &[String], output: &str, dedupe: DedupeMode) -> Result<usize> {
+    let mut seen = Seen::new(dedupe, inputs).await?;
+    let mut writer = create_writer(output).await?;
+    let mut written = 0;
+    
+    for input in inputs {
+        let mut lines = open_lines(input).await?;
+        
+        while let Some(line) = lines.next_line().await? {
+            let Some(entry) = normalize_line(&line) else {
+                continue;
+            };
+            
+            if !seen.insert(entry) {
+                continue;
+            }
+            
+            writer.write_all(entry.as_bytes()).await?;
+            writer.write_all(b"\n").await?;
+            written += 1;
+        }
+    }
+    
+    writer.flush().await?;
+    Ok(written)
+}
+
+/// إزالة التكرارات مع الحفاظ على ترتيب أول ظهور
+pub async fn dedupe(input: &str, output: &str) -> Result<usize> {
+    merge(&[input.to_string()], output, DedupeMode::Exact).await
+}
+
+/// تصفية القائمة حسب سياسة كلمات المرور
+pub async fn filter(input: &str, output: &str, policy: &PasswordPolicy) -> Result<usize> {
+    let mut lines = open_lines(input).await?;
+    let mut writer = create_writer(output).await?;
+    let mut written = 0;
+    
+    while let Some(line) = lines.next_line().await? {
+        match normalize_line(&line) {
+            Some(entry) if policy.matches(entry) => {
+                writer.write_all(entry.as_bytes()).await?;
+                writer.write_all(b"\n").await?;
+                written += 1;
+            }
+            _ => {}
+        }
+    }
+    
+    writer.flush().await?;
+    Ok(written)
+}
+
+/// فرز القائمة (فرز خارجي بقطع لتجنب تحميل الملف كاملًا في الذاكرة)
+pub async fn sort(input: &str, output: &str, unique: bool) -> Result<usize> {
+    let mut lines = open_lines(input).await?;
+    let mut chunk_files = Vec::new();
+    let mut chunk = Vec::with_capacity(SORT_CHUNK_LINES);
+    
+    // المرحلة الأولى: كتابة قطع مفروزة في ملفات مؤقتة
+    loop {
+        let line = lines.next_line().await?;
+        let finished = line.is_none();
+        
+        if let Some(entry) = line.as_deref().and_then(normalize_line) {
+            chunk.push(entry.to_string());
+        }
+        
+        if chunk.len() >= SORT_CHUNK_LINES || (finished && !chunk.is_empty()) {
+            chunk.sort_unstable();
+            
+            let path = std::env::temp_dir().join(format!(
+                "redfox-sort-{}-{}.tmp",
+                std::process::id(),
+                chunk_files.len()
+            ));
+            let mut writer = create_writer(&path.to_string_lossy()).await?;
+            for entry in chunk.drain(..) {
+                writer.write_all(entry.as_bytes()).await?;
+                writer.write_all(b"\n").await?;
+            }
+            writer.flush().await?;
+            chunk_files.push(path);
+        }
+        
+        if finished {
+            break;
+        }
+    }
+    
+    // المرحلة الثانية: دمج القطع المفروزة
+    let result = merge_sorted_chunks(&chunk_files, output, unique).await;
+    
+    for path in &chunk_files {
+        tokio_fs::remove_file(path).await.ok();
+    }
+    
+    result
+}
+
+/// دمج k من الملفات المفروزة في ملف إخراج واحد
+async fn merge_sorted_chunks(chunks: &[PathBuf], output: &str, unique: bool) -> Result<usize> {
+    let mut readers = Vec::with_capacity(chunks.len());
+    let mut heap = BinaryHeap::new();
+    
+    for (index, path) in chunks.iter().enumerate() {
+        let file = tokio_fs::File::open(path).await?;
+        let mut reader = BufReader::new(file).lines();
+        
+        if let Some(line) = reader.next_line().await? {
+            heap.push(Reverse((line, index)));
+        }
+        readers.push(reader);
+    }
+    
+    let mut writer = create_writer(output).await?;
+    let mut last: Option<String> = None;
+    let mut written = 0;
+    
+    while let Some(Reverse((line, index))) = heap.pop() {
+        if let Some(next) = readers[index].next_line().await? {
+            heap.push(Reverse((next, index)));
+        }
+        
+        if unique && last.as_deref() == Some(line.as_str()) {
+            continue;
+        }
+        
+        writer.write_all(line.as_bytes()).await?;
+        writer.write_all(b"\n").await?;
+        written += 1;
+        last = Some(line);
+    }
+    
+    writer.flush().await?;
+    Ok(written)
+}
+
+/// حجم بالبايت مع لاحقة اختيارية (512K، 100M، 2G بوحدات 1024)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ByteSize(pub u64);
+
+impl FromStr for ByteSize {
+    type Err = String;
+    
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let upper = s.to_uppercase();
+        let digits = upper.trim_end_matches('B');
+        let (number, multiplier) = match digits.chars().last() {
+            Some('K') => (&digits[..digits.len() - 1], 1u64 << 10),
+            Some('M') => (&digits[..digits.len() - 1], 1 << 20),
+            Some('G') => (&digits[..digits.len() - 1], 1 << 30),
+            _ => (digits, 1),
+        };
+        
+        number
+            .trim()
+            .parse::<u64>()
+            .ok()
+            .and_then(|n| n.checked_mul(multiplier))
+            .filter(|&bytes| bytes > 0)
+            .map(Self)
+            .ok_or_else(|| format!("حجم غير صالح: {}", s))
+    }
+}
+
+/// طريقة تقسيم القائمة
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SplitMode {
+    /// عدد أجزاء متوازنة بعدد الإدخالات
+    Parts(usize),
+    /// حد أقصى لحجم كل جزء
+    Bytes(ByteSize),
+}
+
+/// جزء من قائمة مقسمة
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct SplitPart {
+    /// مسار الجزء
+    pub path: PathBuf,
+    /// عدد الإدخالات
+    pub entries: usize,
+    /// الحجم بالبايت
+    pub bytes: u64,
+    /// بصمة SHA-256 للتحقق بعد النقل إلى العمال
+    pub sha256: String,
+}
+
+/// بيان التقسيم (يُكتب بجانب الأجزاء لتوزيعها على العمال أو على جلسات متفرقة)
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct SplitManifest {
+    /// القائمة الأصلية
+    pub source: String,
+    /// وقت التقسيم
+    pub created_at: DateTime<Utc>,
+    /// إجمالي الإدخالات
+    pub total_entries: usize,
+    /// الأجزاء بالترتيب
+    pub parts: Vec<SplitPart>,
+}
+
+/// كاتب الجزء الحالي أثناء التقسيم
+struct PartWriter {
+    path: PathBuf,
+    writer: BufWriter<tokio_fs::File>,
+    hasher: Sha256,
+    entries: usize,
+    bytes: u64,
+}
+
+impl PartWriter {
+    /// إنشاء ملف الجزء
+    async fn create(path: PathBuf) -> Result<Self> {
+        let writer = create_writer(&path.to_string_lossy()).await?;
+        Ok(Self { path, writer, hasher: Sha256::new(), entries: 0, bytes: 0 })
+    }
+    
+    /// كتابة إدخال
+    async fn write(&mut self, entry: &str) -> Result<()> {
+        for chunk in [entry.as_bytes(), b"\n"] {
+            self.writer.write_all(chunk).await?;
+            self.hasher.update(chunk);
+        }
+        self.entries += 1;
+        self.bytes += entry.len() as u64 + 1;
+        Ok(())
+    }
+    
+    /// إغلاق الجزء ووصفه
+    async fn finish(mut self) -> Result<SplitPart> {
+        self.writer.flush().await?;
+        Ok(SplitPart {
+            path: self.path,
+            entries: self.entries,
+            bytes: self.bytes,
+            sha256: self.hasher.finalize().iter().map(|b| format!("{:02x}", b)).collect(),
+        })
+    }
+}
+
+/// اسم القائمة دون امتدادات (rockyou.txt.gz -> rockyou)
+fn list_stem(input: &str) -> String {
+    let name = Path::new(input).file_name().map(|n| n.to_string_lossy().into_owned()).unwrap_or_default();
+    let name = [".gz", ".zst", ".bz2"]
+        .iter()
+        .find_map(|ext| name.strip_suffix(ext))
+        .unwrap_or(&name);
+    
+    Path::new(name)
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+        .filter(|stem| !stem.is_empty())
+        .unwrap_or_else(|| "wordlist".to_string())
+}
+
+/// تقسيم القائمة إلى أجزاء متوازنة (بالعدد أو بالحجم) مع بيان JSON، وإرجاع البيان ومساره
+pub async fn split(input: &str, output_dir: Option<&Path>, mode: SplitMode) -> Result<(SplitManifest, PathBuf)> {
+    let dir = output_dir
+        .map(Path::to_path_buf)
+        .unwrap_or_else(|| Path::new(input).parent().map(Path::to_path_buf).unwrap_or_default());
+    let stem = list_stem(input);
+    if !dir.as_os_str().is_empty() {
+        tokio_fs::create_dir_all(&dir)
+            .await
+            .context(format!("فشل في إنشاء المجلد: {}", dir.display()))?;
+    }
+    
+    // حصص الأجزاء بالعدد: الباقي يوزع واحدًا واحدًا على الأجزاء الأولى
+    let quotas = match mode {
+        SplitMode::Parts(parts) => {
+            let total = WordSource::from_input(input).await?.len();
+            let parts = parts.clamp(1, total.max(1));
+            (0..parts).map(|i| total / parts + usize::from(i < total % parts)).collect()
+        }
+        SplitMode::Bytes(_) => Vec::new(),
+    };
+    
+    let mut lines = open_lines(input).await?;
+    let mut parts = Vec::new();
+    let mut current: Option<PartWriter> = None;
+    let mut total_entries = 0;
+    
+    while let Some(line) = lines.next_line().await? {
+        let Some(entry) = normalize_line(&line) else {
+            continue;
+        };
+        
+        let full = match (&current, mode) {
+            (None, _) => true,
+            (Some(part), SplitMode::Parts(_)) => quotas.get(parts.len()).is_some_and(|&quota| part.entries >= quota),
+            (Some(part), SplitMode::Bytes(ByteSize(limit))) => {
+                part.entries > 0 && part.bytes + entry.len() as u64 + 1 > limit
+            }
+        };
+        if full {
+            if let Some(part) = current.take() {
+                parts.push(part.finish().await?);
+            }
+            let path = dir.join(format!("{}.part{:03}.txt", stem, parts.len() + 1));
+            current = Some(PartWriter::create(path).await?);
+        }
+        
+        if let Some(part) = current.as_mut() {
+            part.write(entry).await?;
+        }
+        total_entries += 1;
+    }
+    
+    if let Some(part) = current {
+        parts.push(part.finish().await?);
+    }
+    if parts.is_empty() {
+        return Err(anyhow::anyhow!("الملف فارغ: {}", input));
+    }
+    
+    let manifest = SplitManifest {
+        source: input.to_string(),
+        created_at: Utc::now(),
+        total_entries,
+        parts,
+    };
+    let manifest_path = dir.join(format!("{}.manifest.json", stem));
+    tokio_fs::write(&manifest_path, serde_json::to_string_pretty(&manifest)?)
+        .await
+        .context(format!("فشل في كتابة البيان: {}", manifest_path.display()))?;
+    
+    Ok((manifest, manifest_path))
+}
+
+/// حساب إحصائيات القائمة بقراءة متدفقة (ملف مضغوط أو رابط أو stdin)
+pub async fn stats(input: &str) -> Result<WordlistStats> {
+    let mut entries = WordSource::stream_input(input).await?;
+    let mut stats = WordlistStats {
+        min_length: usize::MAX,
+        ..WordlistStats::default()
+    };
+    let mut seen = HashSet::new();
+    let mut total_length = 0usize;
+    
+    while let Some(entry) = entries.try_next().await? {
+        let entry = entry.as_str();
+        let length = entry.chars().count();
+        stats.total += 1;
+        total_length += length;
+        stats.min_length = stats.min_length.min(length);
+        stats.max_length = stats.max_length.max(length);
+        *stats.length_distribution.entry(length).or_insert(0) += 1;
+        
+        if !seen.insert(line_hash(entry)) {
+            stats.duplicates += 1;
+        }
+        
+        let charset = &mut stats.charset;
+        charset.lowercase += usize::from(entry.chars().any(char::is_lowercase));
+        charset.uppercase += usize::from(entry.chars().any(char::is_uppercase));
+        charset.digits += usize::from(entry.chars().any(|c| c.is_ascii_digit()));
+        charset.special += usize::from(entry.chars().any(|c| !c.is_alphanumeric()));
+        charset.non_ascii += usize::from(!entry.is_ascii());
+    }
+    
+    if stats.total == 0 {
+        return Err(anyhow::anyhow!("الملف فارغ: {}", input));
+    }
+    
+    stats.unique = seen.len();
+    stats.average_length = total_length as f64 / stats.total as f64;
+    
+    Ok(stats)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_policy_parsing_and_matching() {
+        let policy: PasswordPolicy = "min=8,upper,digit".parse().unwrap();
+        
+        assert!(policy.matches("Password1"));
+        assert!(!policy.matches("password1"));
+        assert!(!policy.matches("Pass1"));
+        assert!("min=abc".parse::<PasswordPolicy>().is_err());
+    }
+    
+    #[tokio::test]
+    async fn test_stats_counts_duplicates_and_estimates_duration() {
+        let file = tempfile::NamedTempFile::with_suffix(".txt").unwrap();
+        std::fs::write(file.path(), "admin\nPassw0rd!\nadmin\n# تعليق\n123456\n").unwrap();
+        
+        let stats = stats(file.path().to_str().unwrap()).await.unwrap();
+        
+        assert_eq!((stats.total, stats.unique, stats.duplicates), (4, 3, 1));
+        assert_eq!((stats.min_length, stats.max_length), (5, 9));
+        assert_eq!(stats.charset.special, 1);
+        assert_eq!(stats.estimated_duration(2, 3), Duration::from_secs(2));
+    }
+    
+    #[tokio::test]
+    async fn test_split_balances_parts() {
+        let dir = tempfile::tempdir().unwrap();
+        let input = dir.path().join("big.txt");
+        let words: Vec<String> = (0..10).map(|i| format!("word{}", i)).collect();
+        std::fs::write(&input, words.join("\n")).unwrap();
+        
+        let (manifest, manifest_path) = split(input.to_str().unwrap(), None, SplitMode::Parts(3)).await.unwrap();
+        
+        let counts: Vec<usize> = manifest.parts.iter().map(|p| p.entries).collect();
+        assert_eq!(counts, vec![4, 3, 3]);
+        assert_eq!(manifest.total_entries, 10);
+        assert!(manifest_path.ends_with("big.manifest.json"));
+        assert_eq!(std::fs::read_to_string(&manifest.parts[2].path).unwrap(), "word7\nword8\nword9\n");
+        
+        let (by_size, _) = split(input.to_str().unwrap(), None, SplitMode::Bytes(ByteSize(12))).await.unwrap();
+        assert_eq!(by_size.parts.len(), 5);
+        assert!("100M".parse::<ByteSize>().is_ok_and(|size| size.0 == 100 << 20));
+    }
+    
+    #[test]
+    fn test_bloom_filter_never_keeps_duplicates() {
+        let mut filter = BloomFilter::new(10_000, DEFAULT_BLOOM_FP_RATE);
+        
+        let dropped = (0..10_000).filter(|i| !filter.insert(&format!("pass{}", i))).count();
+        
+        assert!(dropped < 50, "إيجابيات كاذبة كثيرة: {}", dropped);
+        assert!((0..10_000).all(|i| !filter.insert(&format!("pass{}", i))));
+    }
+}